@@ -0,0 +1,196 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2025 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: MIT
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a
+ * copy of this software and associated documentation files (the "Software"),
+ * to deal in the Software without restriction, including without limitation
+ * the rights to use, copy, modify, merge, publish, distribute, sublicense,
+ * and/or sell copies of the Software, and to permit persons to whom the
+ * Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL
+ * THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+ * FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+use std::{
+    collections::{HashSet, VecDeque},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::Stream;
+use futures_util::stream;
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::model::ODataId;
+use crate::network::{from_json_value, RedfishHttpClient};
+use crate::RedfishError;
+
+/// Members of a Redfish collection as a `Stream`, fetched lazily one page and one member
+/// at a time, so a very large collection is never held in memory all at once.
+/// `Members@odata.nextLink` is followed. Members that are bare links are fetched with a
+/// GET; expanded members (e.g. SEL entries) are deserialized in place.
+/// Get one from `RedfishStandard::iter_collection`, or `iter_collection_links` for just the
+/// member links of every page.
+pub struct CollectionIter<T> {
+    inner: Pin<Box<dyn Stream<Item = Result<T, RedfishError>> + Send>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CollectionPage {
+    #[serde(default)]
+    members: Vec<serde_json::Value>,
+    #[serde(rename = "Members@odata.nextLink")]
+    members_next_link: Option<String>,
+}
+
+struct State {
+    client: RedfishHttpClient,
+    // Page the pending members came from, for error messages
+    page_url: String,
+    pending: VecDeque<serde_json::Value>,
+    next_page: Option<String>,
+    seen_pages: HashSet<String>,
+    // Fetch members that are bare links, else hand them out as they are
+    fetch_members: bool,
+}
+
+impl<T> CollectionIter<T>
+where
+    T: DeserializeOwned + std::fmt::Debug + Send + 'static,
+{
+    pub(crate) fn new(client: RedfishHttpClient, url: &str) -> Self {
+        Self::with_fetch(client, url, true)
+    }
+
+    fn with_fetch(client: RedfishHttpClient, url: &str, fetch_members: bool) -> Self {
+        let state = State {
+            client,
+            page_url: url.to_string(),
+            pending: VecDeque::new(),
+            next_page: Some(url.to_string()),
+            seen_pages: HashSet::from([url.to_string()]),
+            fetch_members,
+        };
+        let inner = stream::try_unfold(state, |mut state| async move {
+            loop {
+                if let Some(member) = state.pending.pop_front() {
+                    let item = match member_link(&member).filter(|_| state.fetch_members) {
+                        Some(link) => state.client.get(&link.relative_path()).await?.1,
+                        None => from_json_value(&state.page_url, &member)?,
+                    };
+                    return Ok(Some((item, state)));
+                }
+                let Some(url) = state.next_page.take() else {
+                    return Ok(None);
+                };
+                let (_status_code, page): (_, CollectionPage) = state.client.get(&url).await?;
+                state.pending.extend(page.members);
+                state.page_url = url;
+                // A BMC handing back the same page again would otherwise loop forever
+                state.next_page = page
+                    .members_next_link
+                    .map(|next| ODataId::from(next).relative_path())
+                    .filter(|next| state.seen_pages.insert(next.clone()));
+            }
+        });
+        CollectionIter {
+            inner: Box::pin(inner),
+        }
+    }
+}
+
+impl CollectionIter<ODataId> {
+    /// The @odata.id of each member, across pages, without fetching the members
+    pub(crate) fn links(client: RedfishHttpClient, url: &str) -> Self {
+        Self::with_fetch(client, url, false)
+    }
+}
+
+impl<T> Stream for CollectionIter<T> {
+    type Item = Result<T, RedfishError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+/// The member's @odata.id if that is all the collection gave us, None if it is expanded.
+fn member_link(member: &serde_json::Value) -> Option<ODataId> {
+    let object = member.as_object()?;
+    if object.len() != 1 {
+        return None;
+    }
+    let id = object.get("@odata.id")?.as_str()?;
+    Some(ODataId::from(id.to_string()))
+}
+
+#[test]
+fn test_member_link() {
+    let link = serde_json::json!({"@odata.id": "/redfish/v1/Systems/1/BootOptions/Boot0001"});
+    assert_eq!(
+        member_link(&link).unwrap().relative_path(),
+        "Systems/1/BootOptions/Boot0001"
+    );
+    let expanded = serde_json::json!({
+        "@odata.id": "/redfish/v1/Managers/1/LogServices/Sel/Entries/1",
+        "Id": "1",
+        "Message": "Fan 1 failed",
+    });
+    assert!(member_link(&expanded).is_none());
+    assert!(member_link(&serde_json::json!("Boot0001")).is_none());
+}
+
+#[tokio::test]
+async fn test_links_follow_pages_without_fetching() {
+    use futures_util::TryStreamExt;
+
+    use crate::fake_bmc::{FakeBmc, Response};
+
+    let sensors = "/redfish/v1/Chassis/1/Sensors";
+    let page = |ids: &[&str], next: Option<&str>| {
+        serde_json::json!({
+            "Members": ids
+                .iter()
+                .map(|id| serde_json::json!({"@odata.id": format!("{sensors}/{id}")}))
+                .collect::<Vec<_>>(),
+            "Members@odata.nextLink": next,
+        })
+    };
+    let second = page(&["Temp_2"], None);
+    let fake = FakeBmc::start(&[(
+        sensors,
+        page(
+            &["Temp_0", "Temp_1"],
+            Some("/redfish/v1/Chassis/1/Sensors?$skip=2"),
+        ),
+    )])
+    .await
+    .with_handler(move |req, _| {
+        (req.query.as_deref() == Some("$skip=2")).then(|| Response::json(second.clone()))
+    });
+    let links: Vec<ODataId> = CollectionIter::links(fake.client(), "Chassis/1/Sensors")
+        .try_collect()
+        .await
+        .unwrap();
+    let ids: Vec<_> = links.iter().map(|l| l.relative_path()).collect();
+    assert_eq!(
+        ids,
+        [
+            "Chassis/1/Sensors/Temp_0",
+            "Chassis/1/Sensors/Temp_1",
+            "Chassis/1/Sensors/Temp_2"
+        ]
+    );
+    // The two pages, no member GETs
+    assert_eq!(fake.requests.lock().unwrap().len(), 2);
+}
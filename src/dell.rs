@@ -22,8 +22,8 @@
  */
 use std::{collections::HashMap, path::Path, time::Duration};
 
-use reqwest::{header::HeaderMap, Method};
-use serde::{Deserialize, Serialize};
+use reqwest::header::HeaderMap;
+use serde::Serialize;
 use serde_json::Value;
 use tokio::fs::File;
 
@@ -48,13 +48,14 @@ use crate::{
         storage::Drives,
         task::Task,
         thermal::Thermal,
+        uefi_device_path::normalize_mac,
         update_service::{ComponentType, TransferProtocolType, UpdateService},
         BootOption, ComputerSystem, InvalidValueError, Manager, OnOff,
     },
     standard::RedfishStandard,
     BiosProfileType, Boot, BootOptions, Collection, EnabledDisabled, JobState, MachineSetupDiff,
-    MachineSetupStatus, ODataId, PCIeDevice, PowerState, Redfish, RedfishError, Resource, RoleId,
-    Status, StatusInternal, SystemPowerControl,
+    MachineSetupStatus, NewBootOption, ODataId, PCIeDevice, PowerState, Redfish, RedfishError,
+    Resource, RoleId, SecretString, Status, StatusInternal, SystemPowerControl,
 };
 
 const UEFI_PASSWORD_NAME: &str = "SetupPassword";
@@ -67,10 +68,14 @@ pub struct Bmc {
 
 #[async_trait::async_trait]
 impl Redfish for Bmc {
+    fn standard(&self) -> &RedfishStandard {
+        &self.s
+    }
+
     async fn create_user(
         &self,
         username: &str,
-        password: &str,
+        password: &SecretString,
         role_id: RoleId,
     ) -> Result<(), RedfishError> {
         // Find an unused ID
@@ -109,14 +114,18 @@ impl Redfish for Bmc {
         self.s.change_username(old_name, new_name).await
     }
 
-    async fn change_password(&self, username: &str, new_pass: &str) -> Result<(), RedfishError> {
+    async fn change_password(
+        &self,
+        username: &str,
+        new_pass: &SecretString,
+    ) -> Result<(), RedfishError> {
         self.s.change_password(username, new_pass).await
     }
 
     async fn change_password_by_id(
         &self,
         account_id: &str,
-        new_pass: &str,
+        new_pass: &SecretString,
     ) -> Result<(), RedfishError> {
         self.s.change_password_by_id(account_id, new_pass).await
     }
@@ -157,6 +166,28 @@ impl Redfish for Bmc {
         true
     }
 
+    fn supported_features(&self) -> crate::FeatureSet {
+        crate::FeatureSet {
+            power_metrics: true,
+            thermal_metrics: true,
+            system_event_log: true,
+            bios_settings: true,
+            reset_bios: true,
+            lockdown: true,
+            serial_console: true,
+            secure_boot: true,
+            clear_tpm: true,
+            multipart_firmware_update: true,
+            system_ethernet_interfaces: true,
+            chassis_network_adapters: true,
+            dpu_first_boot_order: true,
+            component_integrity: true,
+            infinite_boot: true,
+            ipmi_over_lan: true,
+            ..Default::default()
+        }
+    }
+
     async fn bmc_reset(&self) -> Result<(), RedfishError> {
         self.s.bmc_reset().await
     }
@@ -185,6 +216,16 @@ impl Redfish for Bmc {
         self.get_system_event_log().await
     }
 
+    async fn get_system_event_log_since(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        limit: Option<usize>,
+    ) -> Result<Vec<LogEntry>, RedfishError> {
+        // Different Dell timestamp formats make server side filtering unreliable
+        let sel = self.get_system_event_log().await?;
+        Ok(crate::model::sel::newest_first(sel, since, limit))
+    }
+
     async fn get_bmc_event_log(
         &self,
         from: Option<chrono::DateTime<chrono::Utc>>,
@@ -214,7 +255,10 @@ impl Redfish for Bmc {
             attributes: values,
         };
 
-        let url = format!("Systems/{}/Bios/Settings/", self.s.system_id());
+        let url = format!(
+            "Systems/{}/Bios/Settings/",
+            self.s.resolve_system_id().await?
+        );
         self.s
             .client
             .patch(&url, set_attrs)
@@ -222,7 +266,7 @@ impl Redfish for Bmc {
             .map(|_status_code| ())
     }
 
-    async fn reset_bios(&self) -> Result<(), RedfishError> {
+    async fn reset_bios(&self) -> Result<crate::ApplyResult, RedfishError> {
         self.s.factory_reset_bios().await
     }
 
@@ -238,8 +282,42 @@ impl Redfish for Bmc {
             HashMap<String, HashMap<BiosProfileType, HashMap<String, serde_json::Value>>>,
         >,
         selected_profile: BiosProfileType,
-    ) -> Result<(), RedfishError> {
-        self.delete_job_queue().await?;
+    ) -> Result<crate::MachineSetupReport, RedfishError> {
+        let extra_values = match bios_profiles.get(&RedfishVendor::Dell) {
+            Some(dell) => {
+                let model = crate::model_coerce(
+                    self.get_system()
+                        .await?
+                        .model
+                        .unwrap_or("".to_string())
+                        .as_str(),
+                );
+                dell.get(&model)
+                    .and_then(|all_extra_values| all_extra_values.get(&selected_profile))
+            }
+            None => None,
+        };
+
+        // machine_setup_status doesn't cover BIOS profiles, so with one selected the settings
+        // are always applied
+        let status = crate::MachineSetupStatus::or_apply_all(
+            self.machine_setup_status(boot_interface_mac).await,
+        );
+        if extra_values.is_none() && status.is_some_and(|s| !s.needs_setup()) {
+            let report = crate::MachineSetupReport::skipped(&[
+                "delete_job_queue",
+                "set_bios_attributes",
+                "machine_setup_oem",
+                "setup_bmc_remote_access",
+            ]);
+            return match boot_interface_mac {
+                Some(_) => Ok(report),
+                None => Err(RedfishError::NoDpu),
+            };
+        }
+
+        let mut report = crate::MachineSetupReport::default();
+        report.record("delete_job_queue", self.delete_job_queue().await)?;
 
         let apply_time = dell::SetSettingsApplyTime {
             apply_time: dell::RedfishSettingsApplyTime::OnReset, // requires reboot to apply
@@ -269,33 +347,30 @@ impl Redfish for Bmc {
             serde_json::from_str(as_json.as_str()).map_err(|e| RedfishError::GenericError {
                 error: { e.to_string() },
             })?;
-        if let Some(dell) = bios_profiles.get(&RedfishVendor::Dell) {
-            let model = crate::model_coerce(
-                self.get_system()
-                    .await?
-                    .model
-                    .unwrap_or("".to_string())
-                    .as_str(),
-            );
-            if let Some(all_extra_values) = dell.get(&model) {
-                if let Some(extra_values) = all_extra_values.get(&selected_profile) {
-                    tracing::debug!("Setting extra BIOS values: {extra_values:?}");
-                    set_machine_attrs.extend(extra_values.clone());
-                }
-            }
+        if let Some(extra_values) = extra_values {
+            tracing::debug!("Setting extra BIOS values: {extra_values:?}");
+            set_machine_attrs.extend(extra_values.clone());
         }
 
-        let url = format!("Systems/{}/Bios/Settings/", self.s.system_id());
-        match self.s.client.patch(&url, set_machine_attrs).await? {
-            (_, Some(headers)) => self.parse_job_id_from_response_headers(&url, headers).await,
-            (_, None) => Err(RedfishError::NoHeader),
-        }?;
+        let url = format!(
+            "Systems/{}/Bios/Settings/",
+            self.s.resolve_system_id().await?
+        );
+        let set_bios = match self.s.client.patch(&url, set_machine_attrs).await {
+            Ok((_, Some(headers))) => self.parse_job_id_from_response_headers(&url, headers).await,
+            Ok((_, None)) => Err(RedfishError::NoHeader),
+            Err(e) => Err(e),
+        };
+        report.record("set_bios_attributes", set_bios)?;
 
-        self.machine_setup_oem().await?;
-        self.setup_bmc_remote_access().await?;
+        report.record("machine_setup_oem", self.machine_setup_oem().await)?;
+        report.record(
+            "setup_bmc_remote_access",
+            self.setup_bmc_remote_access().await,
+        )?;
 
         if has_dpu {
-            Ok(())
+            Ok(report)
         } else {
             // Usually a missing DPU is an error, but for zero-dpu it isn't
             // Tell the caller and let them decide
@@ -427,7 +502,11 @@ impl Redfish for Bmc {
         })
     }
 
-    async fn setup_serial_console(&self) -> Result<(), RedfishError> {
+    async fn setup_serial_console(&self) -> Result<bool, RedfishError> {
+        // If we can't tell, set it up anyway
+        if matches!(self.serial_console_status().await, Ok(status) if status.is_fully_enabled()) {
+            return Ok(false);
+        }
         self.delete_job_queue().await?;
 
         self.setup_bmc_remote_access().await?;
@@ -448,12 +527,15 @@ impl Redfish for Bmc {
             attributes: serial_console,
         };
 
-        let url = format!("Systems/{}/Bios/Settings/", self.s.system_id());
+        let url = format!(
+            "Systems/{}/Bios/Settings/",
+            self.s.resolve_system_id().await?
+        );
         self.s
             .client
             .patch(&url, set_serial_attrs)
             .await
-            .map(|_status_code| ())
+            .map(|_status_code| true)
     }
 
     async fn serial_console_status(&self) -> Result<Status, RedfishError> {
@@ -508,7 +590,7 @@ impl Redfish for Bmc {
         }
     }
 
-    async fn clear_tpm(&self) -> Result<(), RedfishError> {
+    async fn clear_tpm(&self) -> Result<crate::ApplyResult, RedfishError> {
         self.delete_job_queue().await?;
 
         let apply_time = dell::SetSettingsApplyTime {
@@ -522,19 +604,23 @@ impl Redfish for Bmc {
             redfish_settings_apply_time: apply_time,
             attributes: tpm,
         };
-        let url = format!("Systems/{}/Bios/Settings/", self.s.system_id());
-        self.s
+        let url = format!(
+            "Systems/{}/Bios/Settings/",
+            self.s.resolve_system_id().await?
+        );
+        let (_status_code, info) = self
+            .s
             .client
-            .patch(&url, set_tpm_clear)
-            .await
-            .map(|_status_code| ())
+            .patch_with_response(&url, set_tpm_clear)
+            .await?;
+        Ok(crate::ApplyResult::from_extended_info(info, &url))
     }
 
     async fn pending(&self) -> Result<HashMap<String, serde_json::Value>, RedfishError> {
         self.s.pending().await
     }
 
-    async fn clear_pending(&self) -> Result<(), RedfishError> {
+    async fn clear_pending(&self) -> Result<crate::ClearPendingResult, RedfishError> {
         self.delete_job_queue().await
     }
 
@@ -569,7 +655,7 @@ impl Redfish for Bmc {
             }
         })?;
 
-        let (_status_code, loc, _body) = self
+        let response = self
             .s
             .client
             .req_update_firmware_multipart(
@@ -582,13 +668,7 @@ impl Redfish for Bmc {
             )
             .await?;
 
-        let loc = match loc {
-            None => "Unknown".to_string(),
-            Some(x) => x,
-        };
-
-        // iDRAC returns the full endpoint, we just want the task ID
-        Ok(loc.replace("/redfish/v1/TaskService/Tasks/", ""))
+        response.task_id("UpdateService/MultipartUpload")
     }
 
     async fn get_tasks(&self) -> Result<Vec<String>, RedfishError> {
@@ -759,19 +839,25 @@ impl Redfish for Bmc {
 
     async fn change_uefi_password(
         &self,
-        current_uefi_password: &str,
-        new_uefi_password: &str,
-    ) -> Result<Option<String>, RedfishError> {
+        current_uefi_password: &SecretString,
+        new_uefi_password: &SecretString,
+    ) -> Result<crate::BiosPasswordChange, RedfishError> {
         // The uefi password cant be changed if the host is in lockdown
         if self.is_lockdown().await? {
             return Err(RedfishError::Lockdown);
         }
 
-        self.s
+        let change = self
+            .s
             .change_bios_password(UEFI_PASSWORD_NAME, current_uefi_password, new_uefi_password)
             .await?;
+        if !matches!(change, crate::BiosPasswordChange::Changed { .. }) {
+            return Ok(change);
+        }
 
-        Ok(Some(self.create_bios_config_job().await?))
+        Ok(crate::BiosPasswordChange::Changed {
+            job_id: Some(self.create_bios_config_job().await?),
+        })
     }
 
     async fn change_boot_order(&self, boot_array: Vec<String>) -> Result<(), RedfishError> {
@@ -893,7 +979,7 @@ impl Redfish for Bmc {
                     return Ok(None);
                 }
 
-                let url = format!("Systems/{}", self.s.system_id());
+                let url = format!("Systems/{}", self.s.resolve_system_id().await?);
                 let body = HashMap::from([(
                     "Boot",
                     HashMap::from([("BootOrder", vec![boot_option.id.clone()])]),
@@ -914,15 +1000,20 @@ impl Redfish for Bmc {
 
     async fn clear_uefi_password(
         &self,
-        current_uefi_password: &str,
+        current_uefi_password: &SecretString,
     ) -> Result<Option<String>, RedfishError> {
         let job_id = self.clear_uefi_password(current_uefi_password).await?;
         Ok(Some(job_id))
     }
 
-    async fn lockdown_bmc(&self, target: crate::EnabledDisabled) -> Result<(), RedfishError> {
+    async fn lockdown_bmc(&self, target: crate::EnabledDisabled) -> Result<bool, RedfishError> {
         use EnabledDisabled::*;
 
+        // If we can't tell, set it anyway
+        if matches!(self.bmc_lockdown_status().await, Ok(status) if status.is_fully(target)) {
+            return Ok(false);
+        }
+
         // XE9680's can't PXE boot for some reason
         let system = self.s.get_system().await?;
         let entry = match system.model.as_deref() {
@@ -931,9 +1022,15 @@ impl Redfish for Bmc {
         };
 
         match target {
-            Enabled => self.enable_bmc_lockdown(entry).await,
-            Disabled => self.disable_bmc_lockdown(entry).await,
+            Enabled => self.enable_bmc_lockdown(entry).await?,
+            Disabled => self.disable_bmc_lockdown(entry).await?,
         }
+        Ok(true)
+    }
+
+    // Dell's lockdown_status only looks at the BMC
+    async fn bmc_lockdown_status(&self) -> Result<Status, RedfishError> {
+        self.lockdown_status().await
     }
 
     async fn is_ipmi_over_lan_enabled(&self) -> Result<bool, RedfishError> {
@@ -943,7 +1040,7 @@ impl Redfish for Bmc {
     async fn enable_ipmi_over_lan(
         &self,
         target: crate::EnabledDisabled,
-    ) -> Result<(), RedfishError> {
+    ) -> Result<bool, RedfishError> {
         self.s.enable_ipmi_over_lan(target).await
     }
 
@@ -958,11 +1055,11 @@ impl Redfish for Bmc {
             .await
     }
 
-    async fn enable_rshim_bmc(&self) -> Result<(), RedfishError> {
+    async fn enable_rshim_bmc(&self) -> Result<bool, RedfishError> {
         self.s.enable_rshim_bmc().await
     }
 
-    async fn clear_nvram(&self) -> Result<(), RedfishError> {
+    async fn clear_nvram(&self) -> Result<crate::ApplyResult, RedfishError> {
         self.s.clear_nvram().await
     }
 
@@ -987,7 +1084,7 @@ impl Redfish for Bmc {
             None => {
                 return Err(RedfishError::MissingKey {
                     key: "Attributes".to_owned(),
-                    url: format!("Systems/{}/Bios", self.s.system_id()),
+                    url: format!("Systems/{}/Bios", self.s.resolve_system_id().await?),
                 })
             }
         };
@@ -1087,6 +1184,230 @@ impl Redfish for Bmc {
     ) -> Result<crate::model::component_integrity::Evidence, RedfishError> {
         self.s.get_evidence(url).await
     }
+
+    async fn reset_secure_boot_keys(
+        &self,
+        reset_type: crate::model::secure_boot::SecureBootResetType,
+    ) -> Result<(), RedfishError> {
+        self.s.reset_secure_boot_keys(reset_type).await
+    }
+
+    async fn get_boot_progress(&self) -> Result<crate::model::system::BootProgress, RedfishError> {
+        self.s.get_boot_progress().await
+    }
+
+    async fn get_post_codes(&self) -> Result<Vec<LogEntry>, RedfishError> {
+        self.s.get_post_codes().await
+    }
+
+    async fn get_environment_metrics(
+        &self,
+        chassis_id: &str,
+    ) -> Result<crate::model::environment_metrics::EnvironmentMetrics, RedfishError> {
+        self.s.get_environment_metrics(chassis_id).await
+    }
+
+    async fn get_energy_kwh(
+        &self,
+    ) -> Result<Vec<crate::model::sensor::EnergyReading>, RedfishError> {
+        self.s.get_energy_kwh().await
+    }
+
+    async fn get_sensors(
+        &self,
+        chassis_id: &str,
+        reading_type: Option<crate::model::sensor::ReadingType>,
+    ) -> Result<Vec<crate::model::sensor::Sensor>, RedfishError> {
+        self.s.get_sensors(chassis_id, reading_type).await
+    }
+
+    async fn wait_for_power_state(
+        &self,
+        target: crate::PowerState,
+        timeout: std::time::Duration,
+        poll: std::time::Duration,
+    ) -> Result<(), RedfishError> {
+        self.s.wait_for_power_state(target, timeout, poll).await
+    }
+
+    async fn pending_diff(&self) -> Result<Vec<crate::model::BiosAttrChange>, RedfishError> {
+        let pending = self.pending().await?;
+        self.s.bios_attr_changes(pending).await
+    }
+
+    async fn set_bios_attributes(
+        &self,
+        attrs: HashMap<String, serde_json::Value>,
+    ) -> Result<(), RedfishError> {
+        let targets = self.s.bios_attribute_targets(attrs).await?;
+        self.s
+            .write_bios_attribute_targets(targets, |settings| self.set_bios(settings))
+            .await
+    }
+
+    async fn set_uefi_http(
+        &self,
+        cfg: crate::model::oem::nvidia_viking::UefiHttpAttributes,
+    ) -> Result<(), RedfishError> {
+        self.s.set_uefi_http(cfg).await
+    }
+
+    async fn set_virtualization(
+        &self,
+        cfg: crate::model::oem::nvidia_viking::VirtAttributes,
+    ) -> Result<(), RedfishError> {
+        self.s.set_virtualization(cfg).await
+    }
+
+    async fn get_bios_lockdown(
+        &self,
+    ) -> Result<crate::model::oem::nvidia_viking::BiosLockdownAttributes, RedfishError> {
+        self.s.get_bios_lockdown().await
+    }
+
+    async fn get_decoded_sel(
+        &self,
+    ) -> Result<Vec<crate::model::sel::DecodedSelEntry>, RedfishError> {
+        let sel = self.get_system_event_log().await?;
+        Ok(sel
+            .iter()
+            .map(crate::model::sel::DecodedSelEntry::from)
+            .collect())
+    }
+
+    async fn resolve_origin(
+        &self,
+        entry: &crate::model::sel::LogEntry,
+    ) -> Result<crate::model::resource::Resource, RedfishError> {
+        self.s.resolve_origin(entry).await
+    }
+
+    async fn attach_to_update(
+        &self,
+        task_id: &str,
+    ) -> Result<crate::model::task::Task, RedfishError> {
+        self.s.attach_to_update(task_id).await
+    }
+
+    async fn get_port_metrics(
+        &self,
+        chassis_id: &str,
+        network_adapter: &str,
+        id: &str,
+    ) -> Result<crate::model::port::PortMetrics, RedfishError> {
+        self.s
+            .get_port_metrics(chassis_id, network_adapter, id)
+            .await
+    }
+
+    async fn get_fru_inventory(&self) -> Result<crate::model::fru::FruInventory, RedfishError> {
+        self.s.get_fru_inventory().await
+    }
+
+    async fn get_power_supplies(
+        &self,
+    ) -> Result<Vec<crate::model::power::PowerSupplyInfo>, RedfishError> {
+        self.s.get_power_supplies().await
+    }
+
+    async fn download_firmware_image(
+        &self,
+        inventory_id: &str,
+        out: &std::path::Path,
+    ) -> Result<(), RedfishError> {
+        self.s.download_firmware_image(inventory_id, out).await
+    }
+
+    async fn create_boot_option(&self, opt: NewBootOption) -> Result<String, RedfishError> {
+        self.s.create_boot_option(opt).await
+    }
+
+    async fn delete_boot_option(&self, option_id: &str) -> Result<(), RedfishError> {
+        self.s.delete_boot_option_for(self, option_id).await
+    }
+
+    async fn get_bios_prerequisites(
+        &self,
+        attrs: &HashMap<String, serde_json::Value>,
+    ) -> Result<HashMap<String, serde_json::Value>, RedfishError> {
+        self.s.get_bios_prerequisites(attrs).await
+    }
+
+    async fn get_bmc_journal(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<String>, RedfishError> {
+        self.s.get_bmc_journal(since).await
+    }
+
+    async fn get_all_metrics(&self) -> Result<crate::AllMetrics, RedfishError> {
+        crate::AllMetrics::gather(self).await
+    }
+
+    async fn get_power_restore_policy(&self) -> Result<crate::PowerRestorePolicy, RedfishError> {
+        self.s.get_power_restore_policy().await
+    }
+
+    async fn set_power_restore_policy(
+        &self,
+        policy: crate::PowerRestorePolicy,
+    ) -> Result<(), RedfishError> {
+        self.s.set_power_restore_policy(policy).await
+    }
+
+    async fn set_power_on_delay(&self, seconds: u32) -> Result<(), RedfishError> {
+        // iDRAC: AC power recovery delay, applied by the BIOS on the next reboot
+        let values = HashMap::from([
+            ("AcPwrRcvryDelay".to_string(), "User".into()),
+            ("AcPwrRcvryUserDelay".to_string(), seconds.into()),
+        ]);
+        self.set_bios(values).await
+    }
+
+    async fn get_manager_health(&self) -> Result<crate::model::ManagerHealth, RedfishError> {
+        self.s.get_manager_health().await
+    }
+
+    async fn get_active_conditions(&self) -> Result<Vec<crate::model::Condition>, RedfishError> {
+        self.s.get_active_conditions().await
+    }
+
+    async fn get_fabric_topology(
+        &self,
+    ) -> Result<Vec<crate::model::fabric::FabricTopology>, RedfishError> {
+        self.s.get_fabric_topology().await
+    }
+
+    async fn get_pcie_switches(
+        &self,
+        chassis_id: &str,
+    ) -> Result<Vec<crate::model::fabric::SwitchPorts>, RedfishError> {
+        self.s.get_pcie_switches(chassis_id).await
+    }
+
+    async fn reset_erot(&self, target: ComponentType) -> Result<(), RedfishError> {
+        self.s.reset_erot(target).await
+    }
+
+    async fn get_firmware_slots(
+        &self,
+        component: &str,
+    ) -> Result<Vec<crate::model::software_inventory::FirmwareSlot>, RedfishError> {
+        self.s.get_firmware_slots(component).await
+    }
+
+    async fn activate_firmware_slot(
+        &self,
+        component: &str,
+        slot: &str,
+    ) -> Result<(), RedfishError> {
+        self.s.activate_firmware_slot(component, slot).await
+    }
+
+    async fn get_pending_jobs(&self) -> Result<Vec<crate::model::job::Job>, RedfishError> {
+        let url = format!("Managers/{}/Jobs", self.s.manager_id());
+        self.s.pending_jobs_at(&url).await
+    }
 }
 
 impl Bmc {
@@ -1121,12 +1442,7 @@ impl Bmc {
                         url: "bios".to_string(),
                     });
                 };
-                let act =
-                    <$act>::deserialize(act_v).map_err(|e| RedfishError::JsonDeserializeError {
-                        url: "bios".to_string(),
-                        body: act_v.to_string(),
-                        source: e,
-                    })?;
+                let act: $act = crate::network::from_json_value("bios", act_v)?;
                 if exp != act {
                     diffs.push(MachineSetupDiff {
                         key: key.to_string(),
@@ -1253,7 +1569,10 @@ impl Bmc {
             attributes,
         };
 
-        let url = format!("Systems/{}/Bios/Settings", self.s.system_id());
+        let url = format!(
+            "Systems/{}/Bios/Settings",
+            self.s.resolve_system_id().await?
+        );
         let result = self.s.client.patch(&url, set_attrs).await;
 
         // Handle intermittent 400 errors for read-only attributes
@@ -1280,19 +1599,30 @@ impl Bmc {
     }
 
     // No changes can be applied if there are pending jobs
-    async fn delete_job_queue(&self) -> Result<(), RedfishError> {
+    async fn delete_job_queue(&self) -> Result<crate::ClearPendingResult, RedfishError> {
         // The queue can't be cleared if system lockdown is enabled
         if self.is_lockdown().await? {
             return Err(RedfishError::Lockdown);
         }
 
+        // JID_CLEARALL drops every job in the queue, finished or not, but only the
+        // unfinished ones were pending
+        let jobs = self.get_pending_jobs().await?;
+        if jobs.is_empty() {
+            return Ok(crate::ClearPendingResult::NothingPending);
+        }
+
         let url = format!(
             "Managers/{}/Oem/Dell/DellJobService/Actions/DellJobService.DeleteJobQueue",
             self.s.manager_id()
         );
         let mut body = HashMap::new();
         body.insert("JobID", "JID_CLEARALL".to_string());
-        self.s.client.post(&url, body).await.map(|_resp| ())
+        self.s
+            .client
+            .post(&url, body)
+            .await
+            .map(|_resp| crate::ClearPendingResult::Cleared(jobs.len()))
     }
 
     // Is system lockdown enabled?
@@ -1422,7 +1752,10 @@ impl Bmc {
             redfish_settings_apply_time: apply_time,
             attributes: lockdown,
         };
-        let url = format!("Systems/{}/Bios/Settings/", self.s.system_id());
+        let url = format!(
+            "Systems/{}/Bios/Settings/",
+            self.s.resolve_system_id().await?
+        );
         // Sometimes, these settings are read only.  Ignore those errors trying to set them.
         let ret = self
             .s
@@ -1560,7 +1893,7 @@ impl Bmc {
         let mut enabled = true;
         let mut disabled = true;
 
-        let url = &format!("Systems/{}/Bios", self.s.system_id());
+        let url = &format!("Systems/{}/Bios", self.s.resolve_system_id().await?);
         let (_status_code, bios): (_, dell::Bios) = self.s.client.get(url).await?;
         let bios = bios.attributes;
 
@@ -1746,7 +2079,10 @@ impl Bmc {
             redfish_settings_apply_time: apply_time,
             attributes: tpm,
         };
-        let url = format!("Systems/{}/Bios/Settings/", self.s.system_id());
+        let url = format!(
+            "Systems/{}/Bios/Settings/",
+            self.s.resolve_system_id().await?
+        );
         self.s
             .client
             .patch(&url, set_tpm_enabled)
@@ -1769,7 +2105,10 @@ impl Bmc {
             redfish_settings_apply_time: apply_time,
             attributes: tpm,
         };
-        let url = format!("Systems/{}/Bios/Settings/", self.s.system_id());
+        let url = format!(
+            "Systems/{}/Bios/Settings/",
+            self.s.resolve_system_id().await?
+        );
         self.s
             .client
             .patch(&url, set_tpm_disabled)
@@ -1786,10 +2125,7 @@ impl Bmc {
             "/redfish/v1/Systems/System.Embedded.1/Bios/Settings".to_string(),
         );
 
-        match self.s.client.post(url, arg).await? {
-            (_, Some(headers)) => self.parse_job_id_from_response_headers(url, headers).await,
-            (_, None) => Err(RedfishError::NoHeader),
-        }
+        self.s.client.post(url, arg).await?.task_id(url)
     }
 
     async fn machine_setup_attrs(
@@ -1802,7 +2138,7 @@ impl Bmc {
             None => {
                 return Err(RedfishError::MissingKey {
                     key: "SetBootOrderEn".to_owned(),
-                    url: format!("Systems/{}/Bios", self.s.system_id()),
+                    url: format!("Systems/{}/Bios", self.s.resolve_system_id().await?),
                 });
             }
         };
@@ -1841,7 +2177,7 @@ impl Bmc {
     /// Detailed here: https://github.com/dell/iDRAC-Redfish-Scripting/issues/308
     async fn clear_uefi_password(
         &self,
-        current_uefi_password: &str,
+        current_uefi_password: &SecretString,
     ) -> Result<String, RedfishError> {
         let system_configuration = SystemConfiguration {
             shutdown_type: "Forced".to_string(),
@@ -1849,8 +2185,10 @@ impl Bmc {
                 target: "BIOS".to_string(),
             },
             import_buffer: format!(
-                r##"<SystemConfiguration><Component FQDD="BIOS.Setup.1-1"><!-- <Attribute Name="OldSysPassword"></Attribute>--><!-- <Attribute Name="NewSysPassword"></Attribute>--><Attribute Name="OldSetupPassword">{current_uefi_password}</Attribute><Attribute Name="NewSetupPassword"></Attribute></Component></SystemConfiguration>"##
-            ),
+                r##"<SystemConfiguration><Component FQDD="BIOS.Setup.1-1"><!-- <Attribute Name="OldSysPassword"></Attribute>--><!-- <Attribute Name="NewSysPassword"></Attribute>--><Attribute Name="OldSetupPassword">{}</Attribute><Attribute Name="NewSetupPassword"></Attribute></Component></SystemConfiguration>"##,
+                current_uefi_password.expose_secret()
+            )
+            .into(),
         };
 
         self.import_system_configuration(system_configuration).await
@@ -1890,34 +2228,18 @@ impl Bmc {
         system_configuration: SystemConfiguration,
     ) -> Result<String, RedfishError> {
         let url = "Managers/iDRAC.Embedded.1/Actions/Oem/EID_674_Manager.ImportSystemConfiguration";
-        let (_status_code, _resp_body, resp_headers): (
-            _,
-            Option<HashMap<String, serde_json::Value>>,
-            Option<HeaderMap>,
-        ) = self
-            .s
+        self.s
             .client
-            .req(
-                Method::POST,
-                url,
-                Some(system_configuration),
-                None,
-                None,
-                Vec::new(),
-            )
-            .await?;
-
-        match resp_headers {
-            Some(headers) => self.parse_job_id_from_response_headers(url, headers).await,
-            None => Err(RedfishError::NoHeader),
-        }
+            .post(url, system_configuration)
+            .await?
+            .task_id(url)
     }
 
     async fn get_dpu_nw_device_function(
         &self,
         boot_interface_mac_address: &str,
     ) -> Result<NetworkDeviceFunction, RedfishError> {
-        let chassis = self.get_chassis(self.s.system_id()).await?;
+        let chassis = self.get_chassis(self.s.resolve_system_id().await?).await?;
         let na_id = match chassis.network_adapters {
             Some(id) => id,
             None => {
@@ -1956,8 +2278,7 @@ impl Bmc {
             for nw_dev_func in rc_nw_func.members {
                 if let Some(ref ethernet_info) = nw_dev_func.ethernet {
                     if let Some(ref mac) = ethernet_info.mac_address {
-                        let standardized_mac = mac.to_lowercase();
-                        if standardized_mac == boot_interface_mac_address.to_lowercase() {
+                        if normalize_mac(mac) == normalize_mac(boot_interface_mac_address) {
                             return Ok(nw_dev_func);
                         }
                     }
@@ -2052,10 +2373,7 @@ impl Bmc {
         let mut arg = HashMap::new();
         arg.insert("@Redfish.OperationApplyTime", "Immediate");
 
-        match self.s.client.post(&url, arg).await? {
-            (_, Some(headers)) => self.parse_job_id_from_response_headers(&url, headers).await,
-            (_, None) => Err(RedfishError::NoHeader),
-        }
+        self.s.client.post(&url, arg).await?.task_id(&url)
     }
 
     async fn get_storage_drives(&self, controller_id: &str) -> Result<Value, RedfishError> {
@@ -2097,10 +2415,7 @@ impl Bmc {
         arg.insert("RAIDType", Value::String(raid_type.to_string()));
         arg.insert("Drives", drive_info);
 
-        match self.s.client.post(&url, arg).await? {
-            (_, Some(headers)) => self.parse_job_id_from_response_headers(&url, headers).await,
-            (_, None) => Err(RedfishError::NoHeader),
-        }
+        self.s.client.post(&url, arg).await?.task_id(&url)
     }
 
     async fn get_lifecycle_controller_status(&self) -> Result<String, RedfishError> {
@@ -2109,22 +2424,15 @@ impl Bmc {
             self.s.manager_id()
         );
         let arg: HashMap<&'static str, Value> = HashMap::new();
-        let (_status_code, resp_body, _resp_headers): (
-            _,
-            Option<HashMap<String, serde_json::Value>>,
-            Option<HeaderMap>,
-        ) = self
-            .s
-            .client
-            .req(Method::POST, &url, Some(arg), None, None, Vec::new())
-            .await?;
-
-        let lc_status = match resp_body.unwrap_or_default().get("LCStatus") {
-            Some(status) => status.as_str().unwrap_or_default().to_string(),
-            None => todo!(),
-        };
+        let response = self.s.client.post(&url, arg).await?;
 
-        Ok(lc_status)
+        match response.body().and_then(|body| body.get("LCStatus")) {
+            Some(status) => Ok(status.as_str().unwrap_or_default().to_string()),
+            None => Err(RedfishError::MissingKey {
+                key: "LCStatus".to_string(),
+                url,
+            }),
+        }
     }
 
     async fn lifecycle_controller_is_ready(&self) -> Result<(), RedfishError> {
@@ -2214,3 +2522,53 @@ impl UpdateParameters {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use serde_json::json;
+
+    use super::Bmc;
+    use crate::fake_bmc::FakeBmc;
+
+    #[tokio::test]
+    async fn test_delete_job_queue_counts_pending() {
+        let jobs = "/redfish/v1/Managers/BMC/Jobs";
+        let job = |id: &str, state: &str| {
+            json!({
+                "@odata.id": format!("{jobs}/{id}"),
+                "@odata.type": "#DellJob.v1_4_0.DellJob",
+                "Id": id,
+                "JobState": state,
+            })
+        };
+        let fake = FakeBmc::start(&[
+            (
+                "/redfish/v1/Managers/BMC/Oem/Dell/DellAttributes/BMC".to_string(),
+                json!({"Attributes": {"Lockdown.1.SystemLockdown": "Disabled"}}),
+            ),
+            (
+                jobs.to_string(),
+                json!({
+                    "@odata.id": jobs,
+                    "@odata.type": "#DellJobCollection.DellJobCollection",
+                    "Name": "Jobs",
+                    "Members": [
+                        job("JID_1", "Completed"),
+                        job("JID_2", "Scheduled"),
+                        job("JID_3", "Running"),
+                    ],
+                }),
+            ),
+        ])
+        .await;
+        let bmc = Bmc::new(fake.standard()).unwrap();
+        assert_eq!(
+            bmc.delete_job_queue().await.unwrap(),
+            crate::ClearPendingResult::Cleared(2)
+        );
+        let writes = fake.writes();
+        assert_eq!(writes.len(), 1);
+        assert_eq!(writes[0].body, json!({"JobID": "JID_CLEARALL"}));
+    }
+}
@@ -36,9 +36,11 @@ pub enum RedfishError {
         response_body: String,
     },
 
-    #[error("Could not deserialize response from {url}. Body: {body}. {source}")]
+    #[error("Could not deserialize response from {url} at {path}. Body: {body}. {source}")]
     JsonDeserializeError {
         url: String,
+        /// JSON path of the field that failed, e.g. `Members[3].Status.State`
+        path: String,
         body: String,
         source: serde_json::Error,
     },
@@ -53,9 +55,15 @@ pub enum RedfishError {
     #[error("Remote returned empty body")]
     NoContent,
 
+    #[error("The BMC's Systems collection is empty")]
+    NoSystemsFound,
+
     #[error("Remote returned empty header")]
     NoHeader,
 
+    #[error("{url} finished without starting a task or job")]
+    NoTask { url: String },
+
     #[error("No such boot option {0}")]
     MissingBootOption(String),
 
@@ -111,6 +119,59 @@ pub enum RedfishError {
         resource_uri: String,
     },
 
+    #[error("Invalid BIOS attributes: {}", .0.iter().map(|e| e.to_string()).collect::<Vec<String>>().join("; "))]
+    InvalidBiosAttributes(Vec<crate::model::BiosAttributeError>),
+
+    #[error("Boot target {target} is not available on this system, allowed: {}", .allowed.join(", "))]
+    BootTargetNotAvailable {
+        target: String,
+        allowed: Vec<String>,
+    },
+
+    #[error("Timed out waiting for {0}")]
+    Timeout(String),
+
+    #[error("machine_setup step {step} failed: {source}")]
+    MachineSetupFailed {
+        step: String,
+        report: crate::MachineSetupReport,
+        source: Box<RedfishError>,
+    },
+
+    #[error("BIOS attributes {} were applied, writing the rest to the settings object failed: {source}", .applied.join(", "))]
+    BiosAttributesPartiallyApplied {
+        applied: Vec<String>,
+        source: Box<RedfishError>,
+    },
+
+    #[error("Circuit open for BMC {host} after repeated failures, retry in {retry_in:?}")]
+    CircuitOpen {
+        host: String,
+        retry_in: std::time::Duration,
+    },
+
+    #[error("Response from {url} is larger than the {limit} byte limit")]
+    ResponseTooLarge { url: String, limit: usize },
+
+    #[error("Operation cancelled")]
+    Cancelled,
+
+    #[error("{property} at {url} is {actual} after writing it, expected {expected}")]
+    VerificationFailed {
+        url: String,
+        property: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("{property} requires {required} or later, {url} is {actual}")]
+    SchemaVersionTooOld {
+        property: String,
+        required: String,
+        actual: String,
+        url: String,
+    },
+
     #[error("DPU not found")]
     NoDpu, // suport zero-dpu, but warn about it too
 
@@ -142,4 +203,42 @@ impl RedfishError {
                 response_body: _,
             } if *status_code == StatusCode::NOT_FOUND)
     }
+
+    /// The Redfish error envelope (`{"error": {"code", "message", "@Message.ExtendedInfo"}}`)
+    /// of a failed request, if the BMC sent one.
+    pub fn error_envelope(&self) -> Option<crate::model::error::Error> {
+        match self {
+            RedfishError::HTTPErrorCode { response_body, .. } => {
+                serde_json::from_str(response_body).ok()
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RedfishError;
+
+    #[test]
+    fn test_error_envelope() {
+        let err = RedfishError::HTTPErrorCode {
+            url: "AccountService/Accounts/3".to_string(),
+            status_code: reqwest::StatusCode::BAD_REQUEST,
+            response_body: r#"{"error": {"code": "Base.1.8.GeneralError", "message": "A general error has occurred.", "@Message.ExtendedInfo": [{"MessageId": "Base.1.8.ResourceCannotBeDeleted", "Message": "The delete request failed because the resource requested cannot be deleted."}]}}"#.to_string(),
+        };
+        let envelope = err.error_envelope().unwrap();
+        assert_eq!(envelope.error.code, "Base.1.8.GeneralError");
+        assert_eq!(
+            envelope.error.extended[0].message_id,
+            "Base.1.8.ResourceCannotBeDeleted"
+        );
+
+        let err = RedfishError::HTTPErrorCode {
+            url: "SessionService/Sessions/7".to_string(),
+            status_code: reqwest::StatusCode::NOT_FOUND,
+            response_body: "<html>Not Found</html>".to_string(),
+        };
+        assert!(err.error_envelope().is_none());
+    }
 }
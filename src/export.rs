@@ -0,0 +1,500 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2025 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: MIT
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a
+ * copy of this software and associated documentation files (the "Software"),
+ * to deal in the Software without restriction, including without limitation
+ * the rights to use, copy, modify, merge, publish, distribute, sublicense,
+ * and/or sell copies of the Software, and to permit persons to whom the
+ * Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL
+ * THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+ * FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+//! A dump of a node's Redfish tree as one JSON document, see `Redfish::export_state`.
+//!
+//! The crawl follows links from the service root down the URI hierarchy only: a link is
+//! followed if its target is under the resource it was found in (`Systems` ->
+//! `Systems/1` -> `Systems/1/Bios`), so each resource is fetched once and the result is a
+//! tree. Each followed link `{"@odata.id": ...}` is replaced in place by the resource it
+//! points to. Other links, such as those under `Links`, are kept as they are.
+//!
+//! `diff_state` compares two such exports, e.g. from before and after maintenance.
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use futures_util::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::model::ODataId;
+use crate::network::{RedfishHttpClient, REDFISH_ENDPOINT};
+use crate::RedfishError;
+
+/// Requests in flight at once. BMCs are slow and easily overwhelmed.
+const EXPORT_CONCURRENCY: usize = 8;
+
+/// Set on the root of the export when a cap stopped the crawl early
+pub const EXPORT_TRUNCATED: &str = "@export.truncated";
+
+/// Replaces a resource that couldn't be fetched, with the error
+pub const EXPORT_ERROR: &str = "@export.error";
+
+/// Where `Redfish::export_state_with` stops crawling. The defaults are what `export_state` uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExportLimits {
+    /// Resources fetched, the service root included
+    pub max_resources: usize,
+    /// Bytes of JSON fetched. A resource that would go past it is left out.
+    pub max_bytes: usize,
+}
+
+impl Default for ExportLimits {
+    fn default() -> Self {
+        ExportLimits {
+            max_resources: 5000,
+            max_bytes: 64 * 1024 * 1024,
+        }
+    }
+}
+
+pub(crate) async fn export_state(
+    client: &RedfishHttpClient,
+    depth: u8,
+    limits: ExportLimits,
+) -> Result<Value, RedfishError> {
+    let root_id = format!("/{REDFISH_ENDPOINT}");
+    // The service root must work, anything below it may fail
+    let (_status_code, root): (_, Value) = client.get("").await?;
+
+    let mut bytes = root.to_string().len();
+    let mut level = child_links(&root_id, &root);
+    let mut seen: HashSet<String> = level.iter().cloned().collect();
+    let mut fetched: HashMap<String, Value> = HashMap::from([(root_id.clone(), root)]);
+    let mut truncated = false;
+
+    for d in 1..=depth {
+        if level.is_empty() {
+            break;
+        }
+        let room = limits.max_resources.saturating_sub(fetched.len());
+        if level.len() > room {
+            level.truncate(room);
+            truncated = true;
+        }
+        let mut results = stream::iter(level)
+            .map(|id| async move {
+                let path = ODataId {
+                    odata_id: id.clone(),
+                }
+                .relative_path();
+                let value = match client.get::<Value>(&path).await {
+                    Ok((_status_code, value)) => value,
+                    Err(e) => serde_json::json!({ EXPORT_ERROR: e.to_string() }),
+                };
+                (id, value)
+            })
+            .buffer_unordered(EXPORT_CONCURRENCY);
+
+        let mut next = Vec::new();
+        let mut full = false;
+        while let Some((id, value)) = results.next().await {
+            let size = value.to_string().len();
+            if bytes + size > limits.max_bytes {
+                // Dropping the stream cancels the requests still in flight
+                full = true;
+                break;
+            }
+            bytes += size;
+            if d < depth {
+                for child in child_links(&id, &value) {
+                    if seen.insert(child.clone()) {
+                        next.push(child);
+                    }
+                }
+            }
+            fetched.insert(id, value);
+        }
+        if full {
+            truncated = true;
+            break;
+        }
+        level = next;
+    }
+
+    let mut doc = nest(&root_id, &mut fetched);
+    if truncated {
+        if let Some(obj) = doc.as_object_mut() {
+            obj.insert(EXPORT_TRUNCATED.to_string(), Value::Bool(true));
+        }
+    }
+    Ok(doc)
+}
+
+/// The links in value to resources under parent_id, each once
+fn child_links(parent_id: &str, value: &Value) -> Vec<String> {
+    let prefix = format!("{}/", parent_id.trim_end_matches('/'));
+    let mut links = Vec::new();
+    walk_links(value, &mut |id| {
+        let id = id.trim_end_matches('/');
+        if id.starts_with(&prefix) && !id.contains('#') && !links.iter().any(|l| l == id) {
+            links.push(id.to_string());
+        }
+    });
+    links
+}
+
+// Call f with the target of every bare link ({"@odata.id": ...} and nothing else) in value
+fn walk_links(value: &Value, f: &mut impl FnMut(&str)) {
+    match value {
+        Value::Object(obj) => {
+            if let (1, Some(Value::String(id))) = (obj.len(), obj.get("@odata.id")) {
+                f(id);
+                return;
+            }
+            obj.values().for_each(|v| walk_links(v, f));
+        }
+        Value::Array(arr) => arr.iter().for_each(|v| walk_links(v, f)),
+        _ => {}
+    }
+}
+
+// The resource at id with the links to its fetched children replaced by the children
+fn nest(id: &str, fetched: &mut HashMap<String, Value>) -> Value {
+    let Some(mut value) = fetched.remove(id) else {
+        return serde_json::json!({ "@odata.id": id });
+    };
+    let children: Vec<String> = child_links(id, &value)
+        .into_iter()
+        .filter(|child| fetched.contains_key(child))
+        .collect();
+    let nested: HashMap<String, Value> = children
+        .into_iter()
+        .map(|child| {
+            let v = nest(&child, fetched);
+            (child, v)
+        })
+        .collect();
+    replace_links(&mut value, &nested);
+    value
+}
+
+fn replace_links(value: &mut Value, nested: &HashMap<String, Value>) {
+    match value {
+        Value::Object(obj) => {
+            if let (1, Some(Value::String(id))) = (obj.len(), obj.get("@odata.id")) {
+                if let Some(child) = nested.get(id.trim_end_matches('/')) {
+                    *value = child.clone();
+                }
+                return;
+            }
+            obj.values_mut().for_each(|v| replace_links(v, nested));
+        }
+        Value::Array(arr) => arr.iter_mut().for_each(|v| replace_links(v, nested)),
+        _ => {}
+    }
+}
+
+/// Properties that change by themselves, left out of `diff_state`: clocks, sensor readings,
+/// counters and etags. Matched by name at any depth, and everything under them is skipped too.
+pub const VOLATILE_PROPERTIES: &[&str] = &[
+    "@odata.etag",
+    "DateTime",
+    "DateTimeLocalOffset",
+    "LastResetTime",
+    "Created",
+    "Modified",
+    "Reading",
+    "ReadingCelsius",
+    "ReadingRPM",
+    "ReadingVolts",
+    "ReadingAmps",
+    "ReadingWatts",
+    "PowerConsumedWatts",
+    "PowerMetrics",
+    "EnergykWh",
+    "LifetimeReading",
+    "SensorResetTime",
+    "PowerOnHours",
+    "UptimeSeconds",
+];
+
+/// One property that differs between two exports. `old` is None if the property was added,
+/// `new` if it was removed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateChange {
+    /// @odata.id of the resource the property is in
+    pub resource: String,
+    /// Path to the property within the resource, e.g. `Boot.BootOrder` or `Members[2].Name`
+    pub property: String,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+/// The differences between two `Redfish::export_state` documents, ignoring
+/// VOLATILE_PROPERTIES.
+pub fn diff_state(old: &Value, new: &Value) -> Vec<StateChange> {
+    diff_state_with(old, new, VOLATILE_PROPERTIES)
+}
+
+/// diff_state with your own list of properties to ignore
+pub fn diff_state_with(old: &Value, new: &Value, ignore: &[&str]) -> Vec<StateChange> {
+    let mut changes = Vec::new();
+    diff_value("", "", Some(old), Some(new), ignore, &mut changes);
+    changes
+}
+
+fn diff_value(
+    resource: &str,
+    property: &str,
+    old: Option<&Value>,
+    new: Option<&Value>,
+    ignore: &[&str],
+    changes: &mut Vec<StateChange>,
+) {
+    match (old, new) {
+        (Some(Value::Object(o)), Some(Value::Object(n))) => {
+            // A nested resource starts its own property paths
+            let (resource, property) = match n.get("@odata.id").and_then(Value::as_str) {
+                Some(id) if n.len() > 1 => (id, ""),
+                _ => (resource, property),
+            };
+            let keys: BTreeSet<&String> = o.keys().chain(n.keys()).collect();
+            for key in keys {
+                if ignore.contains(&key.as_str()) {
+                    continue;
+                }
+                let path = match property {
+                    "" => key.to_string(),
+                    _ => format!("{property}.{key}"),
+                };
+                diff_value(resource, &path, o.get(key), n.get(key), ignore, changes);
+            }
+        }
+        (Some(Value::Array(o)), Some(Value::Array(n)))
+            if is_resource_list(o) && is_resource_list(n) =>
+        {
+            // Members, matched by @odata.id so that a new or removed one doesn't shift the rest
+            let (o_ids, n_ids) = (members_by_id(o), members_by_id(n));
+            let ids: BTreeSet<&str> = o_ids.keys().chain(n_ids.keys()).copied().collect();
+            for id in ids {
+                let (ov, nv) = (o_ids.get(id).copied(), n_ids.get(id).copied());
+                match (ov, nv) {
+                    (Some(_), Some(_)) => diff_value(id, "", ov, nv, ignore, changes),
+                    _ => changes.push(StateChange {
+                        resource: resource.to_string(),
+                        property: format!("{property}[{id}]"),
+                        old: ov.cloned(),
+                        new: nv.cloned(),
+                    }),
+                }
+            }
+        }
+        (Some(Value::Array(o)), Some(Value::Array(n)))
+            if o.len() == n.len() && o.iter().chain(n).any(|v| v.is_object()) =>
+        {
+            for (i, (ov, nv)) in o.iter().zip(n).enumerate() {
+                let path = format!("{property}[{i}]");
+                diff_value(resource, &path, Some(ov), Some(nv), ignore, changes);
+            }
+        }
+        _ if old == new => {}
+        _ => changes.push(StateChange {
+            resource: resource.to_string(),
+            property: property.to_string(),
+            old: old.cloned(),
+            new: new.cloned(),
+        }),
+    }
+}
+
+// A non-empty array of objects that all have an @odata.id, e.g. a collection's Members
+fn is_resource_list(arr: &[Value]) -> bool {
+    !arr.is_empty()
+        && arr
+            .iter()
+            .all(|v| v.get("@odata.id").is_some_and(Value::is_string))
+}
+
+fn members_by_id(arr: &[Value]) -> BTreeMap<&str, &Value> {
+    arr.iter()
+        .filter_map(|v| Some((v.get("@odata.id")?.as_str()?, v)))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use super::{
+        child_links, diff_state, diff_state_with, export_state, nest, ExportLimits, StateChange,
+        EXPORT_TRUNCATED,
+    };
+    use crate::fake_bmc::FakeBmc;
+
+    #[test]
+    fn test_nest() {
+        let root = json!({
+            "@odata.id": "/redfish/v1",
+            "Systems": {"@odata.id": "/redfish/v1/Systems"},
+            "Chassis": {"@odata.id": "/redfish/v1/Chassis"},
+        });
+        let systems = json!({
+            "@odata.id": "/redfish/v1/Systems",
+            "Members": [{"@odata.id": "/redfish/v1/Systems/1"}],
+        });
+        let system = json!({
+            "@odata.id": "/redfish/v1/Systems/1",
+            "Bios": {"@odata.id": "/redfish/v1/Systems/1/Bios"},
+            "Links": {"Chassis": [{"@odata.id": "/redfish/v1/Chassis/1"}]},
+        });
+        let mut links = child_links("/redfish/v1", &root);
+        links.sort();
+        assert_eq!(links, vec!["/redfish/v1/Chassis", "/redfish/v1/Systems"]);
+        // Links elsewhere in the tree aren't children
+        assert_eq!(
+            child_links("/redfish/v1/Systems/1", &system),
+            vec!["/redfish/v1/Systems/1/Bios"]
+        );
+
+        // Chassis and Bios weren't fetched, so stay links
+        let mut fetched = HashMap::from([
+            ("/redfish/v1".to_string(), root),
+            ("/redfish/v1/Systems".to_string(), systems),
+            ("/redfish/v1/Systems/1".to_string(), system.clone()),
+        ]);
+        let doc = nest("/redfish/v1", &mut fetched);
+        assert_eq!(doc["Systems"]["Members"][0], system);
+        assert_eq!(doc["Chassis"], json!({"@odata.id": "/redfish/v1/Chassis"}));
+        assert!(fetched.is_empty());
+    }
+
+    #[test]
+    fn test_diff_state() {
+        let old = json!({
+            "@odata.id": "/redfish/v1",
+            "Systems": {
+                "@odata.id": "/redfish/v1/Systems",
+                "Members": [
+                    {
+                        "@odata.id": "/redfish/v1/Systems/1",
+                        "@odata.etag": "\"1\"",
+                        "Boot": {"BootOrder": ["Boot0001", "Boot0002"]},
+                        "PowerState": "On",
+                        "Links": {"Chassis": [{"@odata.id": "/redfish/v1/Chassis/1"}]},
+                    },
+                    {"@odata.id": "/redfish/v1/Systems/2", "PowerState": "On"},
+                ],
+            },
+            "Chassis": {"@odata.id": "/redfish/v1/Chassis/1", "Temperatures": [{"ReadingCelsius": 40}]},
+        });
+        let new = json!({
+            "@odata.id": "/redfish/v1",
+            "Systems": {
+                "@odata.id": "/redfish/v1/Systems",
+                "Members": [
+                    {"@odata.id": "/redfish/v1/Systems/3", "PowerState": "Off"},
+                    {
+                        "@odata.id": "/redfish/v1/Systems/1",
+                        "@odata.etag": "\"2\"",
+                        "Boot": {"BootOrder": ["Boot0002", "Boot0001"]},
+                        "PowerState": "On",
+                        "Links": {"Chassis": [{"@odata.id": "/redfish/v1/Chassis/1"}]},
+                    },
+                ],
+            },
+            "Chassis": {"@odata.id": "/redfish/v1/Chassis/1", "Temperatures": [{"ReadingCelsius": 45}]},
+        });
+
+        let changes = diff_state(&old, &new);
+        assert_eq!(
+            changes,
+            vec![
+                StateChange {
+                    resource: "/redfish/v1/Systems/1".to_string(),
+                    property: "Boot.BootOrder".to_string(),
+                    old: Some(json!(["Boot0001", "Boot0002"])),
+                    new: Some(json!(["Boot0002", "Boot0001"])),
+                },
+                StateChange {
+                    resource: "/redfish/v1/Systems".to_string(),
+                    property: "Members[/redfish/v1/Systems/2]".to_string(),
+                    old: Some(json!({"@odata.id": "/redfish/v1/Systems/2", "PowerState": "On"})),
+                    new: None,
+                },
+                StateChange {
+                    resource: "/redfish/v1/Systems".to_string(),
+                    property: "Members[/redfish/v1/Systems/3]".to_string(),
+                    old: None,
+                    new: Some(json!({"@odata.id": "/redfish/v1/Systems/3", "PowerState": "Off"})),
+                },
+            ]
+        );
+
+        // Without the ignore-list the etag and sensor reading show up too
+        let changes = diff_state_with(&old, &new, &[]);
+        assert_eq!(changes.len(), 5);
+        assert!(changes
+            .iter()
+            .any(|c| c.property == "Temperatures[0].ReadingCelsius"
+                && c.resource == "/redfish/v1/Chassis/1"));
+        assert!(diff_state(&old, &old).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_export_state_truncates() {
+        let root = json!({
+            "@odata.id": "/redfish/v1",
+            "Systems": {"@odata.id": "/redfish/v1/Systems"},
+            "Chassis": {"@odata.id": "/redfish/v1/Chassis"}
+        });
+        let systems = json!({"@odata.id": "/redfish/v1/Systems", "Name": "x".repeat(1000)});
+        let chassis = json!({"@odata.id": "/redfish/v1/Chassis", "Name": "y".repeat(1000)});
+        let root_len = root.to_string().len();
+        let fake = FakeBmc::start(&[
+            ("/redfish/v1", root.clone()),
+            ("/redfish/v1/Systems", systems),
+            ("/redfish/v1/Chassis", chassis),
+        ])
+        .await;
+        let expanded = |doc: &serde_json::Value| {
+            ["Systems", "Chassis"]
+                .iter()
+                .filter(|k| doc[**k].get("Name").is_some())
+                .count()
+        };
+
+        let doc = export_state(&fake.client(), 2, ExportLimits::default())
+            .await
+            .unwrap();
+        assert_eq!(expanded(&doc), 2);
+        assert!(doc.get(EXPORT_TRUNCATED).is_none());
+
+        // Room for the root and one of the two collections only
+        let limits = ExportLimits {
+            max_bytes: root_len + 1500,
+            ..Default::default()
+        };
+        let doc = export_state(&fake.client(), 2, limits).await.unwrap();
+        assert_eq!(expanded(&doc), 1);
+        assert_eq!(doc[EXPORT_TRUNCATED], true);
+
+        let limits = ExportLimits {
+            max_resources: 2,
+            ..Default::default()
+        };
+        let doc = export_state(&fake.client(), 2, limits).await.unwrap();
+        assert_eq!(expanded(&doc), 1);
+        assert_eq!(doc[EXPORT_TRUNCATED], true);
+    }
+}
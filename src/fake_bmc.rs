@@ -0,0 +1,325 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2025 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: MIT
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a
+ * copy of this software and associated documentation files (the "Software"),
+ * to deal in the Software without restriction, including without limitation
+ * the rights to use, copy, modify, merge, publish, distribute, sublicense,
+ * and/or sell copies of the Software, and to permit persons to whom the
+ * Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL
+ * THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+ * FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+//! A fake BMC for unit tests: plain HTTP on localhost, serving JSON resources from memory and
+//! recording every request, so tests can check what a call sent and how it read the answer.
+// Not every test uses every helper
+#![allow(dead_code)]
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use crate::network::RedfishHttpClient;
+use crate::standard::RedfishStandard;
+use crate::Endpoint;
+
+/// A request the fake BMC received. `path` has no query string and no trailing slash.
+#[derive(Debug, Clone)]
+pub(crate) struct Request {
+    pub method: String,
+    pub path: String,
+    pub query: Option<String>,
+    /// Lowercase names
+    pub headers: HashMap<String, String>,
+    /// Null when there was none, a string when it wasn't JSON
+    pub body: Value,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Response {
+    pub fn json(body: Value) -> Self {
+        Self::status(200).with_body(body.to_string())
+    }
+
+    pub fn status(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: String::new(),
+        }
+    }
+
+    pub fn with_body(mut self, body: String) -> Self {
+        self.body = body;
+        self
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+/// Resources by path, e.g. /redfish/v1/Systems/1
+pub(crate) type Resources = HashMap<String, Value>;
+
+type Handler = dyn Fn(&Request, &mut Resources) -> Option<Response> + Send + Sync;
+
+/// By default GET returns the resource at the path (404 if there is none), PATCH merges the
+/// body into it and POST and DELETE succeed with 204. A handler set with `with_handler` sees
+/// every request first and can answer it instead.
+#[derive(Clone)]
+pub(crate) struct FakeBmc {
+    port: u16,
+    pub requests: Arc<Mutex<Vec<Request>>>,
+    pub resources: Arc<Mutex<Resources>>,
+    handler: Arc<Mutex<Option<Arc<Handler>>>>,
+}
+
+impl FakeBmc {
+    pub async fn start<P: AsRef<str>>(resources: &[(P, Value)]) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let bmc = FakeBmc {
+            port: listener.local_addr().unwrap().port(),
+            requests: Arc::new(Mutex::new(Vec::new())),
+            resources: Arc::new(Mutex::new(
+                resources
+                    .iter()
+                    .map(|(path, v)| {
+                        let path = path.as_ref().trim_end_matches('/');
+                        (path.to_string(), v.clone())
+                    })
+                    .collect(),
+            )),
+            handler: Arc::new(Mutex::new(None)),
+        };
+        let server = bmc.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let server = server.clone();
+                tokio::spawn(async move { server.serve(stream).await });
+            }
+        });
+        bmc
+    }
+
+    pub fn with_handler(
+        self,
+        handler: impl Fn(&Request, &mut Resources) -> Option<Response> + Send + Sync + 'static,
+    ) -> Self {
+        *self.handler.lock().unwrap() = Some(Arc::new(handler));
+        self
+    }
+
+    pub fn endpoint(&self) -> Endpoint {
+        Endpoint {
+            host: "127.0.0.1".to_string(),
+            port: Some(self.port),
+            user: Some("root".to_string()),
+            password: Some("secret".into()),
+        }
+    }
+
+    pub fn client(&self) -> RedfishHttpClient {
+        RedfishHttpClient::new(reqwest::Client::new(), self.endpoint(), Vec::new())
+            .with_plain_http()
+    }
+
+    /// A standard client for system 1 and manager BMC
+    pub fn standard(&self) -> RedfishStandard {
+        self.standard_from(self.client())
+    }
+
+    /// Like `standard`, on a client configured by the caller, e.g. with_verify_writes
+    pub fn standard_from(&self, client: RedfishHttpClient) -> RedfishStandard {
+        let mut s = RedfishStandard::new(client);
+        s.set_system_id("1").unwrap();
+        s.set_manager_id("BMC").unwrap();
+        s
+    }
+
+    /// The requests other than GETs, in order
+    pub fn writes(&self) -> Vec<Request> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| r.method != "GET")
+            .cloned()
+            .collect()
+    }
+
+    pub fn resource(&self, path: &str) -> Option<Value> {
+        self.resources.lock().unwrap().get(path).cloned()
+    }
+
+    async fn serve(&self, mut stream: tokio::net::TcpStream) {
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 4096];
+        let header_end = loop {
+            if let Some(i) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                break i + 4;
+            }
+            match stream.read(&mut chunk).await {
+                Ok(0) | Err(_) => return,
+                Ok(n) => buf.extend_from_slice(&chunk[..n]),
+            }
+        };
+        let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+        let mut lines = head.lines();
+        let mut start = lines.next().unwrap_or_default().split(' ');
+        let method = start.next().unwrap_or_default().to_string();
+        let target = start.next().unwrap_or_default().to_string();
+        let headers: HashMap<String, String> = lines
+            .filter_map(|l| l.split_once(':'))
+            .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string()))
+            .collect();
+        let length: usize = headers
+            .get("content-length")
+            .and_then(|l| l.parse().ok())
+            .unwrap_or(0);
+        while buf.len() < header_end + length {
+            match stream.read(&mut chunk).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => buf.extend_from_slice(&chunk[..n]),
+            }
+        }
+        let raw_body = String::from_utf8_lossy(&buf[header_end..]).to_string();
+        let body = if raw_body.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_str(&raw_body).unwrap_or(Value::String(raw_body))
+        };
+        let (path, query) = match target.split_once('?') {
+            Some((p, q)) => (p.to_string(), Some(q.to_string())),
+            None => (target.clone(), None),
+        };
+        let request = Request {
+            method,
+            path: path.trim_end_matches('/').to_string(),
+            query,
+            headers,
+            body,
+        };
+        self.requests.lock().unwrap().push(request.clone());
+
+        let response = {
+            let handler = self.handler.lock().unwrap().clone();
+            let mut resources = self.resources.lock().unwrap();
+            handler
+                .and_then(|h| h(&request, &mut resources))
+                .unwrap_or_else(|| default_response(&request, &mut resources))
+        };
+        let mut out = format!(
+            "HTTP/1.1 {} Fake\r\nContent-Length: {}\r\nConnection: close\r\n",
+            response.status,
+            response.body.len()
+        );
+        if !response.body.is_empty() {
+            out.push_str("Content-Type: application/json\r\n");
+        }
+        for (name, value) in &response.headers {
+            out.push_str(&format!("{name}: {value}\r\n"));
+        }
+        out.push_str("\r\n");
+        out.push_str(&response.body);
+        let _ = stream.write_all(out.as_bytes()).await;
+        let _ = stream.shutdown().await;
+    }
+}
+
+fn default_response(request: &Request, resources: &mut Resources) -> Response {
+    match request.method.as_str() {
+        "GET" => match resources.get(&request.path) {
+            Some(v) => Response::json(v.clone()),
+            None => Response::status(404),
+        },
+        "PATCH" => match resources.get_mut(&request.path) {
+            Some(v) => {
+                merge(v, &request.body);
+                Response::status(204)
+            }
+            None => Response::status(404),
+        },
+        "DELETE" => {
+            resources.remove(&request.path);
+            Response::status(204)
+        }
+        _ => Response::status(204),
+    }
+}
+
+fn merge(target: &mut Value, patch: &Value) {
+    match (target, patch) {
+        (Value::Object(t), Value::Object(p)) => {
+            for (k, v) in p {
+                match t.get_mut(k) {
+                    Some(existing) if existing.is_object() && v.is_object() => merge(existing, v),
+                    _ => {
+                        t.insert(k.clone(), v.clone());
+                    }
+                }
+            }
+        }
+        (t, p) => *t = p.clone(),
+    }
+}
+
+/// Systems/1/Bios, its AttributeRegistry, in which LiveAttr applies without a reset and
+/// StagedAttr needs one, and an empty settings object at settings_path. With annotated, the
+/// Bios resource points at the settings object with `@Redfish.Settings`.
+pub(crate) fn bios_resources(settings_path: &str, annotated: bool) -> Vec<(String, Value)> {
+    let registry = "/redfish/v1/Registries/BiosAttributeRegistry.v1_0_0";
+    let mut bios = json!({
+        "@odata.id": "/redfish/v1/Systems/1/Bios",
+        "AttributeRegistry": "BiosAttributeRegistry.v1_0_0",
+        "Attributes": {"LiveAttr": false, "StagedAttr": false}
+    });
+    if annotated {
+        bios["@Redfish.Settings"] = json!({"SettingsObject": {"@odata.id": settings_path}});
+    }
+    vec![
+        ("/redfish/v1/Systems/1/Bios".to_string(), bios),
+        (
+            registry.to_string(),
+            json!({
+                "@odata.id": registry,
+                "@odata.type": "#MessageRegistryFile.v1_1_0.MessageRegistryFile",
+                "Id": "BiosAttributeRegistry.v1_0_0",
+                "Location": [{"Language": "en", "Uri": format!("{registry}/Registry")}]
+            }),
+        ),
+        (
+            format!("{registry}/Registry"),
+            json!({
+                "Id": "BiosAttributeRegistry.v1_0_0",
+                "RegistryEntries": {"Attributes": [
+                    {"AttributeName": "LiveAttr", "Type": "Boolean", "ResetRequired": false},
+                    {"AttributeName": "StagedAttr", "Type": "Boolean", "ResetRequired": true}
+                ]}
+            }),
+        ),
+        (
+            settings_path.to_string(),
+            json!({"@odata.id": settings_path}),
+        ),
+    ]
+}
@@ -51,8 +51,9 @@ use crate::{
     standard::RedfishStandard,
     BiosProfileType, Boot, BootOptions, Collection, Deserialize,
     EnabledDisabled::{self, Disabled, Enabled},
-    JobState, MachineSetupDiff, MachineSetupStatus, OData, ODataId, PCIeDevice, PowerState,
-    Redfish, RedfishError, Resource, RoleId, Serialize, Status, StatusInternal, SystemPowerControl,
+    JobState, MachineSetupDiff, MachineSetupStatus, NewBootOption, OData, ODataId, PCIeDevice,
+    PowerState, Redfish, RedfishError, Resource, RoleId, SecretString, Serialize, Status,
+    StatusInternal, SystemPowerControl,
 };
 
 // The following is specific for the HPE machine since the HPE redfish
@@ -101,10 +102,14 @@ impl Bmc {
 
 #[async_trait::async_trait]
 impl Redfish for Bmc {
+    fn standard(&self) -> &RedfishStandard {
+        &self.s
+    }
+
     async fn create_user(
         &self,
         username: &str,
-        password: &str,
+        password: &SecretString,
         role_id: RoleId,
     ) -> Result<(), RedfishError> {
         self.s.create_user(username, password, role_id).await
@@ -118,14 +123,14 @@ impl Redfish for Bmc {
         self.s.change_username(old_name, new_name).await
     }
 
-    async fn change_password(&self, user: &str, new: &str) -> Result<(), RedfishError> {
+    async fn change_password(&self, user: &str, new: &SecretString) -> Result<(), RedfishError> {
         self.s.change_password(user, new).await
     }
 
     async fn change_password_by_id(
         &self,
         account_id: &str,
-        new_pass: &str,
+        new_pass: &SecretString,
     ) -> Result<(), RedfishError> {
         self.s.change_password_by_id(account_id, new_pass).await
     }
@@ -158,7 +163,7 @@ impl Redfish for Bmc {
                 HashMap::from([("ResetType".to_string(), "AuxCycle".to_string())]);
             let url = format!(
                 "Systems/{}/Actions/Oem/Hpe/HpeComputerSystemExt.SystemReset",
-                self.s.system_id()
+                self.s.resolve_system_id().await?
             );
             return self.s.client.post(&url, args).await.map(|_status_code| ());
         } else {
@@ -170,6 +175,25 @@ impl Redfish for Bmc {
         true
     }
 
+    fn supported_features(&self) -> crate::FeatureSet {
+        crate::FeatureSet {
+            power_metrics: true,
+            thermal_metrics: true,
+            system_event_log: true,
+            reset_bios: true,
+            lockdown: true,
+            serial_console: true,
+            secure_boot: true,
+            clear_tpm: true,
+            system_ethernet_interfaces: true,
+            chassis_network_adapters: true,
+            dpu_first_boot_order: true,
+            component_integrity: true,
+            ipmi_over_lan: true,
+            ..Default::default()
+        }
+    }
+
     async fn bmc_reset(&self) -> Result<(), RedfishError> {
         self.s.bmc_reset().await
     }
@@ -194,6 +218,18 @@ impl Redfish for Bmc {
         self.get_system_event_log().await
     }
 
+    async fn get_system_event_log_since(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        limit: Option<usize>,
+    ) -> Result<Vec<LogEntry>, RedfishError> {
+        let url = format!(
+            "Systems/{}/LogServices/IML/Entries",
+            self.s.resolve_system_id().await?
+        );
+        self.s.fetch_system_event_log(url, since, limit).await
+    }
+
     async fn get_bmc_event_log(
         &self,
         from: Option<chrono::DateTime<chrono::Utc>>,
@@ -218,7 +254,7 @@ impl Redfish for Bmc {
         self.s.set_bios(values).await
     }
 
-    async fn reset_bios(&self) -> Result<(), RedfishError> {
+    async fn reset_bios(&self) -> Result<crate::ApplyResult, RedfishError> {
         let hp_bios = self.s.bios().await?;
         // Access the Actions map
         let actions = hp_bios
@@ -236,27 +272,47 @@ impl Redfish for Bmc {
             .and_then(|v| v.as_str())
             .ok_or(RedfishError::NoContent)?;
         let url = target.replace(&format!("/{REDFISH_ENDPOINT}/"), "");
-        self.s
-            .client
-            .req::<(), ()>(reqwest::Method::POST, &url, None, None, None, Vec::new())
-            .await
-            .map(|_resp| Ok(()))?
+        let arg: HashMap<&'static str, Value> = HashMap::new();
+        let response = self.s.client.post(&url, arg).await?;
+        Ok(crate::ApplyResult::from_reset_bios_response(
+            &response, &url,
+        ))
     }
 
     async fn machine_setup(
         &self,
-        _boot_interface_mac: Option<&str>,
+        boot_interface_mac: Option<&str>,
         _bios_profiles: &HashMap<
             RedfishVendor,
             HashMap<String, HashMap<BiosProfileType, HashMap<String, serde_json::Value>>>,
         >,
         _selected_profile: BiosProfileType,
-    ) -> Result<(), RedfishError> {
-        self.setup_serial_console().await?;
-        self.clear_tpm().await?;
-        self.set_virt_enable().await?;
-        self.set_uefi_nic_boot().await?;
-        self.set_boot_order(BootDevices::Pxe).await
+    ) -> Result<crate::MachineSetupReport, RedfishError> {
+        let status = crate::MachineSetupStatus::or_apply_all(
+            self.machine_setup_status(boot_interface_mac).await,
+        );
+        let configured = status.as_ref().is_some_and(|s| !s.needs_setup());
+
+        // clear_tpm and set_boot_order aren't in the diff, they run every time
+        let mut report = crate::MachineSetupReport::default();
+        if configured {
+            report.skip("setup_serial_console");
+        } else {
+            report.record_change("setup_serial_console", self.setup_serial_console().await)?;
+        }
+        report.record("clear_tpm", self.clear_tpm().await)?;
+        if configured {
+            report.skip("set_virt_enable");
+            report.skip("set_uefi_nic_boot");
+        } else {
+            report.record("set_virt_enable", self.set_virt_enable().await)?;
+            report.record("set_uefi_nic_boot", self.set_uefi_nic_boot().await)?;
+        }
+        report.record(
+            "set_boot_order",
+            self.set_boot_order(BootDevices::Pxe).await,
+        )?;
+        Ok(report)
     }
 
     async fn machine_setup_status(
@@ -331,7 +387,7 @@ impl Redfish for Bmc {
     }
 
     async fn lockdown_status(&self) -> Result<Status, RedfishError> {
-        let mut url = format!("Systems/{}/Bios", self.s.system_id());
+        let mut url = format!("Systems/{}/Bios", self.s.resolve_system_id().await?);
         let (_status_code, bios): (_, hpe::Bios) = self.s.client.get(url.as_str()).await?;
         let bios = bios.attributes;
         url = format!("Managers/{}", self.s.manager_id());
@@ -359,7 +415,11 @@ impl Redfish for Bmc {
         })
     }
 
-    async fn setup_serial_console(&self) -> Result<(), RedfishError> {
+    async fn setup_serial_console(&self) -> Result<bool, RedfishError> {
+        // If we can't tell, set it up anyway
+        if matches!(self.serial_console_status().await, Ok(status) if status.is_fully_enabled()) {
+            return Ok(false);
+        }
         let serial_console = hpe::BiosSerialConsoleAttributes {
             embedded_serial_port: "Com2Irq3".to_string(),
             ems_console: "Virtual".to_string(),
@@ -372,12 +432,15 @@ impl Redfish for Bmc {
         let set_serial_attrs = hpe::SetBiosSerialConsoleAttributes {
             attributes: serial_console,
         };
-        let url = format!("Systems/{}/Bios/settings/", self.s.system_id());
+        let url = format!(
+            "Systems/{}/Bios/settings/",
+            self.s.resolve_system_id().await?
+        );
         self.s
             .client
             .patch(&url, set_serial_attrs)
             .await
-            .map(|_status_code| ())
+            .map(|_status_code| true)
     }
 
     async fn serial_console_status(&self) -> Result<Status, RedfishError> {
@@ -406,33 +469,40 @@ impl Redfish for Bmc {
         self.boot_first(target).await
     }
 
-    async fn clear_tpm(&self) -> Result<(), RedfishError> {
+    async fn clear_tpm(&self) -> Result<crate::ApplyResult, RedfishError> {
         let tpm = hpe::TpmAttributes {
             tpm2_operation: "Clear".to_string(),
             tpm_visibility: "Visible".to_string(),
         };
         let set_tpm_attrs = hpe::SetTpmAttributes { attributes: tpm };
-        let url = format!("Systems/{}/Bios/settings/", self.s.system_id());
-        self.s
+        let url = format!(
+            "Systems/{}/Bios/settings/",
+            self.s.resolve_system_id().await?
+        );
+        let (_status_code, info) = self
+            .s
             .client
-            .patch(&url, set_tpm_attrs)
-            .await
-            .map(|_status_code| ())
+            .patch_with_response(&url, set_tpm_attrs)
+            .await?;
+        Ok(crate::ApplyResult::from_extended_info(info, &url))
     }
 
     async fn pending(&self) -> Result<HashMap<String, serde_json::Value>, RedfishError> {
-        let url = format!("Systems/{}/Bios/settings/", self.s.system_id());
+        let url = format!(
+            "Systems/{}/Bios/settings/",
+            self.s.resolve_system_id().await?
+        );
         self.s.pending_with_url(&url).await
     }
 
-    async fn clear_pending(&self) -> Result<(), RedfishError> {
+    async fn clear_pending(&self) -> Result<crate::ClearPendingResult, RedfishError> {
         // TODO
-        Ok(())
+        Ok(crate::ClearPendingResult::Unknown)
     }
 
     async fn pcie_devices(&self) -> Result<Vec<PCIeDevice>, RedfishError> {
         let mut out = Vec::new();
-        let chassis = self.get_chassis(self.s.system_id()).await?;
+        let chassis = self.get_chassis(self.s.resolve_system_id().await?).await?;
         let pcie_devices_odata = match chassis.pcie_devices {
             Some(odata) => odata,
             None => return Ok(vec![]),
@@ -576,7 +646,10 @@ impl Redfish for Bmc {
     }
 
     async fn get_chassis_all(&self) -> Result<Vec<String>, RedfishError> {
-        self.s.get_members("Chassis").await
+        let url = self
+            .s
+            .root_path(crate::model::service_root::ServiceRootLink::Chassis);
+        self.s.get_members(&url).await
     }
 
     async fn get_chassis(&self, id: &str) -> Result<Chassis, RedfishError> {
@@ -670,9 +743,9 @@ impl Redfish for Bmc {
 
     async fn change_uefi_password(
         &self,
-        current_uefi_password: &str,
-        new_uefi_password: &str,
-    ) -> Result<Option<String>, RedfishError> {
+        current_uefi_password: &SecretString,
+        new_uefi_password: &SecretString,
+    ) -> Result<crate::BiosPasswordChange, RedfishError> {
         let hp_bios = self.s.bios().await?;
         // Access the Actions map
         let actions = hp_bios
@@ -691,14 +764,18 @@ impl Redfish for Bmc {
             .ok_or(RedfishError::NoContent)?;
 
         let mut arg = HashMap::new();
-        arg.insert("PasswordName", "AdministratorPassword".to_string());
-        arg.insert("OldPassword", current_uefi_password.to_string());
-        arg.insert("NewPassword", new_uefi_password.to_string());
+        arg.insert("PasswordName", "AdministratorPassword");
+        arg.insert("OldPassword", current_uefi_password.expose_secret());
+        arg.insert("NewPassword", new_uefi_password.expose_secret());
 
         let url = target.replace(&format!("/{REDFISH_ENDPOINT}/"), "");
-        self.s.client.post(&url, arg).await?;
-
-        Ok(None)
+        let result = self
+            .s
+            .client
+            .post(&url, arg)
+            .await
+            .map(|response| response.task_id(&url).ok());
+        crate::BiosPasswordChange::from_result(result)
     }
 
     async fn change_boot_order(&self, boot_array: Vec<String>) -> Result<(), RedfishError> {
@@ -722,6 +799,20 @@ impl Redfish for Bmc {
     }
 
     async fn bmc_reset_to_defaults(&self) -> Result<(), RedfishError> {
+        // iLO has no reset that keeps the network settings, so this resets everything
+        self.bmc_reset_to_defaults_with(crate::model::manager::ResetToDefaultsType::ResetAll)
+            .await
+    }
+
+    async fn bmc_reset_to_defaults_with(
+        &self,
+        scope: crate::model::manager::ResetToDefaultsType,
+    ) -> Result<(), RedfishError> {
+        if scope != crate::model::manager::ResetToDefaultsType::ResetAll {
+            return Err(RedfishError::NotSupported(format!(
+                "ResetToDefaults {scope}, supported: ResetAll"
+            )));
+        }
         let url = format!(
             "Managers/{}/Actions/Oem/Hpe/HpeiLO.ResetToFactoryDefaults",
             self.s.manager_id()
@@ -760,7 +851,7 @@ impl Redfish for Bmc {
             let id = b.odata_id_get()?;
             let opt = self.get_boot_option(id).await?;
             let opt_name = opt.display_name.to_uppercase();
-            if opt_name.contains("HTTP") && opt_name.contains("IPV4") && opt_name.contains(&mac) {
+            if opt_name.contains("HTTP") && opt_name.contains("IPV4") && opt.matches_mac(&mac) {
                 boot_ref = Some(opt.boot_option_reference);
                 break;
             }
@@ -795,19 +886,25 @@ impl Redfish for Bmc {
 
     async fn clear_uefi_password(
         &self,
-        current_uefi_password: &str,
+        current_uefi_password: &SecretString,
     ) -> Result<Option<String>, RedfishError> {
-        self.change_uefi_password(current_uefi_password, "").await
+        self.change_uefi_password(current_uefi_password, &SecretString::default())
+            .await?
+            .job_id()
     }
 
     async fn get_base_mac_address(&self) -> Result<Option<String>, RedfishError> {
         self.s.get_base_mac_address().await
     }
 
-    async fn lockdown_bmc(&self, target: crate::EnabledDisabled) -> Result<(), RedfishError> {
+    async fn lockdown_bmc(&self, target: crate::EnabledDisabled) -> Result<bool, RedfishError> {
         self.s.lockdown_bmc(target).await
     }
 
+    async fn bmc_lockdown_status(&self) -> Result<Status, RedfishError> {
+        self.s.bmc_lockdown_status().await
+    }
+
     async fn is_ipmi_over_lan_enabled(&self) -> Result<bool, RedfishError> {
         self.s.is_ipmi_over_lan_enabled().await
     }
@@ -815,7 +912,7 @@ impl Redfish for Bmc {
     async fn enable_ipmi_over_lan(
         &self,
         target: crate::EnabledDisabled,
-    ) -> Result<(), RedfishError> {
+    ) -> Result<bool, RedfishError> {
         self.s.enable_ipmi_over_lan(target).await
     }
 
@@ -830,11 +927,11 @@ impl Redfish for Bmc {
             .await
     }
 
-    async fn enable_rshim_bmc(&self) -> Result<(), RedfishError> {
+    async fn enable_rshim_bmc(&self) -> Result<bool, RedfishError> {
         self.s.enable_rshim_bmc().await
     }
 
-    async fn clear_nvram(&self) -> Result<(), RedfishError> {
+    async fn clear_nvram(&self) -> Result<crate::ApplyResult, RedfishError> {
         self.s.clear_nvram().await
     }
 
@@ -934,6 +1031,245 @@ impl Redfish for Bmc {
     ) -> Result<crate::model::component_integrity::Evidence, RedfishError> {
         self.s.get_evidence(url).await
     }
+
+    async fn reset_secure_boot_keys(
+        &self,
+        reset_type: crate::model::secure_boot::SecureBootResetType,
+    ) -> Result<(), RedfishError> {
+        self.s.reset_secure_boot_keys(reset_type).await
+    }
+
+    async fn get_boot_progress(&self) -> Result<crate::model::system::BootProgress, RedfishError> {
+        self.s.get_boot_progress().await
+    }
+
+    async fn get_post_codes(&self) -> Result<Vec<LogEntry>, RedfishError> {
+        self.s.get_post_codes().await
+    }
+
+    async fn get_environment_metrics(
+        &self,
+        chassis_id: &str,
+    ) -> Result<crate::model::environment_metrics::EnvironmentMetrics, RedfishError> {
+        self.s.get_environment_metrics(chassis_id).await
+    }
+
+    async fn get_energy_kwh(
+        &self,
+    ) -> Result<Vec<crate::model::sensor::EnergyReading>, RedfishError> {
+        self.s.get_energy_kwh().await
+    }
+
+    async fn get_sensors(
+        &self,
+        chassis_id: &str,
+        reading_type: Option<crate::model::sensor::ReadingType>,
+    ) -> Result<Vec<crate::model::sensor::Sensor>, RedfishError> {
+        self.s.get_sensors(chassis_id, reading_type).await
+    }
+
+    async fn wait_for_power_state(
+        &self,
+        target: crate::PowerState,
+        timeout: std::time::Duration,
+        poll: std::time::Duration,
+    ) -> Result<(), RedfishError> {
+        self.s.wait_for_power_state(target, timeout, poll).await
+    }
+
+    async fn pending_diff(&self) -> Result<Vec<crate::model::BiosAttrChange>, RedfishError> {
+        let pending = self.pending().await?;
+        self.s.bios_attr_changes(pending).await
+    }
+
+    async fn set_bios_attributes(
+        &self,
+        attrs: HashMap<String, serde_json::Value>,
+    ) -> Result<(), RedfishError> {
+        let targets = self.s.bios_attribute_targets(attrs).await?;
+        self.s
+            .write_bios_attribute_targets(targets, |settings| async move {
+                let url = format!(
+                    "Systems/{}/Bios/settings/",
+                    self.s.resolve_system_id().await?
+                );
+                let body = HashMap::from([("Attributes", settings)]);
+                self.s.client.patch(&url, body).await.map(|_status_code| ())
+            })
+            .await
+    }
+
+    async fn set_uefi_http(
+        &self,
+        cfg: crate::model::oem::nvidia_viking::UefiHttpAttributes,
+    ) -> Result<(), RedfishError> {
+        self.s.set_uefi_http(cfg).await
+    }
+
+    async fn set_virtualization(
+        &self,
+        cfg: crate::model::oem::nvidia_viking::VirtAttributes,
+    ) -> Result<(), RedfishError> {
+        self.s.set_virtualization(cfg).await
+    }
+
+    async fn get_bios_lockdown(
+        &self,
+    ) -> Result<crate::model::oem::nvidia_viking::BiosLockdownAttributes, RedfishError> {
+        self.s.get_bios_lockdown().await
+    }
+
+    async fn get_decoded_sel(
+        &self,
+    ) -> Result<Vec<crate::model::sel::DecodedSelEntry>, RedfishError> {
+        let sel = self.get_system_event_log().await?;
+        Ok(sel
+            .iter()
+            .map(crate::model::sel::DecodedSelEntry::from)
+            .collect())
+    }
+
+    async fn resolve_origin(
+        &self,
+        entry: &crate::model::sel::LogEntry,
+    ) -> Result<crate::model::resource::Resource, RedfishError> {
+        self.s.resolve_origin(entry).await
+    }
+
+    async fn attach_to_update(
+        &self,
+        task_id: &str,
+    ) -> Result<crate::model::task::Task, RedfishError> {
+        self.s.attach_to_update(task_id).await
+    }
+
+    async fn get_port_metrics(
+        &self,
+        chassis_id: &str,
+        network_adapter: &str,
+        id: &str,
+    ) -> Result<crate::model::port::PortMetrics, RedfishError> {
+        self.s
+            .get_port_metrics(chassis_id, network_adapter, id)
+            .await
+    }
+
+    async fn get_fru_inventory(&self) -> Result<crate::model::fru::FruInventory, RedfishError> {
+        self.s.get_fru_inventory().await
+    }
+
+    async fn get_power_supplies(
+        &self,
+    ) -> Result<Vec<crate::model::power::PowerSupplyInfo>, RedfishError> {
+        self.s.get_power_supplies().await
+    }
+
+    async fn download_firmware_image(
+        &self,
+        inventory_id: &str,
+        out: &std::path::Path,
+    ) -> Result<(), RedfishError> {
+        self.s.download_firmware_image(inventory_id, out).await
+    }
+
+    async fn create_boot_option(&self, opt: NewBootOption) -> Result<String, RedfishError> {
+        self.s.create_boot_option(opt).await
+    }
+
+    async fn delete_boot_option(&self, option_id: &str) -> Result<(), RedfishError> {
+        self.s.delete_boot_option_for(self, option_id).await
+    }
+
+    async fn get_bios_prerequisites(
+        &self,
+        attrs: &HashMap<String, serde_json::Value>,
+    ) -> Result<HashMap<String, serde_json::Value>, RedfishError> {
+        self.s.get_bios_prerequisites(attrs).await
+    }
+
+    async fn get_bmc_journal(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<String>, RedfishError> {
+        self.s.get_bmc_journal(since).await
+    }
+
+    async fn get_all_metrics(&self) -> Result<crate::AllMetrics, RedfishError> {
+        crate::AllMetrics::gather(self).await
+    }
+
+    async fn get_power_restore_policy(&self) -> Result<crate::PowerRestorePolicy, RedfishError> {
+        self.s.get_power_restore_policy().await
+    }
+
+    async fn set_power_restore_policy(
+        &self,
+        policy: crate::PowerRestorePolicy,
+    ) -> Result<(), RedfishError> {
+        self.s.set_power_restore_policy(policy).await
+    }
+
+    async fn set_power_on_delay(&self, seconds: u32) -> Result<(), RedfishError> {
+        // iLO only offers a few fixed delays as the PowerOnDelay BIOS attribute
+        let delay = match seconds {
+            0 => "NoDelay",
+            15 => "Delay15Sec",
+            30 => "Delay30Sec",
+            45 => "Delay45Sec",
+            60 => "Delay60Sec",
+            _ => {
+                return Err(RedfishError::NotSupported(format!(
+                    "HPE power on delay must be 0, 15, 30, 45 or 60 seconds, not {seconds}"
+                )))
+            }
+        };
+        let values = HashMap::from([("PowerOnDelay".to_string(), delay.into())]);
+        self.s.set_bios(values).await
+    }
+
+    async fn get_manager_health(&self) -> Result<crate::model::ManagerHealth, RedfishError> {
+        self.s.get_manager_health().await
+    }
+
+    async fn get_active_conditions(&self) -> Result<Vec<crate::model::Condition>, RedfishError> {
+        self.s.get_active_conditions().await
+    }
+
+    async fn get_fabric_topology(
+        &self,
+    ) -> Result<Vec<crate::model::fabric::FabricTopology>, RedfishError> {
+        self.s.get_fabric_topology().await
+    }
+
+    async fn get_pcie_switches(
+        &self,
+        chassis_id: &str,
+    ) -> Result<Vec<crate::model::fabric::SwitchPorts>, RedfishError> {
+        self.s.get_pcie_switches(chassis_id).await
+    }
+
+    async fn reset_erot(&self, target: ComponentType) -> Result<(), RedfishError> {
+        self.s.reset_erot(target).await
+    }
+
+    async fn get_firmware_slots(
+        &self,
+        component: &str,
+    ) -> Result<Vec<crate::model::software_inventory::FirmwareSlot>, RedfishError> {
+        self.s.get_firmware_slots(component).await
+    }
+
+    async fn activate_firmware_slot(
+        &self,
+        component: &str,
+        slot: &str,
+    ) -> Result<(), RedfishError> {
+        self.s.activate_firmware_slot(component, slot).await
+    }
+
+    async fn get_pending_jobs(&self) -> Result<Vec<crate::model::job::Job>, RedfishError> {
+        self.s.get_pending_jobs().await
+    }
 }
 
 impl Bmc {
@@ -988,7 +1324,10 @@ impl Bmc {
         let set_lockdown = hpe::SetBiosLockdownAttributes {
             attributes: lockdown_attrs,
         };
-        let url = format!("Systems/{}/Bios/settings/", self.s.system_id());
+        let url = format!(
+            "Systems/{}/Bios/settings/",
+            self.s.resolve_system_id().await?
+        );
         self.s
             .client
             .patch(&url, set_lockdown)
@@ -1057,7 +1396,10 @@ impl Bmc {
         let set_lockdown = hpe::SetBiosLockdownAttributes {
             attributes: lockdown_attrs,
         };
-        let url = format!("Systems/{}/Bios/settings/", self.s.system_id());
+        let url = format!(
+            "Systems/{}/Bios/settings/",
+            self.s.resolve_system_id().await?
+        );
         self.s
             .client
             .patch(&url, set_lockdown)
@@ -1133,7 +1475,7 @@ impl Bmc {
                     INTEL_ENABLE_VIRTUALIZATION_KEY, AMD_ENABLE_VIRTUALIZATION_KEY
                 )
                 .to_string(),
-                url: format!("Systems/{}/Bios", self.s.system_id()),
+                url: format!("Systems/{}/Bios", self.s.resolve_system_id().await?),
             })
         }
     }
@@ -1146,7 +1488,10 @@ impl Bmc {
             "Attributes",
             HashMap::from([(enable_virtualization_key, "Enabled")]),
         );
-        let url = format!("Systems/{}/Bios/settings", self.s.system_id());
+        let url = format!(
+            "Systems/{}/Bios/settings",
+            self.s.resolve_system_id().await?
+        );
         self.s.client.patch(&url, body).await.map(|_status_code| ())
     }
 
@@ -1181,7 +1526,10 @@ impl Bmc {
         let set_uefi_nic_boot = hpe::SetUefiHttpAttributes {
             attributes: uefi_nic_boot,
         };
-        let url = format!("Systems/{}/Bios/settings/", self.s.system_id());
+        let url = format!(
+            "Systems/{}/Bios/settings/",
+            self.s.resolve_system_id().await?
+        );
         self.s
             .client
             .patch(&url, set_uefi_nic_boot)
@@ -1222,12 +1570,21 @@ impl Bmc {
         let new_boot_order = hpe::SetOemHpeBoot {
             persistent_boot_config_order: boot_array,
         };
-        let url = format!("Systems/{}/Bios/oem/hpe/boot/settings/", self.s.system_id());
+        let url = format!(
+            "Systems/{}/Bios/oem/hpe/boot/settings/",
+            self.s.resolve_system_id().await?
+        );
         self.s
             .client
-            .patch(&url, new_boot_order)
+            .patch_with_etag_retry(&url, &new_boot_order)
+            .await?;
+        self.s
+            .verify_boot_order(
+                &url,
+                "/PersistentBootConfigOrder",
+                &new_boot_order.persistent_boot_config_order,
+            )
             .await
-            .map(|_status_code| ())
     }
 
     async fn set_boot_order(&self, name: BootDevices) -> Result<(), RedfishError> {
@@ -1251,7 +1608,10 @@ impl Bmc {
             _ => ".",
         };
         let mut ordered = Vec::new(); // the final boot options
-        let url = format!("Systems/{}/Bios/oem/hpe/boot/", self.s.system_id());
+        let url = format!(
+            "Systems/{}/Bios/oem/hpe/boot/",
+            self.s.resolve_system_id().await?
+        );
         let (_, body): (_, hpe::OemHpeBoot) = self.s.client.get(&url).await?;
 
         for member in body.persistent_boot_config_order {
@@ -1265,7 +1625,10 @@ impl Bmc {
     }
 
     async fn get_system_event_log(&self) -> Result<Vec<LogEntry>, RedfishError> {
-        let url = format!("Systems/{}/LogServices/IML/Entries", self.s.system_id());
+        let url = format!(
+            "Systems/{}/LogServices/IML/Entries",
+            self.s.resolve_system_id().await?
+        );
         let (_status_code, log_entry_collection): (_, LogEntryCollection) =
             self.s.client.get(&url).await?;
         let log_entries = log_entry_collection.members;
@@ -1346,7 +1709,7 @@ impl Bmc {
         order.swap(0, source_pos);
 
         let body = HashMap::from([("Boot", HashMap::from([("BootOrder", order)]))]);
-        let url = format!("Systems/{}", self.s.system_id());
+        let url = format!("Systems/{}", self.s.resolve_system_id().await?);
         self.s.client.patch(&url, body).await.map(|_status_code| ())
     }
 
@@ -1362,7 +1725,7 @@ impl Bmc {
             let id = b.odata_id_get()?;
             let opt = self.get_boot_option(id).await?;
             let opt_name = opt.display_name.to_uppercase();
-            if opt_name.contains("HTTP") && opt_name.contains("IPV4") && opt_name.contains(&mac) {
+            if opt_name.contains("HTTP") && opt_name.contains("IPV4") && opt.matches_mac(&mac) {
                 expected_first_boot_option = Some(opt.boot_option_reference);
                 break;
             }
@@ -1382,7 +1745,7 @@ impl Bmc {
     ) -> Result<storage::ArrayController, RedfishError> {
         let url = format!(
             "Systems/{}/SmartStorage/ArrayControllers/{}/",
-            self.s.system_id(),
+            self.s.resolve_system_id().await?,
             controller_id
         );
         let (_status_code, body) = self.s.client.get(&url).await?;
@@ -1393,7 +1756,7 @@ impl Bmc {
     pub async fn get_array_controllers(&self) -> Result<storage::ArrayControllers, RedfishError> {
         let url = format!(
             "Systems/{}/SmartStorage/ArrayControllers/",
-            self.s.system_id()
+            self.s.resolve_system_id().await?
         );
         let (_status_code, body) = self.s.client.get(&url).await?;
         Ok(body)
@@ -1407,7 +1770,7 @@ impl Bmc {
     ) -> Result<storage::SmartArray, RedfishError> {
         let url = format!(
             "Systems/{}/SmartStorage/ArrayControllers/{}/",
-            self.s.system_id(),
+            self.s.resolve_system_id().await?,
             controller_id
         );
         let (_status_code, body) = self.s.client.get(&url).await?;
@@ -1421,7 +1784,7 @@ impl Bmc {
     ) -> Result<storage::LogicalDrives, RedfishError> {
         let url = format!(
             "Systems/{}/SmartStorage/ArrayControllers/{}/LogicalDrives/",
-            self.s.system_id(),
+            self.s.resolve_system_id().await?,
             controller_id
         );
         let (_status_code, body) = self.s.client.get(&url).await?;
@@ -1436,7 +1799,7 @@ impl Bmc {
     ) -> Result<storage::DiskDrive, RedfishError> {
         let url = format!(
             "Systems/{}/SmartStorage/ArrayControllers/{}/DiskDrives/{}/",
-            self.s.system_id(),
+            self.s.resolve_system_id().await?,
             controller_id,
             drive_id,
         );
@@ -1451,7 +1814,7 @@ impl Bmc {
     ) -> Result<storage::DiskDrives, RedfishError> {
         let url = format!(
             "Systems/{}/SmartStorage/ArrayControllers/{}/DiskDrives/",
-            self.s.system_id(),
+            self.s.resolve_system_id().await?,
             controller_id
         );
         let (_status_code, body) = self.s.client.get(&url).await?;
@@ -1465,7 +1828,7 @@ impl Bmc {
     ) -> Result<storage::StorageEnclosures, RedfishError> {
         let url = format!(
             "Systems/{}/SmartStorage/ArrayControllers/{}/StorageEnclosures/",
-            self.s.system_id(),
+            self.s.resolve_system_id().await?,
             controller_id
         );
         let (_status_code, body) = self.s.client.get(&url).await?;
@@ -1480,7 +1843,7 @@ impl Bmc {
     ) -> Result<storage::StorageEnclosure, RedfishError> {
         let url = format!(
             "Systems/{}/SmartStorage/ArrayControllers/{}/StorageEnclosures/{}/",
-            self.s.system_id(),
+            self.s.resolve_system_id().await?,
             controller_id,
             enclosure_id,
         );
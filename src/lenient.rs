@@ -0,0 +1,261 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2025 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: MIT
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a
+ * copy of this software and associated documentation files (the "Software"),
+ * to deal in the Software without restriction, including without limitation
+ * the rights to use, copy, modify, merge, publish, distribute, sublicense,
+ * and/or sell copies of the Software, and to permit persons to whom the
+ * Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL
+ * THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+ * FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+//! Forgiving deserialization for BMCs that don't follow the schema.
+//!
+//! When strict deserialization fails, the offending field is located with
+//! serde_path_to_error and the JSON is patched, then deserialization is retried:
+//! - numbers sent as strings ("42") become numbers, and numbers where a string is expected
+//!   become strings
+//! - a missing required field is filled with an empty value ("", 0, false, [] or {}),
+//!   whichever the model accepts
+//! - any other bad field is dropped, which succeeds when the model has it as an Option
+//!
+//! This applies to every response read through the client (`get`, `req` and friends), so to
+//! all models fetched by the `Redfish` implementations. It does not apply to values
+//! re-parsed from a `Resource` or to BIOS attribute maps, which are already untyped.
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use serde_path_to_error::Segment;
+use tracing::warn;
+
+use crate::network::from_json_str;
+use crate::RedfishError;
+
+// Upper bound on patches per response, so a hopeless body can't loop for long
+const MAX_REPAIRS: usize = 32;
+
+// Values tried, in order, for a missing required field
+const EMPTY_CANDIDATES: [fn() -> Value; 5] = [
+    || Value::String(String::new()),
+    || Value::from(0),
+    || Value::Bool(false),
+    || Value::Array(vec![]),
+    || Value::Object(Map::new()),
+];
+
+/// Deserialize a response, patching schema violations if strict deserialization fails.
+/// Returns the strict error if the body can't be repaired.
+pub(crate) fn from_json_str_lenient<T: DeserializeOwned>(
+    url: &str,
+    body: &str,
+) -> Result<T, RedfishError> {
+    let strict_err = match from_json_str(url, body) {
+        Ok(v) => return Ok(v),
+        Err(e) => e,
+    };
+    let Ok(mut value) = serde_json::from_str::<Value>(body) else {
+        return Err(strict_err);
+    };
+    for _ in 0..MAX_REPAIRS {
+        let Some(failure) = Failure::of::<T>(&value) else {
+            // Unreachable in practice: the last repair was only kept if T then deserialized
+            return Err(strict_err);
+        };
+        let Some(repair) = repair::<T>(&mut value, &failure) else {
+            return Err(strict_err);
+        };
+        warn!("lenient: {url} at {}: {}", failure.path_str, repair);
+        if Failure::of::<T>(&value).is_none() {
+            return serde_json::from_value(value).map_err(|_| strict_err);
+        }
+    }
+    Err(strict_err)
+}
+
+// Where and why deserializing T failed
+struct Failure {
+    path: Vec<Segment>,
+    path_str: String,
+    message: String,
+}
+
+impl Failure {
+    fn of<T: DeserializeOwned>(value: &Value) -> Option<Failure> {
+        match serde_path_to_error::deserialize::<_, T>(value) {
+            Ok(_) => None,
+            Err(e) => Some(Failure {
+                path: e.path().iter().cloned().collect(),
+                path_str: e.path().to_string(),
+                message: e.inner().to_string(),
+            }),
+        }
+    }
+}
+
+// Patches `value` so the failure goes away or moves on, describing what was done.
+fn repair<T: DeserializeOwned>(value: &mut Value, failure: &Failure) -> Option<String> {
+    let node = lookup(value, &failure.path)?.clone();
+
+    if let Some(field) = missing_field(&failure.message) {
+        if node.is_object() {
+            for candidate in EMPTY_CANDIDATES {
+                let mut attempt = value.clone();
+                let obj = lookup(&mut attempt, &failure.path)?.as_object_mut()?;
+                obj.insert(field.to_string(), candidate());
+                if progressed::<T>(&attempt, failure, Some(field)) {
+                    *value = attempt;
+                    return Some(format!("filled missing field {field}"));
+                }
+            }
+        }
+        return None;
+    }
+
+    if let Some(coerced) = coerce(&node) {
+        let mut attempt = value.clone();
+        *lookup(&mut attempt, &failure.path)? = coerced.clone();
+        if progressed::<T>(&attempt, failure, None) {
+            *value = attempt;
+            return Some(format!("coerced to {coerced}"));
+        }
+    }
+
+    // With #[serde(flatten)] the path stops at the struct, so try the fields inside it
+    if let Some(obj) = node.as_object() {
+        for (key, child) in obj {
+            let Some(coerced) = coerce(child) else {
+                continue;
+            };
+            let mut attempt = value.clone();
+            lookup(&mut attempt, &failure.path)?
+                .as_object_mut()?
+                .insert(key.clone(), coerced.clone());
+            if progressed::<T>(&attempt, failure, None) {
+                *value = attempt;
+                return Some(format!("coerced {key} to {coerced}"));
+            }
+        }
+    }
+
+    // Last resort: drop the field, which works if the model has it as an Option
+    let (last, parent) = failure.path.split_last()?;
+    let Segment::Map { key } = last else {
+        return None;
+    };
+    let mut attempt = value.clone();
+    lookup(&mut attempt, parent)?.as_object_mut()?.remove(key);
+    if progressed::<T>(&attempt, failure, None) {
+        *value = attempt;
+        return Some("dropped invalid field".to_string());
+    }
+    None
+}
+
+// Whether a patch fixed the failure, rather than leaving it or just moving it onto the new value
+fn progressed<T: DeserializeOwned>(
+    attempt: &Value,
+    before: &Failure,
+    inserted: Option<&str>,
+) -> bool {
+    let Some(after) = Failure::of::<T>(attempt) else {
+        return true;
+    };
+    if after.path_str == before.path_str && after.message == before.message {
+        return false;
+    }
+    match inserted {
+        // A missing struct may be filled with {} and then repaired field by field, but a
+        // scalar of the wrong type is just a different failure at the same place
+        Some(field) => {
+            let field_path = match before.path_str.as_str() {
+                "." => field.to_string(),
+                parent => format!("{parent}.{field}"),
+            };
+            after.path_str != field_path
+        }
+        None => true,
+    }
+}
+
+// Numbers as strings and strings as numbers
+fn coerce(node: &Value) -> Option<Value> {
+    match node {
+        Value::String(s) => {
+            let s = s.trim();
+            if let Ok(i) = s.parse::<i64>() {
+                Some(Value::from(i))
+            } else if let Ok(u) = s.parse::<u64>() {
+                Some(Value::from(u))
+            } else {
+                s.parse::<f64>()
+                    .ok()
+                    .filter(|f| f.is_finite())
+                    .map(Value::from)
+            }
+        }
+        Value::Number(n) => Some(Value::String(n.to_string())),
+        _ => None,
+    }
+}
+
+fn missing_field(message: &str) -> Option<&str> {
+    message.strip_prefix("missing field `")?.split('`').next()
+}
+
+fn lookup<'a>(value: &'a mut Value, path: &[Segment]) -> Option<&'a mut Value> {
+    let mut node = value;
+    for segment in path {
+        node = match segment {
+            Segment::Seq { index } => node.get_mut(*index)?,
+            Segment::Map { key } => node.get_mut(key.as_str())?,
+            Segment::Enum { .. } | Segment::Unknown => return None,
+        };
+    }
+    Some(node)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::from_json_str_lenient;
+    use crate::model::sel::LogEntry;
+    use crate::network::from_json_str;
+
+    #[test]
+    fn test_lenient_repairs() {
+        let mut entry: serde_json::Value =
+            serde_json::from_str(include_str!("model/testdata/sel_entry.json")).unwrap();
+        entry["SensorNumber"] = "12".into();
+        entry.as_object_mut().unwrap().remove("Severity");
+        entry["Links"] = "bogus".into();
+        let body = entry.to_string();
+
+        assert!(from_json_str::<LogEntry>("sel", &body).is_err());
+        let entry: LogEntry = from_json_str_lenient("sel", &body).unwrap();
+        assert_eq!(entry.sensor_number, Some(12));
+        assert_eq!(entry.severity, "");
+        assert!(entry.links.is_none());
+        assert_eq!(entry.id, "42");
+    }
+
+    #[test]
+    fn test_lenient_gives_up() {
+        let body = "<html>Service Unavailable</html>";
+        let strict = from_json_str::<LogEntry>("sel", body)
+            .unwrap_err()
+            .to_string();
+        let lenient = from_json_str_lenient::<LogEntry>("sel", body)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(strict, lenient);
+    }
+}
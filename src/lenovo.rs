@@ -29,7 +29,6 @@ use reqwest::Method;
 use serde::Serialize;
 use serde_json::Value;
 use tokio::fs::File;
-use tokio::time::sleep;
 use tracing::debug;
 
 use crate::model::account_service::ManagerAccount;
@@ -59,8 +58,8 @@ use crate::{
     network::REDFISH_ENDPOINT,
     standard::RedfishStandard,
     BiosProfileType, Boot, BootOptions, Collection, EnabledDisabled, MachineSetupDiff,
-    MachineSetupStatus, ODataId, PCIeDevice, PowerState, Redfish, RedfishError, Resource, Status,
-    StatusInternal, SystemPowerControl,
+    MachineSetupStatus, NewBootOption, ODataId, PCIeDevice, PowerState, Redfish, RedfishError,
+    Resource, SecretString, Status, StatusInternal, SystemPowerControl,
 };
 use crate::{JobState, RoleId};
 
@@ -78,10 +77,14 @@ impl Bmc {
 
 #[async_trait::async_trait]
 impl Redfish for Bmc {
+    fn standard(&self) -> &RedfishStandard {
+        &self.s
+    }
+
     async fn create_user(
         &self,
         username: &str,
-        password: &str,
+        password: &SecretString,
         role_id: RoleId,
     ) -> Result<(), RedfishError> {
         self.s.create_user(username, password, role_id).await
@@ -95,14 +98,14 @@ impl Redfish for Bmc {
         self.s.change_username(old_name, new_name).await
     }
 
-    async fn change_password(&self, user: &str, new: &str) -> Result<(), RedfishError> {
+    async fn change_password(&self, user: &str, new: &SecretString) -> Result<(), RedfishError> {
         self.s.change_password(user, new).await
     }
 
     async fn change_password_by_id(
         &self,
         account_id: &str,
-        new_pass: &str,
+        new_pass: &SecretString,
     ) -> Result<(), RedfishError> {
         self.s.change_password_by_id(account_id, new_pass).await
     }
@@ -125,7 +128,7 @@ impl Redfish for Bmc {
                 HashMap::from([("ResetType".to_string(), "ACPowerCycle".to_string())]);
             let url = format!(
                 "Systems/{}/Actions/Oem/LenovoComputerSystem.SystemReset",
-                self.s.system_id()
+                self.s.resolve_system_id().await?
             );
             return self.s.client.post(&url, args).await.map(|_status_code| ());
         }
@@ -137,7 +140,7 @@ impl Redfish for Bmc {
             // We have observed that GracefulRestart is not a reliable mechanism to reboot hosts.
             // The most reliable workaround provided by Lenovo is to power off the machine, wait, and power on the machine
             self.s.power(SystemPowerControl::ForceOff).await?;
-            sleep(Duration::from_secs(10)).await;
+            self.s.client.sleep(Duration::from_secs(10)).await?;
             if self.get_power_state().await? != PowerState::Off {
                 return Err(RedfishError::GenericError {
                     error: "Server did not turn off within 10 seconds after issuing a ForceOff"
@@ -154,6 +157,27 @@ impl Redfish for Bmc {
         true
     }
 
+    fn supported_features(&self) -> crate::FeatureSet {
+        crate::FeatureSet {
+            power_metrics: true,
+            thermal_metrics: true,
+            system_event_log: true,
+            bios_settings: true,
+            reset_bios: true,
+            lockdown: true,
+            serial_console: true,
+            secure_boot: true,
+            clear_tpm: true,
+            system_ethernet_interfaces: true,
+            chassis_network_adapters: true,
+            dpu_first_boot_order: true,
+            component_integrity: true,
+            infinite_boot: true,
+            ipmi_over_lan: true,
+            ..Default::default()
+        }
+    }
+
     async fn bmc_reset(&self) -> Result<(), RedfishError> {
         self.s.bmc_reset().await
     }
@@ -178,13 +202,34 @@ impl Redfish for Bmc {
         self.get_system_event_log().await
     }
 
+    async fn get_system_event_log_since(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        limit: Option<usize>,
+    ) -> Result<Vec<LogEntry>, RedfishError> {
+        let url = format!(
+            "Systems/{}/LogServices/SEL",
+            self.s.resolve_system_id().await?
+        );
+        let (_status_code, log_service): (_, LogService) = self.s.client.get(&url).await?;
+        // If there are no log entries, this field and the `SEL/Entries` endpoint do not exist.
+        if log_service.entries.is_none() {
+            return Ok(vec![]);
+        }
+        let url = format!(
+            "Systems/{}/LogServices/SEL/Entries",
+            self.s.resolve_system_id().await?
+        );
+        self.s.fetch_system_event_log(url, since, limit).await
+    }
+
     async fn get_bmc_event_log(
         &self,
         from: Option<chrono::DateTime<Utc>>,
     ) -> Result<Vec<LogEntry>, RedfishError> {
         let url = format!(
             "Systems/{}/LogServices/AuditLog/Entries",
-            self.s.system_id()
+            self.s.resolve_system_id().await?
         );
         self.s.fetch_bmc_event_log(url, from).await
     }
@@ -203,31 +248,58 @@ impl Redfish for Bmc {
     ) -> Result<(), RedfishError> {
         let mut body = HashMap::new();
         body.insert("Attributes", values);
-        let url = format!("Systems/{}/Bios/Pending", self.s.system_id());
+        let url = format!("Systems/{}/Bios/Pending", self.s.resolve_system_id().await?);
         self.s.client.patch(&url, body).await.map(|_status_code| ())
     }
 
-    async fn reset_bios(&self) -> Result<(), RedfishError> {
-        let url = format!("Systems/{}/Bios/Actions/Bios.ResetBios", self.s.system_id());
+    async fn reset_bios(&self) -> Result<crate::ApplyResult, RedfishError> {
+        let url = format!(
+            "Systems/{}/Bios/Actions/Bios.ResetBios",
+            self.s.resolve_system_id().await?
+        );
         let mut arg = HashMap::new();
         arg.insert("ResetType", "Reset".to_string());
-        self.s.client.post(&url, arg).await.map(|_resp| Ok(()))?
+        let response = self.s.client.post(&url, arg).await?;
+        Ok(crate::ApplyResult::from_reset_bios_response(
+            &response, &url,
+        ))
     }
 
     async fn machine_setup(
         &self,
-        _boot_interface_mac: Option<&str>,
+        boot_interface_mac: Option<&str>,
         bios_profiles: &HashMap<
             RedfishVendor,
             HashMap<String, HashMap<BiosProfileType, HashMap<String, serde_json::Value>>>,
         >,
         selected_profile: BiosProfileType,
-    ) -> Result<(), RedfishError> {
-        self.setup_serial_console().await?;
-        self.clear_tpm().await?;
-        self.boot_first(Boot::Pxe).await?;
-        self.set_virt_enable().await?;
-        self.set_uefi_boot_only().await?;
+    ) -> Result<crate::MachineSetupReport, RedfishError> {
+        let status = crate::MachineSetupStatus::or_apply_all(
+            self.machine_setup_status(boot_interface_mac).await,
+        );
+        let configured = status.as_ref().is_some_and(|s| !s.needs_setup());
+
+        // clear_tpm isn't in the diff, nor is boot_first without a boot interface, they run
+        // every time
+        let mut report = crate::MachineSetupReport::default();
+        if configured {
+            report.skip("setup_serial_console");
+        } else {
+            report.record_change("setup_serial_console", self.setup_serial_console().await)?;
+        }
+        report.record("clear_tpm", self.clear_tpm().await)?;
+        if configured && boot_interface_mac.is_some() {
+            report.skip("boot_first");
+        } else {
+            report.record("boot_first", self.boot_first(Boot::Pxe).await)?;
+        }
+        if configured {
+            report.skip("set_virt_enable");
+            report.skip("set_uefi_boot_only");
+        } else {
+            report.record("set_virt_enable", self.set_virt_enable().await)?;
+            report.record("set_uefi_boot_only", self.set_uefi_boot_only().await)?;
+        }
         if let Some(lenovo) = bios_profiles.get(&RedfishVendor::Lenovo) {
             let model = crate::model_coerce(
                 self.get_system()
@@ -238,13 +310,25 @@ impl Redfish for Bmc {
             );
             if let Some(all_extra_values) = lenovo.get(&model) {
                 if let Some(extra_values) = all_extra_values.get(&selected_profile) {
-                    tracing::debug!("Setting extra BIOS values: {extra_values:?}");
-                    self.set_bios(extra_values.clone()).await?;
+                    // machine_setup_status doesn't know about profiles, so compare them here
+                    let current = self.s.bios_attributes().await?;
+                    if extra_values
+                        .iter()
+                        .all(|(key, value)| current.get(key) == Some(value))
+                    {
+                        report.skip("set_bios_profile");
+                    } else {
+                        tracing::debug!("Setting extra BIOS values: {extra_values:?}");
+                        report.record(
+                            "set_bios_profile",
+                            self.set_bios(extra_values.clone()).await,
+                        )?;
+                    }
                 }
             }
         }
 
-        Ok(())
+        Ok(report)
     }
 
     async fn machine_setup_status(
@@ -371,7 +455,11 @@ impl Redfish for Bmc {
         })
     }
 
-    async fn setup_serial_console(&self) -> Result<(), RedfishError> {
+    async fn setup_serial_console(&self) -> Result<bool, RedfishError> {
+        // If we can't tell, set it up anyway
+        if matches!(self.serial_console_status().await, Ok(status) if status.is_fully_enabled()) {
+            return Ok(false);
+        }
         let mut body = HashMap::new();
         body.insert(
             "Attributes",
@@ -402,8 +490,12 @@ impl Redfish for Bmc {
                 ),
             ]),
         );
-        let url = format!("Systems/{}/Bios/Pending", self.s.system_id());
-        self.s.client.patch(&url, body).await.map(|_status_code| ())
+        let url = format!("Systems/{}/Bios/Pending", self.s.resolve_system_id().await?);
+        self.s
+            .client
+            .patch(&url, body)
+            .await
+            .map(|_status_code| true)
     }
 
     async fn serial_console_status(&self) -> Result<Status, RedfishError> {
@@ -441,7 +533,7 @@ impl Redfish for Bmc {
         let mut message = String::new();
         let mut enabled = true;
         let mut disabled = true;
-        let url = format!("Systems/{}/Bios", self.s.system_id()); // url for debug only
+        let url = format!("Systems/{}/Bios", self.s.resolve_system_id().await?); // url for debug only
         for (key, val_enabled, val_disabled) in expected {
             let val_current = attrs
                 .get(key)
@@ -502,23 +594,24 @@ impl Redfish for Bmc {
         }
     }
 
-    async fn clear_tpm(&self) -> Result<(), RedfishError> {
+    async fn clear_tpm(&self) -> Result<crate::ApplyResult, RedfishError> {
         let mut body = HashMap::new();
         body.insert(
             "Attributes",
             HashMap::from([("TrustedComputingGroup_DeviceOperation", "Clear")]),
         );
-        let url = format!("Systems/{}/Bios/Pending", self.s.system_id());
-        self.s.client.patch(&url, body).await.map(|_status_code| ())
+        let url = format!("Systems/{}/Bios/Pending", self.s.resolve_system_id().await?);
+        let (_status_code, info) = self.s.client.patch_with_response(&url, body).await?;
+        Ok(crate::ApplyResult::from_extended_info(info, &url))
     }
 
     async fn pending(&self) -> Result<HashMap<String, serde_json::Value>, RedfishError> {
-        let url = format!("Systems/{}/Bios/Pending", self.s.system_id());
+        let url = format!("Systems/{}/Bios/Pending", self.s.resolve_system_id().await?);
         self.s.pending_with_url(&url).await
     }
 
-    async fn clear_pending(&self) -> Result<(), RedfishError> {
-        let url = format!("Systems/{}/Bios/Pending", self.s.system_id());
+    async fn clear_pending(&self) -> Result<crate::ClearPendingResult, RedfishError> {
+        let url = format!("Systems/{}/Bios/Pending", self.s.resolve_system_id().await?);
         self.s.clear_pending_with_url(&url).await
     }
 
@@ -566,7 +659,7 @@ impl Redfish for Bmc {
             }
         })?;
 
-        let (_status_code, _loc, body) = self
+        let response = self
             .s
             .client
             .req_update_firmware_multipart(
@@ -579,14 +672,7 @@ impl Redfish for Bmc {
             )
             .await?;
 
-        let task: Task =
-            serde_json::from_str(&body).map_err(|e| RedfishError::JsonDeserializeError {
-                url: update_service.multipart_http_push_uri,
-                body,
-                source: e,
-            })?;
-
-        Ok(task.id)
+        response.task_id(&update_service.multipart_http_push_uri)
     }
 
     async fn get_tasks(&self) -> Result<Vec<String>, RedfishError> {
@@ -754,17 +840,17 @@ impl Redfish for Bmc {
 
     async fn change_uefi_password(
         &self,
-        current_uefi_password: &str,
-        new_uefi_password: &str,
-    ) -> Result<Option<String>, RedfishError> {
+        current_uefi_password: &SecretString,
+        new_uefi_password: &SecretString,
+    ) -> Result<crate::BiosPasswordChange, RedfishError> {
         self.s
             .change_bios_password(UEFI_PASSWORD_NAME, current_uefi_password, new_uefi_password)
             .await
     }
 
     async fn change_boot_order(&self, boot_array: Vec<String>) -> Result<(), RedfishError> {
-        let body = HashMap::from([("Boot", HashMap::from([("BootOrder", boot_array)]))]);
-        let url = format!("Systems/{}/Pending", self.s.system_id());
+        let body = HashMap::from([("Boot", HashMap::from([("BootOrder", &boot_array)]))]);
+        let url = format!("Systems/{}/Pending", self.s.resolve_system_id().await?);
         // BMC takes longer to respond to this one, so override timeout
         let timeout = Duration::from_secs(10);
         let (_status_code, _resp_body, _resp_headers): (
@@ -783,7 +869,9 @@ impl Redfish for Bmc {
                 Vec::new(),
             )
             .await?;
-        Ok(())
+        self.s
+            .verify_boot_order(&url, "/Boot/BootOrder", &boot_array)
+            .await
     }
 
     async fn get_service_root(&self) -> Result<ServiceRoot, RedfishError> {
@@ -877,7 +965,7 @@ impl Redfish for Bmc {
         // Patch remote
         let url = format!(
             "{}/BootOrder.NetworkBootOrder",
-            self.get_boot_settings_uri()
+            self.get_boot_settings_uri().await?
         );
         let body = HashMap::from([("BootOrderNext", net_boot_order.boot_order_next.clone())]);
         self.s
@@ -890,19 +978,25 @@ impl Redfish for Bmc {
 
     async fn clear_uefi_password(
         &self,
-        current_uefi_password: &str,
+        current_uefi_password: &SecretString,
     ) -> Result<Option<String>, RedfishError> {
-        self.change_uefi_password(current_uefi_password, "").await
+        self.change_uefi_password(current_uefi_password, &SecretString::default())
+            .await?
+            .job_id()
     }
 
     async fn get_base_mac_address(&self) -> Result<Option<String>, RedfishError> {
         self.s.get_base_mac_address().await
     }
 
-    async fn lockdown_bmc(&self, target: crate::EnabledDisabled) -> Result<(), RedfishError> {
+    async fn lockdown_bmc(&self, target: crate::EnabledDisabled) -> Result<bool, RedfishError> {
         self.s.lockdown_bmc(target).await
     }
 
+    async fn bmc_lockdown_status(&self) -> Result<Status, RedfishError> {
+        self.s.bmc_lockdown_status().await
+    }
+
     async fn is_ipmi_over_lan_enabled(&self) -> Result<bool, RedfishError> {
         self.s.is_ipmi_over_lan_enabled().await
     }
@@ -910,7 +1004,7 @@ impl Redfish for Bmc {
     async fn enable_ipmi_over_lan(
         &self,
         target: crate::EnabledDisabled,
-    ) -> Result<(), RedfishError> {
+    ) -> Result<bool, RedfishError> {
         self.s.enable_ipmi_over_lan(target).await
     }
 
@@ -925,11 +1019,11 @@ impl Redfish for Bmc {
             .await
     }
 
-    async fn enable_rshim_bmc(&self) -> Result<(), RedfishError> {
+    async fn enable_rshim_bmc(&self) -> Result<bool, RedfishError> {
         self.s.enable_rshim_bmc().await
     }
 
-    async fn clear_nvram(&self) -> Result<(), RedfishError> {
+    async fn clear_nvram(&self) -> Result<crate::ApplyResult, RedfishError> {
         self.s.clear_nvram().await
     }
 
@@ -954,7 +1048,7 @@ impl Redfish for Bmc {
             None => {
                 return Err(RedfishError::MissingKey {
                     key: "Attributes".to_owned(),
-                    url: format!("Systems/{}/Bios", self.s.system_id()),
+                    url: format!("Systems/{}/Bios", self.s.resolve_system_id().await?),
                 })
             }
         };
@@ -1058,6 +1152,224 @@ impl Redfish for Bmc {
     ) -> Result<crate::model::component_integrity::Evidence, RedfishError> {
         self.s.get_evidence(url).await
     }
+
+    async fn reset_secure_boot_keys(
+        &self,
+        reset_type: crate::model::secure_boot::SecureBootResetType,
+    ) -> Result<(), RedfishError> {
+        self.s.reset_secure_boot_keys(reset_type).await
+    }
+
+    async fn get_boot_progress(&self) -> Result<crate::model::system::BootProgress, RedfishError> {
+        self.s.get_boot_progress().await
+    }
+
+    async fn get_post_codes(&self) -> Result<Vec<LogEntry>, RedfishError> {
+        self.s.get_post_codes().await
+    }
+
+    async fn get_environment_metrics(
+        &self,
+        chassis_id: &str,
+    ) -> Result<crate::model::environment_metrics::EnvironmentMetrics, RedfishError> {
+        self.s.get_environment_metrics(chassis_id).await
+    }
+
+    async fn get_energy_kwh(
+        &self,
+    ) -> Result<Vec<crate::model::sensor::EnergyReading>, RedfishError> {
+        self.s.get_energy_kwh().await
+    }
+
+    async fn get_sensors(
+        &self,
+        chassis_id: &str,
+        reading_type: Option<crate::model::sensor::ReadingType>,
+    ) -> Result<Vec<crate::model::sensor::Sensor>, RedfishError> {
+        self.s.get_sensors(chassis_id, reading_type).await
+    }
+
+    async fn wait_for_power_state(
+        &self,
+        target: crate::PowerState,
+        timeout: std::time::Duration,
+        poll: std::time::Duration,
+    ) -> Result<(), RedfishError> {
+        self.s.wait_for_power_state(target, timeout, poll).await
+    }
+
+    async fn pending_diff(&self) -> Result<Vec<crate::model::BiosAttrChange>, RedfishError> {
+        let pending = self.pending().await?;
+        self.s.bios_attr_changes(pending).await
+    }
+
+    async fn set_bios_attributes(
+        &self,
+        attrs: HashMap<String, serde_json::Value>,
+    ) -> Result<(), RedfishError> {
+        let targets = self.s.bios_attribute_targets(attrs).await?;
+        self.s
+            .write_bios_attribute_targets(targets, |settings| self.set_bios(settings))
+            .await
+    }
+
+    async fn set_uefi_http(
+        &self,
+        cfg: crate::model::oem::nvidia_viking::UefiHttpAttributes,
+    ) -> Result<(), RedfishError> {
+        self.s.set_uefi_http(cfg).await
+    }
+
+    async fn set_virtualization(
+        &self,
+        cfg: crate::model::oem::nvidia_viking::VirtAttributes,
+    ) -> Result<(), RedfishError> {
+        self.s.set_virtualization(cfg).await
+    }
+
+    async fn get_bios_lockdown(
+        &self,
+    ) -> Result<crate::model::oem::nvidia_viking::BiosLockdownAttributes, RedfishError> {
+        self.s.get_bios_lockdown().await
+    }
+
+    async fn get_decoded_sel(
+        &self,
+    ) -> Result<Vec<crate::model::sel::DecodedSelEntry>, RedfishError> {
+        let sel = self.get_system_event_log().await?;
+        Ok(sel
+            .iter()
+            .map(crate::model::sel::DecodedSelEntry::from)
+            .collect())
+    }
+
+    async fn resolve_origin(
+        &self,
+        entry: &crate::model::sel::LogEntry,
+    ) -> Result<crate::model::resource::Resource, RedfishError> {
+        self.s.resolve_origin(entry).await
+    }
+
+    async fn attach_to_update(
+        &self,
+        task_id: &str,
+    ) -> Result<crate::model::task::Task, RedfishError> {
+        self.s.attach_to_update(task_id).await
+    }
+
+    async fn get_port_metrics(
+        &self,
+        chassis_id: &str,
+        network_adapter: &str,
+        id: &str,
+    ) -> Result<crate::model::port::PortMetrics, RedfishError> {
+        self.s
+            .get_port_metrics(chassis_id, network_adapter, id)
+            .await
+    }
+
+    async fn get_fru_inventory(&self) -> Result<crate::model::fru::FruInventory, RedfishError> {
+        self.s.get_fru_inventory().await
+    }
+
+    async fn get_power_supplies(
+        &self,
+    ) -> Result<Vec<crate::model::power::PowerSupplyInfo>, RedfishError> {
+        self.s.get_power_supplies().await
+    }
+
+    async fn download_firmware_image(
+        &self,
+        inventory_id: &str,
+        out: &std::path::Path,
+    ) -> Result<(), RedfishError> {
+        self.s.download_firmware_image(inventory_id, out).await
+    }
+
+    async fn create_boot_option(&self, opt: NewBootOption) -> Result<String, RedfishError> {
+        self.s.create_boot_option(opt).await
+    }
+
+    async fn delete_boot_option(&self, option_id: &str) -> Result<(), RedfishError> {
+        self.s.delete_boot_option_for(self, option_id).await
+    }
+
+    async fn get_bios_prerequisites(
+        &self,
+        attrs: &HashMap<String, serde_json::Value>,
+    ) -> Result<HashMap<String, serde_json::Value>, RedfishError> {
+        self.s.get_bios_prerequisites(attrs).await
+    }
+
+    async fn get_bmc_journal(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<String>, RedfishError> {
+        self.s.get_bmc_journal(since).await
+    }
+
+    async fn get_all_metrics(&self) -> Result<crate::AllMetrics, RedfishError> {
+        crate::AllMetrics::gather(self).await
+    }
+
+    async fn get_power_restore_policy(&self) -> Result<crate::PowerRestorePolicy, RedfishError> {
+        self.s.get_power_restore_policy().await
+    }
+
+    async fn set_power_restore_policy(
+        &self,
+        policy: crate::PowerRestorePolicy,
+    ) -> Result<(), RedfishError> {
+        self.s.set_power_restore_policy(policy).await
+    }
+
+    async fn set_power_on_delay(&self, seconds: u32) -> Result<(), RedfishError> {
+        self.s.set_power_on_delay(seconds).await
+    }
+
+    async fn get_manager_health(&self) -> Result<crate::model::ManagerHealth, RedfishError> {
+        self.s.get_manager_health().await
+    }
+
+    async fn get_active_conditions(&self) -> Result<Vec<crate::model::Condition>, RedfishError> {
+        self.s.get_active_conditions().await
+    }
+
+    async fn get_fabric_topology(
+        &self,
+    ) -> Result<Vec<crate::model::fabric::FabricTopology>, RedfishError> {
+        self.s.get_fabric_topology().await
+    }
+
+    async fn get_pcie_switches(
+        &self,
+        chassis_id: &str,
+    ) -> Result<Vec<crate::model::fabric::SwitchPorts>, RedfishError> {
+        self.s.get_pcie_switches(chassis_id).await
+    }
+
+    async fn reset_erot(&self, target: ComponentType) -> Result<(), RedfishError> {
+        self.s.reset_erot(target).await
+    }
+
+    async fn get_firmware_slots(
+        &self,
+        component: &str,
+    ) -> Result<Vec<crate::model::software_inventory::FirmwareSlot>, RedfishError> {
+        self.s.get_firmware_slots(component).await
+    }
+
+    async fn activate_firmware_slot(
+        &self,
+        component: &str,
+        slot: &str,
+    ) -> Result<(), RedfishError> {
+        self.s.activate_firmware_slot(component, slot).await
+    }
+
+    async fn get_pending_jobs(&self) -> Result<Vec<crate::model::job::Job>, RedfishError> {
+        self.s.get_pending_jobs().await
+    }
 }
 
 impl Bmc {
@@ -1307,7 +1619,7 @@ impl Bmc {
     }
 
     async fn get_front_panel_usb_kv_lenovo(&self) -> Result<(String, FrontPanelUSB), RedfishError> {
-        let url = format!("Systems/{}", self.s.system_id());
+        let url = format!("Systems/{}", self.s.resolve_system_id().await?);
         let (_, body): (_, HashMap<String, serde_json::Value>) = self.s.client.get(&url).await?;
 
         let key = "Oem";
@@ -1355,13 +1667,7 @@ impl Bmc {
             }
         };
 
-        let front_panel_usb_val = serde_json::from_value(val.clone()).map_err(|err| {
-            RedfishError::JsonDeserializeError {
-                url,
-                body: format!("{val:?}"),
-                source: err,
-            }
-        })?;
+        let front_panel_usb_val = crate::network::from_json_value(&url, val)?;
 
         Ok((front_panel_usb_key.to_string(), front_panel_usb_val))
     }
@@ -1386,7 +1692,7 @@ impl Bmc {
                 )]),
             )]),
         );
-        let url = format!("Systems/{}", self.s.system_id());
+        let url = format!("Systems/{}", self.s.resolve_system_id().await?);
         self.s.client.patch(&url, body).await.map(|_status_code| ())
     }
 
@@ -1450,7 +1756,7 @@ impl Bmc {
                     INTEL_ENABLE_VIRTUALIZATION_KEY, AMD_ENABLE_VIRTUALIZATION_KEY
                 )
                 .to_string(),
-                url: format!("Systems/{}/Bios", self.s.system_id()),
+                url: format!("Systems/{}/Bios", self.s.resolve_system_id().await?),
             })
         }
     }
@@ -1463,7 +1769,7 @@ impl Bmc {
             "Attributes",
             HashMap::from([(enable_virtualization_key, "Enabled")]),
         );
-        let url = format!("Systems/{}/Bios/Pending", self.s.system_id());
+        let url = format!("Systems/{}/Bios/Pending", self.s.resolve_system_id().await?);
         self.s.client.patch(&url, body).await.map(|_status_code| ())
     }
 
@@ -1502,7 +1808,7 @@ impl Bmc {
     async fn set_uefi_boot_only(&self) -> Result<(), RedfishError> {
         let mut body = HashMap::new();
         body.insert("Attributes", self.uefi_boot_only_attributes());
-        let url = format!("Systems/{}/Bios/Pending", self.s.system_id());
+        let url = format!("Systems/{}/Bios/Pending", self.s.resolve_system_id().await?);
         self.s.client.patch(&url, body).await.map(|_status_code| ())
     }
 
@@ -1527,7 +1833,7 @@ impl Bmc {
                 ("BootSourceOverrideTarget", target_str),
             ]),
         )]);
-        let url = format!("Systems/{}", self.s.system_id());
+        let url = format!("Systems/{}", self.s.resolve_system_id().await?);
         self.s.client.patch(&url, body).await.map(|_status_code| ())
     }
 
@@ -1585,13 +1891,19 @@ impl Bmc {
 
     // lenovo stores the sel as part of the system
     async fn get_system_event_log(&self) -> Result<Vec<LogEntry>, RedfishError> {
-        let url = format!("Systems/{}/LogServices/SEL", self.s.system_id());
+        let url = format!(
+            "Systems/{}/LogServices/SEL",
+            self.s.resolve_system_id().await?
+        );
         let (_status_code, log_service): (_, LogService) = self.s.client.get(&url).await?;
         // If there are no log entries, this field and the `SEL/Entries` endpoint do not exist.
         if log_service.entries.is_none() {
             return Ok(vec![]);
         }
-        let url = format!("Systems/{}/LogServices/SEL/Entries", self.s.system_id());
+        let url = format!(
+            "Systems/{}/LogServices/SEL/Entries",
+            self.s.resolve_system_id().await?
+        );
         let (_status_code, log_entry_collection): (_, LogEntryCollection) =
             self.s.client.get(&url).await?;
         let log_entries = log_entry_collection.members;
@@ -1643,12 +1955,15 @@ impl Bmc {
         Ok(false)
     }
 
-    fn get_boot_settings_uri(&self) -> String {
-        format!("Systems/{}/Oem/Lenovo/BootSettings", self.s.system_id())
+    async fn get_boot_settings_uri(&self) -> Result<String, RedfishError> {
+        Ok(format!(
+            "Systems/{}/Oem/Lenovo/BootSettings",
+            self.s.resolve_system_id().await?
+        ))
     }
 
     async fn get_network_boot_order(&self) -> Result<LenovoBootOrder, RedfishError> {
-        let url = self.get_boot_settings_uri();
+        let url = self.get_boot_settings_uri().await?;
         let (_status_code, boot_settings): (_, BootSettings) = self.s.client.get(&url).await?;
         for member in &boot_settings.members {
             let id = member.odata_id_get()?;
@@ -1718,3 +2033,49 @@ impl UpdateParameters {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use serde_json::json;
+
+    use super::Bmc;
+    use crate::fake_bmc::FakeBmc;
+    use crate::Redfish;
+
+    #[tokio::test]
+    async fn test_delete_boot_option_prunes_pending_order() {
+        let system: serde_json::Value = serde_json::from_str(include_str!(
+            "../tests/mockups/lenovo/redfish/v1/Systems/1/index.json"
+        ))
+        .unwrap();
+        let option = "/redfish/v1/Systems/1/BootOptions/Boot0003";
+        let fake = FakeBmc::start(&[
+            ("/redfish/v1/Systems/1", system),
+            ("/redfish/v1/Systems/1/Pending", json!({})),
+            (
+                option,
+                json!({
+                    "@odata.id": option,
+                    "@odata.type": "#BootOption.v1_0_4.BootOption",
+                    "Id": "Boot0003",
+                    "Name": "Boot0003",
+                    "BootOptionReference": "Boot0003",
+                    "DisplayName": "UEFI PXE"
+                }),
+            ),
+        ])
+        .await;
+        let bmc = Bmc::new(fake.standard()).unwrap();
+        bmc.delete_boot_option("Boot0003").await.unwrap();
+        let writes = fake.writes();
+        assert_eq!(writes.len(), 2);
+        assert_eq!(
+            (writes[0].method.as_str(), writes[0].path.as_str()),
+            ("DELETE", option)
+        );
+        // The BMC kept Boot0003 in BootOrder, Lenovo takes the new one on Pending
+        assert_eq!(writes[1].path, "/redfish/v1/Systems/1/Pending");
+        assert_eq!(writes[1].body, json!({"Boot": {"BootOrder": ["Boot0002"]}}));
+    }
+}
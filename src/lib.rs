@@ -22,6 +22,7 @@
 use std::{collections::HashMap, fmt, path::Path, time::Duration};
 
 pub mod model;
+use futures_util::{StreamExt, TryStreamExt};
 use model::account_service::ManagerAccount;
 pub use model::chassis::{Assembly, Chassis, NetworkAdapter};
 pub use model::ethernet_interface::EthernetInterface;
@@ -32,16 +33,27 @@ pub use model::resource::{Collection, OData, Resource};
 use model::sensor::GPUSensors;
 use model::service_root::{RedfishVendor, ServiceRoot};
 use model::software_inventory::SoftwareInventory;
-pub use model::system::{BootOptions, PCIeDevice, PowerState, SystemPowerControl, Systems};
+pub use model::system::{
+    BootOptions, NewBootOption, PCIeDevice, PowerRestorePolicy, PowerState, SystemPowerControl,
+    Systems,
+};
 use model::task::Task;
 use model::update_service::{ComponentType, TransferProtocolType, UpdateService};
 pub use model::EnabledDisabled;
 use model::Manager;
-use model::{secure_boot::SecureBoot, BootOption, ComputerSystem, ODataId};
+use model::{
+    secure_boot::{SecureBoot, SecureBootResetType},
+    BootOption, ComputerSystem, ODataId,
+};
 use serde::{Deserialize, Serialize};
+mod collection;
 mod dell;
 mod error;
+mod export;
+#[cfg(test)]
+mod fake_bmc;
 mod hpe;
+mod lenient;
 mod lenovo;
 mod network;
 mod nvidia_dpu;
@@ -50,21 +62,40 @@ mod nvidia_gbswitch;
 mod nvidia_gbx00;
 mod nvidia_gh200;
 mod nvidia_viking;
+mod secret;
 mod supermicro;
-pub use network::{Endpoint, RedfishClientPool, RedfishClientPoolBuilder, REDFISH_ENDPOINT};
+pub use collection::CollectionIter;
+pub use network::{
+    ActionResponse, Backoff, Clock, Endpoint, ManualClock, Metrics, RedfishClientPool,
+    RedfishClientPoolBuilder, RequestMetric, SweepProgress, TaskMonitor, TokioClock,
+    REDFISH_ENDPOINT,
+};
 pub mod standard;
 pub use error::RedfishError;
+pub use export::{
+    diff_state, diff_state_with, ExportLimits, StateChange, EXPORT_ERROR, EXPORT_TRUNCATED,
+    VOLATILE_PROPERTIES,
+};
+pub use secret::SecretString;
+pub use tokio_util::sync::CancellationToken;
 
 use crate::model::certificate::Certificate;
 use crate::model::component_integrity::ComponentIntegrities;
 use crate::model::power::Power;
 use crate::model::sel::LogEntry;
 use crate::model::storage::Drives;
-use crate::model::thermal::Thermal;
+use crate::model::thermal::{LeakDetector, Thermal};
+
+/// Port GETs in flight at once in `get_network_adapter_ports`
+const PORT_CONCURRENCY: usize = 4;
 
 /// Interface to a BMC Redfish server. All calls will include one or more HTTP network calls.
 #[async_trait::async_trait]
 pub trait Redfish: Send + Sync + 'static {
+    /// The standard client this one is built on. Default methods that aren't built from other
+    /// methods of this trait go through it.
+    fn standard(&self) -> &standard::RedfishStandard;
+
     /// Rename a user
     async fn change_username(&self, old_name: &str, new_name: &str) -> Result<(), RedfishError>;
 
@@ -72,13 +103,17 @@ pub trait Redfish: Send + Sync + 'static {
     /// This looks up the ID for given username before calling change_password_by_id.
     /// That lookup makes it unsuitable for changing the initial password on
     /// PasswordChangeRequired.
-    async fn change_password(&self, username: &str, new_pass: &str) -> Result<(), RedfishError>;
+    async fn change_password(
+        &self,
+        username: &str,
+        new_pass: &SecretString,
+    ) -> Result<(), RedfishError>;
 
     /// Change password by id
     async fn change_password_by_id(
         &self,
         account_id: &str,
-        new_pass: &str,
+        new_pass: &SecretString,
     ) -> Result<(), RedfishError>;
 
     /// List current user accounts
@@ -88,7 +123,7 @@ pub trait Redfish: Send + Sync + 'static {
     async fn create_user(
         &self,
         username: &str,
-        password: &str,
+        password: &SecretString,
         role_id: RoleId,
     ) -> Result<(), RedfishError>;
 
@@ -101,19 +136,75 @@ pub trait Redfish: Send + Sync + 'static {
     // Get software inventory collection
     async fn get_software_inventories(&self) -> Result<Vec<String>, RedfishError>;
 
+    /// Stream the image currently installed for a firmware/software inventory entry to `out`,
+    /// for BMCs exposing it via an OEM URI. NotSupported when the entry has no such URI.
+    async fn download_firmware_image(
+        &self,
+        inventory_id: &str,
+        out: &Path,
+    ) -> Result<(), RedfishError>;
+
     // List all Tasks
     async fn get_tasks(&self) -> Result<Vec<String>, RedfishError>;
 
     // Get information about a task
     async fn get_task(&self, id: &str) -> Result<Task, RedfishError>;
 
+    /// The TaskService: whether and when finished tasks are deleted
+    async fn get_task_service(&self) -> Result<model::task::TaskService, RedfishError> {
+        self.standard().get_task_service().await
+    }
+
+    /// Poll get_task until the task reaches a terminal state and return it; check
+    /// `Task::is_success` for the outcome. The first poll waits `poll`, or the configured
+    /// initial interval if None, then backs off as configured with
+    /// RedfishClientPoolBuilder::poll_backoff. Returns RedfishError::Timeout after `timeout`.
+    async fn wait_for_task(
+        &self,
+        task_id: &str,
+        timeout: Duration,
+        poll: Option<Duration>,
+    ) -> Result<Task, RedfishError> {
+        let client = &self.standard().client;
+        let mut backoff = client.poll_backoff();
+        if let Some(poll) = poll {
+            backoff.initial = poll;
+        }
+        let what = format!("task {task_id} to finish");
+        client
+            .poll_until(&what, backoff, timeout, || async {
+                let task = self.get_task(task_id).await?;
+                Ok(task.is_terminal().then_some(task))
+            })
+            .await
+    }
+
+    /// Jobs queued or running on the BMC, e.g. BIOS config jobs waiting for a reboot. Pending
+    /// BIOS attributes without a job are in `pending` instead.
+    async fn get_pending_jobs(&self) -> Result<Vec<model::job::Job>, RedfishError>;
+
+    /// Re-attach to a firmware update started earlier, e.g. by a process that has since restarted.
+    /// Checks the task still exists and is an update task. The returned Task carries the
+    /// task_monitor URL, and can be polled further with get_task(task_id).
+    async fn attach_to_update(&self, task_id: &str) -> Result<Task, RedfishError>;
+
     /// Is this thing even on?
     async fn get_power_state(&self) -> Result<PowerState, RedfishError>;
 
+    /// Poll get_power_state until it reports `target`, first after `poll`, then backing off
+    /// as configured with RedfishClientPoolBuilder::poll_backoff.
+    /// Returns RedfishError::Timeout if that hasn't happened within `timeout`.
+    async fn wait_for_power_state(
+        &self,
+        target: PowerState,
+        timeout: Duration,
+        poll: Duration,
+    ) -> Result<(), RedfishError>;
+
     /// Returns info about operations that the service supports.
     async fn get_service_root(&self) -> Result<ServiceRoot, RedfishError>;
 
-    /// Returns info about available computer systems.
+    /// Returns the ids of the computer systems, empty if the BMC lists none.
     async fn get_systems(&self) -> Result<Vec<String>, RedfishError>;
 
     /// Returns info about computer system.
@@ -125,6 +216,32 @@ pub trait Redfish: Send + Sync + 'static {
     /// Returns info about managers
     async fn get_manager(&self) -> Result<Manager, RedfishError>;
 
+    /// Returns one manager, by an id from get_managers. get_manager only returns the one this
+    /// client was set up for; GB200 for example also has an HGX_BMC_0 manager.
+    async fn get_manager_by_id(&self, id: &str) -> Result<Manager, RedfishError> {
+        self.standard().get_manager_by_id(id).await
+    }
+
+    /// Quick health summary of the BMC itself: Status, active Conditions, uptime, free memory
+    /// (when the BMC links ManagerDiagnosticData) and OEM self-test results. Check it before
+    /// trusting the BMC's other readings.
+    async fn get_manager_health(&self) -> Result<model::ManagerHealth, RedfishError>;
+
+    /// Status.Conditions of the system, the manager and every chassis in one list: what
+    /// the BMC says is wrong right now, each with its severity and origin.
+    async fn get_active_conditions(&self) -> Result<Vec<model::Condition>, RedfishError>;
+
+    /// Every fabric (PCIe, NVLink, ...) with its switches and their ports, including what
+    /// each port is connected to. Empty if the BMC has no Fabrics.
+    async fn get_fabric_topology(&self)
+        -> Result<Vec<model::fabric::FabricTopology>, RedfishError>;
+
+    /// PCIe switches of the chassis, with their ports, from the fabric topology.
+    async fn get_pcie_switches(
+        &self,
+        chassis_id: &str,
+    ) -> Result<Vec<model::fabric::SwitchPorts>, RedfishError>;
+
     /// Get Secure Boot state
     async fn get_secure_boot(&self) -> Result<SecureBoot, RedfishError>;
 
@@ -154,15 +271,58 @@ pub trait Redfish: Send + Sync + 'static {
         database_id: &str,
     ) -> Result<Task, RedfishError>;
 
+    /// Reset the Secure Boot key databases (PK, KEK, db, dbx), either to the factory defaults
+    /// or by deleting all keys so new ones can be enrolled.
+    /// UEFI applies the change on the next boot, so the system usually needs a reboot.
+    async fn reset_secure_boot_keys(
+        &self,
+        reset_type: SecureBootResetType,
+    ) -> Result<(), RedfishError>;
+
     /// Power supplies and voltages metrics
     async fn get_power_metrics(&self) -> Result<Power, RedfishError>;
 
+    /// Every power supply with model, serial, firmware, input and health. Reads
+    /// PowerSubsystem/PowerSupplies, falling back to the legacy Power resource.
+    async fn get_power_supplies(&self) -> Result<Vec<model::power::PowerSupplyInfo>, RedfishError>;
+
     /// Change power state: on, off, reboot, etc
     async fn power(&self, action: SystemPowerControl) -> Result<(), RedfishError>;
 
+    /// What the system does when AC power returns: PowerRestorePolicy on the ComputerSystem.
+    async fn get_power_restore_policy(&self) -> Result<PowerRestorePolicy, RedfishError>;
+
+    /// Set what the system does when AC power returns.
+    async fn set_power_restore_policy(
+        &self,
+        policy: PowerRestorePolicy,
+    ) -> Result<(), RedfishError>;
+
+    /// Wait this many seconds before powering on after AC power returns, so a rack
+    /// doesn't come back all at once. PowerOnDelaySeconds on the ComputerSystem, or
+    /// the vendor's BIOS attribute (Dell, HPE), in which case it applies on the next reboot.
+    async fn set_power_on_delay(&self, seconds: u32) -> Result<(), RedfishError>;
+
     /// Reboot the BMC itself
     async fn bmc_reset(&self) -> Result<(), RedfishError>;
 
+    /// The firmware banks of a dual-bank component with their versions and which one boots
+    /// next. `component` is a Manager id, e.g. the BMC's, that lists its SoftwareImages.
+    async fn get_firmware_slots(
+        &self,
+        component: &str,
+    ) -> Result<Vec<model::software_inventory::FirmwareSlot>, RedfishError>;
+
+    /// Boot `component` from the bank `slot` (a FirmwareSlot id) on its next reset, e.g. to
+    /// roll back a staged update.
+    async fn activate_firmware_slot(&self, component: &str, slot: &str)
+        -> Result<(), RedfishError>;
+
+    /// Reset the External Root of Trust guarding the BMC (EROTBMC) or BIOS (EROTBIOS)
+    /// firmware, e.g. during firmware recovery. Every matching ERoT is reset. GB200 only,
+    /// NotSupported if any of them lacks the NVIDIA AuxPowerReset action.
+    async fn reset_erot(&self, target: ComponentType) -> Result<(), RedfishError>;
+
     /// Reset Chassis
     async fn chassis_reset(
         &self,
@@ -170,27 +330,73 @@ pub trait Redfish: Send + Sync + 'static {
         reset_type: SystemPowerControl,
     ) -> Result<(), RedfishError>;
 
-    /// Reset BMC to the factory defaults.
+    /// Reset BMC to the factory defaults, keeping its network settings (and user accounts
+    /// where the BMC allows) so it stays reachable. Use bmc_reset_to_defaults_with to reset
+    /// everything. iLO can't keep them, so on HPE this resets everything.
     async fn bmc_reset_to_defaults(&self) -> Result<(), RedfishError>;
 
+    /// Reset BMC to the factory defaults, keeping what `scope` says. Checked against the
+    /// ResetTypes the BMC advertises, RedfishError::NotSupported if it isn't one of them.
+    async fn bmc_reset_to_defaults_with(
+        &self,
+        scope: model::manager::ResetToDefaultsType,
+    ) -> Result<(), RedfishError> {
+        self.standard().bmc_reset_to_defaults_with(scope).await
+    }
+
     /// Fans and temperature sensors
     async fn get_thermal_metrics(&self) -> Result<Thermal, RedfishError>;
 
+    /// Power, thermal and leak detector readings in one call. The power and thermal
+    /// reads run concurrently, and vendors that walk every chassis do so only once.
+    async fn get_all_metrics(&self) -> Result<AllMetrics, RedfishError>;
+
     /// Voltage, temperature, etc sensors for gpus if they exist.
     async fn get_gpu_sensors(&self) -> Result<Vec<GPUSensors>, RedfishError>;
 
     /// get system event log similar to ipmitool sel
     async fn get_system_event_log(&self) -> Result<Vec<LogEntry>, RedfishError>;
 
+    /// get_system_event_log newest-first, limited to entries created at or after `since` and to
+    /// at most `limit` entries. Filtered on the BMC where it supports `$filter`.
+    async fn get_system_event_log_since(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        limit: Option<usize>,
+    ) -> Result<Vec<LogEntry>, RedfishError>;
+
+    /// get_system_event_log with the IPMI sensor type, event type and direction decoded
+    async fn get_decoded_sel(&self) -> Result<Vec<model::sel::DecodedSelEntry>, RedfishError>;
+
     /// get bmc event log (power events, etc.)
     async fn get_bmc_event_log(
         &self,
         from: Option<chrono::DateTime<chrono::Utc>>,
     ) -> Result<Vec<LogEntry>, RedfishError>;
 
+    /// The BMC's own journal, from the Manager's Journal log service (NVIDIA OpenBMC), as
+    /// text lines oldest first, limited to entries created at or after `since`. `since` is
+    /// sent as `$filter` when the service root advertises it, and applied here in any case.
+    /// NotSupported if the BMC has no journal log service.
+    async fn get_bmc_journal(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<String>, RedfishError>;
+
+    /// Where the host is in its boot, from ComputerSystem.BootProgress.
+    /// Distinguishes a node stuck in POST from one that has handed off to the OS.
+    async fn get_boot_progress(&self) -> Result<model::system::BootProgress, RedfishError>;
+
+    /// POST code log entries, on BMCs that expose a PostCodes log service
+    async fn get_post_codes(&self) -> Result<Vec<LogEntry>, RedfishError>;
+
     /// get drives metrics
     async fn get_drives_metrics(&self) -> Result<Vec<Drives>, RedfishError>;
 
+    /// Model, serial and part numbers of the chassis, systems, processors, memory, drives and
+    /// power supplies, in one report
+    async fn get_fru_inventory(&self) -> Result<model::fru::FruInventory, RedfishError>;
+
     /// Sets up a reasonable UEFI configuration.
     /// remember to call lockdown() afterwards to secure the server
     /// - boot_interface_mac: MAC Address of the NIC you wish to boot from
@@ -199,12 +405,16 @@ pub trait Redfish: Send + Sync + 'static {
     /// bios_profiles: Map of vendor/model (with spaces replaced by underscores)/profile/type
     ///   to extra settings; expected to come from config rather than hardcoded.
     /// selected_profile: Profile to use (if present)
+    /// Safe to re-run: steps that machine_setup_status shows are already done are skipped.
+    /// One-shot steps it can't see, such as boot_once or clearing the TPM, run every time.
+    /// Returns the steps that were applied or skipped. If a step fails, the error is
+    /// RedfishError::MachineSetupFailed, carrying the steps done until then.
     async fn machine_setup(
         &self,
         boot_interface_mac: Option<&str>,
         bios_profiles: &BiosProfileVendor,
         selected_profile: BiosProfileType,
-    ) -> Result<(), RedfishError>;
+    ) -> Result<MachineSetupReport, RedfishError>;
 
     /// Is everything that machine_setup does already done?
     async fn machine_setup_status(
@@ -226,29 +436,81 @@ pub trait Redfish: Send + Sync + 'static {
     /// Are the BIOS and BMC currently locked down?
     async fn lockdown_status(&self) -> Result<Status, RedfishError>;
 
-    /// Enable SSH access to console
-    async fn setup_serial_console(&self) -> Result<(), RedfishError>;
+    /// Enable SSH access to console. Returns false, changing nothing, if serial_console_status
+    /// says it already is.
+    async fn setup_serial_console(&self) -> Result<bool, RedfishError>;
 
     /// Is the serial console setup?
     async fn serial_console_status(&self) -> Result<Status, RedfishError>;
 
+    /// How to connect to the serial console: connection types, SSH port and the command to
+    /// enter the console once logged in, where the BMC says.
+    async fn get_serial_console_info(&self) -> Result<model::SerialConsoleInfo, RedfishError> {
+        let manager = self.get_manager().await?;
+        let system = self.get_system().await?;
+        Ok(model::SerialConsoleInfo::new(
+            manager.serial_console,
+            system.serial_console,
+        ))
+    }
+
+    /// Bit rate, parity, data and stop bits, flow control and enabled of the BMC's (first)
+    /// serial interface
+    async fn get_serial_interface_config(
+        &self,
+    ) -> Result<model::serial_interface::SerialInterfaceConfig, RedfishError> {
+        self.standard().get_serial_interface_config().await
+    }
+
+    /// PATCH the fields of config that are set onto the BMC's (first) serial interface.
+    /// Does nothing if get_serial_interface_config already satisfies config.
+    async fn set_serial_interface_config(
+        &self,
+        config: model::serial_interface::SerialInterfaceConfig,
+    ) -> Result<(), RedfishError> {
+        self.standard().set_serial_interface_config(config).await
+    }
+
     /// Show available boot options
     async fn get_boot_options(&self) -> Result<BootOptions, RedfishError>;
 
     /// Show available boot options
     async fn get_boot_option(&self, option_id: &str) -> Result<BootOption, RedfishError>;
 
+    /// Create a boot option, e.g. a UEFI HTTP boot entry for an installer URL.
+    /// Returns the new option's id. NotSupported if the BMC doesn't allow creating them.
+    async fn create_boot_option(&self, opt: NewBootOption) -> Result<String, RedfishError>;
+
+    /// Delete a boot option, and drop it from BootOrder if the BMC left it there.
+    /// NotSupported if the BMC doesn't allow deleting them.
+    async fn delete_boot_option(&self, option_id: &str) -> Result<(), RedfishError>;
+
     /// Boot a single time of the given target. Does not change boot order after that.
     async fn boot_once(&self, target: Boot) -> Result<(), RedfishError>;
 
+    /// PXE boot on the next boot only, the same way on every vendor.
+    /// BootTargetNotAvailable if the system doesn't list Pxe as an allowable boot target.
+    async fn set_pxe_boot_once(&self) -> Result<(), RedfishError> {
+        self.get_system()
+            .await?
+            .boot
+            .ensure_target_allowed(model::boot::BootSourceOverrideTarget::Pxe)?;
+        self.boot_once(Boot::Pxe).await
+    }
+
     /// Change boot order putting this target first
     async fn boot_first(&self, target: Boot) -> Result<(), RedfishError>;
 
-    /// Change boot order by setting boot array.
+    /// Change boot order by setting boot array. Goes to the system's settings object when it
+    /// has one, see `BootOrderTarget::detect`, else to the system itself, with the existing
+    /// entries appended after `boot_array`. This includes the standard client, Dell and HPE,
+    /// where it used to be NotSupported. Vendors that need a different resource or shape
+    /// override it.
     async fn change_boot_order(&self, boot_array: Vec<String>) -> Result<(), RedfishError>;
 
-    /// Reset and enable the TPM
-    async fn clear_tpm(&self) -> Result<(), RedfishError>;
+    /// Reset and enable the TPM. Most BMCs stage this in the BIOS settings, in which case
+    /// the result says a reboot is needed before it takes effect.
+    async fn clear_tpm(&self) -> Result<ApplyResult, RedfishError>;
 
     /// List PCIe devices
     async fn pcie_devices(&self) -> Result<Vec<PCIeDevice>, RedfishError>;
@@ -289,15 +551,40 @@ pub trait Redfish: Send + Sync + 'static {
         values: HashMap<String, serde_json::Value>,
     ) -> Result<(), RedfishError>;
 
-    /// Reset BIOS to factory settings
-    async fn reset_bios(&self) -> Result<(), RedfishError>;
+    /// Reset BIOS to factory settings, normally with the Bios.ResetBios action.
+    /// This usually only takes effect on the next reboot, so the result is RebootRequired
+    /// unless the BMC says explicitly that the reset was applied.
+    async fn reset_bios(&self) -> Result<ApplyResult, RedfishError>;
 
     /// Pending BIOS attributes. Changes that were requested but not applied yet because
     /// they need a reboot.
     async fn pending(&self) -> Result<HashMap<String, serde_json::Value>, RedfishError>;
 
-    /// Clear all pending jobs
-    async fn clear_pending(&self) -> Result<(), RedfishError>;
+    /// The pending BIOS attributes that differ from the current ones, with both values,
+    /// sorted by attribute name.
+    async fn pending_diff(&self) -> Result<Vec<model::BiosAttrChange>, RedfishError>;
+
+    /// The other BIOS attributes, and their values, the AttributeRegistry's Dependencies require
+    /// before the BMC accepts attrs. Empty if the BMC doesn't publish a registry.
+    async fn get_bios_prerequisites(
+        &self,
+        attrs: &HashMap<String, serde_json::Value>,
+    ) -> Result<HashMap<String, serde_json::Value>, RedfishError>;
+
+    /// Add the prerequisites from get_bios_prerequisites to attrs (logging a warning for each),
+    /// validate them against the BIOS AttributeRegistry, if the BMC publishes one, then write
+    /// them. If the live Bios resource is writable, i.e. has no `@Redfish.Settings`, attributes
+    /// the registry marks as not needing a reset go to it, the rest in one PATCH to the vendor's
+    /// Settings (pending) resource. Every rejected attribute is listed in
+    /// RedfishError::InvalidBiosAttributes, and nothing is written in that case. If the Settings
+    /// PATCH fails after the live one the error is RedfishError::BiosAttributesPartiallyApplied.
+    async fn set_bios_attributes(
+        &self,
+        attrs: HashMap<String, serde_json::Value>,
+    ) -> Result<(), RedfishError>;
+
+    /// Clear all pending jobs, or pending BIOS attributes where the BMC has no job queue
+    async fn clear_pending(&self) -> Result<ClearPendingResult, RedfishError>;
 
     // List all Network Device Functions of a given Chassis
     async fn get_network_device_functions(
@@ -319,6 +606,22 @@ pub trait Redfish: Send + Sync + 'static {
     // Get Chassis details
     async fn get_chassis(&self, id: &str) -> Result<Chassis, RedfishError>;
 
+    /// Inlet temperature, humidity, dew point, power and energy for the chassis as a whole
+    async fn get_environment_metrics(
+        &self,
+        chassis_id: &str,
+    ) -> Result<model::environment_metrics::EnvironmentMetrics, RedfishError>;
+
+    /// Cumulative EnergykWh readings from the EnvironmentMetrics of every chassis that has one
+    async fn get_energy_kwh(&self) -> Result<Vec<model::sensor::EnergyReading>, RedfishError>;
+
+    /// All sensors under Chassis/{chassis_id}/Sensors, optionally only those of one ReadingType
+    async fn get_sensors(
+        &self,
+        chassis_id: &str,
+        reading_type: Option<model::sensor::ReadingType>,
+    ) -> Result<Vec<model::sensor::Sensor>, RedfishError>;
+
     // Get Chassis Assembly details
     async fn get_chassis_assembly(&self, chassis_id: &str) -> Result<Assembly, RedfishError>;
 
@@ -363,6 +666,29 @@ pub trait Redfish: Send + Sync + 'static {
         id: &str,
     ) -> Result<NetworkPort, RedfishError>;
 
+    /// All ports of a network adapter in one call, in `get_ports` order. A few are fetched
+    /// at a time, adapters with many ports would otherwise flood the BMC.
+    async fn get_network_adapter_ports(
+        &self,
+        chassis_id: &str,
+        adapter_id: &str,
+    ) -> Result<Vec<NetworkPort>, RedfishError> {
+        let ids = self.get_ports(chassis_id, adapter_id).await?;
+        futures_util::stream::iter(ids)
+            .map(|id| async move { self.get_port(chassis_id, adapter_id, &id).await })
+            .buffered(PORT_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+
+    // Get traffic and error counters of a High Speed Port
+    async fn get_port_metrics(
+        &self,
+        chassis_id: &str,
+        network_adapter: &str,
+        id: &str,
+    ) -> Result<model::port::PortMetrics, RedfishError>;
+
     // List all Ethernet Interfaces for the default `Manager`
     async fn get_manager_ethernet_interfaces(&self) -> Result<Vec<String>, RedfishError>;
 
@@ -381,12 +707,13 @@ pub trait Redfish: Send + Sync + 'static {
         id: &str,
     ) -> Result<EthernetInterface, RedfishError>;
 
-    // Change UEFI Password
+    /// Change the UEFI password. A wrong current password and a new password the BIOS
+    /// policy rejects come back as BiosPasswordChange variants rather than errors.
     async fn change_uefi_password(
         &self,
-        current_uefi_password: &str,
-        new_uefi_password: &str,
-    ) -> Result<Option<String>, RedfishError>;
+        current_uefi_password: &SecretString,
+        new_uefi_password: &SecretString,
+    ) -> Result<BiosPasswordChange, RedfishError>;
 
     async fn get_job_state(&self, job_id: &str) -> Result<JobState, RedfishError>;
 
@@ -416,6 +743,28 @@ pub trait Redfish: Send + Sync + 'static {
     ///
     async fn get_resource(&self, id: ODataId) -> Result<Resource, RedfishError>;
 
+    /// Fetch the resource a log entry points to via Links.OriginOfCondition.
+    /// Convert it with try_get(), as for get_resource().
+    async fn resolve_origin(&self, entry: &LogEntry) -> Result<Resource, RedfishError>;
+
+    /// The node's Redfish tree as one JSON document, for archiving. Starts at the service root
+    /// and follows links down the URI hierarchy `depth` levels, several requests at a time,
+    /// replacing each link with the resource. Resources that fail have EXPORT_ERROR instead.
+    /// Stops early, setting EXPORT_TRUNCATED on the root, at the default ExportLimits: 5000
+    /// resources or 64 MiB.
+    async fn export_state(&self, depth: u8) -> Result<serde_json::Value, RedfishError> {
+        self.export_state_with(depth, ExportLimits::default()).await
+    }
+
+    /// export_state with your own limits
+    async fn export_state_with(
+        &self,
+        depth: u8,
+        limits: ExportLimits,
+    ) -> Result<serde_json::Value, RedfishError> {
+        export::export_state(&self.standard().client, depth, limits).await
+    }
+
     /// A kind-of-generic api to retrieve any resource. See get_resource() api for more details.
     /// This method returns Collection object that contains raw JSON and can be conveted to
     /// generic type ResourceCollection<T> via generic method try_get()
@@ -442,6 +791,8 @@ pub trait Redfish: Send + Sync + 'static {
     /// It will choose Uefi Http IPv4 option if any.
     /// If dpu's mac can be passed in as  mac_address to identify the dpu, otherwise method will attempt to find the dpu
     /// by enumeration NetworkAdapters and associated resources.
+    /// On GB200, which has no such resources, an empty mac_address picks the only Uefi Http IPv4
+    /// option, and errors if there are none or several.
     async fn set_boot_order_dpu_first(
         &self,
         mac_address: &str,
@@ -449,23 +800,53 @@ pub trait Redfish: Send + Sync + 'static {
 
     async fn clear_uefi_password(
         &self,
-        current_uefi_password: &str,
+        current_uefi_password: &SecretString,
     ) -> Result<Option<String>, RedfishError>;
 
     async fn get_update_service(&self) -> Result<UpdateService, RedfishError>;
 
     async fn get_base_mac_address(&self) -> Result<Option<String>, RedfishError>;
 
-    async fn lockdown_bmc(&self, target: EnabledDisabled) -> Result<(), RedfishError>;
+    /// Lock the BMC only. Returns false, changing nothing, if bmc_lockdown_status says it's
+    /// already in the target state.
+    async fn lockdown_bmc(&self, target: EnabledDisabled) -> Result<bool, RedfishError>;
+
+    /// Is the BMC currently locked down? The BMC part of lockdown_status.
+    async fn bmc_lockdown_status(&self) -> Result<Status, RedfishError>;
 
     async fn is_ipmi_over_lan_enabled(&self) -> Result<bool, RedfishError>;
 
-    async fn enable_ipmi_over_lan(&self, target: EnabledDisabled) -> Result<(), RedfishError>;
+    /// Returns false, changing nothing, if is_ipmi_over_lan_enabled already matches target.
+    async fn enable_ipmi_over_lan(&self, target: EnabledDisabled) -> Result<bool, RedfishError>;
+
+    /// Returns false, changing nothing, if is_rshim_enabled says it already is.
+    async fn enable_rshim_bmc(&self) -> Result<bool, RedfishError>;
 
-    async fn enable_rshim_bmc(&self) -> Result<(), RedfishError>;
+    /// Whether the BMC's rshim is enabled, to check before enable_rshim_bmc. Only DPUs have one.
+    async fn is_rshim_enabled(&self) -> Result<bool, RedfishError> {
+        Err(RedfishError::NotSupported("is_rshim_enabled".to_string()))
+    }
 
     // Only applicable to Vikings
-    async fn clear_nvram(&self) -> Result<(), RedfishError>;
+    async fn clear_nvram(&self) -> Result<ApplyResult, RedfishError>;
+
+    // Only applicable to Vikings
+    async fn set_uefi_http(
+        &self,
+        cfg: model::oem::nvidia_viking::UefiHttpAttributes,
+    ) -> Result<(), RedfishError>;
+
+    // Only applicable to Vikings
+    async fn set_virtualization(
+        &self,
+        cfg: model::oem::nvidia_viking::VirtAttributes,
+    ) -> Result<(), RedfishError>;
+
+    // Only applicable to Vikings
+    /// The raw BIOS lockdown attribute values, for auditing beyond what lockdown_status reports.
+    async fn get_bios_lockdown(
+        &self,
+    ) -> Result<model::oem::nvidia_viking::BiosLockdownAttributes, RedfishError>;
 
     // Only applicable to DPUs
     async fn get_nic_mode(&self) -> Result<Option<NicMode>, RedfishError>;
@@ -507,6 +888,11 @@ pub trait Redfish: Send + Sync + 'static {
 
     fn ac_powercycle_supported_by_power(&self) -> bool;
 
+    /// Which optional capabilities this implementation provides. This is a static declaration,
+    /// so a caller can plan (e.g. grey out an action) without calling a method and handling
+    /// NotSupported. A feature marked supported can still fail at runtime.
+    fn supported_features(&self) -> FeatureSet;
+
     /// Check if the boot order is configured as we expect (Network boot)
     async fn is_boot_order_setup(&self, mac_address: &str) -> Result<bool, RedfishError>;
 
@@ -608,6 +994,14 @@ impl Status {
         self.status == StatusInternal::Disabled
     }
 
+    /// Is it fully in the target state?
+    pub fn is_fully(&self, target: EnabledDisabled) -> bool {
+        match target {
+            EnabledDisabled::Enabled => self.is_fully_enabled(),
+            EnabledDisabled::Disabled => self.is_fully_disabled(),
+        }
+    }
+
     /// Did lockdown enable/disable fail part way through, so we are partially locked?
     pub fn is_partially_enabled(&self) -> bool {
         self.status == StatusInternal::Partial
@@ -637,6 +1031,30 @@ pub struct MachineSetupStatus {
     pub diffs: Vec<MachineSetupDiff>,
 }
 
+impl MachineSetupStatus {
+    /// Whether machine_setup has anything left to do. Lockdown is applied separately, so a
+    /// lockdown diff alone does not count.
+    pub fn needs_setup(&self) -> bool {
+        self.diffs.iter().any(|d| d.key != "lockdown")
+    }
+
+    pub fn has_diff(&self, key: &str) -> bool {
+        self.diffs.iter().any(|d| d.key == key)
+    }
+
+    /// machine_setup uses this to skip steps that are already done. If the check itself
+    /// failed, nothing is skipped.
+    pub(crate) fn or_apply_all(status: Result<Self, RedfishError>) -> Option<Self> {
+        match status {
+            Ok(status) => Some(status),
+            Err(e) => {
+                tracing::debug!("machine_setup_status failed, applying every step: {e}");
+                None
+            }
+        }
+    }
+}
+
 impl fmt::Display for MachineSetupStatus {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.is_done {
@@ -673,6 +1091,109 @@ impl fmt::Display for MachineSetupDiff {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MachineSetupOutcome {
+    Applied,
+    /// Already in the desired state, so not applied again
+    Skipped,
+    Failed,
+}
+
+/// One step machine_setup attempted, e.g. "disable_secure_boot"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MachineSetupStep {
+    pub name: String,
+    pub outcome: MachineSetupOutcome,
+    pub error: Option<String>,
+}
+
+/// What machine_setup did, step by step. On failure it is carried by
+/// RedfishError::MachineSetupFailed, ending with the step that failed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MachineSetupReport {
+    pub steps: Vec<MachineSetupStep>,
+}
+
+impl MachineSetupReport {
+    /// A report where every step was skipped
+    pub(crate) fn skipped(names: &[&str]) -> Self {
+        let mut report = Self::default();
+        for name in names {
+            report.skip(name);
+        }
+        report
+    }
+
+    pub(crate) fn skip(&mut self, name: &str) {
+        self.steps.push(MachineSetupStep {
+            name: name.to_string(),
+            outcome: MachineSetupOutcome::Skipped,
+            error: None,
+        });
+    }
+
+    /// Record the outcome of a step. A failure is returned as MachineSetupFailed, so the
+    /// caller can stop with `?` as before and still hand back what was done so far.
+    pub(crate) fn record<T>(
+        &mut self,
+        name: &str,
+        result: Result<T, RedfishError>,
+    ) -> Result<T, RedfishError> {
+        match result {
+            Ok(x) => {
+                self.steps.push(MachineSetupStep {
+                    name: name.to_string(),
+                    outcome: MachineSetupOutcome::Applied,
+                    error: None,
+                });
+                Ok(x)
+            }
+            Err(e) => {
+                self.steps.push(MachineSetupStep {
+                    name: name.to_string(),
+                    outcome: MachineSetupOutcome::Failed,
+                    error: Some(e.to_string()),
+                });
+                Err(RedfishError::MachineSetupFailed {
+                    step: name.to_string(),
+                    report: self.clone(),
+                    source: Box::new(e),
+                })
+            }
+        }
+    }
+
+    /// Like record, for steps that say whether they changed anything. Unchanged is Skipped.
+    pub(crate) fn record_change(
+        &mut self,
+        name: &str,
+        result: Result<bool, RedfishError>,
+    ) -> Result<bool, RedfishError> {
+        match result {
+            Ok(false) => {
+                self.skip(name);
+                Ok(false)
+            }
+            result => self.record(name, result),
+        }
+    }
+}
+
+impl fmt::Display for MachineSetupReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let steps = self
+            .steps
+            .iter()
+            .map(|s| match &s.error {
+                Some(e) => format!("{}: {:?} ({e})", s.name, s.outcome),
+                None => format!("{}: {:?}", s.name, s.outcome),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "{steps}")
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
 #[serde(rename_all = "lowercase")] // No tag requried - this is not nested
 pub enum JobState {
@@ -696,6 +1217,277 @@ impl JobState {
     }
 }
 
+/// Optional capabilities of a Redfish implementation, see `Redfish::supported_features`.
+/// Each field names the trait methods it covers.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FeatureSet {
+    /// get_power_metrics
+    pub power_metrics: bool,
+    /// get_thermal_metrics
+    pub thermal_metrics: bool,
+    /// get_gpu_sensors
+    pub gpu_sensors: bool,
+    /// get_system_event_log
+    pub system_event_log: bool,
+    /// set_bios
+    pub bios_settings: bool,
+    /// reset_bios
+    pub reset_bios: bool,
+    /// lockdown and lockdown_status
+    pub lockdown: bool,
+    /// setup_serial_console and serial_console_status
+    pub serial_console: bool,
+    /// enable_secure_boot and disable_secure_boot
+    pub secure_boot: bool,
+    /// clear_tpm
+    pub clear_tpm: bool,
+    /// clear_nvram
+    pub clear_nvram: bool,
+    /// update_firmware_multipart
+    pub multipart_firmware_update: bool,
+    /// get_system_ethernet_interface
+    pub system_ethernet_interfaces: bool,
+    /// get_chassis_network_adapters
+    pub chassis_network_adapters: bool,
+    /// set_boot_order_dpu_first
+    pub dpu_first_boot_order: bool,
+    /// get_component_integrities and the SPDM evidence calls
+    pub component_integrity: bool,
+    /// enable_infinite_boot
+    pub infinite_boot: bool,
+    /// set_nic_mode, which also depends on BMC firmware version
+    pub nic_mode: bool,
+    /// enable_rshim_bmc and set_host_rshim
+    pub rshim: bool,
+    /// enable_ipmi_over_lan
+    pub ipmi_over_lan: bool,
+}
+
+/// Everything `get_all_metrics` gathers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AllMetrics {
+    pub power: Power,
+    /// `leak_detectors` is moved out into the field below.
+    pub thermal: Thermal,
+    /// Empty on air cooled machines.
+    pub leak_detectors: Vec<LeakDetector>,
+}
+
+impl AllMetrics {
+    pub(crate) fn new(power: Power, mut thermal: Thermal) -> Self {
+        let leak_detectors = thermal.leak_detectors.take().unwrap_or_default();
+        AllMetrics {
+            power,
+            thermal,
+            leak_detectors,
+        }
+    }
+
+    /// Read power and thermal metrics concurrently through the vendor's own implementations.
+    pub(crate) async fn gather<R: Redfish + ?Sized>(r: &R) -> Result<Self, RedfishError> {
+        let (power, thermal) = tokio::try_join!(r.get_power_metrics(), r.get_thermal_metrics())?;
+        Ok(AllMetrics::new(power, thermal))
+    }
+}
+
+/// Outcome of an operation the BMC may either apply immediately or stage until the next reset.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ApplyResult {
+    Applied,
+    /// The change is pending. `message` is what the BMC said about it, if anything.
+    RebootRequired {
+        message: String,
+    },
+}
+
+impl ApplyResult {
+    /// Interpret the @Message.ExtendedInfo of an action or PATCH response to `url`. A write to a
+    /// settings object (Settings, Pending or SD) is only applied on reset, so a reboot is
+    /// required whatever the BMC said. Otherwise it is if a message's MessageId says so, e.g.
+    /// Base.1.8.ResetRequired or iLO.2.14.SystemResetRequired.
+    pub(crate) fn from_extended_info(info: Option<model::ExtendedInfo>, url: &str) -> Self {
+        let messages = info.map(|i| i.messages).unwrap_or_default();
+        let staged = url
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .is_some_and(|last| {
+                ["settings", "pending", "sd"]
+                    .iter()
+                    .any(|s| last.eq_ignore_ascii_case(s))
+            });
+        let reboot_message = |m: &model::Message| {
+            let key = m.message_key();
+            key.ends_with("ResetRequired") || key.ends_with("RebootRequired")
+        };
+        if !staged && !messages.iter().any(reboot_message) {
+            return ApplyResult::Applied;
+        }
+        let text = messages
+            .iter()
+            .map(|m| m.message.as_str())
+            .collect::<Vec<&str>>()
+            .join(" ");
+        let message = if text.is_empty() {
+            "Pending until the next system reboot".to_string()
+        } else {
+            text
+        };
+        ApplyResult::RebootRequired { message }
+    }
+
+    /// `from_extended_info` for the response to a POST to `url`
+    pub(crate) fn from_action_response(response: &ActionResponse, url: &str) -> Self {
+        let info = response
+            .body()
+            .and_then(|body| serde_json::from_value(body.clone()).ok());
+        Self::from_extended_info(info, url)
+    }
+
+    /// The response to Bios.ResetBios. The defaults are normally loaded on the next boot, so
+    /// a reboot is required unless a message's MessageId says the reset was applied, e.g.
+    /// one ending in Applied such as an Oem BiosResetApplied. An empty body or a plain
+    /// Base.Success still needs the reboot.
+    pub(crate) fn from_reset_bios_response(response: &ActionResponse, url: &str) -> Self {
+        let info: Option<model::ExtendedInfo> = response
+            .body()
+            .and_then(|body| serde_json::from_value(body.clone()).ok());
+        let applied = info.as_ref().is_some_and(|info| {
+            info.messages.iter().any(|m| {
+                let key = m.message_key();
+                key.ends_with("Applied") && !key.contains("Not")
+            })
+        });
+        if applied {
+            return ApplyResult::Applied;
+        }
+        match Self::from_extended_info(info, url) {
+            ApplyResult::Applied => ApplyResult::RebootRequired {
+                message: "BIOS defaults are loaded on the next system reboot".to_string(),
+            },
+            reboot => reboot,
+        }
+    }
+}
+
+/// Outcome of `Redfish::clear_pending`. A real clear means a previous config attempt left
+/// state behind.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ClearPendingResult {
+    /// This many pending jobs or BIOS attributes were discarded
+    Cleared(usize),
+    /// Nothing was pending, and nothing was sent to the BMC
+    NothingPending,
+    /// The BMC has no way to list or clear pending changes, so nothing was done
+    Unknown,
+}
+
+/// Outcome of a BIOS / UEFI password change, e.g. `change_uefi_password`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum BiosPasswordChange {
+    /// Accepted. `job_id` is set when the change is staged behind a BIOS config job (Dell).
+    Changed { job_id: Option<String> },
+    /// The BMC rejected the current password. `message` is what it said.
+    WrongCurrentPassword { message: String },
+    /// The new password breaks the BIOS password policy (too short, too weak, ...).
+    PolicyViolation { message: String },
+}
+
+/// Base registry MessageIds for a rejected parameter or property value. The first
+/// MessageArg names the parameter, which tells the two password rejections apart.
+const PARAMETER_VALUE_KEYS: &[&str] = &[
+    "PropertyValueIncorrect",
+    "PropertyValueError",
+    "PropertyValueFormatError",
+    "ActionParameterValueError",
+    "ActionParameterValueFormatError",
+];
+
+/// Oem MessageIds vendors use for a wrong current BIOS password
+const WRONG_PASSWORD_KEYS: &[&str] = &["PasswordIncorrect", "InvalidOldPassword"];
+
+/// Oem MessageIds vendors use for a new BIOS password that breaks the policy
+const PASSWORD_POLICY_KEYS: &[&str] = &["PasswordPolicy", "PasswordComplexityFailed"];
+
+impl BiosPasswordChange {
+    /// Turn the result of a password change request into a BiosPasswordChange, using the
+    /// MessageIds in the @Message.ExtendedInfo of a 4xx response to tell the two kinds of
+    /// rejection apart.
+    /// Errors that are neither are returned unchanged.
+    pub(crate) fn from_result(
+        result: Result<Option<String>, RedfishError>,
+    ) -> Result<Self, RedfishError> {
+        let err = match result {
+            Ok(job_id) => return Ok(BiosPasswordChange::Changed { job_id }),
+            Err(err) => err,
+        };
+        let RedfishError::HTTPErrorCode {
+            status_code,
+            response_body,
+            ..
+        } = &err
+        else {
+            return Err(err);
+        };
+        if !status_code.is_client_error() {
+            return Err(err);
+        }
+        let Ok(body) = serde_json::from_str::<model::error::Error>(response_body) else {
+            return Err(err);
+        };
+        let mut messages = body.error.extended;
+        if messages.is_empty() {
+            messages.push(model::Message {
+                message: body.error.message,
+                message_args: vec![],
+                message_id: body.error.code,
+                resolution: None,
+                severity: None,
+            });
+        }
+        for m in &messages {
+            let key = m.message_key();
+            let arg = m
+                .message_args
+                .first()
+                .map(String::as_str)
+                .unwrap_or_default();
+            if PARAMETER_VALUE_KEYS.contains(&key) && arg.eq_ignore_ascii_case("OldPassword")
+                || WRONG_PASSWORD_KEYS.contains(&key)
+            {
+                return Ok(BiosPasswordChange::WrongCurrentPassword {
+                    message: m.message.clone(),
+                });
+            }
+            if PARAMETER_VALUE_KEYS.contains(&key) && arg.eq_ignore_ascii_case("NewPassword")
+                || PASSWORD_POLICY_KEYS.contains(&key)
+            {
+                return Ok(BiosPasswordChange::PolicyViolation {
+                    message: m.message.clone(),
+                });
+            }
+        }
+        Err(err)
+    }
+
+    /// The job id of a successful change, or an error describing the rejection.
+    pub fn job_id(self) -> Result<Option<String>, RedfishError> {
+        match self {
+            BiosPasswordChange::Changed { job_id } => Ok(job_id),
+            BiosPasswordChange::WrongCurrentPassword { message } => {
+                Err(RedfishError::GenericError {
+                    error: format!(
+                        "BIOS password change rejected, wrong current password: {message}"
+                    ),
+                })
+            }
+            BiosPasswordChange::PolicyViolation { message } => Err(RedfishError::GenericError {
+                error: format!("BIOS password change rejected by policy: {message}"),
+            }),
+        }
+    }
+}
+
 #[derive(
     Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Hash, Copy, clap::ValueEnum, Default,
 )]
@@ -714,3 +1506,164 @@ pub type BiosProfileVendor = HashMap<RedfishVendor, BiosProfileModel>;
 pub fn model_coerce(original: &str) -> String {
     str::replace(original, " ", "_")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ActionResponse, ApplyResult, BiosPasswordChange, MachineSetupOutcome, MachineSetupReport,
+    };
+    use crate::model::ExtendedInfo;
+    use crate::RedfishError;
+
+    #[test]
+    fn test_apply_result_from_extended_info() {
+        let action = "Systems/1/Actions/ComputerSystem.Reset";
+        let body = r##"{"@Message.ExtendedInfo":[{"Message":"Changes will be applied after a system reset.","MessageId":"Base.1.8.ResetRequired","MessageArgs":[]}]}"##;
+        let info: ExtendedInfo = serde_json::from_str(body).unwrap();
+        assert_eq!(
+            ApplyResult::from_extended_info(Some(info), action),
+            ApplyResult::RebootRequired {
+                message: "Changes will be applied after a system reset.".to_string()
+            }
+        );
+
+        // Only the MessageId counts, not the wording
+        let body = r##"{"@Message.ExtendedInfo":[{"Message":"Reset of the BIOS settings completed","MessageId":"Base.1.8.Success","MessageArgs":[]}]}"##;
+        let info: ExtendedInfo = serde_json::from_str(body).unwrap();
+        assert_eq!(
+            ApplyResult::from_extended_info(Some(info.clone()), action),
+            ApplyResult::Applied
+        );
+        assert!(matches!(
+            ApplyResult::from_extended_info(Some(info), "Systems/1/Bios/Settings/"),
+            ApplyResult::RebootRequired { .. }
+        ));
+        assert_eq!(
+            ApplyResult::from_extended_info(None, "Systems/1/Bios"),
+            ApplyResult::Applied
+        );
+        for settings in ["Systems/1/Bios/Pending", "Systems/1/Bios/SD"] {
+            assert!(matches!(
+                ApplyResult::from_extended_info(None, settings),
+                ApplyResult::RebootRequired { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn test_apply_result_from_reset_bios_response() {
+        let action = "Systems/1/Bios/Actions/Bios.ResetBios";
+        let response = |body: serde_json::Value| {
+            ActionResponse::new(reqwest::StatusCode::OK, &Default::default(), body)
+        };
+        // No body or a plain Success: the defaults still wait for the next reboot
+        let success = serde_json::json!({"@Message.ExtendedInfo": [
+            {"Message": "Successfully Completed Request", "MessageId": "Base.1.8.Success"}
+        ]});
+        for body in [serde_json::Value::Null, success] {
+            assert!(matches!(
+                ApplyResult::from_reset_bios_response(&response(body), action),
+                ApplyResult::RebootRequired { .. }
+            ));
+        }
+        let reset_required = serde_json::json!({"@Message.ExtendedInfo": [{
+            "Message": "Changes will be applied after a system reset.",
+            "MessageId": "Base.1.8.ResetRequired"
+        }]});
+        assert_eq!(
+            ApplyResult::from_reset_bios_response(&response(reset_required), action),
+            ApplyResult::RebootRequired {
+                message: "Changes will be applied after a system reset.".to_string()
+            }
+        );
+        let applied = serde_json::json!({"@Message.ExtendedInfo": [
+            {"Message": "BIOS defaults were applied.", "MessageId": "Oem.1.0.BiosResetApplied"}
+        ]});
+        assert_eq!(
+            ApplyResult::from_reset_bios_response(&response(applied), action),
+            ApplyResult::Applied
+        );
+    }
+
+    #[test]
+    fn test_bios_password_change_from_result() {
+        let rejected = |body: &str| {
+            BiosPasswordChange::from_result(Err(RedfishError::HTTPErrorCode {
+                url: "Systems/1/Bios/Actions/Bios.ChangePassword".to_string(),
+                status_code: reqwest::StatusCode::BAD_REQUEST,
+                response_body: body.to_string(),
+            }))
+        };
+        let body = r##"{"error":{"code":"Base.1.8.GeneralError","message":"See ExtendedInfo","@Message.ExtendedInfo":[{"Message":"The value provided for OldPassword is incorrect.","MessageId":"Base.1.8.ActionParameterValueError","MessageArgs":["OldPassword","ChangePassword"]}]}}"##;
+        assert!(matches!(
+            rejected(body),
+            Ok(BiosPasswordChange::WrongCurrentPassword { .. })
+        ));
+        let body = r##"{"error":{"code":"Base.1.8.GeneralError","message":"See ExtendedInfo","@Message.ExtendedInfo":[{"Message":"Password does not meet the length requirement.","MessageId":"Bios.1.0.PasswordPolicy","MessageArgs":[]}]}}"##;
+        assert_eq!(
+            rejected(body).unwrap(),
+            BiosPasswordChange::PolicyViolation {
+                message: "Password does not meet the length requirement.".to_string()
+            }
+        );
+        let body = r##"{"error":{"code":"Base.1.8.GeneralError","message":"See ExtendedInfo","@Message.ExtendedInfo":[{"Message":"The value for NewPassword is incorrect.","MessageId":"Base.1.12.PropertyValueIncorrect","MessageArgs":["NewPassword","x"]}]}}"##;
+        assert!(matches!(
+            rejected(body),
+            Ok(BiosPasswordChange::PolicyViolation { .. })
+        ));
+        // Wording alone does not classify a rejection, only the MessageId does
+        let body = r##"{"error":{"code":"Base.1.8.GeneralError","message":"The current password is too short."}}"##;
+        assert!(rejected(body).is_err());
+        assert!(rejected("<html>Bad Request</html>").is_err());
+        assert_eq!(
+            BiosPasswordChange::from_result(Ok(None))
+                .unwrap()
+                .job_id()
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_machine_setup_report_record() {
+        let mut report = MachineSetupReport::default();
+        assert!(report.record("disable_secure_boot", Ok(())).is_ok());
+        let err = report
+            .record::<()>(
+                "boot_once",
+                Err(RedfishError::NotSupported("boot_once".to_string())),
+            )
+            .unwrap_err();
+        let RedfishError::MachineSetupFailed { step, report, .. } = err else {
+            panic!("expected MachineSetupFailed, got {err}");
+        };
+        assert_eq!(step, "boot_once");
+        assert_eq!(report.steps.len(), 2);
+        assert_eq!(report.steps[0].outcome, MachineSetupOutcome::Applied);
+        assert_eq!(report.steps[1].outcome, MachineSetupOutcome::Failed);
+    }
+
+    #[test]
+    fn test_machine_setup_report_record_change() {
+        let mut report = MachineSetupReport::default();
+        assert!(report
+            .record_change("setup_serial_console", Ok(false))
+            .is_ok());
+        assert!(report.record_change("lockdown_bmc", Ok(true)).is_ok());
+        assert!(report
+            .record_change(
+                "enable_rshim_bmc",
+                Err(RedfishError::NotSupported("enable_rshim_bmc".to_string())),
+            )
+            .is_err());
+        let outcomes: Vec<_> = report.steps.iter().map(|s| s.outcome).collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                MachineSetupOutcome::Skipped,
+                MachineSetupOutcome::Applied,
+                MachineSetupOutcome::Failed
+            ]
+        );
+    }
+}
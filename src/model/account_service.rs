@@ -40,7 +40,7 @@ pub struct ManagerAccount {
     pub username: String,
 
     // Set this for POST/PATCH. Not populated by GET.
-    pub password: Option<String>,
+    pub password: Option<crate::SecretString>,
 
     // A RoleId converted to string
     pub role_id: String,
@@ -20,9 +20,11 @@
  * FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
  * DEALINGS IN THE SOFTWARE.
  */
+use std::{collections::HashMap, fmt};
+
 use serde::{Deserialize, Serialize};
 
-use super::ODataId;
+use super::{ODataId, ODataLinks};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
@@ -31,6 +33,15 @@ pub struct SoftwareImage {
     pub active_software_image: ODataId,
 }
 
+/// A BIOS attribute that will change on the next reboot
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BiosAttrChange {
+    pub name: String,
+    /// None if the attribute isn't in the current settings
+    pub current: Option<serde_json::Value>,
+    pub pending: serde_json::Value,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct BiosActions {
     #[serde(rename = "#Bios.ChangePassword")]
@@ -57,3 +68,516 @@ pub struct BiosCommon {
     pub links: SoftwareImage,
     pub actions: BiosActions,
 }
+
+/// Registries/{id}: where to find the registry file itself
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct MessageRegistryFile {
+    #[serde(flatten)]
+    pub odata: ODataLinks,
+    pub id: String,
+    #[serde(default)]
+    pub location: Vec<MessageRegistryLocation>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct MessageRegistryLocation {
+    pub language: Option<String>,
+    pub uri: Option<String>,
+}
+
+/// The BIOS AttributeRegistry: name, type and allowed values of every BIOS attribute
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct AttributeRegistry {
+    pub id: String,
+    pub registry_entries: RegistryEntries,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct RegistryEntries {
+    #[serde(default)]
+    pub attributes: Vec<RegistryAttribute>,
+    #[serde(default)]
+    pub dependencies: Vec<RegistryDependency>,
+}
+
+/// A rule of the form "when these attributes have these values, set this property of that
+/// attribute", e.g. make AcPwrRcvryUserDelay read only unless AcPwrRcvryDelay is User
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct RegistryDependency {
+    pub dependency_for: Option<String>,
+    pub dependency: RegistryMapDependency,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct RegistryMapDependency {
+    #[serde(default)]
+    pub map_from: Vec<RegistryMapFrom>,
+    pub map_to_attribute: String,
+    pub map_to_property: String,
+    pub map_to_value: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct RegistryMapFrom {
+    pub map_from_attribute: String,
+    /// EQU, NEQ, GTR, GEQ, LSS or LEQ
+    pub map_from_condition: String,
+    pub map_from_property: String,
+    pub map_from_value: serde_json::Value,
+    /// AND or OR, how this term combines with the others
+    pub map_terms: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RegistryAttributeType {
+    Enumeration,
+    String,
+    Integer,
+    Boolean,
+    Password,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct RegistryAttribute {
+    pub attribute_name: String,
+    #[serde(rename = "Type")]
+    pub attribute_type: Option<RegistryAttributeType>,
+    pub read_only: Option<bool>,
+    #[serde(default)]
+    pub value: Vec<RegistryAttributeValue>,
+    pub lower_bound: Option<i64>,
+    pub upper_bound: Option<i64>,
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    /// Whether a change only takes effect after a system reset
+    pub reset_required: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct RegistryAttributeValue {
+    pub value_name: String,
+    pub value_display_name: Option<String>,
+}
+
+/// Why the registry rejects a BIOS attribute value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BiosAttributeError {
+    pub name: String,
+    pub reason: String,
+}
+
+impl fmt::Display for BiosAttributeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.reason)
+    }
+}
+
+impl AttributeRegistry {
+    /// Check every attribute against the registry and return all the problems, not just the first.
+    pub fn validate(&self, attrs: &HashMap<String, serde_json::Value>) -> Vec<BiosAttributeError> {
+        let mut errors: Vec<BiosAttributeError> = attrs
+            .iter()
+            .filter_map(|(name, value)| {
+                let reason = match self
+                    .registry_entries
+                    .attributes
+                    .iter()
+                    .find(|a| a.attribute_name == *name)
+                {
+                    None => Some("not in the attribute registry".to_string()),
+                    Some(entry) => entry.check(value),
+                };
+                reason.map(|reason| BiosAttributeError {
+                    name: name.clone(),
+                    reason,
+                })
+            })
+            .collect();
+        errors.sort_by(|a, b| a.name.cmp(&b.name));
+        errors
+    }
+
+    /// Split attrs by where a write takes effect: the ones the registry says need no reset go to
+    /// the live Bios resource, everything else, including attributes it doesn't know, to the
+    /// Settings (pending) resource.
+    pub fn targets(&self, attrs: HashMap<String, serde_json::Value>) -> BiosAttributeTargets {
+        let mut targets = BiosAttributeTargets::default();
+        for (name, value) in attrs {
+            let live = self
+                .registry_entries
+                .attributes
+                .iter()
+                .any(|a| a.attribute_name == name && a.reset_required == Some(false));
+            if live {
+                targets.live.insert(name, value);
+            } else {
+                targets.settings.insert(name, value);
+            }
+        }
+        targets
+    }
+}
+
+/// BIOS attributes to write, by where they take effect, see `AttributeRegistry::targets`
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BiosAttributeTargets {
+    /// Attributes that apply immediately, written to Systems/{id}/Bios
+    pub live: HashMap<String, serde_json::Value>,
+    /// Attributes that apply on the next reset, written to the vendor's Settings resource
+    pub settings: HashMap<String, serde_json::Value>,
+}
+
+impl BiosAttributeTargets {
+    /// All of attrs to the Settings resource, for BMCs without an AttributeRegistry
+    pub fn settings_only(attrs: HashMap<String, serde_json::Value>) -> Self {
+        BiosAttributeTargets {
+            live: HashMap::new(),
+            settings: attrs,
+        }
+    }
+
+    /// For attributes that only work written together, e.g. a current and a new password: all
+    /// of them live if every one can be, otherwise all to the Settings resource
+    pub fn together(mut self) -> Self {
+        if !self.settings.is_empty() {
+            self.settings.extend(self.live.drain());
+        }
+        self
+    }
+}
+
+impl AttributeRegistry {
+    /// The other attributes that must be set, and to what, before the BMC accepts `desired`.
+    /// An attribute is locked while a dependency makes it ReadOnly, Hidden or GrayOut; the
+    /// prerequisites are the values that unlock it, given `current` and `desired` itself.
+    /// Attributes already in `desired` are never changed, and a lock that can't be undone by
+    /// a single known value (e.g. EQU on a many-valued enumeration) is left to the BMC.
+    pub fn prerequisites(
+        &self,
+        desired: &HashMap<String, serde_json::Value>,
+        current: &HashMap<String, serde_json::Value>,
+    ) -> HashMap<String, serde_json::Value> {
+        let mut required: HashMap<String, serde_json::Value> = HashMap::new();
+        let mut todo: Vec<String> = desired.keys().cloned().collect();
+        while let Some(name) = todo.pop() {
+            for dep in self.locks(&name) {
+                let value_of = |attr: &str| {
+                    desired
+                        .get(attr)
+                        .or_else(|| required.get(attr))
+                        .or_else(|| current.get(attr))
+                        .cloned()
+                };
+                let terms: Vec<(&RegistryMapFrom, bool)> = dep
+                    .map_from
+                    .iter()
+                    .map(|t| (t, t.holds(value_of(&t.map_from_attribute).as_ref())))
+                    .collect();
+                let any_or = dep
+                    .map_from
+                    .iter()
+                    .any(|t| t.map_terms.as_deref() == Some("OR"));
+                let locked = if any_or {
+                    terms.iter().any(|(_, holds)| *holds)
+                } else {
+                    !terms.is_empty() && terms.iter().all(|(_, holds)| *holds)
+                };
+                if !locked {
+                    continue;
+                }
+                // With AND breaking one term unlocks it, with OR every true term must break
+                let to_break: Vec<&RegistryMapFrom> = if any_or {
+                    terms.iter().filter(|(_, h)| *h).map(|(t, _)| *t).collect()
+                } else {
+                    terms
+                        .iter()
+                        .map(|(t, _)| *t)
+                        .filter(|t| !desired.contains_key(&t.map_from_attribute))
+                        .find(|t| self.breaking_value(t).is_some())
+                        .into_iter()
+                        .collect()
+                };
+                for term in to_break {
+                    if desired.contains_key(&term.map_from_attribute) {
+                        continue;
+                    }
+                    let Some(value) = self.breaking_value(term) else {
+                        continue;
+                    };
+                    if required
+                        .insert(term.map_from_attribute.clone(), value)
+                        .is_none()
+                    {
+                        todo.push(term.map_from_attribute.clone());
+                    }
+                }
+            }
+        }
+        required
+    }
+
+    // Dependencies that can lock `name` against writes
+    fn locks<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a RegistryMapDependency> {
+        self.registry_entries
+            .dependencies
+            .iter()
+            .map(|d| &d.dependency)
+            .filter(move |d| {
+                d.map_to_attribute == name
+                    && ["ReadOnly", "Hidden", "GrayOut"].contains(&d.map_to_property.as_str())
+                    && d.map_to_value == serde_json::Value::Bool(true)
+            })
+    }
+
+    // A value of the term's attribute that makes the term false, if there is a definite one
+    fn breaking_value(&self, term: &RegistryMapFrom) -> Option<serde_json::Value> {
+        if term.map_from_property != "CurrentValue" {
+            return None;
+        }
+        match term.map_from_condition.as_str() {
+            "NEQ" => Some(term.map_from_value.clone()),
+            "EQU" => {
+                // Only an enumeration with exactly one other value has a definite answer
+                let entry = self
+                    .registry_entries
+                    .attributes
+                    .iter()
+                    .find(|a| a.attribute_name == term.map_from_attribute)?;
+                match term.map_from_value {
+                    serde_json::Value::Bool(b) => Some(serde_json::Value::Bool(!b)),
+                    _ => {
+                        let others: Vec<&RegistryAttributeValue> = entry
+                            .value
+                            .iter()
+                            .filter(|v| term.map_from_value.as_str() != Some(v.value_name.as_str()))
+                            .collect();
+                        match others.as_slice() {
+                            [only] => Some(serde_json::Value::String(only.value_name.clone())),
+                            _ => None,
+                        }
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+impl RegistryMapFrom {
+    // Whether the term is true for the attribute's value. Unknown values and conditions are
+    // treated as false, so they never produce a prerequisite.
+    fn holds(&self, value: Option<&serde_json::Value>) -> bool {
+        if self.map_from_property != "CurrentValue" {
+            return false;
+        }
+        let Some(value) = value else {
+            return false;
+        };
+        let cmp = || Some(value.as_f64()?.total_cmp(&self.map_from_value.as_f64()?));
+        match self.map_from_condition.as_str() {
+            "EQU" => *value == self.map_from_value,
+            "NEQ" => *value != self.map_from_value,
+            "GTR" => cmp().is_some_and(|o| o.is_gt()),
+            "GEQ" => cmp().is_some_and(|o| o.is_ge()),
+            "LSS" => cmp().is_some_and(|o| o.is_lt()),
+            "LEQ" => cmp().is_some_and(|o| o.is_le()),
+            _ => false,
+        }
+    }
+}
+
+impl RegistryAttribute {
+    fn check(&self, value: &serde_json::Value) -> Option<String> {
+        if self.read_only == Some(true) {
+            return Some("read only".to_string());
+        }
+        match self.attribute_type? {
+            RegistryAttributeType::Enumeration => {
+                let Some(v) = value.as_str() else {
+                    return Some(format!(
+                        "expected one of the enumeration values, got {value}"
+                    ));
+                };
+                if self.value.is_empty() || self.value.iter().any(|a| a.value_name == v) {
+                    None
+                } else {
+                    let allowed: Vec<&str> =
+                        self.value.iter().map(|a| a.value_name.as_str()).collect();
+                    Some(format!("'{v}' is not one of {}", allowed.join(", ")))
+                }
+            }
+            RegistryAttributeType::Boolean => {
+                (!value.is_boolean()).then(|| format!("expected a boolean, got {value}"))
+            }
+            RegistryAttributeType::Integer => {
+                let Some(v) = value.as_i64() else {
+                    return Some(format!("expected an integer, got {value}"));
+                };
+                if self.lower_bound.is_some_and(|l| v < l)
+                    || self.upper_bound.is_some_and(|u| v > u)
+                {
+                    Some(format!(
+                        "{v} is outside {}..={}",
+                        self.lower_bound.map(|l| l.to_string()).unwrap_or_default(),
+                        self.upper_bound.map(|u| u.to_string()).unwrap_or_default()
+                    ))
+                } else {
+                    None
+                }
+            }
+            RegistryAttributeType::String | RegistryAttributeType::Password => {
+                let Some(v) = value.as_str() else {
+                    return Some(format!("expected a string, got {value}"));
+                };
+                let len = v.chars().count();
+                if self.min_length.is_some_and(|m| len < m)
+                    || self.max_length.is_some_and(|m| len > m)
+                {
+                    Some(format!("length {len} is outside the allowed length"))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    #[test]
+    fn test_attribute_registry_validate() {
+        let data = include_str!("testdata/bios_attribute_registry.json");
+        let registry: super::AttributeRegistry = serde_json::from_str(data).unwrap();
+        let attrs = HashMap::from([
+            ("SriovGlobalEnable".to_string(), json!("Enabled")),
+            ("BootMode".to_string(), json!("Legacy")),
+            ("SystemServiceTag".to_string(), json!("ABC1234")),
+            ("NotARealAttribute".to_string(), json!(1)),
+            ("PwrPerfSwitch".to_string(), json!(true)),
+            ("AcPwrRcvryUserDelay".to_string(), json!(500)),
+        ]);
+        let errors = registry.validate(&attrs);
+        let names: Vec<&str> = errors.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "AcPwrRcvryUserDelay",
+                "BootMode",
+                "NotARealAttribute",
+                "SystemServiceTag"
+            ]
+        );
+
+        let ok = HashMap::from([
+            ("BootMode".to_string(), json!("Uefi")),
+            ("AcPwrRcvryUserDelay".to_string(), json!(120)),
+        ]);
+        assert!(registry.validate(&ok).is_empty());
+    }
+
+    #[test]
+    fn test_attribute_registry_prerequisites() {
+        let data = include_str!("testdata/bios_attribute_registry.json");
+        let registry: super::AttributeRegistry = serde_json::from_str(data).unwrap();
+        let current = HashMap::from([
+            ("AcPwrRcvryDelay".to_string(), json!("Immediate")),
+            ("BootMode".to_string(), json!("Bios")),
+        ]);
+        let desired = HashMap::from([
+            ("AcPwrRcvryUserDelay".to_string(), json!(120)),
+            ("SriovGlobalEnable".to_string(), json!("Enabled")),
+        ]);
+        let required = registry.prerequisites(&desired, &current);
+        assert_eq!(
+            required,
+            HashMap::from([
+                ("AcPwrRcvryDelay".to_string(), json!("User")),
+                ("BootMode".to_string(), json!("Uefi")),
+            ])
+        );
+
+        // Already unlocked, or the caller sets the prerequisite itself
+        let current = HashMap::from([("AcPwrRcvryDelay".to_string(), json!("User"))]);
+        assert!(registry.prerequisites(&desired, &current).is_empty());
+        let desired = HashMap::from([
+            ("AcPwrRcvryUserDelay".to_string(), json!(120)),
+            ("AcPwrRcvryDelay".to_string(), json!("Random")),
+        ]);
+        assert!(registry.prerequisites(&desired, &HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_attribute_registry_targets() {
+        // Lenovo and Supermicro registries mark a few attributes as applying immediately
+        let mixed: super::AttributeRegistry = serde_json::from_value(json!({
+            "Id": "BiosAttributeRegistry.1.0.0",
+            "RegistryEntries": {"Attributes": [
+                {"AttributeName": "BootModes_InfiniteBootRetry", "Type": "Enumeration", "ResetRequired": false},
+                {"AttributeName": "Processors_IntelVirtualizationTechnology", "Type": "Enumeration", "ResetRequired": true},
+                {"AttributeName": "OperatingModes_ChooseOperatingMode", "Type": "Enumeration"}
+            ]}
+        }))
+        .unwrap();
+        let attrs = HashMap::from([
+            ("BootModes_InfiniteBootRetry".to_string(), json!("Enabled")),
+            (
+                "Processors_IntelVirtualizationTechnology".to_string(),
+                json!("Enabled"),
+            ),
+            (
+                "OperatingModes_ChooseOperatingMode".to_string(),
+                json!("MaximumPerformance"),
+            ),
+            ("NotInRegistry".to_string(), json!(1)),
+        ]);
+        let targets = mixed.targets(attrs);
+        assert_eq!(
+            targets.live,
+            HashMap::from([("BootModes_InfiniteBootRetry".to_string(), json!("Enabled"))])
+        );
+        let mut settings: Vec<&String> = targets.settings.keys().collect();
+        settings.sort();
+        assert_eq!(
+            settings,
+            [
+                "NotInRegistry",
+                "OperatingModes_ChooseOperatingMode",
+                "Processors_IntelVirtualizationTechnology"
+            ]
+        );
+
+        // Dell's registry requires a reset for every attribute
+        let dell: super::AttributeRegistry = serde_json::from_value(json!({
+            "Id": "BiosAttributeRegistry.v1_0_3",
+            "RegistryEntries": {"Attributes": [
+                {"AttributeName": "SriovGlobalEnable", "Type": "Enumeration", "ResetRequired": true},
+                {"AttributeName": "InBandManageabilityInterface", "Type": "Enumeration", "ResetRequired": true}
+            ]}
+        }))
+        .unwrap();
+        let attrs = HashMap::from([
+            ("SriovGlobalEnable".to_string(), json!("Enabled")),
+            (
+                "InBandManageabilityInterface".to_string(),
+                json!("Disabled"),
+            ),
+        ]);
+        assert_eq!(
+            dell.targets(attrs.clone()),
+            super::BiosAttributeTargets::settings_only(attrs)
+        );
+    }
+}
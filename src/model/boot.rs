@@ -40,6 +40,12 @@ pub struct Boot {
     pub boot_order: Vec<String>,
     pub boot_source_override_enabled: Option<BootSourceOverrideEnabled>,
     pub boot_source_override_target: Option<BootSourceOverrideTarget>,
+    #[serde(
+        default,
+        rename = "BootSourceOverrideTarget@Redfish.AllowableValues",
+        skip_serializing_if = "<[_]>::is_empty"
+    )]
+    pub boot_source_override_target_allowable_values: Vec<BootSourceOverrideTarget>,
     pub boot_source_override_mode: Option<BootSourceOverrideMode>,
     pub http_boot_uri: Option<String>,
     pub trusted_module_required_to_boot: Option<TrustedModuleRequiredToBoot>,
@@ -47,6 +53,24 @@ pub struct Boot {
     pub boot_options: Option<ODataId>,
 }
 
+impl Boot {
+    /// Fails with BootTargetNotAvailable if the system lists its allowable boot override
+    /// targets and `target` isn't one of them. Systems that don't list them pass.
+    pub fn ensure_target_allowed(
+        &self,
+        target: BootSourceOverrideTarget,
+    ) -> Result<(), crate::RedfishError> {
+        let allowed = &self.boot_source_override_target_allowable_values;
+        if allowed.is_empty() || allowed.contains(&target) {
+            return Ok(());
+        }
+        Err(crate::RedfishError::BootTargetNotAvailable {
+            target: target.to_string(),
+            allowed: allowed.iter().map(|t| t.to_string()).collect(),
+        })
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub enum AutomaticRetryConfig {
     Disabled,
@@ -124,3 +148,102 @@ impl std::fmt::Display for TrustedModuleRequiredToBoot {
         std::fmt::Debug::fmt(self, f)
     }
 }
+
+/// Where a BootOrder PATCH has to go and what shape of array the BMC takes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BootOrderTarget {
+    /// Relative URL of the resource to PATCH: the ComputerSystem or its settings object.
+    pub url: String,
+    /// Send every existing boot option, not just the ones being moved. Some BMCs reject
+    /// a partial BootOrder and some quietly drop the entries it leaves out.
+    pub full_order: bool,
+}
+
+impl BootOrderTarget {
+    /// The settings object the system advertises in @Redfish.Settings, since the live
+    /// BootOrder is read-only there, otherwise the system itself. Without knowing the BMC
+    /// the full order is sent, so entries left out of the request aren't dropped; vendors
+    /// known to take a partial order use `settings` or their own target instead.
+    pub fn detect(system: &super::ComputerSystem) -> Self {
+        let url = system
+            .redfish_settings
+            .as_ref()
+            .and_then(|s| s.settings_object.as_ref())
+            .unwrap_or(&ODataId {
+                odata_id: system.odata.odata_id.clone(),
+            })
+            .relative_path();
+        BootOrderTarget {
+            url,
+            full_order: true,
+        }
+    }
+
+    /// Systems/{system_id}/Settings. The NVIDIA BMCs (BlueField, GB200, GH200, GB switch)
+    /// stage BootOrder there and accept a partial order, which the BIOS completes with the
+    /// entries it leaves out.
+    pub fn settings(system_id: &str) -> Self {
+        BootOrderTarget {
+            url: format!("Systems/{system_id}/Settings"),
+            full_order: false,
+        }
+    }
+
+    /// The BootOrder to send: `requested` as is, or for a full order followed by the
+    /// entries of `current` it left out, in their existing order.
+    pub fn boot_order(&self, mut requested: Vec<String>, current: &[String]) -> Vec<String> {
+        if self.full_order {
+            let missing: Vec<String> = current
+                .iter()
+                .filter(|c| !requested.contains(c))
+                .cloned()
+                .collect();
+            requested.extend(missing);
+        }
+        requested
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BootOrderTarget;
+    use crate::model::ComputerSystem;
+
+    fn detect(data: &str) -> BootOrderTarget {
+        let system: ComputerSystem = serde_json::from_str(data).unwrap();
+        BootOrderTarget::detect(&system)
+    }
+
+    #[test]
+    fn test_boot_order_target_detect() {
+        // Dell: no @Redfish.Settings, PATCH the system
+        let dell = detect(include_str!("testdata/system_dell.json"));
+        assert_eq!(dell.url, "Systems/System.Embedded.1");
+        // Lenovo: staged in Pending
+        let lenovo = detect(include_str!("testdata/system_lenovo.json"));
+        assert_eq!(lenovo.url, "Systems/1/Pending");
+        // Bluefield: staged in Settings
+        let dpu = detect(include_str!("testdata/system_bluefield_boot_valid.json"));
+        assert_eq!(dpu.url, "Systems/Bluefield/Settings");
+        assert!(dell.full_order && lenovo.full_order && dpu.full_order);
+    }
+
+    #[test]
+    fn test_boot_order_full_and_partial() {
+        let current = ["Boot0003", "Boot0000", "Boot0001"].map(String::from);
+        let requested = vec!["Boot0001".to_string()];
+        let full = BootOrderTarget {
+            url: "Systems/1".to_string(),
+            full_order: true,
+        };
+        assert_eq!(
+            full.boot_order(requested.clone(), &current),
+            ["Boot0001", "Boot0003", "Boot0000"]
+        );
+        let partial = BootOrderTarget {
+            full_order: false,
+            ..full
+        };
+        assert_eq!(partial.boot_order(requested, &current), ["Boot0001"]);
+    }
+}
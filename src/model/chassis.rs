@@ -145,13 +145,95 @@ pub struct NetworkAdapter {
     pub controllers: Option<Vec<NetworkAdapterController>>,
 }
 
+impl NetworkAdapter {
+    /// Firmware, part/serial and port/function counts for inventory, from this resource alone
+    pub fn summary(&self) -> NetworkAdapterSummary {
+        let controllers = self.controllers.as_deref().unwrap_or_default();
+        let mut firmware_versions: Vec<String> = controllers
+            .iter()
+            .filter_map(|c| c.firmware_package_version.clone())
+            .collect();
+        firmware_versions.sort();
+        firmware_versions.dedup();
+        NetworkAdapterSummary {
+            id: self.id.clone(),
+            manufacturer: self.manufacturer.clone(),
+            model: self.model.clone(),
+            // Some BMCs pad these with spaces
+            part_number: self.part_number.as_deref().map(|p| p.trim().to_string()),
+            serial_number: self.serial_number.as_deref().map(|s| s.trim().to_string()),
+            firmware_versions,
+            port_count: controllers.iter().map(|c| c.port_count()).sum(),
+            device_function_count: controllers.iter().map(|c| c.device_function_count()).sum(),
+        }
+    }
+}
+
+/// NetworkAdapter::summary
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct NetworkAdapterSummary {
+    pub id: String,
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+    pub part_number: Option<String>,
+    pub serial_number: Option<String>,
+    /// Distinct Controllers[].FirmwarePackageVersion values, sorted; usually a single version
+    pub firmware_versions: Vec<String>,
+    pub port_count: usize,
+    pub device_function_count: usize,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct NetworkAdapterController {
     pub firmware_package_version: Option<String>,
+    pub controller_capabilities: Option<ControllerCapabilities>,
     pub links: Option<NetworkAdapterControllerLinks>,
 }
 
+impl NetworkAdapterController {
+    /// Ports of this controller, from its links, else from its capabilities
+    pub fn port_count(&self) -> usize {
+        let linked = self.links.as_ref().and_then(|l| {
+            l.ports
+                .as_ref()
+                .or(l.network_ports.as_ref())
+                .map(|p| p.len())
+        });
+        linked
+            .or_else(|| {
+                self.controller_capabilities
+                    .as_ref()?
+                    .network_port_count
+                    .map(|c| c as usize)
+            })
+            .unwrap_or_default()
+    }
+
+    /// Network device functions of this controller, from its links, else from its capabilities
+    pub fn device_function_count(&self) -> usize {
+        let linked = self
+            .links
+            .as_ref()
+            .and_then(|l| l.network_device_functions.as_ref().map(|f| f.len()));
+        linked
+            .or_else(|| {
+                self.controller_capabilities
+                    .as_ref()?
+                    .network_device_function_count
+                    .map(|c| c as usize)
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct ControllerCapabilities {
+    pub network_device_function_count: Option<u32>,
+    pub network_port_count: Option<u32>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct NetworkAdapterControllerLinks {
@@ -217,3 +299,35 @@ pub struct MachineNetworkAdapter {
     pub network_device_function: NetworkDeviceFunction,
     pub pcie_function: PCIeFunction,
 }
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_network_adapter_summary() {
+        let data = include_str!("testdata/network_adapter_dell.json");
+        let result: super::NetworkAdapter = serde_json::from_str(data).unwrap();
+        let summary = result.summary();
+        assert_eq!(summary.id, "NIC.Slot.5");
+        assert_eq!(summary.firmware_versions, vec!["32.41.1000".to_string()]);
+        assert_eq!(summary.port_count, 1);
+        assert_eq!(summary.device_function_count, 1);
+        assert_eq!(summary.part_number.as_deref(), Some("900-9D3B6-00CV-AA0"));
+        assert_eq!(summary.serial_number.as_deref(), Some("MT2328XZ17WR"));
+
+        let mut adapter = result.clone();
+        let controller = adapter.controllers.as_ref().unwrap()[0].clone();
+        adapter.controllers = Some(
+            ["32.41.1000", "28.39.1002", "32.41.1000"]
+                .into_iter()
+                .map(|version| super::NetworkAdapterController {
+                    firmware_package_version: Some(version.to_string()),
+                    ..controller.clone()
+                })
+                .collect(),
+        );
+        assert_eq!(
+            adapter.summary().firmware_versions,
+            vec!["28.39.1002".to_string(), "32.41.1000".to_string()]
+        );
+    }
+}
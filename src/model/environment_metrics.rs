@@ -0,0 +1,56 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2025 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: MIT
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a
+ * copy of this software and associated documentation files (the "Software"),
+ * to deal in the Software without restriction, including without limitation
+ * the rights to use, copy, modify, merge, publish, distribute, sublicense,
+ * and/or sell copies of the Software, and to permit persons to whom the
+ * Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL
+ * THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+ * FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+use serde::{Deserialize, Serialize};
+
+use super::sensor::SensorExcerpt;
+use crate::OData;
+
+/// Chassis/{id}/EnvironmentMetrics: the environment around the chassis as a whole,
+/// as opposed to per-component thermal sensors.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct EnvironmentMetrics {
+    #[serde(flatten)]
+    pub odata: OData,
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub temperature_celsius: Option<SensorExcerpt>,
+    pub humidity_percent: Option<SensorExcerpt>,
+    pub dew_point_celsius: Option<SensorExcerpt>,
+    pub power_watts: Option<SensorExcerpt>,
+    #[serde(rename = "EnergykWh")]
+    pub energy_kwh: Option<SensorExcerpt>,
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_environment_metrics_parser() {
+        let test_data = include_str!("testdata/environment_metrics.json");
+        let result: super::EnvironmentMetrics = serde_json::from_str(test_data).unwrap();
+        assert_eq!(result.temperature_celsius.unwrap().reading, Some(24.5));
+        assert_eq!(result.humidity_percent.unwrap().reading, Some(38.0));
+        assert_eq!(result.energy_kwh.unwrap().reading, Some(1234.5));
+        assert!(result.dew_point_celsius.is_none());
+    }
+}
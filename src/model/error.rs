@@ -29,8 +29,10 @@ pub struct Error {
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct ErrorInternal {
+    #[serde(default)]
     pub code: String,
+    #[serde(default)]
     pub message: String,
-    #[serde(rename = "@Message.ExtendedInfo")]
+    #[serde(default, rename = "@Message.ExtendedInfo")]
     pub extended: Vec<super::Message>,
 }
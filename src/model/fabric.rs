@@ -0,0 +1,202 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2025 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: MIT
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a
+ * copy of this software and associated documentation files (the "Software"),
+ * to deal in the Software without restriction, including without limitation
+ * the rights to use, copy, modify, merge, publish, distribute, sublicense,
+ * and/or sell copies of the Software, and to permit persons to whom the
+ * Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL
+ * THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+ * FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+use serde::{Deserialize, Serialize};
+
+use super::{ODataId, ODataLinks, ResourceStatus};
+
+/// http://redfish.dmtf.org/schemas/v1/Fabric.v1_3_2.json
+/// A PCIe, NVLink, etc fabric: its switches and the endpoints attached to them.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Fabric {
+    #[serde(flatten)]
+    pub odata: ODataLinks,
+    pub id: String,
+    pub name: Option<String>,
+    /// PCIe, NVLink, Ethernet, ...
+    pub fabric_type: Option<String>,
+    pub status: Option<ResourceStatus>,
+    pub switches: Option<ODataId>,
+    pub endpoints: Option<ODataId>,
+}
+
+/// http://redfish.dmtf.org/schemas/v1/Switch.v1_9_2.json
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Switch {
+    #[serde(flatten)]
+    pub odata: ODataLinks,
+    pub id: String,
+    pub name: Option<String>,
+    /// PCIe, NVLink, Ethernet, ...
+    pub switch_type: Option<String>,
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+    pub part_number: Option<String>,
+    pub serial_number: Option<String>,
+    pub firmware_version: Option<String>,
+    pub total_switch_width: Option<i64>,
+    pub status: Option<ResourceStatus>,
+    pub ports: Option<ODataId>,
+    pub links: Option<SwitchLinks>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct SwitchLinks {
+    pub chassis: Option<ODataId>,
+    #[serde(default)]
+    pub endpoints: Vec<ODataId>,
+}
+
+/// http://redfish.dmtf.org/schemas/v1/Port.v1_11_0.json
+/// A switch or device port and what it is connected to.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Port {
+    #[serde(flatten)]
+    pub odata: ODataLinks,
+    pub id: String,
+    pub name: Option<String>,
+    pub port_id: Option<String>,
+    pub port_protocol: Option<String>,
+    /// UpstreamPort, DownstreamPort, InterswitchPort, ...
+    pub port_type: Option<String>,
+    /// LinkUp, LinkDown, NoLink, Starting, Training
+    pub link_status: Option<String>,
+    pub link_state: Option<String>,
+    pub current_speed_gbps: Option<f64>,
+    pub width: Option<i64>,
+    pub status: Option<ResourceStatus>,
+    pub links: Option<PortLinks>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct PortLinks {
+    #[serde(default)]
+    pub associated_endpoints: Vec<ODataId>,
+    #[serde(default)]
+    pub connected_ports: Vec<ODataId>,
+    #[serde(default)]
+    pub connected_switches: Vec<ODataId>,
+    #[serde(default)]
+    pub connected_switch_ports: Vec<ODataId>,
+}
+
+impl Port {
+    /// Everything this port is wired to: ports, switches, switch ports and endpoints.
+    pub fn connections(&self) -> Vec<&ODataId> {
+        let Some(links) = self.links.as_ref() else {
+            return Vec::new();
+        };
+        links
+            .connected_ports
+            .iter()
+            .chain(&links.connected_switch_ports)
+            .chain(&links.connected_switches)
+            .chain(&links.associated_endpoints)
+            .collect()
+    }
+}
+
+/// A switch with its ports
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SwitchPorts {
+    pub switch: Switch,
+    pub ports: Vec<Port>,
+}
+
+/// A fabric with its switches and their ports, see `Redfish::get_fabric_topology`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FabricTopology {
+    pub fabric: Fabric,
+    pub switches: Vec<SwitchPorts>,
+}
+
+impl FabricTopology {
+    /// (port @odata.id, connected @odata.id) for every connection of every port, to compare
+    /// the wiring against the expected topology.
+    pub fn connections(&self) -> Vec<(String, String)> {
+        self.switches
+            .iter()
+            .flat_map(|s| &s.ports)
+            .flat_map(|port| {
+                port.connections()
+                    .into_iter()
+                    .map(|peer| (port.odata.odata_id.clone(), peer.odata_id.clone()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_fabric_topology() {
+        let fabric: super::Fabric =
+            serde_json::from_str(include_str!("testdata/fabric_nvlink.json")).unwrap();
+        let switch: super::Switch =
+            serde_json::from_str(include_str!("testdata/fabric_switch.json")).unwrap();
+        let port: super::Port =
+            serde_json::from_str(include_str!("testdata/fabric_switch_port.json")).unwrap();
+        assert_eq!(fabric.fabric_type.as_deref(), Some("NVLink"));
+        assert_eq!(switch.switch_type.as_deref(), Some("NVLink"));
+        assert_eq!(
+            switch
+                .links
+                .as_ref()
+                .unwrap()
+                .chassis
+                .as_ref()
+                .unwrap()
+                .odata_id,
+            "/redfish/v1/Chassis/HGX_NVSwitch_0"
+        );
+        assert_eq!(port.link_status.as_deref(), Some("LinkUp"));
+
+        let topology = super::FabricTopology {
+            fabric,
+            switches: vec![super::SwitchPorts {
+                switch,
+                ports: vec![port],
+            }],
+        };
+        assert_eq!(
+            topology.connections(),
+            [
+                (
+                    "/redfish/v1/Fabrics/HGX_NVLinkFabric_0/Switches/NVSwitch_0/Ports/NVLink_0"
+                        .to_string(),
+                    "/redfish/v1/Systems/HGX_Baseboard_0/Processors/GPU_0/Ports/NVLink_0"
+                        .to_string()
+                ),
+                (
+                    "/redfish/v1/Fabrics/HGX_NVLinkFabric_0/Switches/NVSwitch_0/Ports/NVLink_0"
+                        .to_string(),
+                    "/redfish/v1/Fabrics/HGX_NVLinkFabric_0/Endpoints/HGX_GPU_0".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,64 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2025 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: MIT
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a
+ * copy of this software and associated documentation files (the "Software"),
+ * to deal in the Software without restriction, including without limitation
+ * the rights to use, copy, modify, merge, publish, distribute, sublicense,
+ * and/or sell copies of the Software, and to permit persons to whom the
+ * Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL
+ * THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+ * FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+use serde::{Deserialize, Serialize};
+
+/// Identity of one field-replaceable unit. Chassis, systems, processors, memory, drives and
+/// power supplies all carry these properties under the same names.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct FruEntry {
+    #[serde(rename = "@odata.id", default)]
+    pub odata_id: String,
+    #[serde(alias = "MemberId")]
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+    pub serial_number: Option<String>,
+    pub part_number: Option<String>,
+}
+
+/// All FRU identity data of a machine, as returned by get_fru_inventory
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct FruInventory {
+    pub chassis: Vec<FruEntry>,
+    pub systems: Vec<FruEntry>,
+    pub processors: Vec<FruEntry>,
+    pub memory: Vec<FruEntry>,
+    pub drives: Vec<FruEntry>,
+    pub power_supplies: Vec<FruEntry>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::FruEntry;
+
+    #[test]
+    fn test_fru_entry_parser() {
+        let data = include_str!("testdata/power_supply.json");
+        let psu: FruEntry = serde_json::from_str(data).unwrap();
+        assert_eq!(psu.id.as_deref(), Some("0"));
+        assert_eq!(psu.serial_number.as_deref(), Some("CNDED0003P1A4B"));
+        assert_eq!(psu.part_number.as_deref(), Some("0FVT4HA00"));
+    }
+}
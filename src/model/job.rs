@@ -27,7 +27,7 @@ use crate::model::{
 use serde::{Deserialize, Serialize};
 
 // A "Job" is very similar to a "Task", but there are a few key differences.
-// We won't export this struct for now, instead cramming the info into a "Task" struct for ease of use.
+// get_task crams the info into a "Task" struct for ease of use, get_pending_jobs returns it as is.
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
@@ -47,10 +47,43 @@ impl Job {
             id: self.id.clone().unwrap_or("".to_string()),
             messages: vec![],
             name: self.name.clone(),
-            task_state: self.job_state.clone(),
+            task_state: self.job_state,
             task_status: None,
             task_monitor: None,
             percent_complete: self.percent_complete,
+            payload: None,
         }
     }
+
+    /// Queued or running, i.e. not finished yet
+    pub fn is_pending(&self) -> bool {
+        !self.job_state.is_some_and(|s| s.is_terminal())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Job;
+    use crate::model::task::TaskState;
+
+    #[test]
+    fn test_dell_job_states() {
+        let job = |state: &str| -> Job {
+            serde_json::from_value(serde_json::json!({
+                "@odata.id": "/redfish/v1/Managers/iDRAC.Embedded.1/Jobs/JID_510613515077",
+                "@odata.type": "#DellJob.v1_4_0.DellJob",
+                "Id": "JID_510613515077",
+                "JobState": state,
+                "Name": "Configure: BIOS.Setup.1-1",
+                "PercentComplete": 0
+            }))
+            .unwrap()
+        };
+        assert_eq!(job("Scheduled").job_state, Some(TaskState::Pending));
+        assert!(job("Scheduled").is_pending());
+        assert!(job("Running").is_pending());
+        assert!(!job("Completed").is_pending());
+        assert_eq!(job("Failed").job_state, Some(TaskState::Exception));
+        assert!(!job("CompletedWithErrors").is_pending());
+    }
 }
@@ -19,12 +19,14 @@
  * FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
  * DEALINGS IN THE SOFTWARE.
  */
-use std::fmt;
+use std::{collections::HashMap, fmt};
 
 use serde::{Deserialize, Serialize};
 
 use super::oem::ManagerExtensions;
-use crate::model::{ODataId, ODataLinks};
+use crate::model::{
+    Condition, ODataId, ODataLinks, ResourceHealth, ResourceStatus, StatusWithConditions,
+};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
@@ -53,6 +55,7 @@ pub struct Manager {
     pub model: Option<String>,
     pub name: String,
     pub network_protocol: ODataId,
+    pub serial_console: Option<Commandshell>,
     pub status: Status,
     #[serde(rename = "UUID")]
     pub uuid: String,
@@ -74,9 +77,12 @@ pub struct Action {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct Commandshell {
+    #[serde(default)]
     pub connect_types_supported: Vec<String>,
     pub enabled: Option<bool>,
+    #[serde(default)]
     pub max_concurrent_sessions: i64,
+    #[serde(default)]
     pub service_enabled: bool,
 }
 
@@ -105,10 +111,270 @@ impl fmt::Display for ManagerResetType {
     }
 }
 
+/// What Manager.ResetToDefaults keeps, its ResetType parameter
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
+pub enum ResetToDefaultsType {
+    /// Everything, network and user accounts included. The BMC may no longer be reachable.
+    ResetAll,
+    PreserveNetworkAndUsers,
+    PreserveNetwork,
+}
+
+impl fmt::Display for ResetToDefaultsType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The Manager fields needed to call Manager.ResetToDefaults
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(rename_all = "PascalCase")]
+pub struct ManagerResetToDefaultsResource {
+    #[serde(default)]
+    pub actions: ManagerResetToDefaultsActions,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ManagerResetToDefaultsActions {
+    #[serde(rename = "#Manager.ResetToDefaults")]
+    pub reset_to_defaults: Option<ResetToDefaultsAction>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ResetToDefaultsAction {
+    pub target: Option<String>,
+    /// The other properties, among them `<parameter>@Redfish.AllowableValues`
+    #[serde(flatten)]
+    pub annotations: HashMap<String, serde_json::Value>,
+}
+
+impl ManagerResetToDefaultsResource {
+    /// The values the BMC advertises for the action's `parameter`, usually ResetType, as
+    /// sent. Empty if it doesn't say.
+    pub fn allowable_values(&self, parameter: &str) -> Vec<String> {
+        self.actions
+            .reset_to_defaults
+            .as_ref()
+            .and_then(|a| {
+                a.annotations
+                    .get(&format!("{parameter}@Redfish.AllowableValues"))
+            })
+            .and_then(|v| v.as_array())
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Whether `scope` may be sent as `parameter`. True if the BMC doesn't say.
+    pub fn allows(&self, parameter: &str, scope: ResetToDefaultsType) -> bool {
+        let allowed = self.allowable_values(parameter);
+        allowed.is_empty() || allowed.contains(&scope.to_string())
+    }
+
+    /// The scope that keeps the most, so the BMC stays reachable with the same credentials:
+    /// PreserveNetworkAndUsers, else PreserveNetwork. None if the BMC only offers ResetAll.
+    pub fn safest(&self, parameter: &str) -> Option<ResetToDefaultsType> {
+        [
+            ResetToDefaultsType::PreserveNetworkAndUsers,
+            ResetToDefaultsType::PreserveNetwork,
+        ]
+        .into_iter()
+        .find(|scope| self.allows(parameter, *scope))
+    }
+}
+
+/// The Manager fields `get_manager_health` reads. Kept apart from `Manager` so a BMC
+/// missing the fields `Manager` requires still gets a health summary.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct ManagerHealthResource {
+    pub id: String,
+    pub firmware_version: Option<String>,
+    #[serde(default)]
+    pub status: StatusWithConditions,
+    pub date_time: Option<String>,
+    pub last_reset_time: Option<String>,
+    pub manager_diagnostic_data: Option<ODataId>,
+    pub oem: Option<serde_json::Value>,
+}
+
+/// ManagerDiagnosticData, linked from the Manager since Redfish 2021.3. Only the memory
+/// statistics are read.
+#[derive(Debug, Default, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct ManagerDiagnosticData {
+    pub memory_statistics: Option<MemoryStatistics>,
+}
+
+#[derive(Debug, Default, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct MemoryStatistics {
+    pub total_bytes: Option<u64>,
+    pub free_bytes: Option<u64>,
+    pub available_bytes: Option<u64>,
+}
+
+/// A BMC's own health, see `Redfish::get_manager_health`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ManagerHealth {
+    pub manager_id: String,
+    pub firmware_version: Option<String>,
+    pub status: ResourceStatus,
+    /// Status.Conditions: what the BMC says needs attention right now
+    pub conditions: Vec<Condition>,
+    /// Time since the BMC last reset, from its DateTime and LastResetTime
+    pub uptime: Option<std::time::Duration>,
+    /// Memory the BMC can still hand out, from ManagerDiagnosticData MemoryStatistics:
+    /// AvailableBytes, or FreeBytes when that is missing. None when the BMC doesn't link
+    /// ManagerDiagnosticData; the Manager resource itself has no memory figures.
+    pub free_memory_bytes: Option<u64>,
+    /// OEM self-test results (iLO)
+    pub self_tests: Vec<ManagerSelfTest>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ManagerSelfTest {
+    pub name: String,
+    pub health: ResourceHealth,
+    pub notes: String,
+}
+
+impl ManagerHealth {
+    /// False if the BMC reports Warning or Critical health for itself, in a condition, or
+    /// in a self-test. Missing health counts as healthy.
+    pub fn is_healthy(&self) -> bool {
+        let bad = |h: Option<ResourceHealth>| {
+            matches!(h, Some(ResourceHealth::Warning | ResourceHealth::Critical))
+        };
+        !bad(self.status.health)
+            && !self.conditions.iter().any(|c| bad(c.severity))
+            && !self.self_tests.iter().any(|t| bad(Some(t.health)))
+    }
+}
+
+impl ManagerHealth {
+    pub fn with_diagnostic_data(mut self, data: &ManagerDiagnosticData) -> Self {
+        self.free_memory_bytes = data
+            .memory_statistics
+            .as_ref()
+            .and_then(|m| m.available_bytes.or(m.free_bytes));
+        self
+    }
+}
+
+impl From<ManagerHealthResource> for ManagerHealth {
+    fn from(m: ManagerHealthResource) -> Self {
+        let uptime = m
+            .date_time
+            .as_deref()
+            .zip(m.last_reset_time.as_deref())
+            .and_then(|(now, reset)| {
+                let now = chrono::DateTime::parse_from_rfc3339(now).ok()?;
+                let reset = chrono::DateTime::parse_from_rfc3339(reset).ok()?;
+                (now - reset).to_std().ok()
+            });
+        // iLO 4 reports under Oem.Hp, iLO 5 and later under Oem.Hpe
+        let self_tests = m
+            .oem
+            .as_ref()
+            .and_then(|oem| oem.get("Hpe").or_else(|| oem.get("Hp")))
+            .and_then(|hp| hp.get("iLOSelfTestResults"))
+            .and_then(|results| results.as_array())
+            .map(|results| {
+                results
+                    .iter()
+                    .filter_map(|r| {
+                        Some(ManagerSelfTest {
+                            name: r.get("SelfTestName")?.as_str()?.to_string(),
+                            health: serde_json::from_value(r.get("Status")?.clone()).ok()?,
+                            notes: r
+                                .get("Notes")
+                                .and_then(|n| n.as_str())
+                                .unwrap_or_default()
+                                .trim()
+                                .to_string(),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        ManagerHealth {
+            manager_id: m.id,
+            firmware_version: m.firmware_version,
+            status: ResourceStatus::from(&m.status),
+            conditions: m.status.conditions,
+            uptime,
+            free_memory_bytes: None,
+            self_tests,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::model::oem::{dell, hpe};
 
+    #[test]
+    fn test_commandshell_partial() {
+        let console: super::Commandshell =
+            serde_json::from_value(serde_json::json!({"ServiceEnabled": true})).unwrap();
+        assert!(console.service_enabled);
+        assert!(console.connect_types_supported.is_empty());
+        let console: super::Commandshell = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(!console.service_enabled);
+    }
+
+    #[test]
+    fn test_reset_to_defaults_safest() {
+        use super::{ManagerResetToDefaultsResource, ResetToDefaultsType};
+        let parse = |parameter: &str, allowed: serde_json::Value| {
+            serde_json::from_value::<ManagerResetToDefaultsResource>(serde_json::json!({
+                "Actions": {"#Manager.ResetToDefaults": {
+                    "target": "/redfish/v1/Managers/BMC_0/Actions/Manager.ResetToDefaults",
+                    format!("{parameter}@Redfish.AllowableValues"): allowed
+                }}
+            }))
+            .unwrap()
+        };
+        let all = parse(
+            "ResetType",
+            serde_json::json!(["ResetAll", "PreserveNetwork", "PreserveNetworkAndUsers"]),
+        );
+        assert_eq!(
+            all.safest("ResetType"),
+            Some(ResetToDefaultsType::PreserveNetworkAndUsers)
+        );
+        let network = parse(
+            "ResetType",
+            serde_json::json!(["ResetAll", "PreserveNetwork"]),
+        );
+        assert_eq!(
+            network.safest("ResetType"),
+            Some(ResetToDefaultsType::PreserveNetwork)
+        );
+        let reset_all = parse("ResetType", serde_json::json!(["ResetAll"]));
+        assert_eq!(reset_all.safest("ResetType"), None);
+        // An OEM value doesn't break parsing, and isn't mistaken for a known one
+        let oem = parse("ResetType", serde_json::json!(["ResetAll", "OemKeepCerts"]));
+        assert_eq!(
+            oem.allowable_values("ResetType"),
+            ["ResetAll", "OemKeepCerts"]
+        );
+        assert_eq!(oem.safest("ResetType"), None);
+        // Read under the parameter name the BMC uses
+        let dpu = parse("ResetToDefaultsType", serde_json::json!(["ResetAll"]));
+        assert_eq!(dpu.safest("ResetToDefaultsType"), None);
+        // Nothing advertised, try the safest and let the BMC reject it
+        assert_eq!(
+            ManagerResetToDefaultsResource::default().safest("ResetType"),
+            Some(ResetToDefaultsType::PreserveNetworkAndUsers)
+        );
+    }
+
     #[test]
     fn test_manager_parser_hp() {
         let test_data = include_str!("testdata/manager_hp.json");
@@ -139,6 +405,50 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_manager_parser_unmodeled_oem() {
+        let test_data = include_str!("testdata/manager_hp.json");
+        let m: super::Manager = serde_json::from_str(test_data).unwrap();
+        let oem = m.oem.unwrap();
+        assert!(oem.dell.is_none());
+        assert!(oem.lenovo.is_none());
+        assert!(oem.other.contains_key("Hp"));
+    }
+
+    #[test]
+    fn test_manager_health() {
+        let test_data = include_str!("testdata/manager_conditions.json");
+        let m: super::ManagerHealthResource = serde_json::from_str(test_data).unwrap();
+        let health = super::ManagerHealth::from(m);
+        assert_eq!(health.manager_id, "bmc");
+        assert_eq!(health.conditions.len(), 1);
+        assert_eq!(
+            health.conditions[0].message_id.as_deref(),
+            Some("Base.1.8.ResourceErrorsDetected")
+        );
+        assert_eq!(
+            health.uptime,
+            Some(std::time::Duration::from_secs(3 * 3600 + 25 * 60))
+        );
+        assert!(!health.is_healthy());
+        assert!(health.free_memory_bytes.is_none());
+        let data: super::ManagerDiagnosticData =
+            serde_json::from_str(include_str!("testdata/manager_diagnostic_data.json")).unwrap();
+        let health = health.with_diagnostic_data(&data);
+        assert_eq!(health.free_memory_bytes, Some(612_667_392));
+
+        let test_data = include_str!("testdata/manager_hp.json");
+        let m: super::ManagerHealthResource = serde_json::from_str(test_data).unwrap();
+        let health = super::ManagerHealth::from(m);
+        assert!(health.conditions.is_empty());
+        assert!(health.uptime.is_none());
+        assert!(health
+            .self_tests
+            .iter()
+            .any(|t| t.name == "EmbeddedFlash/SDCard" && t.notes.contains("2.10.00")));
+        assert!(health.is_healthy());
+    }
+
     #[test]
     fn test_manager_parser_dell_attrs() {
         let test_data3 = include_str!("testdata/manager_dell_attrs.json");
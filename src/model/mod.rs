@@ -45,8 +45,11 @@ pub mod account_service;
 pub mod certificate;
 pub mod chassis;
 pub mod component_integrity;
+pub mod environment_metrics;
 pub mod error;
 pub mod ethernet_interface;
+pub mod fabric;
+pub mod fru;
 pub mod job;
 pub mod manager_network_protocol;
 pub mod network_device_function;
@@ -59,6 +62,7 @@ pub mod software_inventory;
 pub mod storage;
 pub mod task;
 pub mod thermal;
+pub mod uefi_device_path;
 pub mod update_service;
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
@@ -152,6 +156,19 @@ impl From<String> for ODataId {
     }
 }
 
+impl fmt::Display for ODataId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.odata_id)
+    }
+}
+
+impl FromStr for ODataId {
+    type Err = RedfishError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ODataId::new(s)
+    }
+}
+
 impl From<&str> for ODataId {
     fn from(item: &str) -> Self {
         ODataId {
@@ -161,6 +178,47 @@ impl From<&str> for ODataId {
 }
 
 impl ODataId {
+    /// Build an ODataId from a caller supplied path, e.g. "/redfish/v1/Systems/1/".
+    /// The leading slash is added if missing and trailing slashes are dropped. The path must be
+    /// under /redfish/v1 and must not contain empty, "." or ".." segments, a query or a fragment.
+    pub fn new(path: &str) -> Result<ODataId, RedfishError> {
+        let invalid = |reason: &str| RedfishError::GenericError {
+            error: format!("Invalid odata_id '{path}': {reason}"),
+        };
+        let trimmed = path.trim().trim_matches('/');
+        if trimmed.is_empty() {
+            return Err(invalid("empty path"));
+        }
+        if trimmed
+            .chars()
+            .any(|c| c.is_whitespace() || c.is_control() || c == '?' || c == '#')
+        {
+            return Err(invalid("unexpected character"));
+        }
+        let segments: Vec<&str> = trimmed.split('/').collect();
+        if segments
+            .iter()
+            .any(|s| s.is_empty() || *s == "." || *s == "..")
+        {
+            return Err(invalid("empty or relative path segment"));
+        }
+        if segments.len() < 2 || segments[0] != "redfish" || segments[1] != "v1" {
+            return Err(invalid("must start with /redfish/v1"));
+        }
+        Ok(ODataId {
+            odata_id: format!("/{trimmed}"),
+        })
+    }
+
+    /// The path relative to /redfish/v1/, as the client's request methods take it
+    pub fn relative_path(&self) -> String {
+        self.odata_id
+            .trim_start_matches('/')
+            .trim_start_matches(crate::network::REDFISH_ENDPOINT)
+            .trim_start_matches('/')
+            .to_string()
+    }
+
     // Gets last portion of the ID, not including uri path
     pub fn odata_id_get(&self) -> Result<&str, RedfishError> {
         self.odata_id
@@ -268,6 +326,40 @@ impl From<EnableDisable> for serde_json::Value {
     }
 }
 
+impl From<EnableDisable> for EnabledDisabled {
+    fn from(val: EnableDisable) -> Self {
+        match val {
+            EnableDisable::Enable => EnabledDisabled::Enabled,
+            EnableDisable::Disable => EnabledDisabled::Disabled,
+        }
+    }
+}
+
+impl From<EnabledDisabled> for EnableDisable {
+    fn from(val: EnabledDisabled) -> Self {
+        match val {
+            EnabledDisabled::Enabled => EnableDisable::Enable,
+            EnabledDisabled::Disabled => EnableDisable::Disable,
+        }
+    }
+}
+
+impl From<bool> for EnabledDisabled {
+    fn from(enabled: bool) -> Self {
+        if enabled {
+            EnabledDisabled::Enabled
+        } else {
+            EnabledDisabled::Disabled
+        }
+    }
+}
+
+impl From<bool> for EnableDisable {
+    fn from(enabled: bool) -> Self {
+        EnabledDisabled::from(enabled).into()
+    }
+}
+
 #[derive(Debug, Default, Serialize, Deserialize, Copy, Clone, Eq, PartialEq)]
 pub enum YesNo {
     #[default]
@@ -331,9 +423,17 @@ impl fmt::Display for OnOff {
 
 #[derive(Debug, Serialize, Deserialize, Copy, Clone, Eq, PartialEq)]
 pub enum LinkStatus {
+    // The older NetworkPort schema says Up / Down
+    #[serde(alias = "Up")]
     LinkUp,
     NoLink,
+    #[serde(alias = "Down")]
     LinkDown,
+    Starting,
+    Training,
+    /// A status outside the schema
+    #[serde(other)]
+    Unknown,
 }
 
 impl fmt::Display for LinkStatus {
@@ -376,6 +476,8 @@ pub enum ResourceHealth {
     Warning,
     Critical,
     Informational, // HP only, non-standard
+    #[serde(other)]
+    Unknown,
 }
 
 impl fmt::Display for ResourceHealth {
@@ -409,6 +511,86 @@ impl fmt::Display for ResourceState {
     }
 }
 
+/// Status with its Conditions array (Resource.v1_11+): what currently needs attention
+/// on the resource or, for a rollup, on the resources it contains.
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct StatusWithConditions {
+    pub health: Option<ResourceHealth>,
+    pub health_rollup: Option<ResourceHealth>,
+    pub state: Option<ResourceState>,
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+}
+
+impl From<&StatusWithConditions> for ResourceStatus {
+    fn from(status: &StatusWithConditions) -> Self {
+        ResourceStatus {
+            health: status.health,
+            health_rollup: status.health_rollup,
+            state: status.state,
+        }
+    }
+}
+
+/// https://redfish.dmtf.org/schemas/v1/Resource.v1_19_0.json#/definitions/Condition
+/// A condition that requires attention.
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct Condition {
+    pub message: Option<String>,
+    pub message_id: Option<String>,
+    #[serde(default)]
+    pub message_args: Vec<String>,
+    pub severity: Option<ResourceHealth>,
+    pub timestamp: Option<String>,
+    /// The resource the condition is about, when it isn't the one reporting it
+    pub origin_of_condition: Option<ODataId>,
+    pub resolution: Option<String>,
+    pub log_entry: Option<ODataId>,
+}
+
+/// Just the @odata.id and Status of a resource, for collecting its Conditions.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct ResourceWithConditions {
+    #[serde(rename = "@odata.id")]
+    pub odata_id: String,
+    #[serde(default)]
+    pub status: StatusWithConditions,
+}
+
+impl Condition {
+    /// Every condition of `resources` in one list. Conditions without an origin get the
+    /// resource that reported them. A rollup repeats its children's conditions, so a
+    /// condition reported by more than one resource is kept once.
+    pub fn collect(resources: impl IntoIterator<Item = ResourceWithConditions>) -> Vec<Condition> {
+        let mut conditions: Vec<Condition> = Vec::new();
+        for resource in resources {
+            for mut condition in resource.status.conditions {
+                if condition.origin_of_condition.is_none() {
+                    condition.origin_of_condition = Some(ODataId {
+                        odata_id: resource.odata_id.clone(),
+                    });
+                }
+                let key = |c: &Condition| {
+                    (
+                        c.message_id.clone(),
+                        c.message.clone(),
+                        c.timestamp.clone(),
+                        c.origin_of_condition.as_ref().map(|o| o.odata_id.clone()),
+                    )
+                };
+                if !conditions.iter().any(|c| key(c) == key(&condition)) {
+                    conditions.push(condition);
+                }
+            }
+        }
+        conditions
+    }
+}
+
 /// https://redfish.dmtf.org/schemas/v1/Message.v1_1_2.json
 /// The message that the Redfish service returns.
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -421,3 +603,99 @@ pub struct Message {
     pub resolution: Option<String>,
     pub severity: Option<String>,
 }
+
+impl Message {
+    /// The MessageId without its registry prefix and version, e.g. ResetRequired for
+    /// Base.1.8.ResetRequired
+    pub fn message_key(&self) -> &str {
+        self.message_id
+            .rsplit('.')
+            .next()
+            .unwrap_or(&self.message_id)
+    }
+}
+
+/// Successful action and PATCH responses may carry messages describing what the service did,
+/// for example that the change is pending until the next reset.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct ExtendedInfo {
+    #[serde(rename = "@Message.ExtendedInfo", default)]
+    pub messages: Vec<Message>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EnableDisable, EnabledDisabled, ODataId};
+
+    #[test]
+    fn test_odata_id_new() {
+        let id = ODataId::new("redfish/v1/Systems/System.Embedded.1/").unwrap();
+        assert_eq!(id.odata_id, "/redfish/v1/Systems/System.Embedded.1");
+        assert_eq!(id.to_string(), "/redfish/v1/Systems/System.Embedded.1");
+        assert_eq!(ODataId::new("/redfish/v1").unwrap().odata_id, "/redfish/v1");
+
+        let parsed: ODataId = "/redfish/v1/Chassis/1".parse().unwrap();
+        assert_eq!(parsed.odata_id, "/redfish/v1/Chassis/1");
+
+        for bad in [
+            "",
+            "/",
+            "/Systems/1",
+            "/redfish/v2/Systems",
+            "/redfish/v1//Systems",
+            "/redfish/v1/../v1/Systems",
+            "/redfish/v1/Systems?$expand=.",
+            "/redfish/v1/Sys tems",
+        ] {
+            assert!(ODataId::new(bad).is_err(), "{bad} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_enable_disable_conversions() {
+        assert_eq!(
+            EnabledDisabled::from(EnableDisable::Disable),
+            EnabledDisabled::Disabled
+        );
+        assert_eq!(
+            EnableDisable::from(EnabledDisabled::Enabled),
+            EnableDisable::Enable
+        );
+        assert!(EnableDisable::from(true).is_enabled());
+        assert!(!EnabledDisabled::from(false).is_enabled());
+        let parsed: EnableDisable = "Disable".parse().unwrap();
+        assert_eq!(EnabledDisabled::from(parsed).to_string(), "Disabled");
+    }
+
+    #[test]
+    fn test_collect_conditions() {
+        let manager: super::ResourceWithConditions =
+            serde_json::from_str(include_str!("testdata/manager_conditions.json")).unwrap();
+        // The chassis rolls up the manager's condition and adds one of its own without an origin
+        let chassis: super::ResourceWithConditions = serde_json::from_value(serde_json::json!({
+            "@odata.id": "/redfish/v1/Chassis/BMC_0",
+            "Status": {
+                "HealthRollup": "Critical",
+                "Conditions": [
+                    manager.status.conditions[0].clone(),
+                    {
+                        "MessageId": "Base.1.8.ResourceErrorsDetected",
+                        "Message": "Fan 3 has stopped",
+                        "Severity": "Critical"
+                    }
+                ]
+            }
+        }))
+        .unwrap();
+        let conditions = super::Condition::collect([manager, chassis]);
+        assert_eq!(conditions.len(), 2);
+        assert_eq!(
+            conditions[0].origin_of_condition.as_ref().unwrap().odata_id,
+            "/redfish/v1/Managers/bmc/EthernetInterfaces/eth1"
+        );
+        assert_eq!(
+            conditions[1].origin_of_condition.as_ref().unwrap().odata_id,
+            "/redfish/v1/Chassis/BMC_0"
+        );
+    }
+}
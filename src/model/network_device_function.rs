@@ -37,6 +37,7 @@ pub struct NetworkDeviceFunction {
     pub description: Option<String>,
     pub id: Option<String>,
     pub ethernet: Option<Ethernet>,
+    pub infini_band: Option<InfiniBand>,
     pub name: Option<String>,
     pub net_dev_func_capabilities: Option<Vec<String>>,
     pub net_dev_func_type: Option<String>,
@@ -61,3 +62,18 @@ pub struct Ethernet {
     #[serde(rename = "MTUSize")]
     pub mtu_size: Option<i32>,
 }
+
+/// GUIDs of a function running InfiniBand
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InfiniBand {
+    #[serde(rename = "NodeGUID")]
+    pub node_guid: Option<String>,
+    #[serde(rename = "PortGUID")]
+    pub port_guid: Option<String>,
+    #[serde(rename = "PermanentPortGUID")]
+    pub permanent_port_guid: Option<String>,
+    #[serde(rename = "SystemGUID")]
+    pub system_guid: Option<String>,
+    #[serde(rename = "MTUSize")]
+    pub mtu_size: Option<i32>,
+}
@@ -1318,7 +1318,8 @@ pub struct ShareParameters {
 pub struct SystemConfiguration {
     pub shutdown_type: String,
     pub share_parameters: ShareParameters,
-    pub import_buffer: String,
+    /// Can carry passwords, see `clear_uefi_password`
+    pub import_buffer: crate::SecretString,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 pub mod dell;
@@ -12,6 +14,9 @@ pub mod supermicro;
 pub struct ManagerExtensions {
     pub dell: Option<dell::Manager>,
     pub lenovo: Option<lenovo::Manager>,
+    /// Vendor namespaces not modeled above, kept as raw JSON
+    #[serde(flatten)]
+    pub other: HashMap<String, serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -19,10 +24,16 @@ pub struct ManagerExtensions {
 pub struct SystemExtensions {
     pub dell: Option<dell::SystemWrapper>,
     pub lenovo: Option<lenovo::System>,
+    /// Vendor namespaces not modeled above, kept as raw JSON
+    #[serde(flatten)]
+    pub other: HashMap<String, serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct ChassisExtensions {
     pub nvidia: Option<nvidia_dpu::Chassis>,
+    /// Vendor namespaces not modeled above, kept as raw JSON
+    #[serde(flatten)]
+    pub other: HashMap<String, serde_json::Value>,
 }
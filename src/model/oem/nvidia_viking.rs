@@ -116,6 +116,82 @@ pub struct Bios {
 pub struct SetBiosAttributes {
     pub attributes: BiosAttributes,
 }
+
+/// BIOS attributes that make up Viking lockdown, as currently reported by the BMC
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct BiosLockdownAttributes {
+    /// Host KCS (in-band IPMI) policy, e.g. "Deny All" or "Allow All"
+    pub kcs_interface_disable: Option<String>,
+    /// Host interface (in-band Redfish) state
+    pub redfish_enable: Option<EnabledDisabled>,
+}
+
+impl From<&BiosAttributes> for BiosLockdownAttributes {
+    fn from(attrs: &BiosAttributes) -> Self {
+        BiosLockdownAttributes {
+            kcs_interface_disable: attrs.kcs_interface_disable.clone(),
+            redfish_enable: attrs.redfish_enable,
+        }
+    }
+}
+
+/// UEFI network boot stack settings (HTTP and PXE over IPv4/IPv6)
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, Eq, PartialEq)]
+pub struct UefiHttpAttributes {
+    pub ipv4_http: EnabledDisabled,
+    pub ipv4_pxe: EnabledDisabled,
+    pub ipv6_http: EnabledDisabled,
+    pub ipv6_pxe: EnabledDisabled,
+}
+
+impl Default for UefiHttpAttributes {
+    fn default() -> Self {
+        Self {
+            ipv4_http: DEFAULT_IPV4_HTTP,
+            ipv4_pxe: DEFAULT_IPV4_PXE,
+            ipv6_http: DEFAULT_IPV6_HTTP,
+            ipv6_pxe: DEFAULT_IPV6_PXE,
+        }
+    }
+}
+
+impl From<UefiHttpAttributes> for BiosAttributes {
+    fn from(cfg: UefiHttpAttributes) -> Self {
+        BiosAttributes {
+            ipv4_http: cfg.ipv4_http.into(),
+            ipv4_pxe: cfg.ipv4_pxe.into(),
+            ipv6_http: cfg.ipv6_http.into(),
+            ipv6_pxe: cfg.ipv6_pxe.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// SR-IOV and VT-d settings
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, Eq, PartialEq)]
+pub struct VirtAttributes {
+    pub sriov_enable: EnableDisable,
+    pub vtd_support: EnableDisable,
+}
+
+impl Default for VirtAttributes {
+    fn default() -> Self {
+        Self {
+            sriov_enable: DEFAULT_SRIOV_ENABLE,
+            vtd_support: DEFAULT_VTD_SUPPORT,
+        }
+    }
+}
+
+impl From<VirtAttributes> for BiosAttributes {
+    fn from(cfg: VirtAttributes) -> Self {
+        BiosAttributes {
+            sriov_enable: cfg.sriov_enable.into(),
+            vtd_support: cfg.vtd_support.into(),
+            ..Default::default()
+        }
+    }
+}
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct BmcSerialConsoleAttributes {
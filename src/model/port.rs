@@ -22,13 +22,17 @@
  */
 use serde::{Deserialize, Serialize};
 
-use super::{LinkStatus, ODataLinks};
+use super::{network_device_function::NetworkDeviceFunction, LinkStatus, ODataLinks};
 
 #[derive(Debug, Serialize, Deserialize, Copy, Clone, Eq, PartialEq)]
 pub enum LinkNetworkTechnology {
     Ethernet,
     InfiniBand,
     FibreChannel,
+    GenZ,
+    PCIe,
+    #[serde(other)]
+    Unknown,
 }
 
 impl std::fmt::Display for LinkNetworkTechnology {
@@ -40,6 +44,10 @@ impl std::fmt::Display for LinkNetworkTechnology {
 /// http://redfish.dmtf.org/schemas/v1/NetworkPort.v1_4_1.json
 /// The NetworkPort schema contains an inventory of software components.
 /// This can include Network Device parameters such as current speed, link status, etc.
+///
+/// Newer BMCs (GB200, BlueField) serve the Port schema at the same URL, which reports speeds
+/// in Gbps; older ones (Dell, Lenovo) serve NetworkPort, which reports them in Mbps. Both are
+/// read here, use the accessors to get a value whichever the BMC sent.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct NetworkPort {
@@ -50,5 +58,239 @@ pub struct NetworkPort {
     pub name: Option<String>,
     pub link_status: Option<LinkStatus>,
     pub link_network_technology: Option<LinkNetworkTechnology>,
-    pub current_speed_gbps: Option<i32>,
+    /// Breaking change: this was `Option<i32>` up to 0.32.0. The schema allows fractional
+    /// speeds such as 2.5, which failed to parse into an integer.
+    pub current_speed_gbps: Option<f64>,
+    pub max_speed_gbps: Option<f64>,
+    /// NetworkPort schema equivalents of the fields above
+    pub active_link_technology: Option<LinkNetworkTechnology>,
+    pub current_link_speed_mbps: Option<f64>,
+    #[serde(default)]
+    pub supported_link_capabilities: Vec<LinkCapability>,
+    /// BidirectionalPort, UpstreamPort, ...
+    pub port_type: Option<String>,
+    /// Enabled / Disabled, the administrative state
+    pub link_state: Option<String>,
+    /// Negotiated lane count
+    pub width: Option<u32>,
+    pub infini_band: Option<PortInfiniBand>,
+    pub oem: Option<serde_json::Value>,
+}
+
+/// The InfiniBand block of a Port
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct PortInfiniBand {
+    #[serde(default, rename = "AssociatedNodeGUIDs")]
+    pub associated_node_guids: Vec<String>,
+    #[serde(default, rename = "AssociatedPortGUIDs")]
+    pub associated_port_guids: Vec<String>,
+    #[serde(default, rename = "AssociatedSystemGUIDs")]
+    pub associated_system_guids: Vec<String>,
+}
+
+/// What the subnet manager knows a port by, see `NetworkPort::infiniband`
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct InfiniBandPortInfo {
+    pub port_type: Option<String>,
+    pub link_status: Option<LinkStatus>,
+    pub node_guid: Option<String>,
+    pub port_guid: Option<String>,
+    pub lid: Option<u32>,
+    /// e.g. "4X"
+    pub link_width: Option<String>,
+    /// e.g. "NDR", or the speed in Gbps where the BMC gives no IB rate name
+    pub link_speed: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct LinkCapability {
+    pub link_network_technology: Option<LinkNetworkTechnology>,
+    pub link_speed_mbps: Option<f64>,
+}
+
+impl NetworkPort {
+    pub fn is_link_up(&self) -> bool {
+        self.link_status == Some(LinkStatus::LinkUp)
+    }
+
+    /// Negotiated speed
+    pub fn speed_gbps(&self) -> Option<f64> {
+        self.current_speed_gbps
+            .or(self.current_link_speed_mbps.map(|mbps| mbps / 1000.0))
+    }
+
+    /// Fastest speed the port supports
+    pub fn max_speed_gbps(&self) -> Option<f64> {
+        self.max_speed_gbps.or_else(|| {
+            self.supported_link_capabilities
+                .iter()
+                .filter_map(|c| c.link_speed_mbps)
+                .reduce(f64::max)
+                .map(|mbps| mbps / 1000.0)
+        })
+    }
+
+    /// GUIDs, LID and negotiated width / speed of an InfiniBand port. The GUIDs come from
+    /// the InfiniBand block of the port's NetworkDeviceFunction where given, else from the
+    /// port's own Associated*GUIDs. LID, width and speed have no standard property, they are
+    /// read from the NVIDIA OEM block. None for a port running anything else.
+    pub fn infiniband(
+        &self,
+        function: Option<&NetworkDeviceFunction>,
+    ) -> Option<InfiniBandPortInfo> {
+        if self.network_technology() != Some(LinkNetworkTechnology::InfiniBand) {
+            return None;
+        }
+        let oem = self.oem.as_ref().and_then(|o| o.get("Nvidia"));
+        let oem_str = |key: &str| {
+            oem.and_then(|o| o.get(key))
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        };
+        let function = function.and_then(|f| f.infini_band.as_ref());
+        let port = self.infini_band.clone().unwrap_or_default();
+        Some(InfiniBandPortInfo {
+            port_type: self.port_type.clone(),
+            link_status: self.link_status,
+            node_guid: function
+                .and_then(|f| f.node_guid.clone())
+                .or_else(|| port.associated_node_guids.into_iter().next()),
+            port_guid: function
+                .and_then(|f| f.port_guid.clone())
+                .or_else(|| port.associated_port_guids.into_iter().next()),
+            lid: oem
+                .and_then(|o| o.get("LID"))
+                .and_then(|v| v.as_u64())
+                .and_then(|lid| u32::try_from(lid).ok()),
+            link_width: oem_str("ActiveWidth").or(self.width.map(|w| format!("{w}X"))),
+            link_speed: oem_str("ActiveSpeed").or(self.speed_gbps().map(|s| s.to_string())),
+        })
+    }
+
+    /// Ethernet or InfiniBand, for a port that can run either
+    pub fn network_technology(&self) -> Option<LinkNetworkTechnology> {
+        self.link_network_technology
+            .or(self.active_link_technology)
+            .or_else(|| {
+                self.supported_link_capabilities
+                    .iter()
+                    .find_map(|c| c.link_network_technology)
+            })
+    }
+}
+
+/// http://redfish.dmtf.org/schemas/v1/PortMetrics.v1_3_0.json
+/// Traffic and error counters for a port, found at Ports/{id}/Metrics
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct PortMetrics {
+    #[serde(flatten)]
+    pub odata: Option<ODataLinks>,
+    pub id: Option<String>,
+    pub name: Option<String>,
+    #[serde(rename = "RXBytes")]
+    pub rx_bytes: Option<u64>,
+    #[serde(rename = "TXBytes")]
+    pub tx_bytes: Option<u64>,
+    #[serde(rename = "RXErrors")]
+    pub rx_errors: Option<u64>,
+    #[serde(rename = "TXErrors")]
+    pub tx_errors: Option<u64>,
+    pub networking: Option<PortNetworkingMetrics>,
+    /// Vendor link counters, e.g. NVLink CRC and recovery counts under Oem.Nvidia
+    pub oem: Option<serde_json::Value>,
+}
+
+/// Ethernet / InfiniBand frame counters of a port
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PortNetworkingMetrics {
+    #[serde(rename = "RXFrames")]
+    pub rx_frames: Option<u64>,
+    #[serde(rename = "TXFrames")]
+    pub tx_frames: Option<u64>,
+    #[serde(rename = "RXUnicastFrames")]
+    pub rx_unicast_frames: Option<u64>,
+    #[serde(rename = "TXUnicastFrames")]
+    pub tx_unicast_frames: Option<u64>,
+    #[serde(rename = "RXFCSErrors")]
+    pub rx_fcs_errors: Option<u64>,
+    #[serde(rename = "RXFrameAlignmentErrors")]
+    pub rx_frame_alignment_errors: Option<u64>,
+    #[serde(rename = "RXOversizeFrames")]
+    pub rx_oversize_frames: Option<u64>,
+    #[serde(rename = "RXUndersizeFrames")]
+    pub rx_undersize_frames: Option<u64>,
+    #[serde(rename = "RXDiscards")]
+    pub rx_discards: Option<u64>,
+    #[serde(rename = "TXDiscards")]
+    pub tx_discards: Option<u64>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LinkNetworkTechnology, NetworkPort, PortMetrics};
+    use crate::model::LinkStatus;
+
+    #[test]
+    fn test_network_port_speeds() {
+        let data = include_str!("testdata/network_port_gb200.json");
+        let port: NetworkPort = serde_json::from_str(data).unwrap();
+        assert!(port.is_link_up());
+        assert_eq!(port.speed_gbps(), Some(400.0));
+        assert_eq!(port.max_speed_gbps(), Some(400.0));
+        assert_eq!(
+            port.network_technology(),
+            Some(LinkNetworkTechnology::InfiniBand)
+        );
+
+        let ib = port.infiniband(None).unwrap();
+        assert_eq!(ib.port_guid.as_deref(), Some("0xB83FD20300909582"));
+        assert_eq!(ib.node_guid.as_deref(), Some("0xB83FD20300909580"));
+        assert_eq!(ib.link_status, Some(LinkStatus::LinkUp));
+        assert_eq!(ib.lid, Some(17));
+        assert_eq!(ib.link_width.as_deref(), Some("4X"));
+        assert_eq!(ib.link_speed.as_deref(), Some("NDR"));
+
+        // The function's InfiniBand block wins over the port's associated GUIDs
+        let function: crate::NetworkDeviceFunction = serde_json::from_value(serde_json::json!({
+            "Id": "NIC_0",
+            "InfiniBand": {"NodeGUID": "0xB83FD203009095A0", "PortGUID": "0xB83FD203009095A2"}
+        }))
+        .unwrap();
+        let ib = port.infiniband(Some(&function)).unwrap();
+        assert_eq!(ib.node_guid.as_deref(), Some("0xB83FD203009095A0"));
+        assert_eq!(ib.port_guid.as_deref(), Some("0xB83FD203009095A2"));
+
+        let data = include_str!("testdata/network_port_mbps.json");
+        let port: NetworkPort = serde_json::from_str(data).unwrap();
+        assert_eq!(port.link_status, Some(LinkStatus::LinkDown));
+        assert!(!port.is_link_up());
+        assert_eq!(port.speed_gbps(), Some(0.0));
+        assert_eq!(port.max_speed_gbps(), Some(25.0));
+        assert_eq!(
+            port.network_technology(),
+            Some(LinkNetworkTechnology::Ethernet)
+        );
+        assert!(port.infiniband(None).is_none());
+
+        let port: NetworkPort =
+            serde_json::from_str(r#"{"LinkStatus": "Degraded", "CurrentSpeedGbps": 2.5}"#).unwrap();
+        assert_eq!(port.link_status, Some(LinkStatus::Unknown));
+        assert_eq!(port.speed_gbps(), Some(2.5));
+    }
+
+    #[test]
+    fn test_port_metrics_parser() {
+        let data = include_str!("testdata/port_metrics.json");
+        let metrics: PortMetrics = serde_json::from_str(data).unwrap();
+        assert_eq!(metrics.rx_bytes, Some(59825));
+        assert_eq!(metrics.tx_bytes, Some(1024));
+        let networking = metrics.networking.unwrap();
+        assert_eq!(networking.rx_fcs_errors, Some(3));
+        assert_eq!(
+            metrics.oem.unwrap()["Nvidia"]["LinkDownedCount"].as_u64(),
+            Some(2)
+        );
+    }
 }
@@ -19,8 +19,8 @@
  * FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
  * DEALINGS IN THE SOFTWARE.
  */
-use super::{LinkType, ODataId, ODataLinks, ResourceStatus, StatusVec};
-use crate::model::sensor::Sensor;
+use super::{LinkType, ODataId, ODataLinks, ResourceHealth, ResourceStatus, StatusVec};
+use crate::model::sensor::{Sensor, SensorExcerpt};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -137,6 +137,48 @@ pub struct PowerSupply {
     pub status: ResourceStatus,
 }
 
+/// Identity and health of one power supply, read from PowerSubsystem/PowerSupplies or from
+/// the legacy Power.PowerSupplies array
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct PowerSupplyInfo {
+    #[serde(skip_deserializing)]
+    pub chassis_id: String,
+    #[serde(rename = "@odata.id", default)]
+    pub odata_id: String,
+    #[serde(alias = "MemberId")]
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+    pub serial_number: Option<String>,
+    pub part_number: Option<String>,
+    pub firmware_version: Option<String>,
+    pub power_supply_type: Option<String>, // AC, DC, ACorDC
+    #[serde(alias = "InputNominalVoltageType")]
+    pub line_input_voltage_type: Option<String>,
+    pub line_input_voltage: Option<f64>,
+    pub power_capacity_watts: Option<f64>,
+    pub metrics: Option<ODataId>,
+    pub status: Option<ResourceStatus>,
+}
+
+/// Chassis/{id}/PowerSubsystem, the links to the newer power resources
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct PowerSubsystem {
+    pub batteries: Option<ODataId>,
+    pub power_supplies: Option<ODataId>,
+    pub status: Option<ResourceStatus>,
+}
+
+/// PowerSubsystem/PowerSupplies/{id}/Metrics, the readings of a power supply
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct PowerSupplyMetrics {
+    pub input_voltage: Option<SensorExcerpt>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct Redundancy {
@@ -146,6 +188,18 @@ pub struct Redundancy {
     pub mode: String,
     pub name: String,
     pub redundancy_set: Vec<ODataId>,
+    pub status: Option<ResourceStatus>,
+}
+
+impl Redundancy {
+    /// False once the redundancy set reports a Warning or Critical health, i.e. losing one
+    /// more member may take the load down.
+    pub fn is_intact(&self) -> bool {
+        !matches!(
+            self.status.and_then(|s| s.health),
+            Some(ResourceHealth::Warning) | Some(ResourceHealth::Critical)
+        )
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -157,6 +211,8 @@ pub struct Voltages {
     pub lower_threshold_critical: Option<f64>,
     pub upper_threshold_critical: Option<f64>,
     pub status: ResourceStatus,
+    /// When the reading was taken, if the source Sensor says. Not in the legacy Power schema.
+    pub reading_time: Option<String>,
 }
 
 impl From<Sensor> for Voltages {
@@ -171,6 +227,7 @@ impl From<Sensor> for Voltages {
             lower_threshold_critical: None,
             upper_threshold_critical: None,
             status: sensor.status.unwrap_or_default(),
+            reading_time: sensor.reading_time,
         }
     }
 }
@@ -219,4 +276,38 @@ mod test {
         let result_lenovo: super::Power = serde_json::from_str(test_data_lenovo).unwrap();
         println!("power-lenovo_health_critical: {result_lenovo:#?}");
     }
+
+    #[test]
+    fn test_power_supply_info_parser() {
+        let legacy = include_str!("testdata/power_supply.json");
+        let legacy: super::PowerSupplyInfo = serde_json::from_str(legacy).unwrap();
+        assert_eq!(legacy.id.as_deref(), Some("0"));
+        assert_eq!(legacy.line_input_voltage, Some(230.0));
+        assert_eq!(
+            legacy.line_input_voltage_type.as_deref(),
+            Some("ACHighLine")
+        );
+
+        let current = include_str!("testdata/power_supply_subsystem.json");
+        let current: super::PowerSupplyInfo = serde_json::from_str(current).unwrap();
+        assert_eq!(current.id.as_deref(), Some("PSU.Slot.2"));
+        assert_eq!(current.serial_number.as_deref(), Some("CNDED003AG4TQF"));
+        assert_eq!(
+            current.line_input_voltage_type.as_deref(),
+            Some("AC100To240V")
+        );
+        assert!(current.metrics.is_some());
+        assert!(current.status.unwrap().health.is_some());
+    }
+
+    #[test]
+    fn test_power_redundancy_parser() {
+        let data = include_str!("testdata/power_redundancy.json");
+        let power: super::Power = serde_json::from_str(data).unwrap();
+        let redundancy = power.redundancy.unwrap();
+        assert_eq!(redundancy.len(), 1);
+        assert_eq!(redundancy[0].mode, "N+m");
+        assert_eq!(redundancy[0].redundancy_set.len(), 2);
+        assert!(!redundancy[0].is_intact());
+    }
 }
@@ -26,6 +26,7 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::{value::RawValue, Value};
 use tracing::debug;
 
+use crate::network::{from_json_str, from_json_value};
 use crate::{Chassis, RedfishError};
 
 // A Resource is a single entity accessed at a specific URI. A resource collection is a
@@ -72,6 +73,79 @@ impl PartialEq for OData {
 pub trait IsResource {
     fn odata_id(&self) -> String;
     fn odata_type(&self) -> String;
+
+    /// The schema version from @odata.type, None if the BMC didn't give one
+    fn schema_version(&self) -> Option<SchemaVersion> {
+        SchemaVersion::parse(&self.odata_type())
+    }
+
+    /// Error with SchemaVersionTooOld if this resource's schema is older than v{major}_{minor},
+    /// the first to have `property`. Ok if the version is unknown, the BMC might still have it.
+    fn require_schema_version(
+        &self,
+        major: u32,
+        minor: u32,
+        property: &str,
+    ) -> Result<(), RedfishError> {
+        match self.schema_version() {
+            Some(v) if !v.at_least(major, minor) => Err(RedfishError::SchemaVersionTooOld {
+                property: property.to_string(),
+                required: format!("{} v{major}_{minor}", v.resource),
+                actual: v.to_string(),
+                url: self.odata_id(),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+// The resource type and version of a versioned @odata.type, e.g.
+// "#ComputerSystem.v1_20_1.ComputerSystem" is ComputerSystem v1_20_1.
+// Unversioned types ("#ComputerSystemCollection.ComputerSystemCollection") have none.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaVersion {
+    pub resource: String,
+    pub major: u32,
+    pub minor: u32,
+    pub errata: u32,
+}
+
+impl SchemaVersion {
+    pub fn parse(odata_type: &str) -> Option<SchemaVersion> {
+        let mut parts = odata_type.trim_start_matches('#').split('.');
+        let resource = parts.next()?;
+        let version = parts.next()?.strip_prefix('v')?;
+        let mut numbers = version.split('_').map(|n| n.parse::<u32>());
+        let (Some(Ok(major)), Some(Ok(minor)), Some(Ok(errata)), None) = (
+            numbers.next(),
+            numbers.next(),
+            numbers.next(),
+            numbers.next(),
+        ) else {
+            return None;
+        };
+        Some(SchemaVersion {
+            resource: resource.to_string(),
+            major,
+            minor,
+            errata,
+        })
+    }
+
+    /// Is this v{major}_{minor} or later? Errata releases don't add properties.
+    pub fn at_least(&self, major: u32, minor: u32) -> bool {
+        (self.major, self.minor) >= (major, minor)
+    }
+}
+
+impl std::fmt::Display for SchemaVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} v{}_{}_{}",
+            self.resource, self.major, self.minor, self.errata
+        )
+    }
 }
 
 // This is captures raw json of any resource
@@ -87,16 +161,7 @@ impl Resource {
     // @odata.type of the resource is same as T.
     pub fn try_get<U: DeserializeOwned + IsResource>(self) -> Result<U, RedfishError> {
         let requested_type = type_name::<U>().split("::").last().unwrap_or("unknown");
-        let v = match serde_json::from_str::<Value>(self.raw.get()) {
-            Ok(x) => x,
-            Err(e) => {
-                return Err(RedfishError::JsonDeserializeError {
-                    url: self.url,
-                    body: self.raw.get().to_string(),
-                    source: e,
-                })
-            }
-        };
+        let v: Value = from_json_str(&self.url, self.raw.get())?;
 
         let odata_type: String;
         let resource_type = match v.get("@odata.type") {
@@ -117,16 +182,12 @@ impl Resource {
             }
         };
         if resource_type == requested_type {
-            let res: U = match serde_json::from_str(self.raw.get()) {
+            let res: U = match from_json_str(&self.url, self.raw.get()) {
                 Ok(x) => x,
                 Err(e) => {
                     debug!("try_get: from_str failed: expected Type >{}< resource tye {}. Err {}, str = {} ",
                              type_name::<U>().split("::").last().unwrap_or_default(), resource_type, e.to_string(), self.raw.get().to_string());
-                    return Err(RedfishError::JsonDeserializeError {
-                        url: self.url,
-                        body: self.raw.get().to_string(),
-                        source: e,
-                    });
+                    return Err(e);
                 }
             };
             return Ok(res);
@@ -260,7 +321,7 @@ impl Collection {
                 description,
                 members: vec![],
             };
-            match serde_json::from_value::<Vec<T>>(members_json_value.clone()) {
+            match from_json_value::<Vec<T>>(&odata.odata_id, &members_json_value) {
                 Ok(x) => {
                     collection.members = x;
                     Ok(collection)
@@ -302,11 +363,7 @@ impl Collection {
 
                     if collection.members.is_empty() && collection.count != 0 {
                         // we failed to serialize any; return error
-                        return Err(RedfishError::JsonDeserializeError {
-                            url: odata.odata_id.to_string(),
-                            body: members_json_value.to_string(),
-                            source: e,
-                        });
+                        return Err(e);
                     }
                     Ok(collection)
                 }
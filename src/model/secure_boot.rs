@@ -55,3 +55,20 @@ impl std::fmt::Display for SecureBootMode {
         std::fmt::Debug::fmt(self, f)
     }
 }
+
+/// ResetKeysType parameter of the SecureBoot.ResetKeys action.
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, Eq, PartialEq)]
+pub enum SecureBootResetType {
+    /// Reset the contents of all UEFI Secure Boot key databases (PK, KEK, db, dbx) to their
+    /// default values.
+    ResetAllKeysToDefault,
+    /// Delete the contents of all UEFI Secure Boot key databases. This puts the system in
+    /// Setup mode.
+    DeleteAllKeys,
+}
+
+impl std::fmt::Display for SecureBootResetType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
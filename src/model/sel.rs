@@ -44,13 +44,69 @@ pub struct LogEntry {
     pub entry_code: Option<String>,
     pub entry_type: String,
     pub id: String,
+    pub links: Option<LogEntryLinks>,
     pub message: String, // this is the actionable string
+    pub message_args: Option<Vec<String>>,
     pub name: String,
     pub sensor_number: Option<i64>,
     pub sensor_type: Option<String>,
     pub severity: String,
 }
 
+impl LogEntry {
+    /// The resource that caused this entry, e.g. the sensor that crossed a threshold
+    pub fn origin_of_condition(&self) -> Option<&ODataId> {
+        self.links.as_ref()?.origin_of_condition.as_ref()
+    }
+
+    pub fn created_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono::DateTime::parse_from_rfc3339(&self.created)
+            .ok()
+            .map(|t| t.with_timezone(&chrono::Utc))
+    }
+}
+
+/// Sorts entries newest-first, dropping those created before `since` and keeping at most `limit`.
+/// Entries with an unparseable timestamp are kept, after all the others.
+pub fn newest_first(
+    mut entries: Vec<LogEntry>,
+    since: Option<chrono::DateTime<chrono::Utc>>,
+    limit: Option<usize>,
+) -> Vec<LogEntry> {
+    if let Some(since) = since {
+        entries.retain(|e| e.created_at().is_none_or(|t| t >= since));
+    }
+    // None sorts before Some, so reversing puts unparseable timestamps last
+    entries.sort_by_key(|e| std::cmp::Reverse(e.created_at()));
+    if let Some(limit) = limit {
+        entries.truncate(limit);
+    }
+    entries
+}
+
+/// Journal entries as text lines, oldest first, each prefixed with its timestamp, dropping
+/// those created before `since`. OpenBMC puts the syslog line in each entry's Message.
+pub fn journal_lines(
+    mut entries: Vec<LogEntry>,
+    since: Option<chrono::DateTime<chrono::Utc>>,
+) -> Vec<String> {
+    if let Some(since) = since {
+        entries.retain(|e| e.created_at().is_none_or(|t| t >= since));
+    }
+    // Stable, so entries with the same (or no) timestamp keep the BMC's order
+    entries.sort_by_key(|e| e.created_at());
+    entries
+        .into_iter()
+        .map(|e| format!("{} {}", e.created, e.message.trim_end()))
+        .collect()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct LogEntryLinks {
+    pub origin_of_condition: Option<ODataId>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct LogEntryCollection {
@@ -59,4 +115,211 @@ pub struct LogEntryCollection {
     pub name: String,
     pub description: String,
     pub members: Vec<LogEntry>,
+    /// Set when the BMC pages the entries; the next page holds more
+    #[serde(rename = "Members@odata.nextLink")]
+    pub members_next_link: Option<String>,
+}
+
+/// Whether an IPMI event marks a condition starting (assert) or clearing (deassert)
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, Eq, PartialEq)]
+pub enum EventDirection {
+    Assert,
+    Deassert,
+}
+
+/// A SEL entry with the IPMI sensor fields turned into readable values
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DecodedSelEntry {
+    pub id: String,
+    pub created: String,
+    pub severity: String,
+    pub message: String,
+    pub sensor_number: Option<i64>,
+    /// Sensor name, when the BMC passes it as the first message argument
+    pub sensor_name: Option<String>,
+    /// e.g. "Temperature". Numeric IPMI sensor type codes are translated.
+    pub sensor_type: Option<String>,
+    /// The EntryCode, unless it only carries the direction, e.g. "Lower Critical - going low"
+    pub event_type: Option<String>,
+    pub direction: Option<EventDirection>,
+}
+
+impl From<&LogEntry> for DecodedSelEntry {
+    fn from(entry: &LogEntry) -> Self {
+        let entry_code = entry.entry_code.as_deref().map(str::trim);
+        let direction = match entry_code {
+            Some("Assert") => Some(EventDirection::Assert),
+            Some("Deassert") => Some(EventDirection::Deassert),
+            _ => {
+                let message = entry.message.to_lowercase();
+                if message.contains("deassert") {
+                    Some(EventDirection::Deassert)
+                } else if message.contains("assert") {
+                    Some(EventDirection::Assert)
+                } else {
+                    None
+                }
+            }
+        };
+        let event_type = entry_code
+            .filter(|c| !c.is_empty() && *c != "Assert" && *c != "Deassert")
+            .map(str::to_string);
+        let sensor_name = entry
+            .message_args
+            .as_ref()
+            .and_then(|args| args.first())
+            .filter(|name| !name.is_empty())
+            .cloned();
+
+        DecodedSelEntry {
+            id: entry.id.clone(),
+            created: entry.created.clone(),
+            severity: entry.severity.clone(),
+            message: entry.message.clone(),
+            sensor_number: entry.sensor_number,
+            sensor_name,
+            sensor_type: entry.sensor_type.as_deref().map(decode_sensor_type),
+            event_type,
+            direction,
+        }
+    }
+}
+
+/// Translate a numeric IPMI sensor type code ("0x01", "1") to its name.
+/// Anything that is already a name is returned as is.
+fn decode_sensor_type(sensor_type: &str) -> String {
+    let code = match sensor_type
+        .strip_prefix("0x")
+        .or_else(|| sensor_type.strip_prefix("0X"))
+    {
+        Some(hex) => u8::from_str_radix(hex, 16).ok(),
+        None => sensor_type.parse::<u8>().ok(),
+    };
+    let Some(code) = code else {
+        return sensor_type.to_string();
+    };
+    let name = match code {
+        0x01 => "Temperature",
+        0x02 => "Voltage",
+        0x03 => "Current",
+        0x04 => "Fan",
+        0x05 => "Physical Chassis Security",
+        0x06 => "Platform Security Violation Attempt",
+        0x07 => "Processor",
+        0x08 => "Power Supply / Converter",
+        0x09 => "PowerUnit",
+        0x0A => "Cooling Device",
+        0x0B => "Other Units-based Sensor",
+        0x0C => "Memory",
+        0x0D => "Drive Slot/Bay",
+        0x0E => "POST Memory Resize",
+        0x0F => "System Firmware Progress",
+        0x10 => "Event Logging Disabled",
+        0x12 => "System Event",
+        0x13 => "Critical Interrupt",
+        0x14 => "Button/Switch",
+        0x15 => "Module/Board",
+        0x16 => "Microcontroller/Coprocessor",
+        0x17 => "Add-in Card",
+        0x18 => "Chassis",
+        0x19 => "Chip Set",
+        0x1A => "Other FRU",
+        0x1B => "Cable/Interconnect",
+        0x1C => "Terminator",
+        0x1D => "SystemBoot/Restart",
+        0x1E => "Boot Error",
+        0x1F => "BaseOSBoot/InstallationStatus",
+        0x20 => "OS Stop/Shutdown",
+        0x21 => "Slot/Connector",
+        0x22 => "System ACPI PowerState",
+        0x23 => "Watchdog",
+        0x24 => "Platform Alert",
+        0x25 => "Entity Presence",
+        0x26 => "Monitor ASIC/IC",
+        0x27 => "LAN",
+        0x28 => "Management SubsystemHealth",
+        0x29 => "Battery",
+        0x2A => "Session Audit",
+        0x2B => "Version Change",
+        0x2C => "FRUState",
+        _ => return sensor_type.to_string(),
+    };
+    name.to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{newest_first, DecodedSelEntry, EventDirection, LogEntry};
+
+    #[test]
+    fn test_decode_sel_entry() {
+        let data = include_str!("testdata/sel_entry.json");
+        let entry: LogEntry = serde_json::from_str(data).unwrap();
+        let decoded = DecodedSelEntry::from(&entry);
+        assert_eq!(decoded.sensor_type.as_deref(), Some("Temperature"));
+        assert_eq!(decoded.sensor_name.as_deref(), Some("CPU0_Temp"));
+        assert_eq!(
+            decoded.event_type.as_deref(),
+            Some("Upper Critical - going high")
+        );
+        assert_eq!(decoded.direction, Some(EventDirection::Deassert));
+        assert_eq!(decoded.sensor_number, Some(12));
+        assert_eq!(
+            entry.origin_of_condition().unwrap().odata_id,
+            "/redfish/v1/Chassis/BMC_0/Sensors/CPU0_Temp"
+        );
+    }
+
+    #[test]
+    fn test_newest_first() {
+        let data = include_str!("testdata/sel_entry.json");
+        let entry: LogEntry = serde_json::from_str(data).unwrap();
+        let entries: Vec<LogEntry> = [
+            ("1", "2024-02-29T10:00:00+00:00"),
+            ("2", "2024-02-29T12:00:00-01:00"),
+            ("3", "not a date"),
+            ("4", "2024-02-29T11:00:00+00:00"),
+        ]
+        .into_iter()
+        .map(|(id, created)| LogEntry {
+            id: id.to_string(),
+            created: created.to_string(),
+            ..entry.clone()
+        })
+        .collect();
+        let ids = |entries: Vec<LogEntry>| entries.into_iter().map(|e| e.id).collect::<Vec<_>>();
+
+        assert_eq!(
+            ids(newest_first(entries.clone(), None, None)),
+            ["2", "4", "1", "3"]
+        );
+        let since = chrono::DateTime::parse_from_rfc3339("2024-02-29T11:00:00Z")
+            .unwrap()
+            .to_utc();
+        assert_eq!(
+            ids(newest_first(entries.clone(), Some(since), None)),
+            ["2", "4", "3"]
+        );
+        assert_eq!(ids(newest_first(entries, Some(since), Some(1))), ["2"]);
+    }
+
+    #[test]
+    fn test_journal_lines() {
+        let data = include_str!("testdata/bmc_journal.json");
+        let journal: super::LogEntryCollection = serde_json::from_str(data).unwrap();
+        assert!(journal.members_next_link.is_some());
+        let lines = super::journal_lines(journal.members.clone(), None);
+        assert_eq!(
+            lines,
+            [
+                "2024-02-29T10:00:00+00:00 systemd[1]: Started Phosphor Log Manager.",
+                "2024-02-29T10:00:01+00:00 bmcweb[312]: Starting webserver on port 443",
+                "2024-02-29T10:00:05+00:00 ipmid[330]: Error in IPMI command",
+            ]
+        );
+        let since = chrono::DateTime::parse_from_rfc3339("2024-02-29T10:00:01Z")
+            .unwrap()
+            .to_utc();
+        assert_eq!(super::journal_lines(journal.members, Some(since)).len(), 2);
+    }
 }
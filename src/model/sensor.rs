@@ -45,6 +45,11 @@ pub struct Sensor {
     pub reading_range_max: Option<f64>,
     pub reading_range_min: Option<f64>,
     pub status: Option<ResourceStatus>,
+    pub sensor_reset_time: Option<String>, // cumulative sensors, e.g. EnergykWh
+    /// When the BMC took `reading`. Some BMCs call it Timestamp.
+    #[serde(alias = "Timestamp")]
+    pub reading_time: Option<String>,
+    pub oem: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -53,6 +58,9 @@ pub struct Sensors {
     #[serde(flatten)]
     pub odata: OData,
     pub members: Vec<ODataId>,
+    /// Set when the BMC paginates the collection; the next page holds more members
+    #[serde(rename = "Members@odata.nextLink")]
+    pub members_next_link: Option<String>,
     pub name: String,
     pub description: Option<String>,
 }
@@ -144,3 +152,21 @@ impl Display for PhysicalContext {
         std::fmt::Debug::fmt(&self, f)
     }
 }
+
+/// The short form of a Sensor embedded in other resources, e.g. EnvironmentMetrics
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "PascalCase")]
+pub struct SensorExcerpt {
+    pub reading: Option<f64>,
+    pub data_source_uri: Option<String>,
+}
+
+/// A cumulative energy counter. The counter restarts when sensor_reset_time changes,
+/// so compare it across polls before taking a delta.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EnergyReading {
+    pub chassis_id: String,
+    pub data_source_uri: Option<String>,
+    pub kwh: f64,
+    pub sensor_reset_time: Option<String>,
+}
@@ -58,4 +58,85 @@ impl SerialInterface {
             && self.connector_type.as_deref() == Some("RJ45")
             && self.pin_out.as_deref() == Some("Cyclades")
     }
+
+    /// The settings that can be changed with `Redfish::set_serial_interface_config`
+    pub fn config(&self) -> SerialInterfaceConfig {
+        SerialInterfaceConfig {
+            interface_enabled: self.interface_enabled,
+            bit_rate: self.bit_rate.clone(),
+            parity: self.parity.clone(),
+            data_bits: self.data_bits.clone(),
+            stop_bits: self.stop_bits.clone(),
+            flow_control: self.flow_control.clone(),
+        }
+    }
+}
+
+/// The configurable part of a SerialInterface. Only the fields that are Some are written by
+/// `Redfish::set_serial_interface_config`.
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub struct SerialInterfaceConfig {
+    pub interface_enabled: Option<bool>,
+    pub bit_rate: Option<String>,
+    pub parity: Option<String>,
+    pub data_bits: Option<String>,
+    pub stop_bits: Option<String>,
+    pub flow_control: Option<String>,
+}
+
+impl SerialInterfaceConfig {
+    /// Enabled, 115200 baud, 8 data bits, no parity, 1 stop bit, no flow control
+    pub fn baud_115200_8n1() -> Self {
+        SerialInterfaceConfig {
+            interface_enabled: Some(true),
+            bit_rate: Some("115200".to_string()),
+            parity: Some("None".to_string()),
+            data_bits: Some("8".to_string()),
+            stop_bits: Some("1".to_string()),
+            flow_control: Some("None".to_string()),
+        }
+    }
+
+    /// Does this config have every field `want` sets? Fields `want` leaves as None are ignored.
+    pub fn satisfies(&self, want: &SerialInterfaceConfig) -> bool {
+        fn field<T: PartialEq>(have: &Option<T>, want: &Option<T>) -> bool {
+            want.is_none() || have == want
+        }
+        field(&self.interface_enabled, &want.interface_enabled)
+            && field(&self.bit_rate, &want.bit_rate)
+            && field(&self.parity, &want.parity)
+            && field(&self.data_bits, &want.data_bits)
+            && field(&self.stop_bits, &want.stop_bits)
+            && field(&self.flow_control, &want.flow_control)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SerialInterface, SerialInterfaceConfig};
+
+    #[test]
+    fn test_serial_interface_config() {
+        let data = r#"{"Id": "1", "Name": "SerialInterface", "InterfaceEnabled": true,
+            "SignalType": "Rs232", "BitRate": "115200", "Parity": "None", "DataBits": "8",
+            "StopBits": "1", "FlowControl": "None", "ConnectorType": "RJ45", "PinOut": "Cyclades"}"#;
+        let interface: SerialInterface = serde_json::from_str(data).unwrap();
+        assert!(interface.is_supermicro_default());
+        let config = interface.config();
+        assert!(config.satisfies(&SerialInterfaceConfig::baud_115200_8n1()));
+
+        let want = SerialInterfaceConfig {
+            bit_rate: Some("9600".to_string()),
+            ..Default::default()
+        };
+        assert!(!config.satisfies(&want));
+        assert!(config.satisfies(&SerialInterfaceConfig::default()));
+        // Only the fields that are set are PATCHed
+        assert_eq!(
+            serde_json::to_string(&want).unwrap(),
+            r#"{"BitRate":"9600"}"#
+        );
+    }
 }
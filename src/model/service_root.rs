@@ -52,8 +52,61 @@ pub struct ServiceRoot {
     pub managers: Option<ODataId>,
     pub session_service: Option<ODataId>,
     pub systems: Option<ODataId>,
+    /// The TaskService
     pub tasks: Option<ODataId>,
     pub telemetry_service: Option<ODataId>,
+    pub protocol_features_supported: Option<ProtocolFeaturesSupported>,
+}
+
+/// Optional query parameters the service implements
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct ProtocolFeaturesSupported {
+    pub filter_query: Option<bool>,
+    pub only_member_query: Option<bool>,
+    pub select_query: Option<bool>,
+    pub top_skip_query: Option<bool>,
+}
+
+/// The top-level collections and services a ServiceRoot links to, see `ServiceRoot::link`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ServiceRootLink {
+    Systems,
+    Chassis,
+    Managers,
+    UpdateService,
+    AccountService,
+    SessionService,
+    EventService,
+    TelemetryService,
+    CertificateService,
+    TaskService,
+    Fabrics,
+}
+
+impl ServiceRootLink {
+    /// Where the DMTF mockups put it, relative to /redfish/v1
+    pub fn canonical_path(&self) -> &'static str {
+        match self {
+            ServiceRootLink::Systems => "Systems",
+            ServiceRootLink::Chassis => "Chassis",
+            ServiceRootLink::Managers => "Managers",
+            ServiceRootLink::UpdateService => "UpdateService",
+            ServiceRootLink::AccountService => "AccountService",
+            ServiceRootLink::SessionService => "SessionService",
+            ServiceRootLink::EventService => "EventService",
+            ServiceRootLink::TelemetryService => "TelemetryService",
+            ServiceRootLink::CertificateService => "CertificateService",
+            ServiceRootLink::TaskService => "TaskService",
+            ServiceRootLink::Fabrics => "Fabrics",
+        }
+    }
+}
+
+impl fmt::Display for ServiceRootLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
 }
 
 #[allow(clippy::upper_case_acronyms)]
@@ -89,6 +142,44 @@ impl ServiceRoot {
         })
     }
 
+    /// The URI the service root gives for `link`, None if it doesn't list it
+    pub fn link(&self, link: ServiceRootLink) -> Option<ODataId> {
+        match link {
+            ServiceRootLink::Systems => self.systems.clone(),
+            ServiceRootLink::Chassis => self.chassis.clone(),
+            ServiceRootLink::Managers => self.managers.clone(),
+            ServiceRootLink::UpdateService => self
+                .update_service
+                .as_ref()
+                .and_then(|u| u.get("@odata.id"))
+                .and_then(|id| id.as_str())
+                .map(|id| ODataId::from(id.to_string())),
+            ServiceRootLink::AccountService => self.account_service.clone(),
+            ServiceRootLink::SessionService => self.session_service.clone(),
+            ServiceRootLink::EventService => self.event_service.clone(),
+            ServiceRootLink::TelemetryService => self.telemetry_service.clone(),
+            ServiceRootLink::CertificateService => self.certificate_service.clone(),
+            ServiceRootLink::TaskService => self.tasks.clone(),
+            ServiceRootLink::Fabrics => self.fabrics.clone(),
+        }
+    }
+
+    /// `link` as a path for the client, relative to /redfish/v1. Falls back to the canonical
+    /// path when the service root doesn't list it.
+    pub fn link_path(&self, link: ServiceRootLink) -> String {
+        self.link(link)
+            .map(|id| id.relative_path())
+            .unwrap_or_else(|| link.canonical_path().to_string())
+    }
+
+    /// Whether the service honours `$filter`
+    pub fn supports_filter_query(&self) -> bool {
+        self.protocol_features_supported
+            .as_ref()
+            .and_then(|p| p.filter_query)
+            .unwrap_or(false)
+    }
+
     pub fn vendor(&self) -> Option<RedfishVendor> {
         let v = self.vendor_string()?;
         Some(match v.to_lowercase().as_str() {
@@ -110,7 +201,7 @@ impl ServiceRoot {
 
 #[cfg(test)]
 mod test {
-    use crate::model::service_root::RedfishVendor;
+    use crate::model::service_root::{RedfishVendor, ServiceRootLink};
 
     #[test]
     fn test_supermicro_service_root() {
@@ -118,4 +209,22 @@ mod test {
         let result: super::ServiceRoot = serde_json::from_str(data).unwrap();
         assert_eq!(result.vendor().unwrap(), RedfishVendor::Supermicro);
     }
+
+    #[test]
+    fn test_service_root_links() {
+        let data = include_str!("testdata/supermicro_service_root.json");
+        let root: super::ServiceRoot = serde_json::from_str(data).unwrap();
+        assert_eq!(
+            root.link(ServiceRootLink::TaskService).unwrap().odata_id,
+            "/redfish/v1/TaskService"
+        );
+        assert_eq!(
+            root.link_path(ServiceRootLink::UpdateService),
+            "UpdateService"
+        );
+        assert_eq!(root.link_path(ServiceRootLink::Systems), "Systems");
+        // Not listed
+        assert!(root.link(ServiceRootLink::Fabrics).is_none());
+        assert_eq!(root.link_path(ServiceRootLink::Fabrics), "Fabrics");
+    }
 }
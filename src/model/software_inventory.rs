@@ -22,7 +22,7 @@
  */
 use serde::{Deserialize, Serialize};
 
-use super::ODataLinks;
+use super::{ODataId, ODataLinks};
 
 /// http://redfish.dmtf.org/schemas/v1/SoftwareInventory.v1_9_0.json#/definitions/SoftwareInventory
 /// The SoftwareInventory schema contains an inventory of software components.
@@ -34,6 +34,145 @@ pub struct SoftwareInventory {
     pub odata: ODataLinks,
     pub description: Option<String>,
     pub id: String,
+    pub name: Option<String>,
     pub version: Option<String>,
     pub release_date: Option<String>,
+    /// Whether the BMC will accept an update for this component
+    pub updateable: Option<bool>,
+    pub software_id: Option<String>,
+    pub lowest_supported_version: Option<String>,
+    pub manufacturer: Option<String>,
+    pub oem: Option<serde_json::Value>,
+}
+
+impl SoftwareInventory {
+    /// The component this is firmware for, from the id or, failing that, the name
+    pub fn component_type(&self) -> super::update_service::ComponentType {
+        use super::update_service::ComponentType;
+        match ComponentType::from_inventory_id(&self.id) {
+            ComponentType::Unknown => self
+                .name
+                .as_deref()
+                .map(ComponentType::from_inventory_id)
+                .unwrap_or(ComponentType::Unknown),
+            component => component,
+        }
+    }
+
+    /// OEM URI serving the currently running image for backup, if the BMC exposes one.
+    pub fn image_download_uri(&self) -> Option<&str> {
+        const KEYS: [&str; 3] = ["ImageURI", "ImageUri", "DownloadURI"];
+        self.oem
+            .as_ref()?
+            .as_object()?
+            .values()
+            .filter_map(|vendor| vendor.as_object())
+            .find_map(|vendor| KEYS.iter().find_map(|k| vendor.get(*k)?.as_str()))
+    }
+}
+
+/// The firmware images a Manager lists in its Links (Manager.v1_6+): every bank, and the one
+/// that boots on the next reset.
+#[derive(Debug, Default, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct SoftwareImageLinks {
+    pub active_software_image: Option<ODataId>,
+    #[serde(default)]
+    pub software_images: Vec<ODataId>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct ManagerSoftwareImages {
+    #[serde(default)]
+    pub links: SoftwareImageLinks,
+}
+
+/// One firmware bank of a dual-bank component, see `Redfish::get_firmware_slots`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct FirmwareSlot {
+    /// SoftwareInventory id, what `activate_firmware_slot` takes
+    pub id: String,
+    pub version: Option<String>,
+    /// Boots on the next reset of the component
+    pub active: bool,
+}
+
+impl FirmwareSlot {
+    pub fn from_images(images: Vec<SoftwareInventory>, active: Option<&ODataId>) -> Vec<Self> {
+        let active = active.map(|a| a.odata_id.trim_end_matches('/'));
+        images
+            .into_iter()
+            .map(|image| FirmwareSlot {
+                active: active == Some(image.odata.odata_id.trim_end_matches('/')),
+                id: image.id,
+                version: image.version,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_image_download_uri() {
+        let data = r##"{
+            "@odata.id": "/redfish/v1/UpdateService/FirmwareInventory/BMC_Firmware",
+            "@odata.type": "#SoftwareInventory.v1_4_0.SoftwareInventory",
+            "Id": "BMC_Firmware",
+            "Version": "24.10-1",
+            "Oem": {"Nvidia": {"ImageURI": "/redfish/v1/UpdateService/FirmwareInventory/BMC_Firmware/Image"}}
+        }"##;
+        let result: super::SoftwareInventory = serde_json::from_str(data).unwrap();
+        assert_eq!(
+            result.image_download_uri(),
+            Some("/redfish/v1/UpdateService/FirmwareInventory/BMC_Firmware/Image")
+        );
+        let data = r##"{
+            "@odata.id": "/redfish/v1/UpdateService/FirmwareInventory/BIOS",
+            "@odata.type": "#SoftwareInventory.v1_4_0.SoftwareInventory",
+            "Id": "BIOS",
+            "Version": "1.2.3"
+        }"##;
+        let result: super::SoftwareInventory = serde_json::from_str(data).unwrap();
+        assert_eq!(result.image_download_uri(), None);
+    }
+
+    #[test]
+    fn test_firmware_slots() {
+        let manager: super::ManagerSoftwareImages = serde_json::from_str(
+            r##"{
+            "@odata.id": "/redfish/v1/Managers/bmc",
+            "Links": {
+                "ActiveSoftwareImage": {"@odata.id": "/redfish/v1/UpdateService/FirmwareInventory/b1a2c3"},
+                "SoftwareImages": [
+                    {"@odata.id": "/redfish/v1/UpdateService/FirmwareInventory/b1a2c3"},
+                    {"@odata.id": "/redfish/v1/UpdateService/FirmwareInventory/d4e5f6"}
+                ]
+            }
+        }"##,
+        )
+        .unwrap();
+        let image = |id: &str, version: &str| -> super::SoftwareInventory {
+            serde_json::from_value(serde_json::json!({
+                "@odata.id": format!("/redfish/v1/UpdateService/FirmwareInventory/{id}"),
+                "@odata.type": "#SoftwareInventory.v1_4_0.SoftwareInventory",
+                "Id": id,
+                "Version": version,
+            }))
+            .unwrap()
+        };
+        let slots = super::FirmwareSlot::from_images(
+            vec![image("b1a2c3", "24.10-1"), image("d4e5f6", "24.07-3")],
+            manager.links.active_software_image.as_ref(),
+        );
+        assert_eq!(manager.links.software_images.len(), 2);
+        assert_eq!(
+            slots
+                .iter()
+                .map(|s| (s.id.as_str(), s.active))
+                .collect::<Vec<_>>(),
+            [("b1a2c3", true), ("d4e5f6", false)]
+        );
+    }
 }
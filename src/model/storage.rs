@@ -436,8 +436,11 @@ pub struct Drives {
     pub oem: Option<Oem>,
     pub id: Option<String>,
     pub manufacturer: Option<String>,
+    pub media_type: Option<String>, // HDD, SSD or SMR
     pub model: Option<String>,
     pub name: Option<String>,
+    pub part_number: Option<String>,
+    pub protocol: Option<String>, // NVMe, SATA, SAS, etc
     pub revision: Option<String>,
     pub serial_number: Option<String>,
     pub status: Option<ResourceStatus>,
@@ -528,6 +531,17 @@ mod test {
         println!("result: {result:#?}");
     }
 
+    #[test]
+    fn test_drive_failure_predicted() {
+        let test_data = include_str!("testdata/drive_nvme.json");
+        let result: super::Drives = serde_json::from_str(test_data).unwrap();
+        assert_eq!(result.failure_predicted, Some(true));
+        assert_eq!(result.predicted_media_life_left_percent, Some(3.0));
+        assert_eq!(result.media_type.as_deref(), Some("SSD"));
+        assert_eq!(result.protocol.as_deref(), Some("NVMe"));
+        assert_eq!(result.capacity_bytes, Some(3840755982336));
+    }
+
     #[test]
     fn test_storage_enclosures_parser() {
         let test_data = include_str!("testdata/storage-enclosures.json");
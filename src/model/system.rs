@@ -26,7 +26,9 @@ use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use serde_with::DefaultOnNull;
 
+use super::resource::IsResource;
 use super::{boot::Boot, oem::SystemExtensions, OData, ODataId, ODataLinks, RedfishSettings};
+use crate::RedfishError;
 
 const MELLANOX_VENDOR_ID_HEX: &str = "0X15B3";
 const MELLANOX_VENDOR_ID_DEC: &str = "5555";
@@ -43,7 +45,8 @@ const MELLANOX_DPU_DEVICE_IDS_DEC: [&str; 5] = ["41695", "41689", "41692", "4168
 pub enum SystemPowerControl {
     /// Power on a machine
     On,
-    /// Graceful host shutdown
+    /// Graceful host shutdown. Asks the OS to shut down via ACPI, so it needs a running OS
+    /// that honours the request. If the OS ignores it the machine stays on.
     GracefulShutdown,
     /// Forcefully powers a machine off
     ForceOff,
@@ -63,6 +66,30 @@ pub enum SystemPowerControl {
     // Lenovo also has: ForceOn and Nmi
 }
 
+impl SystemPowerControl {
+    /// The ResetType of the standard ComputerSystem.Reset action for this control.
+    /// None for ACPowercycle, which is always an OEM action.
+    pub fn reset_type(&self) -> Option<&'static str> {
+        match self {
+            SystemPowerControl::On => Some("On"),
+            SystemPowerControl::GracefulShutdown => Some("GracefulShutdown"),
+            SystemPowerControl::ForceOff => Some("ForceOff"),
+            SystemPowerControl::GracefulRestart => Some("GracefulRestart"),
+            SystemPowerControl::ForceRestart => Some("ForceRestart"),
+            SystemPowerControl::PowerCycle => Some("PowerCycle"),
+            SystemPowerControl::ACPowercycle => None,
+        }
+    }
+
+    /// True if the action needs the OS to cooperate
+    pub fn is_graceful(&self) -> bool {
+        matches!(
+            self,
+            SystemPowerControl::GracefulShutdown | SystemPowerControl::GracefulRestart
+        )
+    }
+}
+
 impl fmt::Display for SystemPowerControl {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Debug::fmt(self, f)
@@ -78,6 +105,34 @@ pub enum PowerState {
     PoweringOn,
     Paused,
     Reset,
+    /// A value outside the schema, kept so the rest of the resource still parses
+    #[serde(other)]
+    Unknown,
+}
+
+impl PowerState {
+    /// PoweringOn or PoweringOff: the system is on its way to another state,
+    /// so poll again rather than acting on it.
+    pub fn is_transitioning(&self) -> bool {
+        matches!(self, PowerState::PoweringOn | PowerState::PoweringOff)
+    }
+}
+
+/// What the system does when power comes back after an outage.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PowerRestorePolicy {
+    AlwaysOn,
+    AlwaysOff,
+    LastState,
+    /// A value outside the schema. Not accepted by set_power_restore_policy.
+    #[serde(other)]
+    Unknown,
+}
+
+impl fmt::Display for PowerRestorePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
 }
 
 impl fmt::Display for PowerState {
@@ -168,11 +223,25 @@ pub struct BootProgress {
     pub oem_last_state: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SystemActions {
+    #[serde(rename = "#ComputerSystem.Reset")]
+    pub computer_system_reset: Option<SystemResetAction>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SystemResetAction {
+    pub target: Option<String>,
+    #[serde(rename = "ResetType@Redfish.AllowableValues", default)]
+    pub reset_type_allowable_values: Vec<String>,
+}
+
 #[derive(Debug, Serialize, Default, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct ComputerSystem {
     #[serde(flatten)]
     pub odata: OData,
+    pub actions: Option<SystemActions>,
     #[serde(rename = "@Redfish.Settings")]
     pub redfish_settings: Option<RedfishSettings>,
     pub asset_tag: Option<String>,
@@ -200,6 +269,8 @@ pub struct ComputerSystem {
     pub serial_console: Option<SerialConsole>, // Newer Redfish impls, inc Supermicro
     pub links: Option<ComputerSystemLinks>,
     pub boot_progress: Option<BootProgress>,
+    pub power_restore_policy: Option<PowerRestorePolicy>,
+    pub power_on_delay_seconds: Option<f64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
@@ -231,6 +302,64 @@ pub struct SerialConsoleConnectionType {
     pub console_entry_command: Option<String>, // SSH only
 }
 
+/// How to connect to the host's serial console, see `Redfish::get_serial_console_info`.
+/// Connect to the BMC's own address.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct SerialConsoleInfo {
+    pub service_enabled: bool,
+    /// e.g. "SSH", "IPMI"
+    pub connect_types_supported: Vec<String>,
+    pub max_concurrent_sessions: Option<usize>,
+    /// Only BMCs with the newer ComputerSystem SerialConsole give these details
+    #[serde(rename = "SSH")]
+    pub ssh: Option<SerialConsoleConnectionType>,
+    #[serde(rename = "IPMI")]
+    pub ipmi: Option<SerialConsoleConnectionType>,
+}
+
+impl SerialConsoleInfo {
+    /// Combine the Manager's SerialConsole, which says which connection types there are, and
+    /// the ComputerSystem's, which has the per connection type details. Either can be missing.
+    pub fn new(
+        manager: Option<super::manager::Commandshell>,
+        system: Option<SerialConsole>,
+    ) -> Self {
+        let mut info = SerialConsoleInfo::default();
+        if let Some(m) = manager {
+            info.service_enabled = m.service_enabled;
+            info.connect_types_supported = m.connect_types_supported;
+            info.max_concurrent_sessions = usize::try_from(m.max_concurrent_sessions).ok();
+        }
+        if let Some(s) = system {
+            for (name, conn) in [("SSH", &s.ssh), ("IPMI", &s.ipmi)] {
+                if conn.service_enabled && !info.connect_types_supported.iter().any(|t| t == name) {
+                    info.connect_types_supported.push(name.to_string());
+                }
+            }
+            info.service_enabled |= s.ssh.service_enabled || s.ipmi.service_enabled;
+            info.max_concurrent_sessions = Some(s.max_concurrent_sessions);
+            info.ssh = Some(s.ssh);
+            info.ipmi = Some(s.ipmi);
+        }
+        info
+    }
+
+    /// The port to SSH to for the console, None if the BMC doesn't offer SSH.
+    /// 22 if it does but doesn't say which port.
+    pub fn ssh_port(&self) -> Option<usize> {
+        if !self.connect_types_supported.iter().any(|t| t == "SSH") {
+            return None;
+        }
+        Some(self.ssh.as_ref().and_then(|ssh| ssh.port).unwrap_or(22))
+    }
+
+    /// What to type once logged in over SSH to reach the host's console, if anything
+    pub fn ssh_console_entry_command(&self) -> Option<&str> {
+        self.ssh.as_ref()?.console_entry_command.as_deref()
+    }
+}
+
 #[serde_as]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
@@ -258,6 +387,58 @@ pub struct BootOption {
     pub uefi_device_path: Option<String>,
 }
 
+impl ComputerSystem {
+    /// SerialConsole, or why there isn't one: it was added in ComputerSystem v1_13, before
+    /// that BMCs had it in the Manager.
+    pub fn require_serial_console(&self) -> Result<&SerialConsole, RedfishError> {
+        if let Some(serial_console) = &self.serial_console {
+            return Ok(serial_console);
+        }
+        self.require_schema_version(1, 13, "SerialConsole")?;
+        Err(RedfishError::MissingKey {
+            key: "SerialConsole".to_string(),
+            url: self.odata.odata_id.clone(),
+        })
+    }
+}
+
+impl BootOption {
+    /// UefiDevicePath parsed, empty if the BMC didn't give one
+    pub fn device_path(&self) -> super::uefi_device_path::UefiDevicePath {
+        super::uefi_device_path::parse_uefi_device_path(
+            self.uefi_device_path.as_deref().unwrap_or_default(),
+        )
+    }
+
+    /// Whether this boot option is for the NIC with this MAC address. Separators and case don't
+    /// matter on either side, and the MAC can be in the DisplayName or the UefiDevicePath.
+    /// DisplayName formats seen so far:
+    /// `UEFI HTTPv4 (MAC:A088C2EA84D0)`
+    /// `UEFI HTTP IPv4 Mellanox Network Adapter - A0:88:C2:EA:84:D0(MAC:A088C2EA84D0)`
+    /// `UEFI HTTP IPv4 Nvidia Network Adapter - c4-70-bd-f0-40-aa`
+    pub fn matches_mac(&self, mac: &str) -> bool {
+        let mac = super::uefi_device_path::normalize_mac(mac);
+        if mac.is_empty() {
+            return false;
+        }
+        if self.device_path().mac() == Some(mac.as_str()) {
+            return true;
+        }
+        super::uefi_device_path::macs_in(&self.display_name).contains(&mac)
+    }
+}
+
+/// Body for creating a boot option with `create_boot_option`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct NewBootOption {
+    pub boot_option_reference: String,
+    pub display_name: String,
+    /// e.g. `PciRoot(0x0)/Pci(0x1,0x0)/MAC(...)/IPv4(...)/Uri(http://10.0.0.1/ipxe.efi)`
+    pub uefi_device_path: String,
+    pub boot_option_enabled: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct PCIeDevice {
@@ -354,6 +535,139 @@ mod test {
         BootSourceOverrideEnabled, BootSourceOverrideMode, BootSourceOverrideTarget,
     };
 
+    #[test]
+    fn test_boot_option_matches_mac() {
+        let boot_option = |display_name: &str, uefi_device_path: Option<&str>| super::BootOption {
+            odata: crate::model::ODataLinks {
+                odata_context: None,
+                odata_id: "/redfish/v1/Systems/System_0/BootOptions/Boot0001".to_string(),
+                odata_type: "#BootOption.v1_0_4.BootOption".to_string(),
+                odata_etag: None,
+                links: None,
+            },
+            alias: None,
+            description: None,
+            boot_option_enabled: None,
+            boot_option_reference: "Boot0001".to_string(),
+            display_name: display_name.to_string(),
+            id: "Boot0001".to_string(),
+            name: "Boot0001".to_string(),
+            uefi_device_path: uefi_device_path.map(str::to_string),
+        };
+
+        let colons = boot_option("UEFI HTTPv4 (MAC:A088C2EA84D0)", None);
+        assert!(colons.matches_mac("A0:88:C2:EA:84:D0"));
+        assert!(colons.matches_mac("a0:88:c2:ea:84:d0"));
+        assert!(colons.matches_mac("A0-88-C2-EA-84-D0"));
+        assert!(colons.matches_mac("a088c2ea84d0"));
+        assert!(!colons.matches_mac("A0:88:C2:EA:84:D1"));
+        assert!(!colons.matches_mac(""));
+
+        let mellanox = boot_option(
+            "UEFI HTTP IPv4 Mellanox Network Adapter - A0:88:C2:EA:84:D0(MAC:A088C2EA84D0)",
+            None,
+        );
+        assert!(mellanox.matches_mac("a0-88-c2-ea-84-d0"));
+
+        let dashes = boot_option(
+            "UEFI HTTP IPv4 Nvidia Network Adapter - c4-70-bd-f0-40-aa",
+            None,
+        );
+        assert!(dashes.matches_mac("C4:70:BD:F0:40:AA"));
+        assert!(dashes.matches_mac("c470bdf040aa"));
+
+        let dotted = boot_option("UEFI HTTPv4 c470.bdf0.40aa", None);
+        assert!(dotted.matches_mac("C4:70:BD:F0:40:AA"));
+
+        // Nothing in the DisplayName, only in the UefiDevicePath
+        let device_path = boot_option(
+            "UEFI HTTPv4",
+            Some("PciRoot(0x0)/Pci(0x1,0x0)/MAC(c470bdf040aa,0x1)/IPv4(0.0.0.0)/Uri()"),
+        );
+        assert!(device_path.matches_mac("C4:70:BD:F0:40:AA"));
+        assert!(!device_path.matches_mac("A0:88:C2:EA:84:D0"));
+
+        // Part of a longer run of hex digits, or only part of the MAC, is no match
+        let longer = boot_option("UEFI HTTPv4 (MAC:A088C2EA84D0FF)", None);
+        assert!(!longer.matches_mac("A0:88:C2:EA:84:D0"));
+        assert!(!colons.matches_mac("88:C2:EA:84:D0"));
+    }
+
+    #[test]
+    fn test_serial_console_info() {
+        // GH200: Manager says which types, System has the SSH port
+        let manager: crate::model::manager::Commandshell = serde_json::from_str(
+            r#"{"ConnectTypesSupported": ["IPMI", "SSH"], "MaxConcurrentSessions": 15, "ServiceEnabled": true}"#,
+        )
+        .unwrap();
+        let system: super::SerialConsole = serde_json::from_str(
+            r#"{"IPMI": {"ServiceEnabled": true}, "MaxConcurrentSessions": 15,
+                "SSH": {"HotKeySequenceDisplay": "Press ~. to exit console", "Port": 2200, "ServiceEnabled": true}}"#,
+        )
+        .unwrap();
+        let info = super::SerialConsoleInfo::new(Some(manager), Some(system));
+        assert!(info.service_enabled);
+        assert_eq!(info.ssh_port(), Some(2200));
+        assert_eq!(info.ssh_console_entry_command(), None);
+
+        // Supermicro: only the System's
+        let system: super::SerialConsole = serde_json::from_str(
+            r#"{"MaxConcurrentSessions": 1,
+                "SSH": {"ServiceEnabled": true, "Port": 22, "ConsoleEntryCommand": "cd system1/sol1; start"},
+                "IPMI": {"ServiceEnabled": true, "Port": 623}}"#,
+        )
+        .unwrap();
+        let info = super::SerialConsoleInfo::new(None, Some(system));
+        assert_eq!(info.connect_types_supported, vec!["SSH", "IPMI"]);
+        assert_eq!(info.ssh_port(), Some(22));
+        assert_eq!(
+            info.ssh_console_entry_command(),
+            Some("cd system1/sol1; start")
+        );
+
+        // Dell: only the Manager's, no SSH details
+        let manager: crate::model::manager::Commandshell = serde_json::from_str(
+            r#"{"ConnectTypesSupported": [], "MaxConcurrentSessions": 0, "ServiceEnabled": false}"#,
+        )
+        .unwrap();
+        let info = super::SerialConsoleInfo::new(Some(manager), None);
+        assert!(!info.service_enabled);
+        assert_eq!(info.ssh_port(), None);
+    }
+
+    #[test]
+    fn test_schema_version() {
+        use crate::model::resource::{IsResource, SchemaVersion};
+
+        let v = SchemaVersion::parse("#ComputerSystem.v1_20_1.ComputerSystem").unwrap();
+        assert_eq!(v.resource, "ComputerSystem");
+        assert_eq!((v.major, v.minor, v.errata), (1, 20, 1));
+        assert!(v.at_least(1, 13) && v.at_least(1, 20) && !v.at_least(1, 21));
+        assert_eq!(v.to_string(), "ComputerSystem v1_20_1");
+        assert_eq!(
+            SchemaVersion::parse("#ComputerSystemCollection.ComputerSystemCollection"),
+            None
+        );
+        assert_eq!(SchemaVersion::parse(""), None);
+
+        // Dell's v1_10 predates SerialConsole in ComputerSystem
+        let data = include_str!("testdata/system_dell.json");
+        let dell: super::ComputerSystem = serde_json::from_str(data).unwrap();
+        assert_eq!(dell.schema_version().unwrap().minor, 10);
+        let err = dell.require_serial_console().unwrap_err();
+        assert!(
+            matches!(err, crate::RedfishError::SchemaVersionTooOld { .. }),
+            "{err}"
+        );
+        assert!(err
+            .to_string()
+            .starts_with("SerialConsole requires ComputerSystem v1_13 or later"));
+
+        let data = include_str!("testdata/system_bluefield_boot_valid.json");
+        let bluefield: super::ComputerSystem = serde_json::from_str(data).unwrap();
+        assert!(bluefield.require_serial_console().is_ok());
+    }
+
     #[test]
     fn test_systems_parser() {
         let data = include_str!("testdata/systems.json");
@@ -368,6 +682,14 @@ mod test {
         let result: super::ComputerSystem = serde_json::from_str(data).unwrap();
         assert_eq!(result.power_state, crate::PowerState::On);
         assert_eq!(result.processor_summary.unwrap().count, Some(2));
+        let reset = result.actions.unwrap().computer_system_reset.unwrap();
+        assert!(reset
+            .reset_type_allowable_values
+            .contains(&"GracefulShutdown".to_string()));
+        assert!(result
+            .boot
+            .boot_source_override_target_allowable_values
+            .contains(&crate::model::boot::BootSourceOverrideTarget::Pxe));
     }
 
     #[test]
@@ -389,6 +711,10 @@ mod test {
             result.boot.boot_source_override_target,
             Some(BootSourceOverrideTarget::None)
         );
+        assert_eq!(
+            result.power_restore_policy,
+            Some(super::PowerRestorePolicy::AlwaysOn)
+        );
     }
 
     #[test]
@@ -423,6 +749,15 @@ mod test {
         assert_eq!(result.processor_summary.unwrap().count, Some(2));
     }
 
+    #[test]
+    fn test_power_state_transitional() {
+        let parse = |v: &str| serde_json::from_str::<super::PowerState>(v).unwrap();
+        assert_eq!(parse(r#""PoweringOn""#), super::PowerState::PoweringOn);
+        assert!(parse(r#""PoweringOff""#).is_transitioning());
+        assert!(!parse(r#""On""#).is_transitioning());
+        assert_eq!(parse(r#""Standby""#), super::PowerState::Unknown);
+    }
+
     #[test]
     fn test_boot_options() {
         let data = include_str!("testdata/boot_options.json");
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use super::ODataLinks;
+use super::{ODataLinks, ResourceHealth};
 
 /// http://redfish.dmtf.org/schemas/v1/Task.v1_7_1.json#/definitions/Task
 /// The Task schema contains information about a task that the Redfish task service schedules or executes.
@@ -15,26 +15,124 @@ pub struct Task {
     pub messages: Vec<super::Message>,
     pub name: Option<String>,
     pub task_state: Option<TaskState>,
-    pub task_status: Option<String>,
+    pub task_status: Option<ResourceHealth>,
     pub task_monitor: Option<String>,
     pub percent_complete: Option<u32>,
+    pub payload: Option<TaskPayload>,
 }
 
+impl Task {
+    /// Whether this task is running a firmware update, judging by the action that started it,
+    /// its Update registry messages or its name.
+    pub fn is_update_task(&self) -> bool {
+        let targets_update_service = self
+            .payload
+            .as_ref()
+            .and_then(|p| p.target_uri.as_deref())
+            .is_some_and(|uri| uri.contains("UpdateService"));
+        let has_update_messages = self
+            .messages
+            .iter()
+            .any(|m| m.message_id.starts_with("Update."));
+        let named_update = self.name.as_deref().is_some_and(|name| {
+            let name = name.to_lowercase();
+            name.contains("update") || name.contains("firmware")
+        });
+        targets_update_service || has_update_messages || named_update
+    }
+
+    /// The task has finished and won't change state again
+    pub fn is_terminal(&self) -> bool {
+        self.task_state.is_some_and(|s| s.is_terminal())
+    }
+
+    /// Completed without a Critical TaskStatus. Some BMCs mark a failed task Completed and
+    /// report the failure only in TaskStatus.
+    pub fn is_success(&self) -> bool {
+        self.task_state.is_some_and(|s| s.is_success())
+            && !matches!(self.task_status, Some(ResourceHealth::Critical))
+    }
+
+    /// Finished, but not successfully
+    pub fn is_failure(&self) -> bool {
+        self.is_terminal() && !self.is_success()
+    }
+}
+
+/// http://redfish.dmtf.org/schemas/v1/TaskService.v1_2_1.json
+/// Settings of the service that runs tasks
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct TaskService {
+    #[serde(flatten)]
+    pub odata: ODataLinks,
+    pub id: Option<String>,
+    pub service_enabled: Option<bool>,
+    /// Manual, or Oldest: finished tasks are deleted to make room for new ones
+    pub completed_task_over_write_policy: Option<String>,
+    pub life_cycle_event_on_task_state_change: Option<bool>,
+    /// Finished tasks are deleted this long after they finish
+    pub task_auto_delete_timeout_minutes: Option<u64>,
+    pub tasks: Option<super::ODataId>,
+}
+
+impl TaskService {
+    /// Whether a finished task may disappear before anyone reads its outcome
+    pub fn auto_deletes_tasks(&self) -> bool {
+        self.task_auto_delete_timeout_minutes.is_some()
+            || self.completed_task_over_write_policy.as_deref() == Some("Oldest")
+    }
+}
+
+/// The request that created the task
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct TaskPayload {
+    pub http_operation: Option<String>,
+    #[serde(rename = "TargetUri")]
+    pub target_uri: Option<String>,
+}
+
+/// http://redfish.dmtf.org/schemas/v1/Task.v1_7_1.json#/definitions/TaskState
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 pub enum TaskState {
     New,
     Starting,
     Running,
     Suspended,
     Interrupted,
+    // Dell JobState values, for jobs read through the same enum
+    #[serde(alias = "Scheduled")]
     Pending,
     Stopping,
     Completed,
     Killed,
+    #[serde(alias = "Failed", alias = "CompletedWithErrors")]
     Exception,
     Service,
     Cancelling,
     Cancelled,
+    /// A state outside the schema. Not terminal, so pollers keep waiting until their timeout.
+    #[serde(other)]
+    Unknown,
+}
+
+impl TaskState {
+    /// Completed, Killed, Exception or Cancelled: the task won't change state again
+    pub fn is_terminal(&self) -> bool {
+        self.is_success() || self.is_failure()
+    }
+
+    pub fn is_success(&self) -> bool {
+        matches!(self, TaskState::Completed)
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            TaskState::Killed | TaskState::Exception | TaskState::Cancelled
+        )
+    }
 }
 
 impl std::fmt::Display for TaskState {
@@ -42,3 +140,64 @@ impl std::fmt::Display for TaskState {
         std::fmt::Debug::fmt(self, f)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{Task, TaskService, TaskState};
+
+    use crate::model::ResourceHealth;
+
+    #[test]
+    fn test_task_service_auto_delete() {
+        let data = include_str!("testdata/task_service.json");
+        let service: TaskService = serde_json::from_str(data).unwrap();
+        assert!(service.auto_deletes_tasks());
+
+        let manual: TaskService = serde_json::from_str(
+            r##"{"@odata.id": "/redfish/v1/TaskService", "@odata.type": "#TaskService.v1_2_0.TaskService", "CompletedTaskOverWritePolicy": "Manual"}"##,
+        )
+        .unwrap();
+        assert!(!manual.auto_deletes_tasks());
+    }
+
+    #[test]
+    fn test_is_update_task() {
+        let data = include_str!("testdata/task_update.json");
+        let mut task: Task = serde_json::from_str(data).unwrap();
+        assert!(task.is_update_task());
+        assert_eq!(
+            task.task_monitor.as_deref(),
+            Some("/redfish/v1/TaskService/Tasks/3/Monitor")
+        );
+
+        task.payload = None;
+        assert!(!task.is_update_task());
+    }
+
+    #[test]
+    fn test_task_state_predicates() {
+        let data = include_str!("testdata/task_update.json");
+        let mut task: Task = serde_json::from_str(data).unwrap();
+        assert_eq!(task.task_state, Some(TaskState::Running));
+        assert!(!task.is_terminal());
+
+        task.task_state = Some(TaskState::Completed);
+        assert!(task.is_success());
+        task.task_status = Some(ResourceHealth::Critical);
+        assert!(task.is_failure());
+
+        let state: TaskState = serde_json::from_str(r#""Paused""#).unwrap();
+        assert_eq!(state, TaskState::Unknown);
+        assert!(!state.is_terminal());
+        assert!(TaskState::Exception.is_failure());
+        assert!(!TaskState::Interrupted.is_terminal());
+
+        // An OEM health doesn't fail the poll, and isn't taken for Critical
+        let mut oem: serde_json::Value = serde_json::from_str(data).unwrap();
+        oem["TaskState"] = serde_json::json!("Completed");
+        oem["TaskStatus"] = serde_json::json!("Degraded");
+        let task: Task = serde_json::from_value(oem).unwrap();
+        assert!(matches!(task.task_status, Some(ResourceHealth::Unknown)));
+        assert!(task.is_success());
+    }
+}
@@ -115,6 +115,8 @@ pub struct Temperature {
     pub status: ResourceStatus,
     pub upper_threshold_critical: Option<f64>,
     pub upper_threshold_fatal: Option<f64>,
+    /// When the reading was taken, if the source Sensor says. Not in the legacy Thermal schema.
+    pub reading_time: Option<String>,
 }
 
 impl Default for Temperature {
@@ -129,6 +131,7 @@ impl Default for Temperature {
             status: Default::default(),
             upper_threshold_critical: None,
             upper_threshold_fatal: None,
+            reading_time: None,
         }
     }
 }
@@ -159,6 +162,7 @@ impl From<Sensor> for Temperature {
             status: sensor.status.unwrap_or_default(),
             upper_threshold_critical: None,
             upper_threshold_fatal: None,
+            reading_time: sensor.reading_time,
         }
     }
 }
@@ -186,6 +190,23 @@ pub struct LeakDetector {
     pub status: ResourceStatus,
 }
 
+/// Chassis/{id}/ThermalSubsystem, the links to the newer thermal resources
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct ThermalSubsystem {
+    pub fans: Option<ODataId>,
+    pub leak_detection: Option<ODataId>,
+    pub thermal_metrics: Option<ODataId>,
+    pub status: Option<ResourceStatus>,
+}
+
+/// ThermalSubsystem/LeakDetection
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct LeakDetection {
+    pub leak_detectors: Option<ODataId>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct Thermal {
@@ -242,4 +263,48 @@ mod test {
         let result_lenovo: super::Thermal = serde_json::from_str(test_data_lenovo).unwrap();
         println!("result: {result_lenovo:#?}");
     }
+
+    #[test]
+    fn test_thermal_subsystem_parser() {
+        let data = r##"{
+            "@odata.id": "/redfish/v1/Chassis/Chassis_0/ThermalSubsystem",
+            "@odata.type": "#ThermalSubsystem.v1_3_0.ThermalSubsystem",
+            "Id": "ThermalSubsystem",
+            "LeakDetection": {"@odata.id": "/redfish/v1/Chassis/Chassis_0/ThermalSubsystem/LeakDetection"},
+            "ThermalMetrics": {"@odata.id": "/redfish/v1/Chassis/Chassis_0/ThermalSubsystem/ThermalMetrics"},
+            "Status": {"Health": "OK", "State": "Enabled"}
+        }"##;
+        let result: super::ThermalSubsystem = serde_json::from_str(data).unwrap();
+        assert!(result.fans.is_none());
+        assert_eq!(
+            result.thermal_metrics.unwrap().relative_path(),
+            "Chassis/Chassis_0/ThermalSubsystem/ThermalMetrics"
+        );
+        assert_eq!(
+            result.leak_detection.unwrap().relative_path(),
+            "Chassis/Chassis_0/ThermalSubsystem/LeakDetection"
+        );
+    }
+    #[test]
+    fn test_temperature_from_sensor() {
+        let data = r##"{
+            "@odata.id": "/redfish/v1/Chassis/HGX_GPU_0/Sensors/HGX_GPU_0_TEMP_0",
+            "@odata.type": "#Sensor.v1_2_0.Sensor",
+            "Id": "HGX_GPU_0_TEMP_0",
+            "Name": "HGX GPU 0 TEMP 0",
+            "PhysicalContext": "GPU",
+            "Reading": 31.5,
+            "ReadingType": "Temperature",
+            "ReadingUnits": "Cel",
+            "Timestamp": "2024-02-29T10:00:03+00:00",
+            "Status": {"Health": "OK", "State": "Enabled"}
+        }"##;
+        let sensor: crate::model::sensor::Sensor = serde_json::from_str(data).unwrap();
+        let temp = super::Temperature::from(sensor);
+        assert_eq!(temp.reading_celsius, Some(31.5));
+        assert_eq!(
+            temp.reading_time.as_deref(),
+            Some("2024-02-29T10:00:03+00:00")
+        );
+    }
 }
@@ -0,0 +1,243 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2025 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: MIT
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a
+ * copy of this software and associated documentation files (the "Software"),
+ * to deal in the Software without restriction, including without limitation
+ * the rights to use, copy, modify, merge, publish, distribute, sublicense,
+ * and/or sell copies of the Software, and to permit persons to whom the
+ * Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL
+ * THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+ * FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+
+//! UEFI device paths as BootOption.UefiDevicePath shows them, in the UEFI spec's text form:
+//! `PciRoot(0x0)/Pci(0x1,0x0)/MAC(B83FD29099C4,0x1)/IPv4(0.0.0.0)/Uri()` and the like.
+
+/// One node of a UEFI device path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DevicePathNode {
+    /// `HD(partition,GPT|MBR,signature,start,size)`, a hard drive partition
+    HardDrive {
+        partition: String,
+        format: String,
+        signature: String,
+    },
+    /// `MAC(address,iftype)`. The address as 12 upper case hex digits without separators.
+    Mac { address: String },
+    /// `IPv4(...)`, network boot over IPv4
+    IPv4,
+    /// `IPv6(...)`, network boot over IPv6
+    IPv6,
+    /// `Uri(...)`, HTTP boot. Empty until DHCP supplies the URI.
+    Uri(String),
+    /// `\EFI\ubuntu\shimaa64.efi`, a file on the preceding partition
+    FilePath(String),
+    /// Any other node, such as `PciRoot(0x0)` or `Sata(0x0,0xFFFF,0x0)`
+    Other { kind: String, args: String },
+}
+
+/// A parsed UEFI device path, see `parse_uefi_device_path`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UefiDevicePath {
+    pub nodes: Vec<DevicePathNode>,
+}
+
+impl UefiDevicePath {
+    /// Boots from a disk partition
+    pub fn is_hard_drive(&self) -> bool {
+        self.nodes
+            .iter()
+            .any(|n| matches!(n, DevicePathNode::HardDrive { .. }))
+    }
+
+    /// Boots over the network by UEFI HTTP
+    pub fn is_http(&self) -> bool {
+        self.nodes
+            .iter()
+            .any(|n| matches!(n, DevicePathNode::Uri(_)))
+    }
+
+    /// Boots over the network by PXE, i.e. an IP node without a Uri
+    pub fn is_pxe(&self) -> bool {
+        !self.is_http() && (self.is_ipv4() || self.is_ipv6())
+    }
+
+    pub fn is_ipv4(&self) -> bool {
+        self.nodes.contains(&DevicePathNode::IPv4)
+    }
+
+    pub fn is_ipv6(&self) -> bool {
+        self.nodes.contains(&DevicePathNode::IPv6)
+    }
+
+    /// The MAC address of the NIC a network boot option uses, as 12 upper case hex digits
+    pub fn mac(&self) -> Option<&str> {
+        self.nodes.iter().find_map(|n| match n {
+            DevicePathNode::Mac { address } => Some(address.as_str()),
+            _ => None,
+        })
+    }
+}
+
+/// Parse the text form of a UEFI device path. Never fails: nodes it doesn't know are kept as
+/// `DevicePathNode::Other`. Node names are matched case-insensitively since BMCs vary.
+pub fn parse_uefi_device_path(path: &str) -> UefiDevicePath {
+    let nodes = split_nodes(path)
+        .into_iter()
+        .filter(|node| !node.is_empty())
+        .map(parse_node)
+        .collect();
+    UefiDevicePath { nodes }
+}
+
+/// A MAC address as 12 upper case hex digits, whatever the separators and case it came with:
+/// `a0:88:c2:ea:84:d0`, `A0-88-C2-EA-84-D0` and `A088C2EA84D0` are all `A088C2EA84D0`.
+pub fn normalize_mac(mac: &str) -> String {
+    mac.chars()
+        .filter(|c| c.is_ascii_hexdigit())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// The MAC addresses written in free text such as a boot option's DisplayName, normalized like
+/// `normalize_mac`. Recognized forms: 12 hex digits, six pairs separated by ':' or '-', and
+/// three groups of four separated by '.'. Hex digits running into a word don't count.
+pub fn macs_in(text: &str) -> Vec<String> {
+    let mut macs = Vec::new();
+    for run in text.split(|c: char| !(c.is_ascii_hexdigit() || matches!(c, ':' | '-' | '.'))) {
+        let groups: Vec<&str> = run.split([':', '-', '.']).collect();
+        for (i, group) in groups.iter().enumerate() {
+            if group.len() == 12 {
+                macs.push(normalize_mac(group));
+            }
+            for (count, len) in [(6, 2), (3, 4)] {
+                if let Some(window) = groups.get(i..i + count) {
+                    if window.iter().all(|g| g.len() == len) {
+                        macs.push(normalize_mac(&window.concat()));
+                    }
+                }
+            }
+        }
+    }
+    macs
+}
+
+// Split on the '/' between nodes, not the ones inside a node's arguments such as a Uri's
+fn split_nodes(path: &str) -> Vec<&str> {
+    let mut nodes = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in path.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            '/' if depth == 0 => {
+                nodes.push(path[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    nodes.push(path[start..].trim());
+    nodes
+}
+
+fn parse_node(node: &str) -> DevicePathNode {
+    let Some((kind, rest)) = node.split_once('(') else {
+        return DevicePathNode::FilePath(node.to_string());
+    };
+    let args = rest.strip_suffix(')').unwrap_or(rest);
+    let arg = |i: usize| {
+        args.split(',')
+            .nth(i)
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    };
+    match kind.trim().to_ascii_uppercase().as_str() {
+        "HD" => DevicePathNode::HardDrive {
+            partition: arg(0),
+            format: arg(1),
+            signature: arg(2),
+        },
+        "MAC" => {
+            let address = normalize_mac(&arg(0));
+            // Some firmware pads the address to 32 bytes with zeros
+            let address = match address.len() > 12 && address[12..].bytes().all(|b| b == b'0') {
+                true => address[..12].to_string(),
+                false => address,
+            };
+            DevicePathNode::Mac { address }
+        }
+        "IPV4" => DevicePathNode::IPv4,
+        "IPV6" => DevicePathNode::IPv6,
+        "URI" => DevicePathNode::Uri(args.to_string()),
+        _ => DevicePathNode::Other {
+            kind: kind.trim().to_string(),
+            args: args.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_uefi_device_path, DevicePathNode};
+
+    #[test]
+    fn test_parse_uefi_device_path() {
+        let hd = parse_uefi_device_path(
+            r"HD(1,GPT,A04D0F1E-E02F-4725-9434-0699B52D8FF2,0x800,0x100000)/\EFI\ubuntu\shimaa64.efi",
+        );
+        assert!(hd.is_hard_drive());
+        assert!(!hd.is_http() && !hd.is_pxe());
+        assert_eq!(
+            hd.nodes[0],
+            DevicePathNode::HardDrive {
+                partition: "1".to_string(),
+                format: "GPT".to_string(),
+                signature: "A04D0F1E-E02F-4725-9434-0699B52D8FF2".to_string(),
+            }
+        );
+        assert_eq!(
+            hd.nodes[1],
+            DevicePathNode::FilePath(r"\EFI\ubuntu\shimaa64.efi".to_string())
+        );
+
+        // The HD node isn't always first
+        let nvme = parse_uefi_device_path(
+            "PciRoot(0x0)/Pci(0x1,0x1)/Pci(0x0,0x0)/NVMe(0x1,00-00-00-00-00-00-00-00)/HD(1,GPT,5B4C,0x800,0x100000)",
+        );
+        assert!(nvme.is_hard_drive());
+
+        let http = parse_uefi_device_path(
+            "PciRoot(0x2)/Pci(0x1,0x0)/Pci(0x0,0x0)/MAC(b83fd29099c4,0x1)/IPv4(0.0.0.0,0x0,DHCP,0.0.0.0,0.0.0.0,0.0.0.0)/Uri(http://10.0.0.1/boot/ipxe.efi)",
+        );
+        assert!(http.is_http() && http.is_ipv4() && !http.is_pxe());
+        assert_eq!(http.mac(), Some("B83FD29099C4"));
+        assert_eq!(
+            http.nodes.last(),
+            Some(&DevicePathNode::Uri(
+                "http://10.0.0.1/boot/ipxe.efi".to_string()
+            ))
+        );
+
+        let pxe = parse_uefi_device_path(
+            "PciRoot(0x0)/Pci(0x1,0x0)/MAC(B83FD29099C40000000000000000000000000000000000000000000000000000,0x1)/IPV6(0000:0000:0000:0000:0000:0000:0000:0000,0x0,Static,0000:0000:0000:0000:0000:0000:0000:0000,0x40,0000:0000:0000:0000:0000:0000:0000:0000)",
+        );
+        assert!(pxe.is_pxe() && pxe.is_ipv6());
+        assert_eq!(pxe.mac(), Some("B83FD29099C4"));
+
+        assert_eq!(parse_uefi_device_path("").nodes, vec![]);
+    }
+}
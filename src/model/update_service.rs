@@ -22,6 +22,8 @@
  */
 use serde::{Deserialize, Serialize};
 
+use super::ODataId;
+
 /// https://redfish.dmtf.org/schemas/v1/UpdateService.v1_14_0.json
 /// Service for Software Update
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
@@ -30,11 +32,71 @@ pub struct UpdateService {
     pub http_push_uri: String,
     pub max_image_size_bytes: i32,
     pub multipart_http_push_uri: String,
+    pub firmware_inventory: Option<ODataId>,
+    pub software_inventory: Option<ODataId>,
+    pub actions: Option<UpdateServiceActions>,
+}
+
+impl UpdateService {
+    /// Transfer protocols the BMC advertises for SimpleUpdate.
+    /// Empty when the BMC doesn't publish an allowable values list.
+    pub fn allowable_transfer_protocols(&self) -> &[TransferProtocolType] {
+        self.actions
+            .as_ref()
+            .and_then(|a| a.simple_update.as_ref())
+            .map(|s| s.transfer_protocol_allowable_values.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct UpdateServiceActions {
+    #[serde(rename = "#UpdateService.SimpleUpdate")]
+    pub simple_update: Option<SimpleUpdateAction>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct SimpleUpdateAction {
+    pub target: Option<String>,
+    #[serde(rename = "@Redfish.ActionInfo")]
+    pub action_info: Option<String>,
+    #[serde(rename = "TransferProtocol@Redfish.AllowableValues", default)]
+    pub transfer_protocol_allowable_values: Vec<TransferProtocolType>,
+}
+
+/// https://redfish.dmtf.org/schemas/v1/ActionInfo.v1_4_0.json
+/// Parameters an action accepts, referenced by `@Redfish.ActionInfo`
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ActionInfo {
+    pub parameters: Vec<ActionInfoParameter>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ActionInfoParameter {
+    pub name: String,
+    pub required: bool,
+    pub allowable_values: Vec<String>,
+}
+
+impl ActionInfo {
+    /// Transfer protocols listed for the TransferProtocol parameter. Values
+    /// this crate doesn't model are dropped.
+    pub fn transfer_protocols(&self) -> Vec<TransferProtocolType> {
+        self.parameters
+            .iter()
+            .filter(|p| p.name == "TransferProtocol")
+            .flat_map(|p| p.allowable_values.iter())
+            .filter_map(|v| serde_json::from_value(serde_json::Value::String(v.clone())).ok())
+            .collect()
+    }
 }
 
 #[allow(clippy::upper_case_acronyms)]
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq)]
 pub enum TransferProtocolType {
+    CIFS,
     FTP,
     SFTP,
     HTTP,
@@ -71,3 +133,125 @@ pub enum ComponentType {
     #[clap(skip)]
     Unknown,
 }
+
+impl ComponentType {
+    /// Which component a FirmwareInventory id or name such as `BMC_0`, `HGX_FW_BMC_0`,
+    /// `EROT_BIOS_0` or `PSU_1` is the firmware of. Unknown for anything this crate can't
+    /// update, HGX GPUs and NVSwitches included.
+    pub fn from_inventory_id(id: &str) -> ComponentType {
+        let id: String = id
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        let num = || {
+            let digits = id.trim_start_matches(|c: char| !c.is_ascii_digit());
+            digits.parse().unwrap_or_default()
+        };
+        if id.contains("EROT") {
+            if id.contains("BMC") {
+                ComponentType::EROTBMC
+            } else if id.contains("BIOS") {
+                ComponentType::EROTBIOS
+            } else {
+                ComponentType::Unknown
+            }
+        } else if id.starts_with("HGX") {
+            if id.contains("BMC") {
+                ComponentType::HGXBMC
+            } else {
+                ComponentType::Unknown
+            }
+        } else if id.contains("CPLDMID") {
+            ComponentType::CPLDMID
+        } else if id.contains("CPLDMB") {
+            ComponentType::CPLDMB
+        } else if id.contains("CPLDPDB") {
+            ComponentType::CPLDPDB
+        } else if id.starts_with("PSU") {
+            ComponentType::PSU { num: num() }
+        } else if id.starts_with("PCIESWITCH") {
+            ComponentType::PCIeSwitch { num: num() }
+        } else if id.starts_with("PCIERETIMER") {
+            ComponentType::PCIeRetimer { num: num() }
+        } else if id.contains("BMC") {
+            ComponentType::BMC
+        } else if id.contains("BIOS") || id.contains("UEFI") {
+            ComponentType::UEFI
+        } else {
+            ComponentType::Unknown
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ActionInfo, ComponentType, TransferProtocolType};
+
+    #[test]
+    fn test_component_type_from_inventory_id() {
+        let cases = [
+            ("BMC_0", ComponentType::BMC),
+            ("bmc", ComponentType::BMC),
+            ("HGX_FW_BMC_0", ComponentType::HGXBMC),
+            ("HGX_FW_ERoT_BMC_0", ComponentType::EROTBMC),
+            ("EROT_BIOS_0", ComponentType::EROTBIOS),
+            ("HostBIOS_0", ComponentType::UEFI),
+            ("UEFI", ComponentType::UEFI),
+            ("CPLDMID_0", ComponentType::CPLDMID),
+            ("CPLDMB_0", ComponentType::CPLDMB),
+            ("CPLD_PDB_0", ComponentType::CPLDPDB),
+            ("PSU_3", ComponentType::PSU { num: 3 }),
+            ("PCIeSwitch_1", ComponentType::PCIeSwitch { num: 1 }),
+            ("PCIeRetimer_7", ComponentType::PCIeRetimer { num: 7 }),
+            ("HGX_FW_GPU_SXM_1", ComponentType::Unknown),
+            ("Installed-0-2.3.4", ComponentType::Unknown),
+        ];
+        for (id, expected) in cases {
+            assert_eq!(ComponentType::from_inventory_id(id), expected, "{id}");
+        }
+    }
+
+    #[test]
+    fn test_update_service_parser() {
+        let data = include_str!("testdata/update_service.json");
+        let result: super::UpdateService = serde_json::from_str(data).unwrap();
+        assert_eq!(
+            result.firmware_inventory.as_ref().unwrap().odata_id,
+            "/redfish/v1/UpdateService/FirmwareInventory"
+        );
+        assert_eq!(
+            result.software_inventory.as_ref().unwrap().odata_id,
+            "/redfish/v1/UpdateService/SoftwareInventory"
+        );
+        assert_eq!(
+            result.allowable_transfer_protocols(),
+            &[TransferProtocolType::HTTP, TransferProtocolType::HTTPS]
+        );
+        assert!(super::UpdateService::default()
+            .allowable_transfer_protocols()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_action_info_transfer_protocols() {
+        let data = r#"{
+            "@odata.id": "/redfish/v1/UpdateService/SimpleUpdateActionInfo",
+            "Id": "SimpleUpdateActionInfo",
+            "Parameters": [
+                {"Name": "ImageURI", "Required": true, "DataType": "String"},
+                {"Name": "TransferProtocol", "Required": false, "DataType": "String",
+                 "AllowableValues": ["HTTPS", "SCP", "CIFS", "Unknown"]}
+            ]
+        }"#;
+        let result: ActionInfo = serde_json::from_str(data).unwrap();
+        assert_eq!(
+            result.transfer_protocols(),
+            vec![
+                TransferProtocolType::HTTPS,
+                TransferProtocolType::SCP,
+                TransferProtocolType::CIFS
+            ]
+        );
+    }
+}
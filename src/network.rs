@@ -20,18 +20,30 @@
  * FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
  * DEALINGS IN THE SOFTWARE.
  */
-use std::{collections::HashMap, path::Path, time::Duration};
+use std::{
+    collections::HashMap,
+    future::Future,
+    path::Path,
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant},
+};
 
 use reqwest::{
-    header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, CONTENT_TYPE, IF_MATCH},
+    header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, CONTENT_TYPE, ETAG, IF_MATCH},
     multipart::{Form, Part},
     Client as HttpClient, ClientBuilder as HttpClientBuilder, Method, Proxy, StatusCode,
 };
 use serde::{de::DeserializeOwned, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio_util::sync::CancellationToken;
 use tracing::debug;
+use zeroize::Zeroizing;
 
 use crate::model::service_root::RedfishVendor;
-use crate::{model::InvalidValueError, standard::RedfishStandard, Redfish, RedfishError};
+use crate::secret::redact_body;
+use crate::{
+    model::InvalidValueError, standard::RedfishStandard, Redfish, RedfishError, SecretString,
+};
 
 pub const REDFISH_ENDPOINT: &str = "redfish/v1";
 const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
@@ -44,6 +56,17 @@ pub struct RedfishClientPoolBuilder {
     timeout: Duration,
     accept_invalid_certs: bool,
     proxy: Option<String>,
+    etag_retries: u32,
+    rate_limit: Option<RateLimit>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    retry_budget: Option<RateLimit>,
+    lenient: bool,
+    cancellation: Option<CancellationToken>,
+    max_response_bytes: Option<usize>,
+    verify_writes: bool,
+    clock: Arc<dyn Clock>,
+    poll_backoff: Backoff,
+    metrics: Option<Arc<dyn Metrics>>,
 }
 
 impl RedfishClientPoolBuilder {
@@ -74,6 +97,137 @@ impl RedfishClientPoolBuilder {
         self
     }
 
+    /// How many times a settings PATCH is retried after 412 Precondition Failed, i.e. when
+    /// another writer changed the resource between our read and our write.
+    ///
+    /// By default this is 0, and those PATCHes are sent without If-Match.
+    pub fn etag_retries(mut self, retries: u32) -> Self {
+        self.etag_retries = retries;
+        self
+    }
+
+    /// Paces requests to each endpoint with a token bucket refilled at `requests_per_second`
+    /// and holding at most `burst` tokens, so we don't trip the BMC's own throttling.
+    /// Clients created from the pool for the same host and port share one bucket.
+    ///
+    /// By default requests are not rate limited.
+    pub fn rate_limit(mut self, requests_per_second: f64, burst: u32) -> Self {
+        self.rate_limit = Some(RateLimit {
+            requests_per_second,
+            burst,
+        });
+        self
+    }
+
+    /// Stops talking to an endpoint after `failure_threshold` consecutive network errors or 5xx
+    /// responses. Calls then fail fast with `RedfishError::CircuitOpen` until `cool_down` has
+    /// passed, after which a single probe request is let through: success closes the circuit,
+    /// failure opens it for another `cool_down`.
+    /// Clients created from the pool for the same host and port share one breaker.
+    ///
+    /// By default there is no circuit breaker.
+    pub fn circuit_breaker(mut self, failure_threshold: u32, cool_down: Duration) -> Self {
+        self.circuit_breaker = Some(CircuitBreakerConfig {
+            failure_threshold: failure_threshold.max(1),
+            cool_down,
+        });
+        self
+    }
+
+    /// Caps retries across every client of the pool with one token bucket refilled at
+    /// `retries_per_second` and holding at most `burst` tokens. Each retry, after a dropped
+    /// connection or a 412 on an ETag-guarded PATCH, takes a token; once they run out the
+    /// request fails with its original error instead of being retried. When a whole fleet of
+    /// BMCs degrades at once this keeps a sweep from doubling the load on them.
+    ///
+    /// By default retries are not budgeted.
+    pub fn retry_budget(mut self, retries_per_second: f64, burst: u32) -> Self {
+        self.retry_budget = Some(RateLimit {
+            requests_per_second: retries_per_second,
+            burst,
+        });
+        self
+    }
+
+    /// Forgiving deserialization for non-conformant BMCs. When a response doesn't match its
+    /// model, the offending field is repaired and deserialization retried, instead of failing
+    /// the whole call. The repairs, tried in this order:
+    /// - a missing required field is filled with the first of `""`, `0`, `false`, `[]` and
+    ///   `{}` that the model accepts
+    /// - a number sent as a string (`"42"`, `" 1.5 "`) becomes a number, and a number where
+    ///   a string is expected becomes a string
+    /// - any other invalid field is dropped, which works when the model has it as an `Option`
+    ///
+    /// At most 32 repairs are made per response; if the body still doesn't fit, or isn't
+    /// JSON, the strict error is returned. Every repair is logged as a warning. It applies to
+    /// every typed response the client reads, not to BIOS attribute maps, which are untyped.
+    ///
+    /// By default responses are deserialized strictly.
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Once `token` is cancelled, in-flight requests, firmware uploads and downloads, and the
+    /// waits between polls are abandoned and return `RedfishError::Cancelled`, as do any later
+    /// calls. Use it to shut a service down without waiting out long operations.
+    ///
+    /// By default operations can't be cancelled other than by dropping their future.
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Stop reading a response once it passes `max` bytes and fail with
+    /// `RedfishError::ResponseTooLarge`, so a rogue BMC can't exhaust memory. Applies to every
+    /// Redfish response, error bodies included; the file `download_to_file` streams to disk is
+    /// not limited, but its error body is.
+    ///
+    /// By default response size is not limited.
+    pub fn max_response_bytes(mut self, max: usize) -> Self {
+        self.max_response_bytes = Some(max);
+        self
+    }
+
+    /// Re-read settings that must stick after writing them and fail with
+    /// `RedfishError::VerificationFailed` if the BMC didn't take the value. Covers
+    /// `enable_secure_boot`, `disable_secure_boot` and `change_boot_order`. A value waiting in the resource's
+    /// settings object for the next reboot counts as taken. UEFI passwords can't be read
+    /// back, so `change_uefi_password` relies on the BMC's response alone.
+    ///
+    /// By default writes are not verified.
+    pub fn verify_writes(mut self, verify: bool) -> Self {
+        self.verify_writes = verify;
+        self
+    }
+
+    /// The time source polling helpers such as `wait_for_power_state` read and sleep on.
+    /// Tests can pass a `ManualClock` to run timeouts without waiting for them.
+    ///
+    /// By default the tokio clock is used.
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// How the gaps between polls grow in `wait_for_power_state` and other waits. Where the
+    /// caller passes a poll interval it replaces `backoff.initial`.
+    ///
+    /// By default `Backoff::default()`: doubling from 1s up to 30s, with 20% jitter.
+    pub fn poll_backoff(mut self, backoff: Backoff) -> Self {
+        self.poll_backoff = backoff;
+        self
+    }
+
+    /// Report every HTTP request, and every retry, to `metrics`, e.g. to count requests by
+    /// method and status and record their latency.
+    ///
+    /// By default nothing is reported.
+    pub fn metrics(mut self, metrics: Arc<dyn Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
     /// Builds a Redfish Client Network Configuration
     pub fn build(&self) -> Result<RedfishClientPool, RedfishError> {
         let mut builder = HttpClientBuilder::new();
@@ -90,7 +244,43 @@ impl RedfishClientPoolBuilder {
             .map_err(|e| RedfishError::GenericError {
                 error: format!("Failed to build RedfishClientPool HTTP client: {}", e),
             })?;
-        let pool = RedfishClientPool { http_client };
+        if let Some(limit) = self.rate_limit.as_ref() {
+            if limit.requests_per_second.is_nan()
+                || limit.requests_per_second <= 0.0
+                || limit.burst == 0
+            {
+                return Err(RedfishError::GenericError {
+                    error: format!("Invalid rate limit: {limit:?}"),
+                });
+            }
+        }
+        if let Some(limit) = self.retry_budget.as_ref() {
+            // A burst of 0 is allowed and turns retries off
+            if limit.requests_per_second.is_nan() || limit.requests_per_second < 0.0 {
+                return Err(RedfishError::GenericError {
+                    error: format!("Invalid retry budget: {limit:?}"),
+                });
+            }
+        }
+        let pool = RedfishClientPool {
+            http_client,
+            etag_retries: self.etag_retries,
+            rate_limit: self.rate_limit,
+            rate_limiters: Arc::new(Mutex::new(HashMap::new())),
+            circuit_breaker: self.circuit_breaker,
+            circuit_breakers: Arc::new(Mutex::new(HashMap::new())),
+            retry_budget: self
+                .retry_budget
+                .map(|limit| Arc::new(RetryBudget::new(limit))),
+            credentials: Arc::new(Mutex::new(HashMap::new())),
+            lenient: self.lenient,
+            cancellation: self.cancellation.clone(),
+            max_response_bytes: self.max_response_bytes,
+            verify_writes: self.verify_writes,
+            clock: self.clock.clone(),
+            poll_backoff: self.poll_backoff,
+            metrics: self.metrics.clone(),
+        };
 
         Ok(pool)
     }
@@ -106,7 +296,41 @@ pub struct Endpoint {
     /// BMC username
     pub user: Option<String>,
     /// BMC password
-    pub password: Option<String>,
+    pub password: Option<SecretString>,
+}
+
+impl Endpoint {
+    /// `https://host[:port]`. An IPv6 literal host, given with or without brackets, is
+    /// bracketed, and its zone id, e.g. the eth0 of fe80::1%eth0, is percent-encoded as
+    /// RFC 6874 asks. reqwest doesn't parse zone ids yet, so requests to such a URL fail;
+    /// use a global address for link-local BMCs.
+    pub fn base_url(&self) -> String {
+        let host = self
+            .host
+            .strip_prefix('[')
+            .and_then(|h| h.strip_suffix(']'))
+            .unwrap_or(&self.host);
+        // Only an IPv6 literal is bracketed, a host:port such as 127.0.0.1:8733 is left alone
+        let (address, zone) = match host.split_once('%') {
+            Some((address, zone)) => (address, Some(zone)),
+            None => (host, None),
+        };
+        let host = if address.parse::<std::net::Ipv6Addr>().is_ok() {
+            match zone {
+                Some(zone) => {
+                    let zone = zone.strip_prefix("25").unwrap_or(zone);
+                    format!("[{address}%25{zone}]")
+                }
+                None => format!("[{address}]"),
+            }
+        } else {
+            host.to_string()
+        };
+        match self.port {
+            Some(port) => format!("https://{host}:{port}"),
+            None => format!("https://{host}"),
+        }
+    }
 }
 
 impl Default for Endpoint {
@@ -120,9 +344,40 @@ impl Default for Endpoint {
     }
 }
 
+// Host and port identifying an endpoint for per-endpoint state
+type EndpointKey = (String, Option<u16>);
+
+// The user and password a client logs in with. The pool shares one per endpoint between all
+// its clients, so rotate_credentials reaches the ones already in use.
+#[derive(Debug, Clone, Default)]
+struct Credentials {
+    user: Option<String>,
+    password: Option<SecretString>,
+}
+
+type SharedCredentials = Arc<RwLock<Credentials>>;
+
+/// Clients authenticate each request with HTTP Basic auth and never open a SessionService
+/// session, so dropping one leaves nothing behind on the BMC.
 #[derive(Debug, Clone)]
 pub struct RedfishClientPool {
     http_client: HttpClient,
+    etag_retries: u32,
+    rate_limit: Option<RateLimit>,
+    // One bucket per (host, port), shared by every client for that endpoint
+    rate_limiters: Arc<Mutex<HashMap<EndpointKey, Arc<RateLimiter>>>>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    circuit_breakers: Arc<Mutex<HashMap<EndpointKey, Arc<CircuitBreaker>>>>,
+    // One bucket for the whole pool
+    retry_budget: Option<Arc<RetryBudget>>,
+    credentials: Arc<Mutex<HashMap<EndpointKey, SharedCredentials>>>,
+    lenient: bool,
+    cancellation: Option<CancellationToken>,
+    max_response_bytes: Option<usize>,
+    verify_writes: bool,
+    clock: Arc<dyn Clock>,
+    poll_backoff: Backoff,
+    metrics: Option<Arc<dyn Metrics>>,
 }
 
 impl RedfishClientPool {
@@ -134,13 +389,124 @@ impl RedfishClientPool {
             // BMCs often have a self-signed cert, so usually this has to be true
             accept_invalid_certs: true,
             proxy: None,
+            etag_retries: 0,
+            rate_limit: None,
+            circuit_breaker: None,
+            retry_budget: None,
+            lenient: false,
+            cancellation: None,
+            max_response_bytes: None,
+            verify_writes: false,
+            clock: Arc::new(TokioClock),
+            poll_backoff: Backoff::default(),
+            metrics: None,
+        }
+    }
+
+    fn circuit_breaker(&self, endpoint: &Endpoint) -> Option<Arc<CircuitBreaker>> {
+        let config = self.circuit_breaker?;
+        let mut breakers = self
+            .circuit_breakers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let breaker = breakers
+            .entry((endpoint.host.clone(), endpoint.port))
+            .or_insert_with(|| Arc::new(CircuitBreaker::new(config)));
+        Some(breaker.clone())
+    }
+
+    fn rate_limiter(&self, endpoint: &Endpoint) -> Option<Arc<RateLimiter>> {
+        let limit = self.rate_limit?;
+        let mut limiters = self.rate_limiters.lock().unwrap_or_else(|e| e.into_inner());
+        let limiter = limiters
+            .entry((endpoint.host.clone(), endpoint.port))
+            .or_insert_with(|| Arc::new(RateLimiter::new(limit)));
+        Some(limiter.clone())
+    }
+
+    // The endpoint's shared credentials. The user and password in `endpoint` only seed them
+    // for the first client, after a rotation callers may still pass the old ones.
+    fn credentials(&self, endpoint: &Endpoint) -> SharedCredentials {
+        let mut all = self.credentials.lock().unwrap_or_else(|e| e.into_inner());
+        all.entry((endpoint.host.clone(), endpoint.port))
+            .or_insert_with(|| {
+                Arc::new(RwLock::new(Credentials {
+                    user: endpoint.user.clone(),
+                    password: endpoint.password.clone(),
+                }))
+            })
+            .clone()
+    }
+
+    // The HTTP client for `endpoint`, sharing this pool's connections, limits and settings
+    fn http_client(
+        &self,
+        endpoint: Endpoint,
+        custom_headers: Vec<(HeaderName, String)>,
+    ) -> RedfishHttpClient {
+        let rate_limiter = self.rate_limiter(&endpoint);
+        let circuit_breaker = self.circuit_breaker(&endpoint);
+        let credentials = self.credentials(&endpoint);
+        RedfishHttpClient::new(self.http_client.clone(), endpoint, custom_headers)
+            .with_credentials(credentials)
+            .with_etag_retries(self.etag_retries)
+            .with_rate_limiter(rate_limiter)
+            .with_circuit_breaker(circuit_breaker)
+            .with_retry_budget(self.retry_budget.clone())
+            .with_lenient(self.lenient)
+            .with_cancellation(self.cancellation.clone())
+            .with_max_response_bytes(self.max_response_bytes)
+            .with_verify_writes(self.verify_writes)
+            .with_clock(self.clock.clone())
+            .with_poll_backoff(self.poll_backoff)
+            .with_metrics(self.metrics.clone())
+    }
+
+    /// Make every client this pool created for `endpoint`'s host and port, including ones in
+    /// use, log in as `user` with `password` from their next request on. For when the
+    /// password was changed some other way than rotate_credentials.
+    pub fn set_credentials(&self, endpoint: &Endpoint, user: &str, password: &SecretString) {
+        let mut all = self.credentials.lock().unwrap_or_else(|e| e.into_inner());
+        let credentials = all
+            .entry((endpoint.host.clone(), endpoint.port))
+            .or_default();
+        *credentials.write().unwrap_or_else(|e| e.into_inner()) = Credentials {
+            user: Some(user.to_string()),
+            password: Some(password.clone()),
+        };
+    }
+
+    /// Change the password of BMC account `new_user` to `new_pass`, logged in with the
+    /// credentials the pool's clients for `endpoint` use now, then switch them all to
+    /// `new_user` and `new_pass`. If the change fails they keep the old credentials.
+    pub async fn rotate_credentials(
+        &self,
+        endpoint: &Endpoint,
+        new_user: &str,
+        new_pass: &SecretString,
+    ) -> Result<(), RedfishError> {
+        let current = self
+            .credentials
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&(endpoint.host.clone(), endpoint.port))
+            .map(|c| c.read().unwrap_or_else(|e| e.into_inner()).clone());
+        let mut login = endpoint.clone();
+        if let Some(current) = current {
+            login.user = current.user;
+            login.password = current.password;
         }
+        let client = self.create_client(login).await?;
+        client.change_password(new_user, new_pass).await?;
+        self.set_credentials(endpoint, new_user, new_pass);
+        Ok(())
     }
 
     /// Creates a Redfish BMC client for a certain endpoint
     ///
     /// Creating the client will immediately start a HTTP requests
-    /// to set system_id, manager_id and vendor type.
+    /// to set manager_id and vendor type. The system id is looked up when first needed, see
+    /// `RedfishStandard::resolve_system_id`.
     pub async fn create_client(
         &self,
         endpoint: Endpoint,
@@ -152,34 +518,35 @@ impl RedfishClientPool {
     /// Creates a Redfish BMC client for a certain endpoint and adds custom headers to subsequent requests.
     ///
     /// Creating the client will immediately start a HTTP requests
-    /// to set system_id, manager_id and vendor type.
+    /// to set manager_id and vendor type. The system id is looked up when first needed, see
+    /// `RedfishStandard::resolve_system_id`.
     /// `custom_headers` will be added to any headers used by vendor specific implementations or the http client.
     pub async fn create_client_with_custom_headers(
         &self,
         endpoint: Endpoint,
         custom_headers: Vec<(HeaderName, String)>,
     ) -> Result<Box<dyn crate::Redfish>, RedfishError> {
-        let client = RedfishHttpClient::new(self.http_client.clone(), endpoint, custom_headers);
+        let client = self.http_client(endpoint, custom_headers);
         let mut s = RedfishStandard::new(client);
         let service_root = s.get_service_root().await?;
-        let systems = s.get_systems().await?;
+        // Before anything else, so collections are found where the service root says they are
+        s.set_service_root(service_root.clone())?;
         let managers = s.get_managers().await?;
-        let system_id = systems.first().ok_or_else(|| RedfishError::GenericError {
-            error: "No systems found in service root".to_string(),
-        })?;
         let manager_id = managers.first().ok_or_else(|| RedfishError::GenericError {
             error: "No managers found in service root".to_string(),
         })?;
         let chassis = s.get_chassis_all().await?;
 
-        s.set_system_id(system_id)?;
-        // call set_system_id always before calling set_vendor
+        // call set_manager_id always before calling set_vendor
         s.set_manager_id(manager_id)?;
-        s.set_service_root(service_root.clone())?;
 
         let Some(mut vendor) = service_root.vendor() else {
             return Err(RedfishError::MissingVendor);
         };
+        // The system id is looked up on first use, except where it picks the vendor client
+        if vendor == RedfishVendor::AMI {
+            s.resolve_system_id().await?;
+        }
         if vendor == RedfishVendor::P3809 {
             if chassis.contains(&"MGX_NVSwitch_0".to_string()) {
                 vendor = RedfishVendor::NvidiaGBSwitch;
@@ -209,18 +576,122 @@ impl RedfishClientPool {
         endpoint: Endpoint,
         custom_headers: Vec<(HeaderName, String)>,
     ) -> Result<Box<RedfishStandard>, RedfishError> {
-        let client = RedfishHttpClient::new(self.http_client.clone(), endpoint, custom_headers);
+        let client = self.http_client(endpoint, custom_headers);
         let s = RedfishStandard::new(client);
         Ok(Box::new(s))
     }
+
+    /// Runs `f` against a client for every endpoint, at most `concurrency` at a time, and
+    /// returns each endpoint's result in the order given. Creating the client counts as
+    /// part of the endpoint's work, so an unreachable BMC shows up as its Err.
+    pub async fn for_each<T, F, Fut>(
+        &self,
+        endpoints: Vec<Endpoint>,
+        concurrency: usize,
+        f: F,
+    ) -> Vec<(Endpoint, Result<T, RedfishError>)>
+    where
+        T: Send + 'static,
+        F: Fn(Box<dyn crate::Redfish>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, RedfishError>> + Send + 'static,
+    {
+        self.for_each_with_progress(endpoints, concurrency, f, |_| {})
+            .await
+    }
+
+    /// Like `for_each`, calling `progress` as each endpoint finishes. `progress` is only
+    /// ever called from the task awaiting this future, never concurrently, so it can
+    /// update a progress bar or counters without locking.
+    pub async fn for_each_with_progress<T, F, Fut, P>(
+        &self,
+        endpoints: Vec<Endpoint>,
+        concurrency: usize,
+        f: F,
+        mut progress: P,
+    ) -> Vec<(Endpoint, Result<T, RedfishError>)>
+    where
+        T: Send + 'static,
+        F: Fn(Box<dyn crate::Redfish>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, RedfishError>> + Send + 'static,
+        P: FnMut(&SweepProgress),
+    {
+        let total = endpoints.len();
+        let f = Arc::new(f);
+        let mut pending = endpoints.into_iter().enumerate();
+        let mut tasks = tokio::task::JoinSet::new();
+        let mut results: Vec<Option<(Endpoint, Result<T, RedfishError>)>> =
+            (0..total).map(|_| None).collect();
+        let mut state = SweepProgress {
+            total,
+            ..Default::default()
+        };
+        loop {
+            // Top up to the concurrency limit, then wait for one to finish
+            while tasks.len() < concurrency.max(1) {
+                let Some((index, endpoint)) = pending.next() else {
+                    break;
+                };
+                let pool = self.clone();
+                let f = f.clone();
+                tasks.spawn(async move {
+                    let result = match pool.create_client(endpoint.clone()).await {
+                        Ok(client) => f(client).await,
+                        Err(e) => Err(e),
+                    };
+                    (index, endpoint, result)
+                });
+            }
+            let Some(joined) = tasks.join_next().await else {
+                break;
+            };
+            let (index, endpoint, result) = joined.unwrap_or_else(|e| {
+                // f panicked; the JoinSet lost its output, so this can't be attributed
+                std::panic::resume_unwind(e.into_panic())
+            });
+            state.done += 1;
+            state.failed += usize::from(result.is_err());
+            state.last_host = endpoint.host.clone();
+            state.last_succeeded = result.is_ok();
+            progress(&state);
+            results[index] = Some((endpoint, result));
+        }
+        results.into_iter().flatten().collect()
+    }
+}
+
+/// Where a `RedfishClientPool::for_each_with_progress` sweep is, after each endpoint.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SweepProgress {
+    /// Endpoints finished so far, successful or not
+    pub done: usize,
+    /// How many of those failed
+    pub failed: usize,
+    pub total: usize,
+    /// Host of the endpoint that just finished
+    pub last_host: String,
+    pub last_succeeded: bool,
 }
 
 /// A HTTP client which targets a single libredfish endpoint
 #[derive(Clone)]
 pub struct RedfishHttpClient {
     endpoint: Endpoint,
+    credentials: SharedCredentials,
     http_client: HttpClient,
     custom_headers: Vec<(HeaderName, String)>,
+    etag_retries: u32,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    retry_budget: Option<Arc<RetryBudget>>,
+    lenient: bool,
+    cancellation: Option<CancellationToken>,
+    max_response_bytes: Option<usize>,
+    verify_writes: bool,
+    clock: Arc<dyn Clock>,
+    poll_backoff: Backoff,
+    metrics: Option<Arc<dyn Metrics>>,
+    // Unit tests talk to a fake BMC without TLS
+    plain_http: bool,
 }
 
 impl RedfishHttpClient {
@@ -229,10 +700,299 @@ impl RedfishHttpClient {
         endpoint: Endpoint,
         custom_headers: Vec<(HeaderName, String)>,
     ) -> Self {
+        let credentials = Credentials {
+            user: endpoint.user.clone(),
+            password: endpoint.password.clone(),
+        };
         Self {
             endpoint,
+            credentials: Arc::new(RwLock::new(credentials)),
             http_client,
             custom_headers,
+            etag_retries: 0,
+            rate_limiter: None,
+            circuit_breaker: None,
+            retry_budget: None,
+            lenient: false,
+            cancellation: None,
+            max_response_bytes: None,
+            verify_writes: false,
+            clock: Arc::new(TokioClock),
+            poll_backoff: Backoff::default(),
+            metrics: None,
+            plain_http: false,
+        }
+    }
+
+    // See RedfishClientPool::rotate_credentials
+    fn with_credentials(mut self, credentials: SharedCredentials) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    // The current login. Read per request, it can change under a client in use.
+    fn login(&self) -> Credentials {
+        self.credentials
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// See RedfishClientPoolBuilder::etag_retries
+    pub fn with_etag_retries(mut self, retries: u32) -> Self {
+        self.etag_retries = retries;
+        self
+    }
+
+    /// See RedfishClientPoolBuilder::rate_limit
+    pub fn with_rate_limiter(mut self, rate_limiter: Option<Arc<RateLimiter>>) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    /// See RedfishClientPoolBuilder::circuit_breaker
+    pub fn with_circuit_breaker(mut self, circuit_breaker: Option<Arc<CircuitBreaker>>) -> Self {
+        self.circuit_breaker = circuit_breaker;
+        self
+    }
+
+    /// See RedfishClientPoolBuilder::retry_budget
+    pub fn with_retry_budget(mut self, retry_budget: Option<Arc<RetryBudget>>) -> Self {
+        self.retry_budget = retry_budget;
+        self
+    }
+
+    /// See RedfishClientPoolBuilder::lenient
+    pub fn with_lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// See RedfishClientPoolBuilder::cancellation
+    pub fn with_cancellation(mut self, token: Option<CancellationToken>) -> Self {
+        self.cancellation = token;
+        self
+    }
+
+    /// See RedfishClientPoolBuilder::max_response_bytes
+    pub fn with_max_response_bytes(mut self, max: Option<usize>) -> Self {
+        self.max_response_bytes = max;
+        self
+    }
+
+    /// See RedfishClientPoolBuilder::verify_writes
+    pub fn with_verify_writes(mut self, verify: bool) -> Self {
+        self.verify_writes = verify;
+        self
+    }
+
+    pub fn verify_writes(&self) -> bool {
+        self.verify_writes
+    }
+
+    /// See RedfishClientPoolBuilder::clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// See RedfishClientPoolBuilder::metrics
+    pub fn with_metrics(mut self, metrics: Option<Arc<dyn Metrics>>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_plain_http(mut self) -> Self {
+        self.plain_http = true;
+        self
+    }
+
+    fn base_url(&self) -> String {
+        let url = self.endpoint.base_url();
+        if self.plain_http {
+            url.replacen("https://", "http://", 1)
+        } else {
+            url
+        }
+    }
+
+    fn record_request(
+        &self,
+        method: &Method,
+        api: &str,
+        response: &Result<reqwest::Response, reqwest::Error>,
+        started: Instant,
+    ) {
+        if let Some(metrics) = &self.metrics {
+            metrics.request(&RequestMetric {
+                host: &self.endpoint.host,
+                method,
+                api,
+                status: response.as_ref().ok().map(|r| r.status()),
+                timed_out: response.as_ref().is_err_and(|e| e.is_timeout()),
+                elapsed: started.elapsed(),
+            });
+        }
+    }
+
+    // Whether the retry budget allows another retry. If so the retry is reported to metrics.
+    fn try_retry(&self, method: &Method, api: &str) -> bool {
+        if let Some(budget) = self.retry_budget.as_ref() {
+            if !budget.try_acquire(Instant::now()) {
+                debug!("Retry budget exhausted, not retrying {method} {api}");
+                return false;
+            }
+        }
+        if let Some(metrics) = &self.metrics {
+            metrics.retry(&self.endpoint.host, method, api);
+        }
+        true
+    }
+
+    /// The current time according to the client's clock, for poll deadlines
+    pub fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    /// See RedfishClientPoolBuilder::poll_backoff
+    pub fn with_poll_backoff(mut self, backoff: Backoff) -> Self {
+        self.poll_backoff = backoff;
+        self
+    }
+
+    pub fn poll_backoff(&self) -> Backoff {
+        self.poll_backoff
+    }
+
+    /// Calls `check` until it returns Some, sleeping between calls as `backoff` says. An error
+    /// from `check` ends the wait. Fails with RedfishError::Timeout naming `what` if `timeout`
+    /// passes first; a sleep that would overshoot it is not started.
+    pub async fn poll_until<T, F, Fut>(
+        &self,
+        what: &str,
+        backoff: Backoff,
+        timeout: Duration,
+        mut check: F,
+    ) -> Result<T, RedfishError>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<Option<T>, RedfishError>>,
+    {
+        let deadline = self.now() + timeout;
+        let mut attempt = 0;
+        loop {
+            if let Some(done) = check().await? {
+                return Ok(done);
+            }
+            let delay = backoff.delay(attempt, jitter_sample());
+            if self.now() + delay > deadline {
+                return Err(RedfishError::Timeout(format!(
+                    "{what} after {}s",
+                    timeout.as_secs()
+                )));
+            }
+            self.sleep(delay).await?;
+            attempt += 1;
+        }
+    }
+
+    // Reads the whole body, failing with ResponseTooLarge past max_response_bytes,
+    // and decodes it with decode_body
+    async fn read_body(
+        &self,
+        url: &str,
+        mut response: reqwest::Response,
+    ) -> Result<String, RedfishError> {
+        let network_error = |e| RedfishError::NetworkError {
+            url: url.to_string(),
+            source: e,
+        };
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let Some(limit) = self.max_response_bytes else {
+            let body = response.bytes().await.map_err(network_error)?;
+            return Ok(decode_body(&body, content_type.as_deref()));
+        };
+        let too_large = || RedfishError::ResponseTooLarge {
+            url: url.to_string(),
+            limit,
+        };
+        if response
+            .content_length()
+            .is_some_and(|len| len > limit as u64)
+        {
+            return Err(too_large());
+        }
+        // Content-Length may be absent or wrong, so count what actually arrives
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(network_error)? {
+            if body.len() + chunk.len() > limit {
+                return Err(too_large());
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(decode_body(&body, content_type.as_deref()))
+    }
+
+    // Runs `fut` unless the client is cancelled first
+    async fn cancellable<T>(
+        &self,
+        fut: impl std::future::Future<Output = Result<T, RedfishError>>,
+    ) -> Result<T, RedfishError> {
+        let Some(token) = self.cancellation.as_ref() else {
+            return fut.await;
+        };
+        if token.is_cancelled() {
+            return Err(RedfishError::Cancelled);
+        }
+        tokio::select! {
+            _ = token.cancelled() => Err(RedfishError::Cancelled),
+            result = fut => result,
+        }
+    }
+
+    /// Sleep between polls, cut short with RedfishError::Cancelled if the client is cancelled
+    pub async fn sleep(&self, duration: Duration) -> Result<(), RedfishError> {
+        self.cancellable(async {
+            self.clock.sleep(duration).await;
+            Ok(())
+        })
+        .await
+    }
+
+    // Fails fast if the endpoint's circuit is open
+    fn circuit_check(&self) -> Result<(), RedfishError> {
+        match self.circuit_breaker.as_ref() {
+            Some(breaker) => breaker.check(&self.endpoint.host, Instant::now()),
+            None => Ok(()),
+        }
+    }
+
+    fn circuit_record<T>(&self, result: &Result<T, RedfishError>) {
+        if let Some(breaker) = self.circuit_breaker.as_ref() {
+            breaker.record(is_endpoint_failure(result), Instant::now());
+        }
+    }
+
+    // Like circuit_record, for a raw response whose status hasn't been turned into an error
+    fn circuit_record_response(&self, response: &Result<reqwest::Response, RedfishError>) {
+        if let Some(breaker) = self.circuit_breaker.as_ref() {
+            let failed = response.as_ref().map_or_else(
+                |_| is_endpoint_failure(response),
+                |r| r.status().is_server_error(),
+            );
+            breaker.record(failed, Instant::now());
+        }
+    }
+
+    // Waits until the endpoint's rate limit allows another request
+    async fn throttle(&self) {
+        if let Some(limiter) = self.rate_limiter.as_ref() {
+            limiter.acquire().await;
         }
     }
 
@@ -258,11 +1018,11 @@ impl RedfishHttpClient {
             None => Err(RedfishError::NoContent),
         }
     }
-    pub async fn post<B>(
-        &self,
-        api: &str,
-        data: B,
-    ) -> Result<(StatusCode, Option<HeaderMap>), RedfishError>
+    /// POST a JSON body to an action or collection. The response says whether the BMC did the
+    /// work already, started a task for it (202, or a Task in the body), created a resource
+    /// (201 and its Location) or sent nothing back, see `ActionResponse`. New actions should
+    /// go through here rather than build their own request.
+    pub async fn post<B>(&self, api: &str, data: B) -> Result<ActionResponse, RedfishError>
     where
         B: Serialize + ::std::fmt::Debug,
     {
@@ -274,16 +1034,12 @@ impl RedfishHttpClient {
         api: &str,
         data: B,
         headers: Option<Vec<(HeaderName, String)>>,
-    ) -> Result<(StatusCode, Option<HeaderMap>), RedfishError>
+    ) -> Result<ActionResponse, RedfishError>
     where
         B: Serialize + ::std::fmt::Debug,
     {
-        let (status_code, _resp_body, resp_headers): (
-            _,
-            Option<HashMap<String, serde_json::Value>>,
-            Option<HeaderMap>,
-        ) = self
-            .req(
+        let (status_code, resp_body, resp_headers) = self
+            .req::<serde_json::Value, _>(
                 Method::POST,
                 api,
                 Some(data),
@@ -292,25 +1048,28 @@ impl RedfishHttpClient {
                 headers.unwrap_or_default(),
             )
             .await?;
-        Ok((status_code, resp_headers))
+        Ok(ActionResponse::new(
+            status_code,
+            &resp_headers.unwrap_or_default(),
+            resp_body.unwrap_or_default(),
+        ))
     }
 
-    pub async fn post_file<T>(
+    /// POST a file as the raw request body, e.g. an image to the UpdateService's HttpPushUri.
+    /// The response is read like `post`'s.
+    pub async fn post_file(
         &self,
         api: &str,
         file: tokio::fs::File,
-    ) -> Result<(StatusCode, T), RedfishError>
-    where
-        T: DeserializeOwned + ::std::fmt::Debug,
-    {
+    ) -> Result<ActionResponse, RedfishError> {
         let body_option: Option<HashMap<&str, String>> = None;
         let timeout = DEFAULT_TIMEOUT
             + file.metadata().await.map_or_else(
                 |_err| DEFAULT_TIMEOUT,
                 |m| Duration::from_secs(m.len() / MIN_UPLOAD_BANDWIDTH),
             );
-        let (status_code, resp_opt, _resp_headers) = self
-            .req::<T, _>(
+        let (status_code, resp_body, resp_headers) = self
+            .req::<serde_json::Value, _>(
                 Method::POST,
                 api,
                 body_option,
@@ -319,10 +1078,11 @@ impl RedfishHttpClient {
                 Vec::new(),
             )
             .await?;
-        match resp_opt {
-            Some(response_body) => Ok((status_code, response_body)),
-            None => Err(RedfishError::NoContent),
-        }
+        Ok(ActionResponse::new(
+            status_code,
+            &resp_headers.unwrap_or_default(),
+            resp_body.unwrap_or_default(),
+        ))
     }
 
     pub async fn patch<T>(
@@ -343,6 +1103,22 @@ impl RedfishHttpClient {
         Ok((status_code, resp_headers))
     }
 
+    /// Like `patch`, but also returns the response body if there is one.
+    pub async fn patch_with_response<T, B>(
+        &self,
+        api: &str,
+        data: B,
+    ) -> Result<(StatusCode, Option<T>), RedfishError>
+    where
+        T: DeserializeOwned + ::std::fmt::Debug,
+        B: Serialize + ::std::fmt::Debug,
+    {
+        let (status_code, resp_body, _resp_headers) = self
+            .req(Method::PATCH, api, Some(data), None, None, Vec::new())
+            .await?;
+        Ok((status_code, resp_body))
+    }
+
     pub async fn patch_with_if_match<B>(&self, api: &str, data: B) -> Result<(), RedfishError>
     where
         B: Serialize + ::std::fmt::Debug,
@@ -366,18 +1142,101 @@ impl RedfishHttpClient {
         }
     }
 
-    pub async fn delete(&self, api: &str) -> Result<StatusCode, RedfishError> {
-        let (status_code, _resp_body, _resp_headers): (
-            _,
-            Option<HashMap<String, serde_json::Value>>,
-            Option<HeaderMap>,
-        ) = self
-            .req::<_, String>(Method::DELETE, api, None, None, None, Vec::new())
-            .await?;
-        Ok(status_code)
-    }
-
-    // All the HTTP requests happen from here.
+    /// PATCH guarded by the resource's current ETag. On 412 Precondition Failed the ETag is
+    /// re-read and the same changes are sent again, up to the configured number of retries.
+    /// With no retries configured this is a plain PATCH.
+    pub async fn patch_with_etag_retry<B>(&self, api: &str, data: B) -> Result<(), RedfishError>
+    where
+        B: Serialize + ::std::fmt::Debug,
+    {
+        if self.etag_retries == 0 {
+            return self.patch(api, data).await.map(|_status_code| ());
+        }
+        let mut attempt = 0;
+        loop {
+            let (_status_code, body, headers): (
+                _,
+                Option<HashMap<String, serde_json::Value>>,
+                Option<HeaderMap>,
+            ) = self
+                .req::<_, String>(Method::GET, api, None, None, None, Vec::new())
+                .await?;
+            let headers = if_match(
+                headers.as_ref(),
+                body.as_ref().and_then(|b| b.get("@odata.etag")),
+            );
+            let res: Result<(_, Option<HashMap<String, serde_json::Value>>, _), _> = self
+                .req(Method::PATCH, api, Some(&data), None, None, headers)
+                .await;
+            match res {
+                Ok(_) => return Ok(()),
+                Err(e) if self.retry_precondition_failed(&e, api, attempt) => {
+                    attempt += 1;
+                    debug!("{api} changed under us, retrying PATCH ({attempt})");
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Read-modify-write of `api`: `changes_for` gets the resource as just read and returns the
+    /// PATCH body, or None if there is nothing to change. The PATCH is guarded by the ETag of
+    /// that read, if the BMC gives one. On 412 Precondition Failed the resource is read again
+    /// and `changes_for` asked again, up to the configured number of retries.
+    pub async fn patch_read_modify_write<F>(
+        &self,
+        api: &str,
+        changes_for: F,
+    ) -> Result<(), RedfishError>
+    where
+        F: Fn(&serde_json::Value) -> Option<serde_json::Value>,
+    {
+        let mut attempt = 0;
+        loop {
+            let (_status_code, body, headers): (_, Option<serde_json::Value>, Option<HeaderMap>) =
+                self.req::<_, String>(Method::GET, api, None, None, None, Vec::new())
+                    .await?;
+            let current = body.unwrap_or_default();
+            let Some(changes) = changes_for(&current) else {
+                return Ok(());
+            };
+            let headers = if_match(headers.as_ref(), current.get("@odata.etag"));
+            let res: Result<(_, Option<serde::de::IgnoredAny>, _), _> = self
+                .req(Method::PATCH, api, Some(&changes), None, None, headers)
+                .await;
+            match res {
+                Ok(_) => return Ok(()),
+                Err(e) if self.retry_precondition_failed(&e, api, attempt) => {
+                    attempt += 1;
+                    debug!("{api} changed under us, retrying PATCH ({attempt})");
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    // Whether an ETag-guarded PATCH that failed with `err` should be retried
+    fn retry_precondition_failed(&self, err: &RedfishError, api: &str, attempt: u32) -> bool {
+        matches!(err, RedfishError::HTTPErrorCode { status_code, .. }
+            if *status_code == StatusCode::PRECONDITION_FAILED)
+            && attempt < self.etag_retries
+            && self.try_retry(&Method::PATCH, api)
+    }
+
+    /// DELETE a resource. 200, 202 and 204 all count as success, and the body is ignored. On
+    /// failure `RedfishError::error_envelope` gives the BMC's explanation.
+    pub async fn delete(&self, api: &str) -> Result<StatusCode, RedfishError> {
+        let (status_code, _resp_body, _resp_headers): (
+            _,
+            Option<serde::de::IgnoredAny>,
+            Option<HeaderMap>,
+        ) = self
+            .req::<_, String>(Method::DELETE, api, None, None, None, Vec::new())
+            .await?;
+        Ok(status_code)
+    }
+
+    // All the HTTP requests happen from here.
     pub async fn req<T, B>(
         &self,
         method: Method,
@@ -392,25 +1251,46 @@ impl RedfishHttpClient {
         B: Serialize + ::std::fmt::Debug,
     {
         custom_headers.extend_from_slice(&self.custom_headers);
+        self.cancellable(self.req_inner(method, api, body, override_timeout, file, custom_headers))
+            .await
+    }
+
+    async fn req_inner<T, B>(
+        &self,
+        method: Method,
+        api: &str,
+        body: Option<B>,
+        override_timeout: Option<Duration>,
+        file: Option<tokio::fs::File>,
+        custom_headers: Vec<(HeaderName, String)>,
+    ) -> Result<(StatusCode, Option<T>, Option<HeaderMap>), RedfishError>
+    where
+        T: DeserializeOwned + ::std::fmt::Debug,
+        B: Serialize + ::std::fmt::Debug,
+    {
+        self.circuit_check()?;
 
         let is_file = file.is_some();
-        match self
+        let result = match self
             ._req(&method, api, &body, override_timeout, file, &custom_headers)
             .await
         {
             Ok(x) => Ok(x),
             // HPE sends RST in case same connection is reused. To avoid that let's retry.
-            Err(a) if matches!(a, RedfishError::NetworkError { .. }) => {
-                // Handling of post_file failure must be done manually. The seek is moved and we
-                // can't reuse file by cloning. Clone shares read, writes and seek.
-                if is_file {
-                    return Err(a);
-                }
+            // Handling of post_file failure must be done manually. The seek is moved and we
+            // can't reuse file by cloning. Clone shares read, writes and seek.
+            Err(a)
+                if matches!(a, RedfishError::NetworkError { .. })
+                    && !is_file
+                    && self.try_retry(&method, api) =>
+            {
                 self._req(&method, api, &body, override_timeout, None, &custom_headers)
                     .await
             }
             Err(x) => Err(x),
-        }
+        };
+        self.circuit_record(&result);
+        result
     }
 
     // All the HTTP requests happen from here.
@@ -428,16 +1308,7 @@ impl RedfishHttpClient {
         T: DeserializeOwned + ::std::fmt::Debug,
         B: Serialize + ::std::fmt::Debug,
     {
-        let url = match self.endpoint.port {
-            Some(p) => format!(
-                "https://{}:{}/{}/{}",
-                self.endpoint.host, p, REDFISH_ENDPOINT, api
-            ),
-            None => format!(
-                "https://{}/{}/{}",
-                self.endpoint.host, REDFISH_ENDPOINT, api
-            ),
-        };
+        let url = format!("{}/{}/{}", self.base_url(), REDFISH_ENDPOINT, api);
         let body_enc = match body {
             Some(b) => {
                 let url: String = url.clone();
@@ -448,15 +1319,19 @@ impl RedfishHttpClient {
                         source: e,
                     })?;
 
-                Some(body_enc)
+                Some(Zeroizing::new(body_enc))
             }
             None => None,
         };
+        self.throttle().await;
         debug!(
             "TX {} {} {}",
             method,
             url,
-            body_enc.as_deref().unwrap_or_default()
+            body_enc
+                .as_deref()
+                .map(|b| redact_body(b))
+                .unwrap_or_default()
         );
         let mut req_b = match *method {
             Method::GET => self.http_client.get(&url),
@@ -493,19 +1368,29 @@ impl RedfishHttpClient {
             req_b = req_b.header(key, value);
         }
 
-        if let Some(user) = &self.endpoint.user {
-            req_b = req_b.basic_auth(user, self.endpoint.password.as_ref());
+        let login = self.login();
+        if let Some(user) = &login.user {
+            req_b = req_b.basic_auth(
+                user,
+                login.password.as_ref().map(SecretString::expose_secret),
+            );
         }
         if let Some(t) = override_timeout {
             req_b = req_b.timeout(t);
         }
         if let Some(b) = body_enc {
-            req_b = req_b.body(b);
+            // The body may carry a password, so reqwest gets a copy that is wiped on drop and
+            // `b` is wiped when it goes out of scope
+            let owned = Zeroizing::new(b.as_bytes().to_vec());
+            req_b = req_b.body(bytes::Bytes::from_owner(owned));
         }
         if let Some(f) = file {
             req_b = req_b.body(f);
         }
-        let response = req_b.send().await.map_err(|e| RedfishError::NetworkError {
+        let started = Instant::now();
+        let response = req_b.send().await;
+        self.record_request(method, api, &response, started);
+        let response = response.map_err(|e| RedfishError::NetworkError {
             url: url.clone(),
             source: e,
         })?;
@@ -524,13 +1409,7 @@ impl RedfishHttpClient {
         }
 
         // read the body even if not status 2XX, because BMCs give useful error messages as JSON
-        let response_body = response
-            .text()
-            .await
-            .map_err(|e| RedfishError::NetworkError {
-                url: url.clone(),
-                source: e,
-            })?;
+        let response_body = self.read_body(&url, response).await?;
         debug!("RX {status_code} {}", truncate(&response_body, 1500));
 
         if !status_code.is_success() {
@@ -563,21 +1442,83 @@ impl RedfishHttpClient {
 
         let mut res = None;
         if !response_body.is_empty() {
-            match serde_json::from_str(&response_body) {
-                Ok(v) => res.insert(v),
-                Err(e) => {
-                    return Err(RedfishError::JsonDeserializeError {
-                        url,
-                        body: response_body,
-                        source: e,
-                    });
-                }
-            };
+            res = Some(match self.lenient {
+                true => crate::lenient::from_json_str_lenient(&url, &response_body)?,
+                false => from_json_str(&url, &response_body)?,
+            });
         }
 
         Ok((status_code, res, res_headers))
     }
 
+    /// GET a binary resource and stream it to `out`, returning the number of bytes written.
+    /// `uri` is a path on the BMC such as `/redfish/v1/...`, so OEM URIs outside the Redfish
+    /// tree work too.
+    pub async fn download_to_file(&self, uri: &str, out: &Path) -> Result<u64, RedfishError> {
+        self.cancellable(self.download_to_file_inner(uri, out))
+            .await
+    }
+
+    async fn download_to_file_inner(&self, uri: &str, out: &Path) -> Result<u64, RedfishError> {
+        let uri = uri.strip_prefix('/').unwrap_or(uri);
+        let url = format!("{}/{}", self.base_url(), uri);
+        self.circuit_check()?;
+        self.throttle().await;
+        debug!("TX GET {url}");
+        let mut req_b = self.http_client.get(&url);
+        let login = self.login();
+        if let Some(user) = &login.user {
+            req_b = req_b.basic_auth(
+                user,
+                login.password.as_ref().map(SecretString::expose_secret),
+            );
+        }
+        let started = Instant::now();
+        let response = req_b.send().await;
+        self.record_request(&Method::GET, uri, &response, started);
+        let response = response.map_err(|e| RedfishError::NetworkError {
+            url: url.clone(),
+            source: e,
+        });
+        self.circuit_record_response(&response);
+        let mut response = response?;
+
+        let status_code = response.status();
+        debug!("RX {status_code}");
+        if !status_code.is_success() {
+            // An error page is read like any other response body, so it is capped too
+            let response_body = self.read_body(&url, response).await?;
+            return Err(RedfishError::HTTPErrorCode {
+                url,
+                status_code,
+                response_body,
+            });
+        }
+
+        let mut file = tokio::fs::File::create(out)
+            .await
+            .map_err(|e| RedfishError::FileError(format!("{}: {e}", out.display())))?;
+        let mut written = 0u64;
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| RedfishError::NetworkError {
+                url: url.clone(),
+                source: e,
+            })?
+        {
+            file.write_all(&chunk)
+                .await
+                .map_err(|e| RedfishError::FileError(format!("{}: {e}", out.display())))?;
+            written += chunk.len() as u64;
+        }
+        file.flush()
+            .await
+            .map_err(|e| RedfishError::FileError(format!("{}: {e}", out.display())))?;
+        debug!("RX {written} bytes into {}", out.display());
+        Ok(written)
+    }
+
     // req_multipart_firmware_upload does a Redfish request for a multipart based firmware upload.
     pub async fn req_update_firmware_multipart(
         &self,
@@ -587,8 +1528,29 @@ impl RedfishHttpClient {
         api: &str,
         drop_redfish_url_part: bool,
         timeout: Duration,
-    ) -> Result<(StatusCode, Option<String>, String), RedfishError> {
-        let user = match &self.endpoint.user {
+    ) -> Result<ActionResponse, RedfishError> {
+        self.cancellable(self.req_update_firmware_multipart_inner(
+            filename,
+            file,
+            parameters,
+            api,
+            drop_redfish_url_part,
+            timeout,
+        ))
+        .await
+    }
+
+    async fn req_update_firmware_multipart_inner(
+        &self,
+        filename: &Path,
+        file: tokio::fs::File,
+        parameters: String,
+        api: &str,
+        drop_redfish_url_part: bool,
+        timeout: Duration,
+    ) -> Result<ActionResponse, RedfishError> {
+        let login = self.login();
+        let user = match &login.user {
             Some(user) => user,
             None => return Err(RedfishError::NotSupported("User not specified".to_string())),
         };
@@ -609,18 +1571,15 @@ impl RedfishHttpClient {
         } else {
             format!("{}/{}", REDFISH_ENDPOINT, api)
         };
-        let url = match self.endpoint.port {
-            Some(p) => format!(
-                "https://{}:{}/{}",
-                self.endpoint.host, p, with_redfish_endpoint
-            ),
-            None => format!("https://{}/{}", self.endpoint.host, with_redfish_endpoint),
-        };
+        let url = format!("{}/{}", self.base_url(), with_redfish_endpoint);
 
         let length = filename
             .metadata()
             .map_err(|e| RedfishError::FileError(e.to_string()))?
             .len();
+        self.circuit_check()?;
+        self.throttle().await;
+        let started = Instant::now();
         let response = self
             .http_client
             .post(url.clone())
@@ -657,31 +1616,28 @@ impl RedfishHttpClient {
                             .file_name(basename.clone()),
                     ),
             )
-            .basic_auth(user, self.endpoint.password.as_ref())
+            .basic_auth(
+                user,
+                login.password.as_ref().map(SecretString::expose_secret),
+            )
             .send()
-            .await
-            .map_err(|e| RedfishError::NetworkError {
-                url: url.to_string(),
-                source: e,
-            })?;
+            .await;
+        self.record_request(&Method::POST, api, &response, started);
+        let response = response.map_err(|e| RedfishError::NetworkError {
+            url: url.to_string(),
+            source: e,
+        });
+        self.circuit_record_response(&response);
+        let response = response?;
 
         let status_code = response.status();
         debug!("RX {status_code}");
 
         // Some (or all?) implementations will return the task ID in the Location header, with an empty body.
-        let loc = response
-            .headers()
-            .get("Location")
-            .map(|x| x.to_str().unwrap_or_default().to_string());
+        let headers = response.headers().clone();
 
         // read the body even if not status 2XX, because BMCs give useful error messages as JSON
-        let response_body = response
-            .text()
-            .await
-            .map_err(|e| RedfishError::NetworkError {
-                url: url.to_string(),
-                source: e,
-            })?;
+        let response_body = self.read_body(&url, response).await?;
         debug!("RX {status_code} {}", truncate(&response_body, 1500));
 
         if !status_code.is_success() {
@@ -692,7 +1648,526 @@ impl RedfishHttpClient {
             });
         }
 
-        Ok((status_code, loc, response_body))
+        // Some BMCs answer an accepted upload with a plain text or HTML page, which only the
+        // status and Location header matter for
+        let body = serde_json::from_str(&response_body).unwrap_or_default();
+        Ok(ActionResponse::new(status_code, &headers, body))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RateLimit {
+    requests_per_second: f64,
+    burst: u32,
+}
+
+/// Delays between polls: `initial`, growing by `multiplier` after each poll up to `max`. Each
+/// delay is shortened by a random fraction of up to `jitter` (0.0 to 1.0), so clients started
+/// together across a fleet drift apart instead of polling their BMCs in lockstep.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Backoff {
+    pub initial: Duration,
+    pub max: Duration,
+    pub multiplier: f64,
+    pub jitter: f64,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_secs(1),
+            max: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: 0.2,
+        }
+    }
+}
+
+impl Backoff {
+    /// The same delay every time
+    pub fn fixed(delay: Duration) -> Self {
+        Self {
+            initial: delay,
+            max: delay,
+            multiplier: 1.0,
+            jitter: 0.0,
+        }
+    }
+
+    /// Delay after poll number `attempt` (from 0). `sample` is uniform in [0, 1).
+    fn delay(&self, attempt: u32, sample: f64) -> Duration {
+        let max = self.max.max(self.initial);
+        let grown = self.initial.as_secs_f64() * self.multiplier.max(1.0).powi(attempt as i32);
+        let delay = grown.min(max.as_secs_f64());
+        // A NaN jitter in the public fields would make from_secs_f64 panic
+        Duration::try_from_secs_f64(delay * (1.0 - self.jitter.clamp(0.0, 1.0) * sample))
+            .unwrap_or(max)
+    }
+}
+
+// A number in [0, 1) that differs between calls and processes. Good enough for jitter, and
+// saves a dependency on rand.
+fn jitter_sample() -> f64 {
+    use std::hash::{BuildHasher, Hasher};
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Receives every HTTP request a client makes, for counters and latency histograms, see
+/// RedfishClientPoolBuilder::metrics. Called on the request path, so it should not block.
+pub trait Metrics: Send + Sync + std::fmt::Debug {
+    /// A request got a response, of any status, or failed without one
+    fn request(&self, request: &RequestMetric);
+
+    /// A request is about to be sent again, after a dropped connection or, for a PATCH, a
+    /// 412 Precondition Failed
+    fn retry(&self, _host: &str, _method: &Method, _api: &str) {}
+}
+
+/// One HTTP request, see `Metrics::request`
+#[derive(Debug, Clone)]
+pub struct RequestMetric<'a> {
+    pub host: &'a str,
+    pub method: &'a Method,
+    /// Path below /redfish/v1, such as Systems/1/Bios. Contains ids, so collapse it before
+    /// using it as a label.
+    pub api: &'a str,
+    /// None if there was no response
+    pub status: Option<StatusCode>,
+    pub timed_out: bool,
+    /// From sending the request until the response headers arrived
+    pub elapsed: Duration,
+}
+
+/// Time source for the waits between polls, see RedfishClientPoolBuilder::clock
+#[async_trait::async_trait]
+pub trait Clock: Send + Sync + std::fmt::Debug {
+    fn now(&self) -> Instant;
+    async fn sleep(&self, duration: Duration);
+}
+
+/// Wall-clock time and tokio sleep
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioClock;
+
+#[async_trait::async_trait]
+impl Clock for TokioClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::sleep(duration).await
+    }
+}
+
+/// A clock for tests: sleeping moves its time forward by the requested duration and returns
+/// at once, so a poll that times out after an hour finishes in microseconds.
+#[derive(Debug)]
+pub struct ManualClock {
+    now: Mutex<Instant>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self {
+            now: Mutex::new(Instant::now()),
+        }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) += duration;
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+        // Still a yield point, like a real sleep
+        tokio::task::yield_now().await
+    }
+}
+
+/// Token bucket pacing requests to one endpoint
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: RateLimit,
+    // (available tokens, last refill). Tokens go negative when callers queue up.
+    state: Mutex<(f64, Instant)>,
+}
+
+impl RateLimiter {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            state: Mutex::new((limit.burst as f64, Instant::now())),
+        }
+    }
+
+    /// Takes a token, returning how long the caller must wait before using it.
+    fn reserve(&self, now: Instant) -> Duration {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let (tokens, last) = *state;
+        let refilled =
+            now.saturating_duration_since(last).as_secs_f64() * self.limit.requests_per_second;
+        let tokens = (tokens + refilled).min(self.limit.burst as f64) - 1.0;
+        *state = (tokens, now.max(last));
+        if tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            // A tiny rate can put the wait past what a Duration holds
+            Duration::try_from_secs_f64(-tokens / self.limit.requests_per_second)
+                .unwrap_or(Duration::MAX)
+        }
+    }
+
+    async fn acquire(&self) {
+        let wait = self.reserve(Instant::now());
+        if !wait.is_zero() {
+            debug!("Rate limited, waiting {wait:?}");
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// Token bucket shared by every client of a pool, each retry takes a token
+#[derive(Debug)]
+pub struct RetryBudget {
+    limit: RateLimit,
+    // (available tokens, last refill)
+    state: Mutex<(f64, Instant)>,
+}
+
+impl RetryBudget {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            state: Mutex::new((limit.burst as f64, Instant::now())),
+        }
+    }
+
+    /// Takes a token if there is one. Unlike `RateLimiter` it never waits for one.
+    fn try_acquire(&self, now: Instant) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let (tokens, last) = *state;
+        let refilled =
+            now.saturating_duration_since(last).as_secs_f64() * self.limit.requests_per_second;
+        let tokens = (tokens + refilled).min(self.limit.burst as f64);
+        let allowed = tokens >= 1.0;
+        *state = (if allowed { tokens - 1.0 } else { tokens }, now.max(last));
+        allowed
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CircuitBreakerConfig {
+    failure_threshold: u32,
+    cool_down: Duration,
+}
+
+/// Failure-counting circuit breaker for one endpoint
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    // (consecutive failures, open until)
+    state: Mutex<(u32, Option<Instant>)>,
+}
+
+impl CircuitBreaker {
+    fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new((0, None)),
+        }
+    }
+
+    /// Errors while the circuit is open. Once the cool-down has passed one caller is let
+    /// through as a probe, and the circuit stays open for everyone else until it reports back
+    /// or another cool-down passes.
+    fn check(&self, host: &str, now: Instant) -> Result<(), RedfishError> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match state.1 {
+            Some(until) if now < until => Err(RedfishError::CircuitOpen {
+                host: host.to_string(),
+                retry_in: until - now,
+            }),
+            Some(_) => {
+                state.1 = Some(now + self.config.cool_down);
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn record(&self, failed: bool, now: Instant) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if !failed {
+            *state = (0, None);
+            return;
+        }
+        state.0 = state.0.saturating_add(1);
+        if state.0 >= self.config.failure_threshold {
+            state.1 = Some(now + self.config.cool_down);
+        }
+    }
+}
+
+// Whether a result means the BMC itself is unhealthy, as opposed to rejecting our request
+fn is_endpoint_failure<T>(result: &Result<T, RedfishError>) -> bool {
+    match result {
+        Err(RedfishError::NetworkError { .. }) | Err(RedfishError::ReqwestError(_)) => true,
+        Err(RedfishError::HTTPErrorCode { status_code, .. }) => status_code.is_server_error(),
+        _ => false,
+    }
+}
+
+// If-Match for the resource read with these headers and `@odata.etag`, if it has an ETag
+fn if_match(
+    headers: Option<&HeaderMap>,
+    odata_etag: Option<&serde_json::Value>,
+) -> Vec<(HeaderName, String)> {
+    headers
+        .and_then(|h| h.get(ETAG))
+        .and_then(|v| v.to_str().ok())
+        .or_else(|| odata_etag.and_then(|v| v.as_str()))
+        .map(|etag| vec![(IF_MATCH, etag.to_string())])
+        .unwrap_or_default()
+}
+
+/// What the BMC did with an action or other POST. Depending on the BMC and the action the same
+/// request may finish at once, run in the background behind a task or job that is only
+/// named in the Location header or returned in the body, or create a new resource. Read it
+/// from here rather than from the raw response.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActionResponse {
+    /// Done, with this response body
+    Immediate(serde_json::Value),
+    /// Running in the background
+    Accepted(TaskMonitor),
+    /// 201 Created. `uri` is the new resource, from the Location header or else the body's
+    /// @odata.id. `body` is the resource if the BMC returned it, else null.
+    Created {
+        uri: String,
+        body: serde_json::Value,
+    },
+    /// Done, and there was no response body
+    NoContent,
+}
+
+/// Where to follow an action that runs in the background
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskMonitor {
+    /// The Location header, else the TaskMonitor of the task in the body, else the task itself
+    pub uri: String,
+    /// The Task the BMC returned in the body, if any. Tasks in a 200 response can still be
+    /// running, so check its TaskState.
+    pub task: Option<serde_json::Value>,
+}
+
+impl ActionResponse {
+    pub(crate) fn new(
+        status_code: StatusCode,
+        headers: &HeaderMap,
+        body: serde_json::Value,
+    ) -> Self {
+        let location = headers
+            .get(reqwest::header::LOCATION)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let is_task = body
+            .get("@odata.type")
+            .and_then(|t| t.as_str())
+            .is_some_and(|t| t.starts_with("#Task."));
+        if is_task {
+            let uri = location.clone().or_else(|| {
+                ["TaskMonitor", "@odata.id"]
+                    .iter()
+                    .find_map(|key| body.get(*key).and_then(|v| v.as_str()))
+                    .map(str::to_string)
+            });
+            if let Some(uri) = uri {
+                return ActionResponse::Accepted(TaskMonitor {
+                    uri,
+                    task: Some(body),
+                });
+            }
+        }
+        // iDRAC names the job in Location whatever the status, often next to an
+        // @Message.ExtendedInfo body, so a Location naming a task or job always wins
+        if let Some(monitor) = location
+            .as_ref()
+            .map(|uri| TaskMonitor {
+                uri: uri.clone(),
+                task: None,
+            })
+            .filter(|monitor| monitor.task_id().is_some())
+        {
+            return ActionResponse::Accepted(monitor);
+        }
+        if status_code == StatusCode::CREATED {
+            let uri = location.or_else(|| {
+                body.get("@odata.id")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+            });
+            if let Some(uri) = uri {
+                return ActionResponse::Created { uri, body };
+            }
+        } else if status_code == StatusCode::ACCEPTED
+            // Dell names the job it created in the Location header of an empty 200
+            || body.is_null()
+        {
+            if let Some(uri) = location {
+                return ActionResponse::Accepted(TaskMonitor { uri, task: None });
+            }
+        }
+        if body.is_null() {
+            ActionResponse::NoContent
+        } else {
+            ActionResponse::Immediate(body)
+        }
+    }
+
+    pub fn task_monitor(&self) -> Option<&TaskMonitor> {
+        match self {
+            ActionResponse::Accepted(monitor) => Some(monitor),
+            _ => None,
+        }
+    }
+
+    /// The response body: the result of an immediate action or the created resource.
+    /// None if the BMC sent no body, or the action is still running.
+    pub fn body(&self) -> Option<&serde_json::Value> {
+        match self {
+            ActionResponse::Immediate(body) => Some(body),
+            ActionResponse::Created { body, .. } if !body.is_null() => Some(body),
+            _ => None,
+        }
+    }
+
+    /// The URI of the resource a 201 Created made
+    pub fn created_uri(&self) -> Option<&str> {
+        match self {
+            ActionResponse::Created { uri, .. } => Some(uri),
+            _ => None,
+        }
+    }
+
+    /// `created_uri` relative to /redfish/v1/, to GET the new resource with
+    pub fn created_path(&self) -> Option<&str> {
+        self.created_uri().map(api_path)
+    }
+
+    /// The id of the task or job the action started, for `Redfish::get_task` or
+    /// `get_job_state`. Errors with `RedfishError::NoTask` if it finished at once, or if the
+    /// BMC only gave an opaque task monitor, see `TaskMonitor::task_id`.
+    pub fn task_id(&self, url: &str) -> Result<String, RedfishError> {
+        self.task_monitor()
+            .and_then(TaskMonitor::task_id)
+            .ok_or_else(|| RedfishError::NoTask {
+                url: url.to_string(),
+            })
+    }
+}
+
+impl TaskMonitor {
+    /// The Id of the task in the body, else the id in a URI of the form `.../Tasks/<id>` or
+    /// `.../Jobs/<id>`, optionally followed by /Monitor. A monitor such as
+    /// /redfish/v1/TaskService/TaskMonitors/<opaque> names no task, so this is None and the
+    /// monitor has to be polled instead.
+    pub fn task_id(&self) -> Option<String> {
+        if let Some(id) = self
+            .task
+            .as_ref()
+            .and_then(|t| t.get("Id"))
+            .and_then(|id| id.as_str())
+        {
+            return Some(id.to_string());
+        }
+        let uri = self.uri.trim_end_matches('/');
+        let uri = uri.strip_suffix("/Monitor").unwrap_or(uri);
+        let (collection, id) = uri.rsplit_once('/')?;
+        let collection = collection.rsplit('/').next().unwrap_or_default();
+        (!id.is_empty() && (collection == "Tasks" || collection == "Jobs")).then(|| id.to_string())
+    }
+
+    /// The monitor's URI relative to /redfish/v1/, as the client's request methods take it
+    pub fn path(&self) -> &str {
+        api_path(&self.uri)
+    }
+}
+
+// A URI, absolute or not, relative to /redfish/v1/
+fn api_path(uri: &str) -> &str {
+    let endpoint = format!("/{REDFISH_ENDPOINT}/");
+    match uri.find(&endpoint) {
+        Some(start) => &uri[start + endpoint.len()..],
+        None => uri.trim_start_matches('/'),
+    }
+}
+
+/// Deserializes a response body. On failure the error names the JSON path of the offending
+/// field, e.g. `Members[3].Status.State`, instead of only serde's message.
+pub(crate) fn from_json_str<T: DeserializeOwned>(url: &str, body: &str) -> Result<T, RedfishError> {
+    let deserializer = &mut serde_json::Deserializer::from_str(body);
+    serde_path_to_error::deserialize(deserializer).map_err(|e| RedfishError::JsonDeserializeError {
+        url: url.to_string(),
+        path: e.path().to_string(),
+        body: body.to_string(),
+        source: e.into_inner(),
+    })
+}
+
+/// from_json_str for an already parsed value
+pub(crate) fn from_json_value<T: DeserializeOwned>(
+    url: &str,
+    value: &serde_json::Value,
+) -> Result<T, RedfishError> {
+    serde_path_to_error::deserialize(value).map_err(|e| RedfishError::JsonDeserializeError {
+        url: url.to_string(),
+        path: e.path().to_string(),
+        body: value.to_string(),
+        source: e.into_inner(),
+    })
+}
+
+/// Decodes a response body by the charset in its Content-Type: Latin-1 is transcoded, anything
+/// else is read as UTF-8. A leading UTF-8 BOM, which serde rejects, is dropped whatever the
+/// declared charset, since it means the body is really UTF-8.
+fn decode_body(body: &[u8], content_type: Option<&str>) -> String {
+    const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+    if let Some(rest) = body.strip_prefix(UTF8_BOM) {
+        return String::from_utf8_lossy(rest).into_owned();
+    }
+    let charset = content_type
+        .into_iter()
+        .flat_map(|ct| ct.split(';').skip(1))
+        .filter_map(|param| param.trim().split_once('='))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("charset"))
+        .map(|(_, value)| value.trim().trim_matches('"').to_ascii_lowercase());
+    match charset.as_deref() {
+        // Every Latin-1 byte is the code point of the same value
+        Some("iso-8859-1" | "iso_8859-1" | "latin1" | "latin-1" | "l1") => {
+            body.iter().map(|&b| b as char).collect()
+        }
+        _ => String::from_utf8_lossy(body).into_owned(),
     }
 }
 
@@ -707,3 +2182,619 @@ fn test_truncate() {
     let big = "a".repeat(2000);
     assert_eq!(truncate(&big, 1500).len(), 1500);
 }
+
+#[test]
+fn test_decode_body() {
+    let body = b"\xEF\xBB\xBF{\"Id\": \"1\"}";
+    let decoded = decode_body(body, Some("application/json"));
+    let value: serde_json::Value = from_json_str("Systems/1", &decoded).unwrap();
+    assert_eq!(value["Id"], "1");
+    // A BOM wins over a wrong charset
+    assert_eq!(
+        decode_body(body, Some("application/json; charset=ISO-8859-1")),
+        decoded
+    );
+
+    let latin1 = b"{\"Manufacturer\": \"Soci\xE9t\xE9\"}";
+    assert_eq!(
+        decode_body(latin1, Some("application/json;charset=\"iso-8859-1\"")),
+        "{\"Manufacturer\": \"Soci\u{e9}t\u{e9}\"}"
+    );
+    // Undeclared, so read as UTF-8
+    assert_eq!(
+        decode_body(latin1, None),
+        "{\"Manufacturer\": \"Soci\u{fffd}t\u{fffd}\"}"
+    );
+}
+
+#[test]
+fn test_action_response() {
+    let task = serde_json::json!({
+        "@odata.id": "/redfish/v1/TaskService/Tasks/4",
+        "@odata.type": "#Task.v1_4_3.Task",
+        "Id": "4",
+        "TaskState": "Running"
+    });
+    let mut headers = HeaderMap::new();
+    let response = ActionResponse::new(StatusCode::ACCEPTED, &headers, task.clone());
+    assert_eq!(
+        response.task_monitor().map(|m| m.uri.as_str()),
+        Some("/redfish/v1/TaskService/Tasks/4")
+    );
+    assert_eq!(response.task_id("url").unwrap(), "4");
+    // An inline task in a 200 is followed the same way
+    let response = ActionResponse::new(StatusCode::OK, &headers, task.clone());
+    assert_eq!(response.task_id("url").unwrap(), "4");
+
+    headers.insert(
+        reqwest::header::LOCATION,
+        HeaderValue::from_static("/redfish/v1/TaskService/Tasks/JID_123/Monitor"),
+    );
+    let response = ActionResponse::new(StatusCode::ACCEPTED, &headers, task);
+    assert_eq!(
+        response.task_monitor().map(|m| m.uri.as_str()),
+        Some("/redfish/v1/TaskService/Tasks/JID_123/Monitor")
+    );
+    assert_eq!(response.task_id("url").unwrap(), "4");
+    // Location only, the id comes from the monitor's URI
+    let response = ActionResponse::new(StatusCode::ACCEPTED, &headers, serde_json::Value::Null);
+    assert_eq!(response.task_id("url").unwrap(), "JID_123");
+    let response = ActionResponse::new(StatusCode::OK, &headers, serde_json::Value::Null);
+    assert_eq!(response.task_id("url").unwrap(), "JID_123");
+
+    // A spec task monitor is opaque, it doesn't name the task
+    let mut opaque = HeaderMap::new();
+    opaque.insert(
+        reqwest::header::LOCATION,
+        HeaderValue::from_static("/redfish/v1/TaskService/TaskMonitors/Z5nQ0"),
+    );
+    let response = ActionResponse::new(StatusCode::ACCEPTED, &opaque, serde_json::Value::Null);
+    assert_eq!(
+        response.task_monitor().map(TaskMonitor::path),
+        Some("TaskService/TaskMonitors/Z5nQ0")
+    );
+    assert!(matches!(
+        response.task_id("url"),
+        Err(RedfishError::NoTask { .. })
+    ));
+    // Dell jobs
+    let mut job = HeaderMap::new();
+    job.insert(
+        reqwest::header::LOCATION,
+        HeaderValue::from_static("/redfish/v1/Managers/iDRAC.Embedded.1/Jobs/JID_456"),
+    );
+    let response = ActionResponse::new(StatusCode::OK, &job, serde_json::Value::Null);
+    assert_eq!(response.task_id("url").unwrap(), "JID_456");
+    // iDRAC also sends the job's Location with a 200 carrying ExtendedInfo
+    let info = serde_json::json!({"@Message.ExtendedInfo": [{
+        "Message": "Successfully Completed Request",
+        "MessageId": "Base.1.8.Success"
+    }]});
+    let response = ActionResponse::new(StatusCode::OK, &job, info);
+    assert_eq!(response.task_id("url").unwrap(), "JID_456");
+
+    // A created resource is not a task, but keeps its Location
+    let mut created = HeaderMap::new();
+    created.insert(
+        reqwest::header::LOCATION,
+        HeaderValue::from_static("/redfish/v1/AccountService/Accounts/3"),
+    );
+    let response = ActionResponse::new(StatusCode::CREATED, &created, serde_json::Value::Null);
+    assert_eq!(
+        response.created_uri(),
+        Some("/redfish/v1/AccountService/Accounts/3")
+    );
+    assert_eq!(response.body(), None);
+    assert!(matches!(
+        response.task_id("url"),
+        Err(RedfishError::NoTask { .. })
+    ));
+    let account = serde_json::json!({
+        "@odata.id": "/redfish/v1/AccountService/Accounts/4",
+        "Id": "4"
+    });
+    let response = ActionResponse::new(StatusCode::CREATED, &HeaderMap::new(), account.clone());
+    assert_eq!(
+        response,
+        ActionResponse::Created {
+            uri: "/redfish/v1/AccountService/Accounts/4".to_string(),
+            body: account.clone(),
+        }
+    );
+    assert_eq!(response.body(), Some(&account));
+    let body = serde_json::json!({"LCStatus": "Ready"});
+    assert_eq!(
+        ActionResponse::new(StatusCode::OK, &HeaderMap::new(), body.clone()),
+        ActionResponse::Immediate(body)
+    );
+    assert_eq!(
+        ActionResponse::new(
+            StatusCode::NO_CONTENT,
+            &HeaderMap::new(),
+            serde_json::Value::Null
+        ),
+        ActionResponse::NoContent
+    );
+}
+
+#[test]
+fn test_rate_limiter() {
+    let limiter = RateLimiter::new(RateLimit {
+        requests_per_second: 2.0,
+        burst: 2,
+    });
+    let now = Instant::now();
+    // The burst goes through immediately, then requests are spaced 500ms apart
+    assert_eq!(limiter.reserve(now), Duration::ZERO);
+    assert_eq!(limiter.reserve(now), Duration::ZERO);
+    assert_eq!(limiter.reserve(now), Duration::from_millis(500));
+    assert_eq!(limiter.reserve(now), Duration::from_millis(1000));
+    // Idle time refills the bucket, but never beyond the burst
+    let later = now + Duration::from_secs(10);
+    assert_eq!(limiter.reserve(later), Duration::ZERO);
+    assert_eq!(limiter.reserve(later), Duration::ZERO);
+    assert_eq!(limiter.reserve(later), Duration::from_millis(500));
+
+    let limiter = RateLimiter::new(RateLimit {
+        requests_per_second: f64::MIN_POSITIVE,
+        burst: 1,
+    });
+    assert_eq!(limiter.reserve(now), Duration::ZERO);
+    assert_eq!(limiter.reserve(now), Duration::MAX);
+}
+
+#[test]
+fn test_retry_budget() {
+    let budget = RetryBudget::new(RateLimit {
+        requests_per_second: 0.5,
+        burst: 2,
+    });
+    let now = Instant::now();
+    // The burst is spent, then retries are refused rather than delayed
+    assert!(budget.try_acquire(now));
+    assert!(budget.try_acquire(now));
+    assert!(!budget.try_acquire(now));
+    assert!(!budget.try_acquire(now + Duration::from_secs(1)));
+    assert!(budget.try_acquire(now + Duration::from_secs(2)));
+    // Idle time refills the bucket, but never beyond the burst
+    let later = now + Duration::from_secs(60);
+    assert!(budget.try_acquire(later));
+    assert!(budget.try_acquire(later));
+    assert!(!budget.try_acquire(later));
+
+    let off = RetryBudget::new(RateLimit {
+        requests_per_second: 1.0,
+        burst: 0,
+    });
+    assert!(!off.try_acquire(now + Duration::from_secs(60)));
+}
+
+#[test]
+fn test_circuit_breaker() {
+    let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+        failure_threshold: 2,
+        cool_down: Duration::from_secs(30),
+    });
+    let now = Instant::now();
+    assert!(breaker.check("bmc", now).is_ok());
+    breaker.record(true, now);
+    assert!(breaker.check("bmc", now).is_ok());
+    breaker.record(true, now);
+    assert!(matches!(
+        breaker.check("bmc", now + Duration::from_secs(10)),
+        Err(RedfishError::CircuitOpen { retry_in, .. }) if retry_in == Duration::from_secs(20)
+    ));
+
+    // After the cool-down a single probe goes through
+    let later = now + Duration::from_secs(31);
+    assert!(breaker.check("bmc", later).is_ok());
+    assert!(breaker.check("bmc", later).is_err());
+    // A failed probe re-opens the circuit, a successful one closes it
+    breaker.record(true, later);
+    assert!(breaker.check("bmc", later).is_err());
+    let later = later + Duration::from_secs(31);
+    assert!(breaker.check("bmc", later).is_ok());
+    breaker.record(false, later);
+    assert!(breaker.check("bmc", later).is_ok());
+    assert!(breaker.check("bmc", later).is_ok());
+}
+
+#[tokio::test]
+async fn test_download_5xx_opens_circuit() {
+    use crate::fake_bmc::{FakeBmc, Response};
+
+    let fake = FakeBmc::start::<&str>(&[])
+        .await
+        .with_handler(|_, _| Some(Response::status(503)));
+    let breaker = Arc::new(CircuitBreaker::new(CircuitBreakerConfig {
+        failure_threshold: 1,
+        cool_down: Duration::from_secs(30),
+    }));
+    let client = fake.client().with_circuit_breaker(Some(breaker));
+    let out = std::env::temp_dir().join(format!("download-5xx-{}", std::process::id()));
+    let err = client
+        .download_to_file("/redfish/v1/Dump", &out)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RedfishError::HTTPErrorCode { .. }), "{err:?}");
+    let err = client
+        .download_to_file("/redfish/v1/Dump", &out)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RedfishError::CircuitOpen { .. }), "{err:?}");
+}
+
+#[test]
+fn test_json_deserialize_error_path() {
+    #[derive(serde::Deserialize, Debug)]
+    #[serde(rename_all = "PascalCase")]
+    #[allow(dead_code)]
+    struct Member {
+        status: crate::model::ResourceStatus,
+    }
+    #[derive(serde::Deserialize, Debug)]
+    #[serde(rename_all = "PascalCase")]
+    #[allow(dead_code)]
+    struct Collection {
+        members: Vec<Member>,
+    }
+
+    let body = r#"{"Members": [{"Status": {}}, {"Status": {"State": 42}}]}"#;
+    let err = from_json_str::<Collection>("Chassis", body).unwrap_err();
+    let RedfishError::JsonDeserializeError { path, .. } = &err else {
+        panic!("unexpected error {err}");
+    };
+    assert_eq!(path, "Members[1].Status.State");
+
+    let value: serde_json::Value = serde_json::from_str(body).unwrap();
+    let err = from_json_value::<Collection>("Chassis", &value).unwrap_err();
+    assert!(err.to_string().contains("at Members[1].Status.State"));
+}
+
+#[tokio::test]
+async fn test_cancellation() {
+    let token = CancellationToken::new();
+    let client = RedfishHttpClient::new(
+        HttpClient::new(),
+        Endpoint {
+            host: "192.0.2.1".to_string(),
+            ..Default::default()
+        },
+        Vec::new(),
+    )
+    .with_cancellation(Some(token.clone()));
+
+    // A wait in progress is cut short
+    let cancel = token.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        cancel.cancel();
+    });
+    let started = Instant::now();
+    assert!(matches!(
+        client.sleep(Duration::from_secs(3600)).await,
+        Err(RedfishError::Cancelled)
+    ));
+    assert!(started.elapsed() < Duration::from_secs(60));
+
+    // Later calls fail without touching the network
+    assert!(matches!(
+        client.get::<serde_json::Value>("Systems").await,
+        Err(RedfishError::Cancelled)
+    ));
+}
+
+#[tokio::test]
+async fn test_manual_clock() {
+    let clock = Arc::new(ManualClock::new());
+    let client = RedfishHttpClient::new(HttpClient::new(), Endpoint::default(), Vec::new())
+        .with_clock(clock.clone());
+    let started = client.now();
+    let wall = Instant::now();
+    client.sleep(Duration::from_secs(3600)).await.unwrap();
+    client.sleep(Duration::from_secs(1800)).await.unwrap();
+    assert_eq!(client.now() - started, Duration::from_secs(5400));
+    assert!(wall.elapsed() < Duration::from_secs(60));
+}
+
+#[test]
+fn test_backoff_delay() {
+    let backoff = Backoff {
+        initial: Duration::from_secs(1),
+        max: Duration::from_secs(10),
+        multiplier: 2.0,
+        jitter: 0.5,
+    };
+    let delays: Vec<u64> = (0..6).map(|a| backoff.delay(a, 0.0).as_secs()).collect();
+    assert_eq!(delays, [1, 2, 4, 8, 10, 10]);
+    // Jitter only ever shortens the delay
+    assert_eq!(backoff.delay(3, 0.5), Duration::from_secs(6));
+    assert_eq!(
+        Backoff::fixed(Duration::from_secs(5)).delay(9, 0.9),
+        Duration::from_secs(5)
+    );
+    let sample = jitter_sample();
+    assert!((0.0..1.0).contains(&sample));
+    let broken = Backoff {
+        jitter: f64::NAN,
+        ..backoff
+    };
+    assert_eq!(broken.delay(3, 0.0), Duration::from_secs(10));
+}
+
+#[tokio::test]
+async fn test_poll_until() {
+    let clock = Arc::new(ManualClock::new());
+    let client = RedfishHttpClient::new(HttpClient::new(), Endpoint::default(), Vec::new())
+        .with_clock(clock.clone());
+    let backoff = Backoff {
+        jitter: 0.0,
+        ..Backoff::default()
+    };
+
+    // Done on the fourth poll, after sleeping 1 + 2 + 4 seconds
+    let started = client.now();
+    let mut polls = 0;
+    let result = client
+        .poll_until("ready", backoff, Duration::from_secs(60), || {
+            polls += 1;
+            let done = polls == 4;
+            async move { Ok(done.then_some(polls)) }
+        })
+        .await;
+    assert_eq!(result.unwrap(), 4);
+    assert_eq!(client.now() - started, Duration::from_secs(7));
+
+    // Never done: 1 + 2 + 4 + 8 + 16 = 31s slept, the next 30s sleep would pass the minute
+    let started = client.now();
+    let result = client
+        .poll_until("never", backoff, Duration::from_secs(60), || async {
+            Ok(None::<()>)
+        })
+        .await;
+    assert!(matches!(result, Err(RedfishError::Timeout(msg)) if msg == "never after 60s"));
+    assert_eq!(client.now() - started, Duration::from_secs(31));
+}
+
+#[tokio::test]
+async fn test_for_each_with_progress() {
+    // A cancelled pool fails every endpoint at client creation, without any network traffic
+    let token = CancellationToken::new();
+    token.cancel();
+    let pool = RedfishClientPool::builder()
+        .cancellation(token)
+        .build()
+        .unwrap();
+    let endpoints: Vec<Endpoint> = (1..=5)
+        .map(|i| Endpoint {
+            host: format!("192.0.2.{i}"),
+            ..Default::default()
+        })
+        .collect();
+    let mut seen = Vec::new();
+    let results = pool
+        .for_each_with_progress(
+            endpoints.clone(),
+            2,
+            |_client| async { Ok(()) },
+            |p| seen.push(p.clone()),
+        )
+        .await;
+    assert_eq!(results.len(), 5);
+    for ((endpoint, result), expected) in results.iter().zip(&endpoints) {
+        assert_eq!(endpoint, expected);
+        assert!(matches!(result, Err(RedfishError::Cancelled)));
+    }
+    assert_eq!(
+        seen.iter().map(|p| p.done).collect::<Vec<_>>(),
+        [1, 2, 3, 4, 5]
+    );
+    let last = seen.last().unwrap();
+    assert_eq!((last.done, last.failed, last.total), (5, 5, 5));
+    assert!(!last.last_succeeded);
+}
+
+#[test]
+fn test_set_credentials() {
+    let pool = RedfishClientPool::builder().build().unwrap();
+    let endpoint = Endpoint {
+        host: "10.0.0.1".to_string(),
+        user: Some("root".to_string()),
+        password: Some("old".into()),
+        ..Default::default()
+    };
+    let s = pool.create_standard_client(endpoint.clone()).unwrap();
+    let other = pool
+        .create_standard_client(Endpoint {
+            host: "10.0.0.2".to_string(),
+            ..endpoint.clone()
+        })
+        .unwrap();
+    pool.set_credentials(&endpoint, "admin", &"new".into());
+
+    // The client already in use logs in with the new credentials, another BMC's doesn't
+    let login = s.client.login();
+    assert_eq!(login.user.as_deref(), Some("admin"));
+    assert_eq!(login.password.unwrap().expose_secret(), "new");
+    assert_eq!(
+        other.client.login().password.unwrap().expose_secret(),
+        "old"
+    );
+
+    // A client created afterwards with the stale endpoint doesn't undo the change
+    let later = pool.create_standard_client(endpoint.clone()).unwrap();
+    assert_eq!(
+        later.client.login().password.unwrap().expose_secret(),
+        "new"
+    );
+    assert_eq!(s.client.login().password.unwrap().expose_secret(), "new");
+}
+
+#[test]
+fn test_endpoint_base_url() {
+    let url = |host: &str, port: Option<u16>| {
+        Endpoint {
+            host: host.to_string(),
+            port,
+            ..Default::default()
+        }
+        .base_url()
+    };
+    assert_eq!(url("10.0.0.1", None), "https://10.0.0.1");
+    assert_eq!(url("127.0.0.1:8733", None), "https://127.0.0.1:8733");
+    assert_eq!(
+        url("bmc.example.com:8443", None),
+        "https://bmc.example.com:8443"
+    );
+    assert_eq!(
+        url("bmc.example.com", Some(8443)),
+        "https://bmc.example.com:8443"
+    );
+    assert_eq!(url("2001:db8::1", None), "https://[2001:db8::1]");
+    assert_eq!(url("[2001:db8::1]", Some(443)), "https://[2001:db8::1]:443");
+    assert_eq!(url("::ffff:10.0.0.1", None), "https://[::ffff:10.0.0.1]");
+    assert_eq!(
+        url("fe80::1%eth0", Some(443)),
+        "https://[fe80::1%25eth0]:443"
+    );
+    assert_eq!(url("[fe80::1%25eth0]", None), "https://[fe80::1%25eth0]");
+
+    let parsed =
+        reqwest::Url::parse(&format!("{}/redfish/v1", url("2001:db8::1", Some(8443)))).unwrap();
+    assert_eq!(parsed.host_str(), Some("[2001:db8::1]"));
+    assert_eq!(parsed.port(), Some(8443));
+}
+
+#[tokio::test]
+async fn test_metrics() {
+    #[derive(Debug, Default)]
+    struct Recorder {
+        requests: Mutex<Vec<(Method, String, Option<StatusCode>)>>,
+        retries: Mutex<usize>,
+    }
+    impl Metrics for Recorder {
+        fn request(&self, request: &RequestMetric) {
+            self.requests.lock().unwrap().push((
+                request.method.clone(),
+                request.api.to_string(),
+                request.status,
+            ));
+        }
+        fn retry(&self, _host: &str, _method: &Method, _api: &str) {
+            *self.retries.lock().unwrap() += 1;
+        }
+    }
+
+    let recorder = Arc::new(Recorder::default());
+    let client = RedfishHttpClient::new(
+        HttpClient::new(),
+        Endpoint {
+            // Nothing listens there, the connection is refused
+            host: "127.0.0.1".to_string(),
+            port: Some(1),
+            ..Default::default()
+        },
+        Vec::new(),
+    )
+    .with_metrics(Some(recorder.clone()));
+    let res: Result<(StatusCode, serde_json::Value), _> = client.get("Systems").await;
+    assert!(matches!(res, Err(RedfishError::NetworkError { .. })));
+
+    // Sent, retried once after the network error, sent again
+    assert_eq!(
+        *recorder.requests.lock().unwrap(),
+        vec![
+            (Method::GET, "Systems".to_string(), None),
+            (Method::GET, "Systems".to_string(), None)
+        ]
+    );
+    assert_eq!(*recorder.retries.lock().unwrap(), 1);
+
+    // With the retry budget spent the error comes back without a retry
+    let client = client.with_retry_budget(Some(Arc::new(RetryBudget::new(RateLimit {
+        requests_per_second: 0.0,
+        burst: 0,
+    }))));
+    let res: Result<(StatusCode, serde_json::Value), _> = client.get("Systems").await;
+    assert!(matches!(res, Err(RedfishError::NetworkError { .. })));
+    assert_eq!(recorder.requests.lock().unwrap().len(), 3);
+    assert_eq!(*recorder.retries.lock().unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_max_response_bytes() {
+    use crate::fake_bmc::{FakeBmc, Response};
+
+    let big = "x".repeat(4096);
+    let fake = FakeBmc::start(&[("/redfish/v1/Systems/1", serde_json::json!({"Id": "1"}))])
+        .await
+        .with_handler(move |req, _| match req.path.as_str() {
+            "/redfish/v1/Big" => Some(Response::json(serde_json::json!({"Pad": big.clone()}))),
+            "/redfish/v1/BigError" | "/redfish/v1/Dump" => {
+                Some(Response::status(500).with_body(big.clone()))
+            }
+            _ => None,
+        });
+    let client = fake.client().with_max_response_bytes(Some(1024));
+
+    let ok = client.get::<serde_json::Value>("Systems/1").await;
+    assert!(ok.is_ok(), "{ok:?}");
+    for api in ["Big", "BigError"] {
+        let err = client.get::<serde_json::Value>(api).await.unwrap_err();
+        assert!(
+            matches!(err, RedfishError::ResponseTooLarge { limit: 1024, .. }),
+            "{api}: {err:?}"
+        );
+    }
+    let out = std::env::temp_dir().join(format!("max-response-bytes-{}", std::process::id()));
+    let err = client
+        .download_to_file("/redfish/v1/Dump", &out)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, RedfishError::ResponseTooLarge { .. }),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_create_user_tx_log_is_redacted() {
+    use crate::fake_bmc::{FakeBmc, Response};
+
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+    impl std::io::Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let fake = FakeBmc::start::<&str>(&[]).await.with_handler(|req, _| {
+        (req.path == "/redfish/v1/AccountService/Accounts").then(|| Response::status(201))
+    });
+    let capture = Capture::default();
+    let writer = capture.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .with_writer(move || writer.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let password = SecretString::from("hunter2-hunter2");
+    fake.standard()
+        .create_user("ops", &password, crate::RoleId::Administrator)
+        .await
+        .unwrap();
+
+    let log = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+    let tx = log
+        .lines()
+        .find(|l| l.contains("TX POST") && l.contains("AccountService/Accounts"))
+        .expect("TX line logged");
+    assert!(tx.contains("\"UserName\":\"ops\""), "{tx}");
+    assert!(!log.contains("hunter2"), "{log}");
+}
@@ -24,7 +24,6 @@ use std::str::FromStr;
 use std::{collections::HashMap, path::Path, time::Duration};
 
 use reqwest::StatusCode;
-use serde::Deserialize;
 use tokio::fs::File;
 
 use crate::model::account_service::ManagerAccount;
@@ -40,7 +39,7 @@ use crate::HostPrivilegeLevel::Restricted;
 use crate::InternalCPUModel::Embedded;
 use crate::{
     model::{
-        boot::{BootSourceOverrideEnabled, BootSourceOverrideTarget},
+        boot::{BootOrderTarget, BootSourceOverrideEnabled, BootSourceOverrideTarget},
         chassis::{Assembly, NetworkAdapter},
         oem::nvidia_dpu::{HostPrivilegeLevel, InternalCPUModel},
         sel::{LogEntry, LogEntryCollection},
@@ -49,7 +48,8 @@ use crate::{
         BootOption, ComputerSystem, Manager,
     },
     standard::RedfishStandard,
-    BiosProfileType, Collection, NetworkDeviceFunction, ODataId, Redfish, RedfishError, Resource,
+    BiosProfileType, Collection, NetworkDeviceFunction, NewBootOption, ODataId, Redfish,
+    RedfishError, Resource, SecretString,
 };
 use crate::{EnabledDisabled, JobState, MachineSetupDiff, MachineSetupStatus, RoleId};
 
@@ -80,10 +80,14 @@ impl Bmc {
 
 #[async_trait::async_trait]
 impl Redfish for Bmc {
+    fn standard(&self) -> &RedfishStandard {
+        &self.s
+    }
+
     async fn create_user(
         &self,
         username: &str,
-        password: &str,
+        password: &SecretString,
         role_id: RoleId,
     ) -> Result<(), RedfishError> {
         self.s.create_user(username, password, role_id).await
@@ -97,7 +101,7 @@ impl Redfish for Bmc {
         self.s.change_username(old_name, new_name).await
     }
 
-    async fn change_password(&self, user: &str, new: &str) -> Result<(), RedfishError> {
+    async fn change_password(&self, user: &str, new: &SecretString) -> Result<(), RedfishError> {
         self.s.change_password(user, new).await
     }
 
@@ -105,7 +109,7 @@ impl Redfish for Bmc {
     async fn change_password_by_id(
         &self,
         account_id: &str,
-        new_pass: &str,
+        new_pass: &SecretString,
     ) -> Result<(), RedfishError> {
         self.s.change_password_by_id(account_id, new_pass).await
     }
@@ -150,6 +154,23 @@ impl Redfish for Bmc {
         false
     }
 
+    fn supported_features(&self) -> crate::FeatureSet {
+        crate::FeatureSet {
+            power_metrics: true,
+            thermal_metrics: true,
+            system_event_log: true,
+            reset_bios: true,
+            secure_boot: true,
+            system_ethernet_interfaces: true,
+            chassis_network_adapters: true,
+            component_integrity: true,
+            nic_mode: true,
+            rshim: true,
+            ipmi_over_lan: true,
+            ..Default::default()
+        }
+    }
+
     async fn bmc_reset(&self) -> Result<(), RedfishError> {
         self.s.bmc_reset().await
     }
@@ -175,13 +196,25 @@ impl Redfish for Bmc {
         self.get_system_event_log().await
     }
 
+    async fn get_system_event_log_since(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        limit: Option<usize>,
+    ) -> Result<Vec<LogEntry>, RedfishError> {
+        let url = format!(
+            "Systems/{}/LogServices/SEL/Entries",
+            self.s.resolve_system_id().await?
+        );
+        self.s.fetch_system_event_log(url, since, limit).await
+    }
+
     async fn get_bmc_event_log(
         &self,
         from: Option<chrono::DateTime<chrono::Utc>>,
     ) -> Result<Vec<LogEntry>, RedfishError> {
         let url = format!(
             "Systems/{}/LogServices/EventLog/Entries",
-            self.s.system_id()
+            self.s.resolve_system_id().await?
         );
         self.s.fetch_bmc_event_log(url, from).await
     }
@@ -192,22 +225,51 @@ impl Redfish for Bmc {
 
     async fn machine_setup(
         &self,
-        _boot_interface_mac: Option<&str>,
+        boot_interface_mac: Option<&str>,
         _bios_profiles: &HashMap<
             RedfishVendor,
             HashMap<String, HashMap<BiosProfileType, HashMap<String, serde_json::Value>>>,
         >,
         _selected_profile: BiosProfileType,
-    ) -> Result<(), RedfishError> {
-        self.disable_secure_boot().await?;
-        self.set_host_privilege_level(Restricted).await?;
+    ) -> Result<crate::MachineSetupReport, RedfishError> {
+        let status = crate::MachineSetupStatus::or_apply_all(
+            self.machine_setup_status(boot_interface_mac).await,
+        );
+        // set_host_rshim and boot_once aren't in the diff, they run every time
+        let done = |key: &str| status.as_ref().is_some_and(|s| !s.has_diff(key));
+
+        let mut report = crate::MachineSetupReport::default();
+        if done("SecureBoot") {
+            report.skip("disable_secure_boot");
+        } else {
+            report.record("disable_secure_boot", self.disable_secure_boot().await)?;
+        }
+        if done("HostPrivilegeLevel") {
+            report.skip("set_host_privilege_level");
+        } else {
+            report.record(
+                "set_host_privilege_level",
+                self.set_host_privilege_level(Restricted).await,
+            )?;
+        }
         // we have found that only newer BMC fws support this action.
         // Until we re-enable DPU BMC firmware updates in preingestion,
         // ignore an error from trying to disable host rshim against
         // BF3s that have a BMC that is too old.
-        self.set_host_rshim(EnabledDisabled::Disabled).await?;
-        self.set_internal_cpu_model(Embedded).await?;
-        self.boot_once(UefiHttp).await
+        report.record(
+            "set_host_rshim",
+            self.set_host_rshim(EnabledDisabled::Disabled).await,
+        )?;
+        if done("InternalCPUModel") {
+            report.skip("set_internal_cpu_model");
+        } else {
+            report.record(
+                "set_internal_cpu_model",
+                self.set_internal_cpu_model(Embedded).await,
+            )?;
+        }
+        report.record("boot_once", self.boot_once(UefiHttp).await)?;
+        Ok(report)
     }
 
     async fn machine_setup_status(
@@ -235,13 +297,7 @@ impl Redfish for Bmc {
             });
         };
 
-        let actual = HostPrivilegeLevel::deserialize(hpl).map_err(|e| {
-            RedfishError::JsonDeserializeError {
-                url: "Systems/{}/Bios".to_string(),
-                body: hpl.to_string(),
-                source: e,
-            }
-        })?;
+        let actual: HostPrivilegeLevel = crate::network::from_json_value("Systems/{}/Bios", hpl)?;
         let expected = HostPrivilegeLevel::Restricted;
         if actual != expected {
             diffs.push(MachineSetupDiff {
@@ -260,12 +316,7 @@ impl Redfish for Bmc {
             });
         };
 
-        let actual =
-            InternalCPUModel::deserialize(icm).map_err(|e| RedfishError::JsonDeserializeError {
-                url: "Systems/{}/Bios".to_string(),
-                body: hpl.to_string(),
-                source: e,
-            })?;
+        let actual: InternalCPUModel = crate::network::from_json_value("Systems/{}/Bios", icm)?;
         let expected = InternalCPUModel::Embedded;
         if actual != expected {
             diffs.push(MachineSetupDiff {
@@ -298,7 +349,7 @@ impl Redfish for Bmc {
         self.s.lockdown_status().await
     }
 
-    async fn setup_serial_console(&self) -> Result<(), RedfishError> {
+    async fn setup_serial_console(&self) -> Result<bool, RedfishError> {
         self.s.setup_serial_console().await
     }
 
@@ -348,7 +399,7 @@ impl Redfish for Bmc {
         }
     }
 
-    async fn clear_tpm(&self) -> Result<(), RedfishError> {
+    async fn clear_tpm(&self) -> Result<crate::ApplyResult, RedfishError> {
         self.s.clear_tpm().await
     }
 
@@ -388,7 +439,7 @@ impl Redfish for Bmc {
 
         let parameters = "{}".to_string();
 
-        let (_status_code, _loc, body) = self
+        let response = self
             .s
             .client
             .req_update_firmware_multipart(
@@ -411,14 +462,7 @@ impl Redfish for Bmc {
                 e => e,
             })?;
 
-        let task: Task =
-            serde_json::from_str(&body).map_err(|e| RedfishError::JsonDeserializeError {
-                url: update_service.multipart_http_push_uri,
-                body,
-                source: e,
-            })?;
-
-        Ok(task.id)
+        response.task_id(&update_service.multipart_http_push_uri)
     }
 
     async fn bios(
@@ -434,17 +478,17 @@ impl Redfish for Bmc {
         self.s.set_bios(values).await
     }
 
-    async fn reset_bios(&self) -> Result<(), RedfishError> {
-        let url = format!("Systems/{}/Bios/Settings", self.s.system_id());
+    async fn reset_bios(&self) -> Result<crate::ApplyResult, RedfishError> {
+        let url = format!(
+            "Systems/{}/Bios/Settings",
+            self.s.resolve_system_id().await?
+        );
         let mut attributes = HashMap::new();
         let mut data = HashMap::new();
         data.insert("ResetEfiVars", true);
         attributes.insert("Attributes", data);
-        self.s
-            .client
-            .patch(&url, attributes)
-            .await
-            .map(|_resp| Ok(()))?
+        let (_status_code, info) = self.s.client.patch_with_response(&url, attributes).await?;
+        Ok(crate::ApplyResult::from_extended_info(info, &url))
     }
 
     async fn pending(
@@ -453,7 +497,7 @@ impl Redfish for Bmc {
         self.s.pending().await
     }
 
-    async fn clear_pending(&self) -> Result<(), RedfishError> {
+    async fn clear_pending(&self) -> Result<crate::ClearPendingResult, RedfishError> {
         self.s.clear_pending().await
     }
 
@@ -483,6 +527,28 @@ impl Redfish for Bmc {
             .await
     }
 
+    /// The BlueField UEFI only picks up key database changes from the BMC while it is in
+    /// Setup mode, so restoring the default keys is refused otherwise. DeleteAllKeys is how
+    /// the DPU gets into Setup mode in the first place, so that is always allowed.
+    async fn reset_secure_boot_keys(
+        &self,
+        reset_type: crate::model::secure_boot::SecureBootResetType,
+    ) -> Result<(), RedfishError> {
+        use crate::model::secure_boot::{SecureBootMode, SecureBootResetType};
+        if reset_type == SecureBootResetType::ResetAllKeysToDefault {
+            let sb = self.get_secure_boot().await?;
+            let mode = sb.secure_boot_mode.unwrap_or_default();
+            if mode != SecureBootMode::SetupMode {
+                return Err(RedfishError::GenericError {
+                    error: format!(
+                        "{reset_type} requires Secure Boot SetupMode, DPU is in {mode}. Use DeleteAllKeys and reboot first."
+                    ),
+                });
+            }
+        }
+        self.s.reset_secure_boot_keys(reset_type).await
+    }
+
     async fn get_chassis_all(&self) -> Result<Vec<String>, RedfishError> {
         self.s.get_chassis_all().await
     }
@@ -591,6 +657,20 @@ impl Redfish for Bmc {
         Ok(body)
     }
 
+    async fn get_port_metrics(
+        &self,
+        chassis_id: &str,
+        network_adapter: &str,
+        id: &str,
+    ) -> Result<crate::model::port::PortMetrics, RedfishError> {
+        let url = format!(
+            "Chassis/{}/NetworkAdapters/{}/Ports/{}/Metrics",
+            chassis_id, network_adapter, id
+        );
+        let (_status_code, body) = self.s.client.get(&url).await?;
+        Ok(body)
+    }
+
     async fn get_network_device_function(
         &self,
         chassis_id: &str,
@@ -619,24 +699,36 @@ impl Redfish for Bmc {
 
     async fn change_uefi_password(
         &self,
-        current_uefi_password: &str,
-        new_uefi_password: &str,
-    ) -> Result<Option<String>, RedfishError> {
-        let mut attributes = HashMap::new();
-        let mut data = HashMap::new();
-        data.insert("CurrentUefiPassword", current_uefi_password.to_string());
-        data.insert("UefiPassword", new_uefi_password.to_string());
-        attributes.insert("Attributes", data);
-        let url = format!("Systems/{}/Bios/Settings", self.s.system_id());
-        let _status_code = self.s.client.patch(&url, attributes).await?;
-        Ok(None)
+        current_uefi_password: &SecretString,
+        new_uefi_password: &SecretString,
+    ) -> Result<crate::BiosPasswordChange, RedfishError> {
+        let attrs = HashMap::from([
+            (
+                "CurrentUefiPassword".to_string(),
+                current_uefi_password.expose_secret().into(),
+            ),
+            (
+                "UefiPassword".to_string(),
+                new_uefi_password.expose_secret().into(),
+            ),
+        ]);
+        let registry = self.s.bios_attribute_registry_if_any().await?;
+        let targets = self
+            .s
+            .split_bios_attributes(registry.as_ref(), attrs)
+            .await?
+            .together();
+        let result = self
+            .s
+            .write_bios_attribute_targets(targets, |settings| self.s.patch_bios_settings(settings))
+            .await
+            .map(|()| None);
+        crate::BiosPasswordChange::from_result(result)
     }
 
     async fn change_boot_order(&self, boot_array: Vec<String>) -> Result<(), RedfishError> {
-        let body = HashMap::from([("Boot", HashMap::from([("BootOrder", boot_array)]))]);
-        let url = format!("Systems/{}/Settings", self.s.system_id());
-        self.s.client.patch(&url, body).await?;
-        Ok(())
+        let target = BootOrderTarget::settings(self.s.resolve_system_id().await?);
+        self.s.change_boot_order_at(&target, boot_array).await
     }
 
     async fn get_service_root(&self) -> Result<ServiceRoot, RedfishError> {
@@ -656,13 +748,19 @@ impl Redfish for Bmc {
     }
 
     async fn bmc_reset_to_defaults(&self) -> Result<(), RedfishError> {
-        let url = format!(
-            "Managers/{}/Actions/Manager.ResetToDefaults",
-            self.s.manager_id()
-        );
-        let mut arg = HashMap::new();
-        arg.insert("ResetToDefaultsType", "ResetAll".to_string());
-        self.s.client.post(&url, arg).await.map(|_resp| Ok(()))?
+        // The BlueField BMC takes ResetToDefaultsType rather than ResetType
+        self.s
+            .reset_manager_to_defaults("ResetToDefaultsType", None)
+            .await
+    }
+
+    async fn bmc_reset_to_defaults_with(
+        &self,
+        scope: crate::model::manager::ResetToDefaultsType,
+    ) -> Result<(), RedfishError> {
+        self.s
+            .reset_manager_to_defaults("ResetToDefaultsType", Some(scope))
+            .await
     }
 
     async fn get_job_state(&self, job_id: &str) -> Result<JobState, RedfishError> {
@@ -688,22 +786,28 @@ impl Redfish for Bmc {
 
     async fn clear_uefi_password(
         &self,
-        current_uefi_password: &str,
+        current_uefi_password: &SecretString,
     ) -> Result<Option<String>, RedfishError> {
-        self.change_uefi_password(current_uefi_password, "").await
+        self.change_uefi_password(current_uefi_password, &SecretString::default())
+            .await?
+            .job_id()
     }
 
     async fn get_base_mac_address(&self) -> Result<Option<String>, RedfishError> {
-        let url = format!("Systems/{}/Oem/Nvidia", self.s.system_id());
+        let url = format!("Systems/{}/Oem/Nvidia", self.s.resolve_system_id().await?);
         let (_sc, body): (reqwest::StatusCode, HashMap<String, serde_json::Value>) =
             self.s.client.get(url.as_str()).await?;
         Ok(body.get("BaseMAC").map(|v| v.to_string()))
     }
 
-    async fn lockdown_bmc(&self, target: crate::EnabledDisabled) -> Result<(), RedfishError> {
+    async fn lockdown_bmc(&self, target: crate::EnabledDisabled) -> Result<bool, RedfishError> {
         self.s.lockdown_bmc(target).await
     }
 
+    async fn bmc_lockdown_status(&self) -> Result<crate::Status, RedfishError> {
+        self.s.bmc_lockdown_status().await
+    }
+
     async fn is_ipmi_over_lan_enabled(&self) -> Result<bool, RedfishError> {
         self.s.is_ipmi_over_lan_enabled().await
     }
@@ -711,7 +815,7 @@ impl Redfish for Bmc {
     async fn enable_ipmi_over_lan(
         &self,
         target: crate::EnabledDisabled,
-    ) -> Result<(), RedfishError> {
+    ) -> Result<bool, RedfishError> {
         self.s.enable_ipmi_over_lan(target).await
     }
 
@@ -726,17 +830,34 @@ impl Redfish for Bmc {
             .await
     }
 
-    async fn enable_rshim_bmc(&self) -> Result<(), RedfishError> {
+    async fn enable_rshim_bmc(&self) -> Result<bool, RedfishError> {
+        // If we can't tell, set it anyway
+        if matches!(self.is_rshim_enabled().await, Ok(true)) {
+            return Ok(false);
+        }
         let data = HashMap::from([("BmcRShim", HashMap::from([("BmcRShimEnabled", true)]))]);
 
         self.s
             .client
             .patch("Managers/Bluefield_BMC/Oem/Nvidia", data)
             .await
-            .map(|_status_code| Ok(()))?
+            .map(|_status_code| true)
+    }
+
+    async fn is_rshim_enabled(&self) -> Result<bool, RedfishError> {
+        let url = "Managers/Bluefield_BMC/Oem/Nvidia";
+        let (_sc, body): (reqwest::StatusCode, HashMap<String, serde_json::Value>) =
+            self.s.client.get(url).await?;
+        body.get("BmcRShim")
+            .and_then(|rshim| rshim.get("BmcRShimEnabled"))
+            .and_then(|enabled| enabled.as_bool())
+            .ok_or_else(|| RedfishError::MissingKey {
+                key: "BmcRShim.BmcRShimEnabled".to_string(),
+                url: url.to_string(),
+            })
     }
 
-    async fn clear_nvram(&self) -> Result<(), RedfishError> {
+    async fn clear_nvram(&self) -> Result<crate::ApplyResult, RedfishError> {
         self.s.clear_nvram().await
     }
 
@@ -765,7 +886,7 @@ impl Redfish for Bmc {
         data.insert("HostRshim", enabled.to_string());
         let url = format!(
             "Systems/{}/Oem/Nvidia/Actions/HostRshim.Set",
-            self.s.system_id()
+            self.s.resolve_system_id().await?
         );
 
         self.s.client.post(&url, data).await.map(|_resp| Ok(()))?
@@ -776,7 +897,7 @@ impl Redfish for Bmc {
             return Ok(None);
         }
 
-        let url = format!("Systems/{}/Oem/Nvidia", self.s.system_id());
+        let url = format!("Systems/{}/Oem/Nvidia", self.s.resolve_system_id().await?);
         let (_sc, body): (reqwest::StatusCode, HashMap<String, serde_json::Value>) =
             self.s.client.get(url.as_str()).await?;
         let val = body.get("HostRshim").map(|v| v.to_string());
@@ -858,6 +979,203 @@ impl Redfish for Bmc {
     ) -> Result<crate::model::component_integrity::Evidence, RedfishError> {
         self.s.get_evidence(url).await
     }
+
+    async fn get_boot_progress(&self) -> Result<crate::model::system::BootProgress, RedfishError> {
+        self.s.get_boot_progress().await
+    }
+
+    async fn get_post_codes(&self) -> Result<Vec<LogEntry>, RedfishError> {
+        self.s.get_post_codes().await
+    }
+
+    async fn get_environment_metrics(
+        &self,
+        chassis_id: &str,
+    ) -> Result<crate::model::environment_metrics::EnvironmentMetrics, RedfishError> {
+        self.s.get_environment_metrics(chassis_id).await
+    }
+
+    async fn get_energy_kwh(
+        &self,
+    ) -> Result<Vec<crate::model::sensor::EnergyReading>, RedfishError> {
+        self.s.get_energy_kwh().await
+    }
+
+    async fn get_sensors(
+        &self,
+        chassis_id: &str,
+        reading_type: Option<crate::model::sensor::ReadingType>,
+    ) -> Result<Vec<crate::model::sensor::Sensor>, RedfishError> {
+        self.s.get_sensors(chassis_id, reading_type).await
+    }
+
+    async fn wait_for_power_state(
+        &self,
+        target: crate::PowerState,
+        timeout: std::time::Duration,
+        poll: std::time::Duration,
+    ) -> Result<(), RedfishError> {
+        self.s.wait_for_power_state(target, timeout, poll).await
+    }
+
+    async fn pending_diff(&self) -> Result<Vec<crate::model::BiosAttrChange>, RedfishError> {
+        let pending = self.pending().await?;
+        self.s.bios_attr_changes(pending).await
+    }
+
+    async fn set_bios_attributes(
+        &self,
+        attrs: HashMap<String, serde_json::Value>,
+    ) -> Result<(), RedfishError> {
+        self.s.set_bios_attributes(attrs).await
+    }
+
+    async fn set_uefi_http(
+        &self,
+        cfg: crate::model::oem::nvidia_viking::UefiHttpAttributes,
+    ) -> Result<(), RedfishError> {
+        self.s.set_uefi_http(cfg).await
+    }
+
+    async fn set_virtualization(
+        &self,
+        cfg: crate::model::oem::nvidia_viking::VirtAttributes,
+    ) -> Result<(), RedfishError> {
+        self.s.set_virtualization(cfg).await
+    }
+
+    async fn get_bios_lockdown(
+        &self,
+    ) -> Result<crate::model::oem::nvidia_viking::BiosLockdownAttributes, RedfishError> {
+        self.s.get_bios_lockdown().await
+    }
+
+    async fn get_decoded_sel(
+        &self,
+    ) -> Result<Vec<crate::model::sel::DecodedSelEntry>, RedfishError> {
+        let sel = self.get_system_event_log().await?;
+        Ok(sel
+            .iter()
+            .map(crate::model::sel::DecodedSelEntry::from)
+            .collect())
+    }
+
+    async fn resolve_origin(
+        &self,
+        entry: &crate::model::sel::LogEntry,
+    ) -> Result<crate::model::resource::Resource, RedfishError> {
+        self.s.resolve_origin(entry).await
+    }
+
+    async fn attach_to_update(
+        &self,
+        task_id: &str,
+    ) -> Result<crate::model::task::Task, RedfishError> {
+        self.s.attach_to_update(task_id).await
+    }
+
+    async fn get_fru_inventory(&self) -> Result<crate::model::fru::FruInventory, RedfishError> {
+        self.s.get_fru_inventory().await
+    }
+
+    async fn get_power_supplies(
+        &self,
+    ) -> Result<Vec<crate::model::power::PowerSupplyInfo>, RedfishError> {
+        self.s.get_power_supplies().await
+    }
+
+    async fn download_firmware_image(
+        &self,
+        inventory_id: &str,
+        out: &std::path::Path,
+    ) -> Result<(), RedfishError> {
+        self.s.download_firmware_image(inventory_id, out).await
+    }
+
+    async fn create_boot_option(&self, opt: NewBootOption) -> Result<String, RedfishError> {
+        self.s.create_boot_option(opt).await
+    }
+
+    async fn delete_boot_option(&self, option_id: &str) -> Result<(), RedfishError> {
+        self.s.delete_boot_option_for(self, option_id).await
+    }
+
+    async fn get_bios_prerequisites(
+        &self,
+        attrs: &HashMap<String, serde_json::Value>,
+    ) -> Result<HashMap<String, serde_json::Value>, RedfishError> {
+        self.s.get_bios_prerequisites(attrs).await
+    }
+
+    async fn get_bmc_journal(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<String>, RedfishError> {
+        self.s.get_bmc_journal(since).await
+    }
+
+    async fn get_all_metrics(&self) -> Result<crate::AllMetrics, RedfishError> {
+        crate::AllMetrics::gather(self).await
+    }
+
+    async fn get_power_restore_policy(&self) -> Result<crate::PowerRestorePolicy, RedfishError> {
+        self.s.get_power_restore_policy().await
+    }
+
+    async fn set_power_restore_policy(
+        &self,
+        policy: crate::PowerRestorePolicy,
+    ) -> Result<(), RedfishError> {
+        self.s.set_power_restore_policy(policy).await
+    }
+
+    async fn set_power_on_delay(&self, seconds: u32) -> Result<(), RedfishError> {
+        self.s.set_power_on_delay(seconds).await
+    }
+
+    async fn get_manager_health(&self) -> Result<crate::model::ManagerHealth, RedfishError> {
+        self.s.get_manager_health().await
+    }
+
+    async fn get_active_conditions(&self) -> Result<Vec<crate::model::Condition>, RedfishError> {
+        self.s.get_active_conditions().await
+    }
+
+    async fn get_fabric_topology(
+        &self,
+    ) -> Result<Vec<crate::model::fabric::FabricTopology>, RedfishError> {
+        self.s.get_fabric_topology().await
+    }
+
+    async fn get_pcie_switches(
+        &self,
+        chassis_id: &str,
+    ) -> Result<Vec<crate::model::fabric::SwitchPorts>, RedfishError> {
+        self.s.get_pcie_switches(chassis_id).await
+    }
+
+    async fn reset_erot(&self, target: ComponentType) -> Result<(), RedfishError> {
+        self.s.reset_erot(target).await
+    }
+
+    async fn get_firmware_slots(
+        &self,
+        component: &str,
+    ) -> Result<Vec<crate::model::software_inventory::FirmwareSlot>, RedfishError> {
+        self.s.get_firmware_slots(component).await
+    }
+
+    async fn activate_firmware_slot(
+        &self,
+        component: &str,
+        slot: &str,
+    ) -> Result<(), RedfishError> {
+        self.s.activate_firmware_slot(component, slot).await
+    }
+
+    async fn get_pending_jobs(&self) -> Result<Vec<crate::model::job::Job>, RedfishError> {
+        self.s.get_pending_jobs().await
+    }
 }
 
 impl Bmc {
@@ -865,7 +1183,10 @@ impl Bmc {
         &self,
         data: HashMap<&str, HashMap<&str, String>>,
     ) -> Result<(), RedfishError> {
-        let url = format!("Systems/{}/Bios/Settings", self.s.system_id());
+        let url = format!(
+            "Systems/{}/Bios/Settings",
+            self.s.resolve_system_id().await?
+        );
         self.s
             .client
             .patch(&url, data)
@@ -946,7 +1267,7 @@ impl Bmc {
             "BootSourceOverrideTarget".to_string(),
             format!("{}", override_target),
         );
-        let url = format!("Systems/{}/Settings ", self.s.system_id());
+        let url = format!("Systems/{}/Settings ", self.s.resolve_system_id().await?);
         self.s
             .client
             .patch(&url, HashMap::from([("Boot", data)]))
@@ -993,7 +1314,10 @@ impl Bmc {
 
     // dpu stores the sel as part of the system? there's a LogServices for the bmc too, but no sel
     async fn get_system_event_log(&self) -> Result<Vec<LogEntry>, RedfishError> {
-        let url = format!("Systems/{}/LogServices/SEL/Entries", self.s.system_id());
+        let url = format!(
+            "Systems/{}/LogServices/SEL/Entries",
+            self.s.resolve_system_id().await?
+        );
         let (_status_code, log_entry_collection): (_, LogEntryCollection) =
             self.s.client.get(&url).await?;
         let log_entries = log_entry_collection.members;
@@ -1106,7 +1430,7 @@ impl Bmc {
     }
 
     async fn get_nic_mode_bf3_oem_extension(&self) -> Result<Option<NicMode>, RedfishError> {
-        let url = format!("Systems/{}/Oem/Nvidia", self.s.system_id());
+        let url = format!("Systems/{}/Oem/Nvidia", self.s.resolve_system_id().await?);
         let (_sc, body): (reqwest::StatusCode, HashMap<String, serde_json::Value>) =
             self.s.client.get(url.as_str()).await?;
         let val = body.get("Mode").map(|v| v.to_string());
@@ -1203,7 +1527,10 @@ impl Bmc {
             let mut attributes = HashMap::new();
             data.insert("NicMode", val);
             attributes.insert("Attributes", data);
-            let url = format!("Systems/{}/Bios/Settings", self.s.system_id());
+            let url = format!(
+                "Systems/{}/Bios/Settings",
+                self.s.resolve_system_id().await?
+            );
             return self
                 .s
                 .client
@@ -1214,8 +1541,111 @@ impl Bmc {
 
         data.insert("Mode", val);
         tracing::warn!("data: {data:#?}");
-        let url = format!("Systems/{}/Oem/Nvidia/Actions/Mode.Set", self.s.system_id());
+        let url = format!(
+            "Systems/{}/Oem/Nvidia/Actions/Mode.Set",
+            self.s.resolve_system_id().await?
+        );
 
         self.s.client.post(&url, data).await.map(|_resp| Ok(()))?
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::Bmc;
+    use crate::fake_bmc::{bios_resources, FakeBmc};
+    use crate::{Redfish, SecretString};
+
+    #[tokio::test]
+    async fn test_change_uefi_password_to_settings_object() {
+        let settings = "/redfish/v1/Systems/1/Bios/Settings";
+        let fake = FakeBmc::start(&bios_resources(settings, false)).await;
+        let bmc = Bmc::new(fake.standard()).unwrap();
+        bmc.change_uefi_password(&SecretString::from("old"), &SecretString::from("new"))
+            .await
+            .unwrap();
+        let writes = fake.writes();
+        assert_eq!(writes.len(), 1);
+        assert_eq!(writes[0].path, settings);
+        assert_eq!(
+            writes[0].body,
+            json!({"Attributes": {"CurrentUefiPassword": "old", "UefiPassword": "new"}})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_change_boot_order_partial_to_settings() {
+        let fake = FakeBmc::start(&[("/redfish/v1/Systems/1/Settings", json!({}))]).await;
+        let bmc = Bmc::new(fake.standard()).unwrap();
+        bmc.change_boot_order(vec!["Boot0001".to_string()])
+            .await
+            .unwrap();
+        let writes = fake.writes();
+        assert_eq!(writes.len(), 1);
+        assert_eq!(writes[0].path, "/redfish/v1/Systems/1/Settings");
+        assert_eq!(writes[0].body, json!({"Boot": {"BootOrder": ["Boot0001"]}}));
+    }
+
+    #[tokio::test]
+    async fn test_bmc_reset_to_defaults() {
+        let manager = "/redfish/v1/Managers/BMC";
+        let action = format!("{manager}/Actions/Manager.ResetToDefaults");
+        let fake = FakeBmc::start(&[(
+            manager,
+            json!({"Actions": {"#Manager.ResetToDefaults": {
+                "target": action,
+                "ResetToDefaultsType@Redfish.AllowableValues": ["ResetAll", "PreserveNetwork"]
+            }}}),
+        )])
+        .await;
+        let bmc = Bmc::new(fake.standard()).unwrap();
+        bmc.bmc_reset_to_defaults().await.unwrap();
+        let requests = fake.requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 2, "one GET of the manager, then the POST");
+        assert_eq!(requests[1].path, action);
+        assert_eq!(
+            requests[1].body,
+            json!({"ResetToDefaultsType": "PreserveNetwork"})
+        );
+
+        // Validated against the BlueField's own parameter
+        assert!(matches!(
+            bmc.bmc_reset_to_defaults_with(
+                crate::model::manager::ResetToDefaultsType::PreserveNetworkAndUsers
+            )
+            .await,
+            Err(crate::RedfishError::NotSupported(_))
+        ));
+        assert_eq!(fake.writes().len(), 1);
+    }
+    #[tokio::test]
+    async fn test_get_network_adapter_ports() {
+        let ports = "/redfish/v1/Chassis/Card1/NetworkAdapters/NvidiaNetworkAdapter/Ports";
+        let mut resources = vec![(
+            ports.to_string(),
+            json!({
+                "@odata.id": ports,
+                "@odata.type": "#PortCollection.PortCollection",
+                "Name": "Ports",
+                "Members": (0..6).map(|i| json!({"@odata.id": format!("{ports}/eth{i}")})).collect::<Vec<_>>(),
+            }),
+        )];
+        for i in 0..6 {
+            resources.push((
+                format!("{ports}/eth{i}"),
+                json!({"Id": format!("eth{i}"), "LinkStatus": "LinkUp", "CurrentSpeedGbps": 100}),
+            ));
+        }
+        let fake = FakeBmc::start(&resources).await;
+        let bmc = Bmc::new(fake.standard()).unwrap();
+        let got = bmc
+            .get_network_adapter_ports("Card1", "NvidiaNetworkAdapter")
+            .await
+            .unwrap();
+        let ids: Vec<_> = got.iter().filter_map(|p| p.id.clone()).collect();
+        assert_eq!(ids, ["eth0", "eth1", "eth2", "eth3", "eth4", "eth5"]);
+        assert_eq!(got[0].current_speed_gbps, Some(100.0));
+    }
+}
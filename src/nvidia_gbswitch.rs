@@ -8,13 +8,16 @@ use crate::model::oem::nvidia_dpu::NicMode;
 use crate::model::sensor::{GPUSensors, Sensors};
 use crate::model::service_root::RedfishVendor;
 use crate::model::task::Task;
-use crate::model::thermal::{LeakDetector, Temperature, TemperaturesOemNvidia, Thermal};
+use crate::model::thermal::{
+    LeakDetection, LeakDetector, Temperature, TemperaturesOemNvidia, Thermal, ThermalSubsystem,
+};
+use crate::model::uefi_device_path::UefiDevicePath;
 use crate::model::update_service::{ComponentType, TransferProtocolType, UpdateService};
 use crate::model::PCIeDevices;
 use crate::REDFISH_ENDPOINT;
 use crate::{
     model::{
-        boot::{BootSourceOverrideEnabled, BootSourceOverrideTarget},
+        boot::{BootOrderTarget, BootSourceOverrideEnabled, BootSourceOverrideTarget},
         chassis::{Assembly, NetworkAdapter},
         sel::{LogEntry, LogEntryCollection},
         service_root::ServiceRoot,
@@ -22,8 +25,8 @@ use crate::{
         BootOption, ComputerSystem, Manager,
     },
     standard::RedfishStandard,
-    BiosProfileType, Chassis, Collection, NetworkDeviceFunction, ODataId, Redfish, RedfishError,
-    Resource,
+    BiosProfileType, Chassis, Collection, NetworkDeviceFunction, NewBootOption, ODataId, Redfish,
+    RedfishError, Resource, SecretString,
 };
 use crate::{EnabledDisabled, JobState, MachineSetupStatus, PCIeDevice, RoleId};
 
@@ -54,6 +57,16 @@ impl BootOptionName {
             BootOptionName::UefiHd => "HD(",
         }
     }
+
+    // Whether a boot option with this UefiDevicePath boots this way, over IPv4 for the network
+    // ones like the DisplayName prefixes say
+    fn matches_device_path(self, path: &UefiDevicePath) -> bool {
+        match self {
+            BootOptionName::Http => path.is_http() && path.is_ipv4(),
+            BootOptionName::Pxe => path.is_pxe() && path.is_ipv4(),
+            BootOptionName::UefiHd => path.is_hard_drive(),
+        }
+    }
 }
 
 enum BootOptionMatchField {
@@ -63,10 +76,14 @@ enum BootOptionMatchField {
 
 #[async_trait::async_trait]
 impl Redfish for Bmc {
+    fn standard(&self) -> &RedfishStandard {
+        &self.s
+    }
+
     async fn create_user(
         &self,
         username: &str,
-        password: &str,
+        password: &SecretString,
         role_id: RoleId,
     ) -> Result<(), RedfishError> {
         self.s.create_user(username, password, role_id).await
@@ -80,7 +97,7 @@ impl Redfish for Bmc {
         self.s.change_username(old_name, new_name).await
     }
 
-    async fn change_password(&self, user: &str, new: &str) -> Result<(), RedfishError> {
+    async fn change_password(&self, user: &str, new: &SecretString) -> Result<(), RedfishError> {
         self.s.change_password(user, new).await
     }
 
@@ -88,7 +105,7 @@ impl Redfish for Bmc {
     async fn change_password_by_id(
         &self,
         account_id: &str,
-        new_pass: &str,
+        new_pass: &SecretString,
     ) -> Result<(), RedfishError> {
         self.s.change_password_by_id(account_id, new_pass).await
     }
@@ -155,26 +172,34 @@ impl Redfish for Bmc {
             }
             let mut url = format!("Chassis/{}", chassis_id);
             let (_status_code, chassis): (StatusCode, Chassis) = self.s.client.get(&url).await?;
-            if chassis.thermal_subsystem.is_some() {
-                url = format!("Chassis/{}/ThermalSubsystem/ThermalMetrics", chassis_id);
-                let (_status_code, temps): (StatusCode, TemperaturesOemNvidia) =
-                    self.s.client.get(&url).await?;
-                if let Some(temp) = temps.temperature_readings_celsius {
+            let subsystem: Option<ThermalSubsystem> = self
+                .s
+                .follow_link(chassis.thermal_subsystem.as_ref())
+                .await?;
+            if let Some(subsystem) = subsystem {
+                let temps: Option<TemperaturesOemNvidia> = self
+                    .s
+                    .follow_link(subsystem.thermal_metrics.as_ref())
+                    .await?;
+                if let Some(temp) = temps.and_then(|t| t.temperature_readings_celsius) {
                     for t in temp {
                         let sensor: Temperature = Temperature::from(t);
                         temperatures.push(sensor);
                     }
                 }
                 // walk through leak detection sensors and add those
-                url = format!(
-                    "Chassis/{}/ThermalSubsystem/LeakDetection/LeakDetectors",
-                    chassis_id
-                );
-
-                let res: Result<(StatusCode, Sensors), RedfishError> =
-                    self.s.client.get(&url).await;
+                let detectors: Result<Option<Sensors>, RedfishError> = async {
+                    let leak_detection: Option<LeakDetection> = self
+                        .s
+                        .follow_link(subsystem.leak_detection.as_ref())
+                        .await?;
+                    self.s
+                        .follow_link(leak_detection.and_then(|l| l.leak_detectors).as_ref())
+                        .await
+                }
+                .await;
 
-                if let Ok((_, sensors)) = res {
+                if let Ok(Some(sensors)) = detectors {
                     for sensor in sensors.members {
                         url = sensor
                             .odata_id
@@ -205,6 +230,18 @@ impl Redfish for Bmc {
         self.get_system_event_log().await
     }
 
+    async fn get_system_event_log_since(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        limit: Option<usize>,
+    ) -> Result<Vec<LogEntry>, RedfishError> {
+        let url = format!(
+            "Systems/{}/LogServices/SEL/Entries",
+            self.s.resolve_system_id().await?
+        );
+        self.s.fetch_system_event_log(url, since, limit).await
+    }
+
     async fn get_bmc_event_log(
         &self,
         from: Option<chrono::DateTime<chrono::Utc>>,
@@ -224,8 +261,8 @@ impl Redfish for Bmc {
             HashMap<String, HashMap<BiosProfileType, HashMap<String, serde_json::Value>>>,
         >,
         _selected_profile: BiosProfileType,
-    ) -> Result<(), RedfishError> {
-        Ok(())
+    ) -> Result<crate::MachineSetupReport, RedfishError> {
+        Ok(crate::MachineSetupReport::default())
     }
 
     async fn machine_setup_status(
@@ -266,7 +303,7 @@ impl Redfish for Bmc {
         self.s.lockdown_status().await
     }
 
-    async fn setup_serial_console(&self) -> Result<(), RedfishError> {
+    async fn setup_serial_console(&self) -> Result<bool, RedfishError> {
         self.s.setup_serial_console().await
     }
 
@@ -328,7 +365,7 @@ impl Redfish for Bmc {
         }
     }
 
-    async fn clear_tpm(&self) -> Result<(), RedfishError> {
+    async fn clear_tpm(&self) -> Result<crate::ApplyResult, RedfishError> {
         self.s.clear_tpm().await
     }
 
@@ -418,7 +455,7 @@ impl Redfish for Bmc {
         ))
     }
 
-    async fn reset_bios(&self) -> Result<(), RedfishError> {
+    async fn reset_bios(&self) -> Result<crate::ApplyResult, RedfishError> {
         Err(RedfishError::NotSupported(
             "GB Switch Bios unsupported".to_string(),
         ))
@@ -434,7 +471,7 @@ impl Redfish for Bmc {
     }
 
     /// gh200 has no bios attributes
-    async fn clear_pending(&self) -> Result<(), RedfishError> {
+    async fn clear_pending(&self) -> Result<crate::ClearPendingResult, RedfishError> {
         Err(RedfishError::NotSupported(
             "GB Switch Bios unsupported".to_string(),
         ))
@@ -584,19 +621,17 @@ impl Redfish for Bmc {
     /// Set new_uefi_password to "" to disable it.
     async fn change_uefi_password(
         &self,
-        current_uefi_password: &str,
-        new_uefi_password: &str,
-    ) -> Result<Option<String>, RedfishError> {
+        current_uefi_password: &SecretString,
+        new_uefi_password: &SecretString,
+    ) -> Result<crate::BiosPasswordChange, RedfishError> {
         self.s
             .change_bios_password(UEFI_PASSWORD_NAME, current_uefi_password, new_uefi_password)
             .await
     }
 
     async fn change_boot_order(&self, boot_array: Vec<String>) -> Result<(), RedfishError> {
-        let body = HashMap::from([("Boot", HashMap::from([("BootOrder", boot_array)]))]);
-        let url = format!("Systems/{}/Settings", self.s.system_id());
-        self.s.client.patch(&url, body).await?;
-        Ok(())
+        let target = BootOrderTarget::settings(self.s.resolve_system_id().await?);
+        self.s.change_boot_order_at(&target, boot_array).await
     }
 
     async fn get_service_root(&self) -> Result<ServiceRoot, RedfishError> {
@@ -642,19 +677,25 @@ impl Redfish for Bmc {
 
     async fn clear_uefi_password(
         &self,
-        current_uefi_password: &str,
+        current_uefi_password: &SecretString,
     ) -> Result<Option<String>, RedfishError> {
-        self.change_uefi_password(current_uefi_password, "").await
+        self.change_uefi_password(current_uefi_password, &SecretString::default())
+            .await?
+            .job_id()
     }
 
     async fn get_base_mac_address(&self) -> Result<Option<String>, RedfishError> {
         self.s.get_base_mac_address().await
     }
 
-    async fn lockdown_bmc(&self, target: crate::EnabledDisabled) -> Result<(), RedfishError> {
+    async fn lockdown_bmc(&self, target: crate::EnabledDisabled) -> Result<bool, RedfishError> {
         self.s.lockdown_bmc(target).await
     }
 
+    async fn bmc_lockdown_status(&self) -> Result<crate::Status, RedfishError> {
+        self.s.bmc_lockdown_status().await
+    }
+
     async fn is_ipmi_over_lan_enabled(&self) -> Result<bool, RedfishError> {
         self.s.is_ipmi_over_lan_enabled().await
     }
@@ -662,7 +703,7 @@ impl Redfish for Bmc {
     async fn enable_ipmi_over_lan(
         &self,
         target: crate::EnabledDisabled,
-    ) -> Result<(), RedfishError> {
+    ) -> Result<bool, RedfishError> {
         self.s.enable_ipmi_over_lan(target).await
     }
 
@@ -677,11 +718,11 @@ impl Redfish for Bmc {
             .await
     }
 
-    async fn enable_rshim_bmc(&self) -> Result<(), RedfishError> {
+    async fn enable_rshim_bmc(&self) -> Result<bool, RedfishError> {
         self.s.enable_rshim_bmc().await
     }
 
-    async fn clear_nvram(&self) -> Result<(), RedfishError> {
+    async fn clear_nvram(&self) -> Result<crate::ApplyResult, RedfishError> {
         self.s.clear_nvram().await
     }
 
@@ -735,6 +776,16 @@ impl Redfish for Bmc {
         false
     }
 
+    fn supported_features(&self) -> crate::FeatureSet {
+        crate::FeatureSet {
+            thermal_metrics: true,
+            system_event_log: true,
+            lockdown: true,
+            ipmi_over_lan: true,
+            ..Default::default()
+        }
+    }
+
     async fn is_boot_order_setup(&self, _mac_address: &str) -> Result<bool, RedfishError> {
         Err(RedfishError::NotSupported(
             "not populated for GBSwitch".to_string(),
@@ -820,6 +871,221 @@ impl Redfish for Bmc {
             "not populated for GBSwitch".to_string(),
         ))
     }
+
+    async fn reset_secure_boot_keys(
+        &self,
+        reset_type: crate::model::secure_boot::SecureBootResetType,
+    ) -> Result<(), RedfishError> {
+        self.s.reset_secure_boot_keys(reset_type).await
+    }
+
+    async fn get_boot_progress(&self) -> Result<crate::model::system::BootProgress, RedfishError> {
+        self.s.get_boot_progress().await
+    }
+
+    async fn get_post_codes(&self) -> Result<Vec<LogEntry>, RedfishError> {
+        self.s.get_post_codes().await
+    }
+
+    async fn get_environment_metrics(
+        &self,
+        chassis_id: &str,
+    ) -> Result<crate::model::environment_metrics::EnvironmentMetrics, RedfishError> {
+        self.s.get_environment_metrics(chassis_id).await
+    }
+
+    async fn get_energy_kwh(
+        &self,
+    ) -> Result<Vec<crate::model::sensor::EnergyReading>, RedfishError> {
+        self.s.get_energy_kwh().await
+    }
+
+    async fn get_sensors(
+        &self,
+        chassis_id: &str,
+        reading_type: Option<crate::model::sensor::ReadingType>,
+    ) -> Result<Vec<crate::model::sensor::Sensor>, RedfishError> {
+        self.s.get_sensors(chassis_id, reading_type).await
+    }
+
+    async fn wait_for_power_state(
+        &self,
+        target: crate::PowerState,
+        timeout: std::time::Duration,
+        poll: std::time::Duration,
+    ) -> Result<(), RedfishError> {
+        self.s.wait_for_power_state(target, timeout, poll).await
+    }
+
+    async fn pending_diff(&self) -> Result<Vec<crate::model::BiosAttrChange>, RedfishError> {
+        let pending = self.pending().await?;
+        self.s.bios_attr_changes(pending).await
+    }
+
+    async fn set_bios_attributes(
+        &self,
+        attrs: HashMap<String, serde_json::Value>,
+    ) -> Result<(), RedfishError> {
+        self.s.set_bios_attributes(attrs).await
+    }
+
+    async fn set_uefi_http(
+        &self,
+        cfg: crate::model::oem::nvidia_viking::UefiHttpAttributes,
+    ) -> Result<(), RedfishError> {
+        self.s.set_uefi_http(cfg).await
+    }
+
+    async fn set_virtualization(
+        &self,
+        cfg: crate::model::oem::nvidia_viking::VirtAttributes,
+    ) -> Result<(), RedfishError> {
+        self.s.set_virtualization(cfg).await
+    }
+
+    async fn get_bios_lockdown(
+        &self,
+    ) -> Result<crate::model::oem::nvidia_viking::BiosLockdownAttributes, RedfishError> {
+        self.s.get_bios_lockdown().await
+    }
+
+    async fn get_decoded_sel(
+        &self,
+    ) -> Result<Vec<crate::model::sel::DecodedSelEntry>, RedfishError> {
+        let sel = self.get_system_event_log().await?;
+        Ok(sel
+            .iter()
+            .map(crate::model::sel::DecodedSelEntry::from)
+            .collect())
+    }
+
+    async fn resolve_origin(
+        &self,
+        entry: &crate::model::sel::LogEntry,
+    ) -> Result<crate::model::resource::Resource, RedfishError> {
+        self.s.resolve_origin(entry).await
+    }
+
+    async fn attach_to_update(
+        &self,
+        task_id: &str,
+    ) -> Result<crate::model::task::Task, RedfishError> {
+        self.s.attach_to_update(task_id).await
+    }
+
+    async fn get_port_metrics(
+        &self,
+        chassis_id: &str,
+        network_adapter: &str,
+        id: &str,
+    ) -> Result<crate::model::port::PortMetrics, RedfishError> {
+        self.s
+            .get_port_metrics(chassis_id, network_adapter, id)
+            .await
+    }
+
+    async fn get_fru_inventory(&self) -> Result<crate::model::fru::FruInventory, RedfishError> {
+        self.s.get_fru_inventory().await
+    }
+
+    async fn get_power_supplies(
+        &self,
+    ) -> Result<Vec<crate::model::power::PowerSupplyInfo>, RedfishError> {
+        self.s.get_power_supplies().await
+    }
+
+    async fn download_firmware_image(
+        &self,
+        inventory_id: &str,
+        out: &std::path::Path,
+    ) -> Result<(), RedfishError> {
+        self.s.download_firmware_image(inventory_id, out).await
+    }
+
+    async fn create_boot_option(&self, opt: NewBootOption) -> Result<String, RedfishError> {
+        self.s.create_boot_option(opt).await
+    }
+
+    async fn delete_boot_option(&self, option_id: &str) -> Result<(), RedfishError> {
+        self.s.delete_boot_option_for(self, option_id).await
+    }
+
+    async fn get_bios_prerequisites(
+        &self,
+        attrs: &HashMap<String, serde_json::Value>,
+    ) -> Result<HashMap<String, serde_json::Value>, RedfishError> {
+        self.s.get_bios_prerequisites(attrs).await
+    }
+
+    async fn get_bmc_journal(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<String>, RedfishError> {
+        self.s.get_bmc_journal(since).await
+    }
+
+    async fn get_all_metrics(&self) -> Result<crate::AllMetrics, RedfishError> {
+        crate::AllMetrics::gather(self).await
+    }
+
+    async fn get_power_restore_policy(&self) -> Result<crate::PowerRestorePolicy, RedfishError> {
+        self.s.get_power_restore_policy().await
+    }
+
+    async fn set_power_restore_policy(
+        &self,
+        policy: crate::PowerRestorePolicy,
+    ) -> Result<(), RedfishError> {
+        self.s.set_power_restore_policy(policy).await
+    }
+
+    async fn set_power_on_delay(&self, seconds: u32) -> Result<(), RedfishError> {
+        self.s.set_power_on_delay(seconds).await
+    }
+
+    async fn get_manager_health(&self) -> Result<crate::model::ManagerHealth, RedfishError> {
+        self.s.get_manager_health().await
+    }
+
+    async fn get_active_conditions(&self) -> Result<Vec<crate::model::Condition>, RedfishError> {
+        self.s.get_active_conditions().await
+    }
+
+    async fn get_fabric_topology(
+        &self,
+    ) -> Result<Vec<crate::model::fabric::FabricTopology>, RedfishError> {
+        self.s.get_fabric_topology().await
+    }
+
+    async fn get_pcie_switches(
+        &self,
+        chassis_id: &str,
+    ) -> Result<Vec<crate::model::fabric::SwitchPorts>, RedfishError> {
+        self.s.get_pcie_switches(chassis_id).await
+    }
+
+    async fn reset_erot(&self, target: ComponentType) -> Result<(), RedfishError> {
+        self.s.reset_erot(target).await
+    }
+
+    async fn get_firmware_slots(
+        &self,
+        component: &str,
+    ) -> Result<Vec<crate::model::software_inventory::FirmwareSlot>, RedfishError> {
+        self.s.get_firmware_slots(component).await
+    }
+
+    async fn activate_firmware_slot(
+        &self,
+        component: &str,
+        slot: &str,
+    ) -> Result<(), RedfishError> {
+        self.s.activate_firmware_slot(component, slot).await
+    }
+
+    async fn get_pending_jobs(&self) -> Result<Vec<crate::model::job::Job>, RedfishError> {
+        self.s.get_pending_jobs().await
+    }
 }
 
 impl Bmc {
@@ -837,7 +1103,7 @@ impl Bmc {
             "BootSourceOverrideTarget".to_string(),
             format!("{}", override_target),
         );
-        let url = format!("Systems/{}/Settings ", self.s.system_id());
+        let url = format!("Systems/{}/Settings ", self.s.resolve_system_id().await?);
         self.s
             .client
             .patch(&url, HashMap::from([("Boot", data)]))
@@ -874,7 +1140,7 @@ impl Bmc {
             let is_match = match match_field {
                 BootOptionMatchField::DisplayName => b.display_name.starts_with(with_name_str),
                 BootOptionMatchField::UefiDevicePath => {
-                    matches!(b.uefi_device_path, Some(x) if x.starts_with(with_name_str))
+                    with_name.matches_device_path(&b.device_path())
                 }
             };
             if is_match {
@@ -887,7 +1153,10 @@ impl Bmc {
     }
 
     async fn get_system_event_log(&self) -> Result<Vec<LogEntry>, RedfishError> {
-        let url = format!("Systems/{}/LogServices/SEL/Entries", self.s.system_id());
+        let url = format!(
+            "Systems/{}/LogServices/SEL/Entries",
+            self.s.resolve_system_id().await?
+        );
         let (_status_code, log_entry_collection): (_, LogEntryCollection) =
             self.s.client.get(&url).await?;
         let log_entries = log_entry_collection.members;
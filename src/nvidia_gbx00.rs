@@ -38,20 +38,25 @@ use crate::model::service_root::RedfishVendor;
 use crate::model::storage::DriveCollection;
 use crate::model::task::Task;
 use crate::model::thermal::Fan;
+use crate::model::uefi_device_path::UefiDevicePath;
 use crate::model::update_service::{ComponentType, TransferProtocolType, UpdateService};
 use crate::{
     model::{
-        boot::{BootSourceOverrideEnabled, BootSourceOverrideTarget},
+        boot::{BootOrderTarget, BootSourceOverrideEnabled, BootSourceOverrideTarget},
         chassis::{Assembly, NetworkAdapter},
-        power::{Power, PowerSupply, Voltages},
+        power::{Power, PowerSupply, Redundancy, Voltages},
         sel::{LogEntry, LogEntryCollection},
         service_root::ServiceRoot,
         storage::Drives,
-        thermal::{LeakDetector, Temperature, TemperaturesOemNvidia, Thermal},
+        thermal::{
+            LeakDetection, LeakDetector, Temperature, TemperaturesOemNvidia, Thermal,
+            ThermalSubsystem,
+        },
         BootOption, ComputerSystem, Manager,
     },
     standard::RedfishStandard,
-    BiosProfileType, Collection, NetworkDeviceFunction, ODataId, Redfish, RedfishError, Resource,
+    BiosProfileType, Collection, NetworkDeviceFunction, NewBootOption, ODataId, Redfish,
+    RedfishError, Resource, SecretString,
 };
 use crate::{JobState, MachineSetupDiff, MachineSetupStatus, RoleId};
 
@@ -82,12 +87,31 @@ impl BootOptionName {
             BootOptionName::Hdd => "HD(",
         }
     }
+
+    // Whether a boot option with this UefiDevicePath boots this way, over IPv4 for the network
+    // ones like the DisplayName prefixes say
+    fn matches_device_path(self, path: &UefiDevicePath) -> bool {
+        match self {
+            BootOptionName::Http => path.is_http() && path.is_ipv4(),
+            BootOptionName::Pxe => path.is_pxe() && path.is_ipv4(),
+            BootOptionName::Hdd => path.is_hard_drive(),
+        }
+    }
+
+    // Whether a boot option boots this way from the NIC with this MAC address
+    fn matches_mac(self, b: &BootOption, mac_address: &str) -> bool {
+        (b.display_name.starts_with(self.to_string()) || self.matches_device_path(&b.device_path()))
+            && b.matches_mac(mac_address)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Copy, Clone, Eq, PartialEq)]
 enum BootOptionMatchField {
     DisplayName,
     UefiDevicePath,
+    // The option's kind by DisplayName or UefiDevicePath, for the NIC with the MAC address given
+    // as the name
+    Mac,
 }
 
 impl BootOptionMatchField {
@@ -96,6 +120,7 @@ impl BootOptionMatchField {
         match self {
             BootOptionMatchField::DisplayName => "Display Name",
             BootOptionMatchField::UefiDevicePath => "Uefi Device Path",
+            BootOptionMatchField::Mac => "MAC Address",
         }
     }
 }
@@ -127,10 +152,14 @@ fn get_component_integrity_id_to_firmware_inventory_id_options(
 
 #[async_trait::async_trait]
 impl Redfish for Bmc {
+    fn standard(&self) -> &RedfishStandard {
+        &self.s
+    }
+
     async fn create_user(
         &self,
         username: &str,
-        password: &str,
+        password: &SecretString,
         role_id: RoleId,
     ) -> Result<(), RedfishError> {
         self.s.create_user(username, password, role_id).await
@@ -144,14 +173,14 @@ impl Redfish for Bmc {
         self.s.change_username(old_name, new_name).await
     }
 
-    async fn change_password(&self, user: &str, new: &str) -> Result<(), RedfishError> {
+    async fn change_password(&self, user: &str, new: &SecretString) -> Result<(), RedfishError> {
         self.s.change_password(user, new).await
     }
 
     async fn change_password_by_id(
         &self,
         account_id: &str,
-        new_pass: &str,
+        new_pass: &SecretString,
     ) -> Result<(), RedfishError> {
         self.s.change_password_by_id(account_id, new_pass).await
     }
@@ -191,90 +220,8 @@ impl Redfish for Bmc {
     }
 
     async fn get_power_metrics(&self) -> Result<crate::Power, RedfishError> {
-        let mut voltages = Vec::new();
-        let mut power_supplies = Vec::new();
-        // gb200 bianca has empty PowerSupplies on several chassis items
-        // for now assemble power supply details from PDB_0 chassis entries
-        let mut url = "Chassis/PDB_0".to_string();
-        let (_status_code, pdb): (StatusCode, PowerSupply) = self.s.client.get(&url).await?;
-        let mut hsc0 = pdb.clone();
-        let mut hsc1 = pdb.clone();
-        // voltage sensors are on several chassis items under sensors
-        let chassis_all = self.s.get_chassis_all().await?;
-        for chassis_id in chassis_all {
-            url = format!("Chassis/{}", chassis_id);
-            let (_status_code, chassis): (StatusCode, Chassis) = self.s.client.get(&url).await?;
-            if chassis.sensors.is_none() {
-                continue;
-            }
-            // walk through all Chassis/*/Sensors/ for voltage and PDB_0 for power supply details
-            url = format!("Chassis/{}/Sensors", chassis_id);
-            let (_status_code, sensors): (StatusCode, Sensors) = self.s.client.get(&url).await?;
-            for sensor in sensors.members {
-                if chassis_id == *"PDB_0" {
-                    // get amps and watts for power supply
-                    if sensor.odata_id.contains("HSC_0_Pwr") {
-                        url = sensor
-                            .odata_id
-                            .replace(&format!("/{REDFISH_ENDPOINT}/"), "");
-                        let (_status_code, t): (StatusCode, Sensor) =
-                            self.s.client.get(&url).await?;
-                        hsc0.last_power_output_watts = t.reading;
-                        hsc0.power_output_watts = t.reading;
-                        hsc0.power_capacity_watts = t.reading_range_max;
-                    }
-                    if sensor.odata_id.contains("HSC_0_Cur") {
-                        url = sensor
-                            .odata_id
-                            .replace(&format!("/{REDFISH_ENDPOINT}/"), "");
-                        let (_status_code, t): (StatusCode, Sensor) =
-                            self.s.client.get(&url).await?;
-                        hsc0.power_output_amps = t.reading;
-                    }
-                    if sensor.odata_id.contains("HSC_1_Pwr") {
-                        url = sensor
-                            .odata_id
-                            .replace(&format!("/{REDFISH_ENDPOINT}/"), "");
-                        let (_status_code, t): (StatusCode, Sensor) =
-                            self.s.client.get(&url).await?;
-                        hsc1.last_power_output_watts = t.reading;
-                        hsc1.power_output_watts = t.reading;
-                        hsc1.power_capacity_watts = t.reading_range_max;
-                    }
-                    if sensor.odata_id.contains("HSC_1_Cur") {
-                        url = sensor
-                            .odata_id
-                            .replace(&format!("/{REDFISH_ENDPOINT}/"), "");
-                        let (_status_code, t): (StatusCode, Sensor) =
-                            self.s.client.get(&url).await?;
-                        hsc1.power_output_amps = t.reading;
-                    }
-                }
-                // now all voltage sensors in all chassis
-                if !sensor.odata_id.contains("Volt") {
-                    continue;
-                }
-                url = sensor
-                    .odata_id
-                    .replace(&format!("/{REDFISH_ENDPOINT}/"), "");
-                let (_status_code, t): (StatusCode, Sensor) = self.s.client.get(&url).await?;
-                let sensor: Voltages = Voltages::from(t);
-                voltages.push(sensor);
-            }
-        }
-
-        power_supplies.push(hsc0);
-        power_supplies.push(hsc1);
-        let power = Power {
-            odata: None,
-            id: "Power".to_string(),
-            name: "Power".to_string(),
-            power_control: vec![],
-            power_supplies: Some(power_supplies),
-            voltages: Some(voltages),
-            redundancy: None,
-        };
-        Ok(power)
+        let chassis_all = self.walk_chassis().await?;
+        self.power_metrics_from(&chassis_all).await
     }
 
     async fn power(&self, action: crate::SystemPowerControl) -> Result<(), RedfishError> {
@@ -299,6 +246,23 @@ impl Redfish for Bmc {
         true
     }
 
+    fn supported_features(&self) -> crate::FeatureSet {
+        crate::FeatureSet {
+            power_metrics: true,
+            thermal_metrics: true,
+            system_event_log: true,
+            reset_bios: true,
+            lockdown: true,
+            secure_boot: true,
+            chassis_network_adapters: true,
+            dpu_first_boot_order: true,
+            component_integrity: true,
+            infinite_boot: true,
+            ipmi_over_lan: true,
+            ..Default::default()
+        }
+    }
+
     async fn bmc_reset(&self) -> Result<(), RedfishError> {
         self.s.bmc_reset().await
     }
@@ -312,130 +276,8 @@ impl Redfish for Bmc {
     }
 
     async fn get_thermal_metrics(&self) -> Result<crate::Thermal, RedfishError> {
-        let mut temperatures = Vec::new();
-        let mut fans = Vec::new();
-        let mut leak_detectors = Vec::new();
-
-        // gb200 bianca has temperature sensors in several chassis items
-        let chassis_all = self.s.get_chassis_all().await?;
-        for chassis_id in chassis_all {
-            let mut url = format!("Chassis/{}", chassis_id);
-            let (_status_code, chassis): (StatusCode, Chassis) = self.s.client.get(&url).await?;
-            if chassis.thermal_subsystem.is_some() {
-                url = format!("Chassis/{}/ThermalSubsystem/ThermalMetrics", chassis_id);
-                let (_status_code, temps): (StatusCode, TemperaturesOemNvidia) =
-                    self.s.client.get(&url).await?;
-                if let Some(temp) = temps.temperature_readings_celsius {
-                    for t in temp {
-                        let sensor: Temperature = Temperature::from(t);
-                        temperatures.push(sensor);
-                    }
-                }
-                // currently the gb200 bianca board we have uses liquid cooling
-                // walk through leak detection sensors and add those
-                url = format!(
-                    "Chassis/{}/ThermalSubsystem/LeakDetection/LeakDetectors",
-                    chassis_id
-                );
-
-                let res: Result<(StatusCode, Sensors), RedfishError> =
-                    self.s.client.get(&url).await;
-
-                if let Ok((_, sensors)) = res {
-                    for sensor in sensors.members {
-                        url = sensor
-                            .odata_id
-                            .replace(&format!("/{REDFISH_ENDPOINT}/"), "");
-                        let (_status_code, l): (StatusCode, LeakDetector) =
-                            self.s.client.get(&url).await?;
-                        leak_detectors.push(l);
-                    }
-                }
-            }
-            if chassis.sensors.is_some() {
-                // Special handling for GB200s that may not have all their drives installed but still have sensors
-                if let Some(backplane_num) = chassis_id.strip_prefix("StorageBackplane_") {
-                    url = format!("Chassis/{}/Drives", chassis_id);
-
-                    // Fetch drives and find their respective sensor
-                    if let Ok((_status_code, drives)) =
-                        self.s.client.get::<DriveCollection>(&url).await
-                    {
-                        for sensor in drives
-                            .members
-                            .iter()
-                            .filter_map(|drive| {
-                                // Extract drive slot ID: "/path/NVMe_SSD_200" -> "200" -> 200
-                                let drive_id = drive
-                                    .odata_id
-                                    .split('/')
-                                    .next_back()?
-                                    .split('_')
-                                    .next_back()?
-                                    .parse::<u32>()
-                                    .ok()?;
-
-                                Some((drive_id % 4, backplane_num))
-                            })
-                            .map(|(sensor_index, backplane)| {
-                                format!(
-                                    "Chassis/{}/Sensors/StorageBackplane_{}_SSD_{}_Temp_0",
-                                    chassis_id, backplane, sensor_index
-                                )
-                            })
-                        {
-                            // Fetch sensor and add to temperatures if successful
-                            if let Ok((_status_code, sensor_data)) =
-                                self.s.client.get::<Sensor>(&sensor).await
-                            {
-                                temperatures.push(Temperature::from(sensor_data));
-                            }
-                        }
-                    }
-                } else {
-                    // walk through Chassis/*/Sensors/*/*Temp*/
-                    url = format!("Chassis/{}/Sensors", chassis_id);
-                    let (_status_code, sensors): (StatusCode, Sensors) =
-                        self.s.client.get(&url).await?;
-                    for sensor in sensors.members {
-                        if !sensor.odata_id.contains("Temp") {
-                            continue;
-                        }
-                        url = sensor
-                            .odata_id
-                            .replace(&format!("/{REDFISH_ENDPOINT}/"), "");
-                        let (_status_code, t): (StatusCode, Sensor) =
-                            self.s.client.get(&url).await?;
-                        let sensor: Temperature = Temperature::from(t);
-                        temperatures.push(sensor);
-                    }
-                }
-            }
-
-            // gb200 has fans under chassis sensors instead of thermal like other vendors, look for them in Chassis_0
-            if chassis_id == *"Chassis_0" {
-                url = format!("Chassis/{}/Sensors", chassis_id);
-                let (_status_code, sensors): (StatusCode, Sensors) =
-                    self.s.client.get(&url).await?;
-                for sensor in sensors.members {
-                    if sensor.odata_id.contains("FAN") {
-                        url = sensor
-                            .odata_id
-                            .replace(&format!("/{REDFISH_ENDPOINT}/"), "");
-                        let (_status_code, fan): (StatusCode, Fan) =
-                            self.s.client.get(&url).await?;
-                        fans.push(fan);
-                    }
-                }
-            }
-        }
-        let thermals = Thermal {
-            temperatures,
-            fans,
-            leak_detectors: Some(leak_detectors),
-            ..Default::default()
-        };
-        Ok(thermals)
+        let chassis_all = self.walk_chassis().await?;
+        self.thermal_metrics_from(&chassis_all).await
     }
 
     async fn get_gpu_sensors(&self) -> Result<Vec<GPUSensors>, RedfishError> {
@@ -448,6 +290,18 @@ impl Redfish for Bmc {
         self.get_system_event_log().await
     }
 
+    async fn get_system_event_log_since(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        limit: Option<usize>,
+    ) -> Result<Vec<LogEntry>, RedfishError> {
+        let url = format!(
+            "Systems/{}/LogServices/SEL/Entries",
+            self.s.resolve_system_id().await?
+        );
+        self.s.fetch_system_event_log(url, since, limit).await
+    }
+
     async fn get_bmc_event_log(
         &self,
         from: Option<chrono::DateTime<chrono::Utc>>,
@@ -461,21 +315,43 @@ impl Redfish for Bmc {
 
     async fn machine_setup(
         &self,
-        _boot_interface_mac: Option<&str>,
+        boot_interface_mac: Option<&str>,
         _bios_profiles: &HashMap<
             RedfishVendor,
             HashMap<String, HashMap<BiosProfileType, HashMap<String, serde_json::Value>>>,
         >,
         _selected_profile: BiosProfileType,
-    ) -> Result<(), RedfishError> {
-        self.disable_secure_boot().await?;
+    ) -> Result<crate::MachineSetupReport, RedfishError> {
+        let status = crate::MachineSetupStatus::or_apply_all(
+            self.machine_setup_status(boot_interface_mac).await,
+        );
+        if status.as_ref().is_some_and(|s| !s.needs_setup()) {
+            return Ok(crate::MachineSetupReport::skipped(&[
+                "disable_secure_boot",
+                "set_bios_attributes",
+            ]));
+        }
+
+        let mut report = crate::MachineSetupReport::default();
+        if status.as_ref().is_some_and(|s| !s.has_diff("SecureBoot")) {
+            report.skip("disable_secure_boot");
+        } else {
+            report.record("disable_secure_boot", self.disable_secure_boot().await)?;
+        }
 
         let bios_attrs = self.machine_setup_attrs().await?;
         let mut attrs = HashMap::new();
         attrs.extend(bios_attrs);
         let body = HashMap::from([("Attributes", attrs)]);
-        let url = format!("Systems/{}/Bios/Settings", self.s.system_id());
-        self.s.client.patch(&url, body).await.map(|_status_code| ())
+        let url = format!(
+            "Systems/{}/Bios/Settings",
+            self.s.resolve_system_id().await?
+        );
+        report.record(
+            "set_bios_attributes",
+            self.s.client.patch(&url, body).await.map(|_status_code| ()),
+        )?;
+        Ok(report)
     }
 
     async fn machine_setup_status(
@@ -538,7 +414,7 @@ impl Redfish for Bmc {
         self.s.lockdown_status().await
     }
 
-    async fn setup_serial_console(&self) -> Result<(), RedfishError> {
+    async fn setup_serial_console(&self) -> Result<bool, RedfishError> {
         self.s.setup_serial_console().await
     }
 
@@ -600,7 +476,7 @@ impl Redfish for Bmc {
         }
     }
 
-    async fn clear_tpm(&self) -> Result<(), RedfishError> {
+    async fn clear_tpm(&self) -> Result<crate::ApplyResult, RedfishError> {
         self.s.clear_tpm().await
     }
 
@@ -630,7 +506,8 @@ impl Redfish for Bmc {
             .await
             .map_err(|e| RedfishError::FileError(format!("Could not open file: {}", e)))?;
 
-        let update_service = self.s.get_update_service().await?;
+        let update_service_url = self.update_service_for(&component_type).await?;
+        let update_service = self.s.get_update_service_at(&update_service_url).await?;
 
         if update_service.multipart_http_push_uri.is_empty() {
             return Err(RedfishError::NotSupported(
@@ -647,7 +524,7 @@ impl Redfish for Bmc {
                 }
             })?;
 
-        let (_status_code, _loc, body) = self
+        let response = self
             .s
             .client
             .req_update_firmware_multipart(
@@ -660,14 +537,7 @@ impl Redfish for Bmc {
             )
             .await?;
 
-        let task: Task =
-            serde_json::from_str(&body).map_err(|e| RedfishError::JsonDeserializeError {
-                url: update_service.multipart_http_push_uri,
-                body,
-                source: e,
-            })?;
-
-        Ok(task.id)
+        response.task_id(&update_service.multipart_http_push_uri)
     }
 
     async fn bios(
@@ -683,7 +553,7 @@ impl Redfish for Bmc {
         self.s.set_bios(values).await
     }
 
-    async fn reset_bios(&self) -> Result<(), RedfishError> {
+    async fn reset_bios(&self) -> Result<crate::ApplyResult, RedfishError> {
         self.s.reset_bios().await
     }
 
@@ -693,7 +563,7 @@ impl Redfish for Bmc {
         self.s.pending().await
     }
 
-    async fn clear_pending(&self) -> Result<(), RedfishError> {
+    async fn clear_pending(&self) -> Result<crate::ClearPendingResult, RedfishError> {
         self.s.clear_pending().await
     }
 
@@ -832,6 +702,20 @@ impl Redfish for Bmc {
         Ok(body)
     }
 
+    async fn get_port_metrics(
+        &self,
+        chassis_id: &str,
+        network_adapter: &str,
+        id: &str,
+    ) -> Result<crate::model::port::PortMetrics, RedfishError> {
+        let url = format!(
+            "Chassis/{}/NetworkAdapters/{}/Ports/{}/Metrics",
+            chassis_id, network_adapter, id
+        );
+        let (_status_code, body) = self.s.client.get(&url).await?;
+        Ok(body)
+    }
+
     async fn get_network_device_function(
         &self,
         _chassis_id: &str,
@@ -857,19 +741,17 @@ impl Redfish for Bmc {
     /// Set new_uefi_password to "" to disable it.
     async fn change_uefi_password(
         &self,
-        current_uefi_password: &str,
-        new_uefi_password: &str,
-    ) -> Result<Option<String>, RedfishError> {
+        current_uefi_password: &SecretString,
+        new_uefi_password: &SecretString,
+    ) -> Result<crate::BiosPasswordChange, RedfishError> {
         self.s
             .change_bios_password(UEFI_PASSWORD_NAME, current_uefi_password, new_uefi_password)
             .await
     }
 
     async fn change_boot_order(&self, boot_array: Vec<String>) -> Result<(), RedfishError> {
-        let body = HashMap::from([("Boot", HashMap::from([("BootOrder", boot_array)]))]);
-        let url = format!("Systems/{}/Settings", self.s.system_id());
-        self.s.client.patch(&url, body).await?;
-        Ok(())
+        let target = BootOrderTarget::settings(self.s.resolve_system_id().await?);
+        self.s.change_boot_order_at(&target, boot_array).await
     }
 
     async fn get_service_root(&self) -> Result<ServiceRoot, RedfishError> {
@@ -908,35 +790,43 @@ impl Redfish for Bmc {
         &self,
         address: &str,
     ) -> Result<Option<String>, RedfishError> {
-        let mac_address = address.replace(':', "").to_uppercase();
-        let boot_option_name =
-            format!("{} (MAC:{})", BootOptionName::Http.to_string(), mac_address);
-        let boot_array = self
-            .get_boot_options_ids_with_first(
+        // GB200 has no NetworkDeviceFunctions or PCIeDevices to find the DPU by, but a node
+        // with a single NIC has a single HTTP boot option, which must be it.
+        let boot_array = if address.is_empty() {
+            self.get_boot_options_ids_with_only_http_first().await?
+        } else {
+            self.get_boot_options_ids_with_first(
                 BootOptionName::Http,
-                BootOptionMatchField::DisplayName,
-                Some(&boot_option_name),
+                BootOptionMatchField::Mac,
+                Some(address),
             )
-            .await?;
+            .await?
+        };
         self.change_boot_order(boot_array).await?;
         Ok(None)
     }
 
     async fn clear_uefi_password(
         &self,
-        current_uefi_password: &str,
+        current_uefi_password: &SecretString,
     ) -> Result<Option<String>, RedfishError> {
-        self.change_uefi_password(current_uefi_password, "").await
+        self.change_uefi_password(current_uefi_password, &SecretString::default())
+            .await?
+            .job_id()
     }
 
     async fn get_base_mac_address(&self) -> Result<Option<String>, RedfishError> {
         self.s.get_base_mac_address().await
     }
 
-    async fn lockdown_bmc(&self, target: crate::EnabledDisabled) -> Result<(), RedfishError> {
+    async fn lockdown_bmc(&self, target: crate::EnabledDisabled) -> Result<bool, RedfishError> {
         self.s.lockdown_bmc(target).await
     }
 
+    async fn bmc_lockdown_status(&self) -> Result<crate::Status, RedfishError> {
+        self.s.bmc_lockdown_status().await
+    }
+
     async fn is_ipmi_over_lan_enabled(&self) -> Result<bool, RedfishError> {
         self.s.is_ipmi_over_lan_enabled().await
     }
@@ -944,7 +834,7 @@ impl Redfish for Bmc {
     async fn enable_ipmi_over_lan(
         &self,
         target: crate::EnabledDisabled,
-    ) -> Result<(), RedfishError> {
+    ) -> Result<bool, RedfishError> {
         self.s.enable_ipmi_over_lan(target).await
     }
 
@@ -954,16 +844,22 @@ impl Redfish for Bmc {
         targets: Vec<String>,
         transfer_protocol: TransferProtocolType,
     ) -> Result<Task, RedfishError> {
+        let component = if targets.iter().any(|t| t == HGX_BMC_FIRMWARE) {
+            ComponentType::HGXBMC
+        } else {
+            ComponentType::Unknown
+        };
+        let update_service_url = self.update_service_for(&component).await?;
         self.s
-            .update_firmware_simple_update(image_uri, targets, transfer_protocol)
+            .simple_update_at(&update_service_url, image_uri, targets, transfer_protocol)
             .await
     }
 
-    async fn enable_rshim_bmc(&self) -> Result<(), RedfishError> {
+    async fn enable_rshim_bmc(&self) -> Result<bool, RedfishError> {
         self.s.enable_rshim_bmc().await
     }
 
-    async fn clear_nvram(&self) -> Result<(), RedfishError> {
+    async fn clear_nvram(&self) -> Result<crate::ApplyResult, RedfishError> {
         self.s.clear_nvram().await
     }
 
@@ -979,7 +875,10 @@ impl Redfish for Bmc {
         let attrs: HashMap<String, serde_json::Value> =
             HashMap::from([("EmbeddedUefiShell".to_string(), "Disabled".into())]);
         let body = HashMap::from([("Attributes", attrs)]);
-        let url = format!("Systems/{}/Bios/Settings", self.s.system_id());
+        let url = format!(
+            "Systems/{}/Bios/Settings",
+            self.s.resolve_system_id().await?
+        );
         self.s.client.patch(&url, body).await.map(|_status_code| ())
     }
 
@@ -1094,30 +993,576 @@ impl Redfish for Bmc {
     ) -> Result<crate::model::component_integrity::Evidence, RedfishError> {
         self.s.get_evidence(url).await
     }
-}
 
-impl Bmc {
-    /// Check BIOS and BMC attributes and return differences
-    async fn diff_bios_bmc_attr(&self) -> Result<Vec<MachineSetupDiff>, RedfishError> {
-        let mut diffs = vec![];
+    async fn reset_secure_boot_keys(
+        &self,
+        reset_type: crate::model::secure_boot::SecureBootResetType,
+    ) -> Result<(), RedfishError> {
+        self.s.reset_secure_boot_keys(reset_type).await
+    }
 
-        // Check BIOS and BMC attributes
-        let sb = self.get_secure_boot().await?;
-        if sb.secure_boot_enable.unwrap_or(false) {
-            diffs.push(MachineSetupDiff {
-                key: "SecureBoot".to_string(),
-                expected: "false".to_string(),
-                actual: "true".to_string(),
-            });
-        }
+    async fn get_boot_progress(&self) -> Result<crate::model::system::BootProgress, RedfishError> {
+        self.s.get_boot_progress().await
+    }
 
-        let bios = self.s.bios_attributes().await?;
-        let expected_attrs = self.machine_setup_attrs().await?;
-        for (key, expected) in expected_attrs {
-            let Some(actual) = bios.get(&key) else {
-                diffs.push(MachineSetupDiff {
-                    key: key.to_string(),
-                    expected: expected.to_string(),
+    async fn get_post_codes(&self) -> Result<Vec<LogEntry>, RedfishError> {
+        self.s.get_post_codes().await
+    }
+
+    async fn get_environment_metrics(
+        &self,
+        chassis_id: &str,
+    ) -> Result<crate::model::environment_metrics::EnvironmentMetrics, RedfishError> {
+        self.s.get_environment_metrics(chassis_id).await
+    }
+
+    async fn get_energy_kwh(
+        &self,
+    ) -> Result<Vec<crate::model::sensor::EnergyReading>, RedfishError> {
+        self.s.get_energy_kwh().await
+    }
+
+    async fn get_sensors(
+        &self,
+        chassis_id: &str,
+        reading_type: Option<crate::model::sensor::ReadingType>,
+    ) -> Result<Vec<crate::model::sensor::Sensor>, RedfishError> {
+        self.s.get_sensors(chassis_id, reading_type).await
+    }
+
+    async fn wait_for_power_state(
+        &self,
+        target: crate::PowerState,
+        timeout: std::time::Duration,
+        poll: std::time::Duration,
+    ) -> Result<(), RedfishError> {
+        self.s.wait_for_power_state(target, timeout, poll).await
+    }
+
+    async fn pending_diff(&self) -> Result<Vec<crate::model::BiosAttrChange>, RedfishError> {
+        let pending = self.pending().await?;
+        self.s.bios_attr_changes(pending).await
+    }
+
+    async fn set_bios_attributes(
+        &self,
+        attrs: HashMap<String, serde_json::Value>,
+    ) -> Result<(), RedfishError> {
+        self.s.set_bios_attributes(attrs).await
+    }
+
+    async fn set_uefi_http(
+        &self,
+        cfg: crate::model::oem::nvidia_viking::UefiHttpAttributes,
+    ) -> Result<(), RedfishError> {
+        self.s.set_uefi_http(cfg).await
+    }
+
+    async fn set_virtualization(
+        &self,
+        cfg: crate::model::oem::nvidia_viking::VirtAttributes,
+    ) -> Result<(), RedfishError> {
+        self.s.set_virtualization(cfg).await
+    }
+
+    async fn get_bios_lockdown(
+        &self,
+    ) -> Result<crate::model::oem::nvidia_viking::BiosLockdownAttributes, RedfishError> {
+        self.s.get_bios_lockdown().await
+    }
+
+    async fn get_decoded_sel(
+        &self,
+    ) -> Result<Vec<crate::model::sel::DecodedSelEntry>, RedfishError> {
+        let sel = self.get_system_event_log().await?;
+        Ok(sel
+            .iter()
+            .map(crate::model::sel::DecodedSelEntry::from)
+            .collect())
+    }
+
+    async fn resolve_origin(
+        &self,
+        entry: &crate::model::sel::LogEntry,
+    ) -> Result<crate::model::resource::Resource, RedfishError> {
+        self.s.resolve_origin(entry).await
+    }
+
+    async fn attach_to_update(
+        &self,
+        task_id: &str,
+    ) -> Result<crate::model::task::Task, RedfishError> {
+        self.s.attach_to_update(task_id).await
+    }
+
+    async fn get_fru_inventory(&self) -> Result<crate::model::fru::FruInventory, RedfishError> {
+        self.s.get_fru_inventory().await
+    }
+
+    async fn get_power_supplies(
+        &self,
+    ) -> Result<Vec<crate::model::power::PowerSupplyInfo>, RedfishError> {
+        self.s.get_power_supplies().await
+    }
+
+    async fn download_firmware_image(
+        &self,
+        inventory_id: &str,
+        out: &std::path::Path,
+    ) -> Result<(), RedfishError> {
+        self.s.download_firmware_image(inventory_id, out).await
+    }
+
+    async fn create_boot_option(&self, opt: NewBootOption) -> Result<String, RedfishError> {
+        self.s.create_boot_option(opt).await
+    }
+
+    async fn delete_boot_option(&self, option_id: &str) -> Result<(), RedfishError> {
+        self.s.delete_boot_option_for(self, option_id).await
+    }
+
+    async fn get_bios_prerequisites(
+        &self,
+        attrs: &HashMap<String, serde_json::Value>,
+    ) -> Result<HashMap<String, serde_json::Value>, RedfishError> {
+        self.s.get_bios_prerequisites(attrs).await
+    }
+
+    async fn get_bmc_journal(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<String>, RedfishError> {
+        self.s.get_bmc_journal(since).await
+    }
+
+    async fn get_all_metrics(&self) -> Result<crate::AllMetrics, RedfishError> {
+        // Both readings walk every chassis, so fetch the chassis once and share it.
+        let chassis_all = self.walk_chassis().await?;
+        let (power, thermal) = tokio::try_join!(
+            self.power_metrics_from(&chassis_all),
+            self.thermal_metrics_from(&chassis_all)
+        )?;
+        Ok(crate::AllMetrics::new(power, thermal))
+    }
+
+    async fn get_power_restore_policy(&self) -> Result<crate::PowerRestorePolicy, RedfishError> {
+        self.s.get_power_restore_policy().await
+    }
+
+    async fn set_power_restore_policy(
+        &self,
+        policy: crate::PowerRestorePolicy,
+    ) -> Result<(), RedfishError> {
+        self.s.set_power_restore_policy(policy).await
+    }
+
+    async fn set_power_on_delay(&self, seconds: u32) -> Result<(), RedfishError> {
+        self.s.set_power_on_delay(seconds).await
+    }
+
+    async fn get_manager_health(&self) -> Result<crate::model::ManagerHealth, RedfishError> {
+        self.s.get_manager_health().await
+    }
+
+    async fn get_active_conditions(&self) -> Result<Vec<crate::model::Condition>, RedfishError> {
+        self.s.get_active_conditions().await
+    }
+
+    async fn get_fabric_topology(
+        &self,
+    ) -> Result<Vec<crate::model::fabric::FabricTopology>, RedfishError> {
+        self.s.get_fabric_topology().await
+    }
+
+    async fn get_pcie_switches(
+        &self,
+        chassis_id: &str,
+    ) -> Result<Vec<crate::model::fabric::SwitchPorts>, RedfishError> {
+        self.s.get_pcie_switches(chassis_id).await
+    }
+
+    async fn reset_erot(&self, target: ComponentType) -> Result<(), RedfishError> {
+        // The BIOS ERoT shows up as ERoT_CPU on some trays
+        let names: &[&str] = match target {
+            ComponentType::EROTBMC => &["ERoT_BMC"],
+            ComponentType::EROTBIOS => &["ERoT_BIOS", "ERoT_CPU"],
+            _ => {
+                return Err(RedfishError::NotSupported(format!(
+                    "{target:?} is not an ERoT"
+                )))
+            }
+        };
+        let chassis_ids: Vec<String> = self
+            .s
+            .get_chassis_all()
+            .await?
+            .into_iter()
+            .filter(|id| names.iter().any(|n| id.contains(n)))
+            .collect();
+        if chassis_ids.is_empty() {
+            return Err(RedfishError::NotSupported(format!(
+                "No ERoT chassis found for {target:?}"
+            )));
+        }
+
+        // Look up every action first, so either all of the ERoTs are reset or none
+        let mut targets = Vec::with_capacity(chassis_ids.len());
+        for chassis_id in &chassis_ids {
+            let url = format!("Chassis/{chassis_id}");
+            let (_status_code, chassis): (_, serde_json::Value) = self.s.client.get(&url).await?;
+            let action = chassis
+                .pointer("/Actions/Oem")
+                .and_then(|oem| oem.get(EROT_RESET_ACTION))
+                .ok_or_else(|| {
+                    RedfishError::NotSupported(format!("{EROT_RESET_ACTION} on {url}"))
+                })?;
+            let target_url = action
+                .get("target")
+                .and_then(|t| t.as_str())
+                .ok_or_else(|| RedfishError::MissingKey {
+                    key: format!("Actions.Oem.{EROT_RESET_ACTION}.target"),
+                    url: url.clone(),
+                })?;
+            targets.push(target_url.replace(&format!("/{REDFISH_ENDPOINT}/"), ""));
+        }
+        for target_url in targets {
+            let args = HashMap::from([("ResetType", EROT_RESET_TYPE)]);
+            self.s.client.post(&target_url, args).await?;
+        }
+        Ok(())
+    }
+
+    async fn get_firmware_slots(
+        &self,
+        component: &str,
+    ) -> Result<Vec<crate::model::software_inventory::FirmwareSlot>, RedfishError> {
+        self.s.get_firmware_slots(component).await
+    }
+
+    async fn activate_firmware_slot(
+        &self,
+        component: &str,
+        slot: &str,
+    ) -> Result<(), RedfishError> {
+        self.s.activate_firmware_slot(component, slot).await
+    }
+
+    async fn get_pending_jobs(&self) -> Result<Vec<crate::model::job::Job>, RedfishError> {
+        self.s.get_pending_jobs().await
+    }
+}
+
+impl Bmc {
+    // The HGX BMC's own UpdateService, if it has one that isn't the host BMC's: the one whose
+    // FirmwareInventory holds the HGX BMC's active image. On the GB200s we've seen that's the
+    // host BMC's, which then takes HGX images too and routes them by Targets.
+    async fn hgx_update_service(&self) -> Result<Option<String>, RedfishError> {
+        let url = format!("Managers/{HGX_BMC_MANAGER_ID}");
+        let manager: serde_json::Value = match self.s.client.get(&url).await {
+            Ok((_status_code, manager)) => manager,
+            Err(e) if e.not_found() => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let link = manager
+            .pointer("/Links/ActiveSoftwareImage/@odata.id")
+            .and_then(|v| v.as_str())
+            .and_then(|image| image.split_once("/FirmwareInventory/"))
+            .map(|(service, _)| service.replace(&format!("/{REDFISH_ENDPOINT}/"), ""));
+        Ok(link.filter(|link| *link != self.s.update_service()))
+    }
+
+    // Where to send an update for `component`
+    async fn update_service_for(&self, component: &ComponentType) -> Result<String, RedfishError> {
+        if *component == ComponentType::HGXBMC {
+            if let Some(url) = self.hgx_update_service().await? {
+                return Ok(url);
+            }
+        }
+        Ok(self.s.update_service())
+    }
+
+    /// Every chassis with its id, in collection order.
+    async fn walk_chassis(&self) -> Result<Vec<(String, Chassis)>, RedfishError> {
+        let mut chassis_all = Vec::new();
+        for chassis_id in self.s.get_chassis_all().await? {
+            let url = format!("Chassis/{}", chassis_id);
+            let (_status_code, chassis): (StatusCode, Chassis) = self.s.client.get(&url).await?;
+            chassis_all.push((chassis_id, chassis));
+        }
+        Ok(chassis_all)
+    }
+
+    async fn power_metrics_from(
+        &self,
+        chassis_all: &[(String, Chassis)],
+    ) -> Result<crate::Power, RedfishError> {
+        let mut voltages = Vec::new();
+        let mut power_supplies = Vec::new();
+        // gb200 bianca has empty PowerSupplies on several chassis items
+        // for now assemble power supply details from PDB_0 chassis entries
+        let mut url = "Chassis/PDB_0".to_string();
+        let (_status_code, pdb): (StatusCode, PowerSupply) = self.s.client.get(&url).await?;
+        let mut hsc0 = pdb.clone();
+        let mut hsc1 = pdb.clone();
+        // voltage sensors are on several chassis items under sensors
+        for (chassis_id, chassis) in chassis_all {
+            // walk through all Chassis/*/Sensors/ for voltage and PDB_0 for power supply details
+            let Some(sensors) = self.s.follow_sensors_link(chassis.sensors.as_ref()).await? else {
+                continue;
+            };
+            for sensor in sensors.members {
+                if chassis_id == "PDB_0" {
+                    // get amps and watts for power supply
+                    if sensor.odata_id.contains("HSC_0_Pwr") {
+                        url = sensor
+                            .odata_id
+                            .replace(&format!("/{REDFISH_ENDPOINT}/"), "");
+                        let (_status_code, t): (StatusCode, Sensor) =
+                            self.s.client.get(&url).await?;
+                        hsc0.last_power_output_watts = t.reading;
+                        hsc0.power_output_watts = t.reading;
+                        hsc0.power_capacity_watts = t.reading_range_max;
+                    }
+                    if sensor.odata_id.contains("HSC_0_Cur") {
+                        url = sensor
+                            .odata_id
+                            .replace(&format!("/{REDFISH_ENDPOINT}/"), "");
+                        let (_status_code, t): (StatusCode, Sensor) =
+                            self.s.client.get(&url).await?;
+                        hsc0.power_output_amps = t.reading;
+                    }
+                    if sensor.odata_id.contains("HSC_1_Pwr") {
+                        url = sensor
+                            .odata_id
+                            .replace(&format!("/{REDFISH_ENDPOINT}/"), "");
+                        let (_status_code, t): (StatusCode, Sensor) =
+                            self.s.client.get(&url).await?;
+                        hsc1.last_power_output_watts = t.reading;
+                        hsc1.power_output_watts = t.reading;
+                        hsc1.power_capacity_watts = t.reading_range_max;
+                    }
+                    if sensor.odata_id.contains("HSC_1_Cur") {
+                        url = sensor
+                            .odata_id
+                            .replace(&format!("/{REDFISH_ENDPOINT}/"), "");
+                        let (_status_code, t): (StatusCode, Sensor) =
+                            self.s.client.get(&url).await?;
+                        hsc1.power_output_amps = t.reading;
+                    }
+                }
+                // now all voltage sensors in all chassis
+                if !sensor.odata_id.contains("Volt") {
+                    continue;
+                }
+                url = sensor
+                    .odata_id
+                    .replace(&format!("/{REDFISH_ENDPOINT}/"), "");
+                let (_status_code, t): (StatusCode, Sensor) = self.s.client.get(&url).await?;
+                let sensor: Voltages = Voltages::from(t);
+                voltages.push(sensor);
+            }
+        }
+
+        power_supplies.push(hsc0);
+        power_supplies.push(hsc1);
+        let redundancy = self.get_power_redundancy(chassis_all).await?;
+        let power = Power {
+            odata: None,
+            id: "Power".to_string(),
+            name: "Power".to_string(),
+            power_control: vec![],
+            power_supplies: Some(power_supplies),
+            voltages: Some(voltages),
+            redundancy: (!redundancy.is_empty()).then_some(redundancy),
+        };
+        Ok(power)
+    }
+
+    async fn thermal_metrics_from(
+        &self,
+        chassis_all: &[(String, Chassis)],
+    ) -> Result<crate::Thermal, RedfishError> {
+        let mut temperatures = Vec::new();
+        let mut fans = Vec::new();
+        let mut leak_detectors = Vec::new();
+
+        // gb200 bianca has temperature sensors in several chassis items
+        for (chassis_id, chassis) in chassis_all {
+            let mut url;
+            let subsystem: Option<ThermalSubsystem> = self
+                .s
+                .follow_link(chassis.thermal_subsystem.as_ref())
+                .await?;
+            if let Some(subsystem) = subsystem {
+                let temps: Option<TemperaturesOemNvidia> = self
+                    .s
+                    .follow_link(subsystem.thermal_metrics.as_ref())
+                    .await?;
+                if let Some(temp) = temps.and_then(|t| t.temperature_readings_celsius) {
+                    for t in temp {
+                        let sensor: Temperature = Temperature::from(t);
+                        temperatures.push(sensor);
+                    }
+                }
+                // currently the gb200 bianca board we have uses liquid cooling
+                // walk through leak detection sensors and add those
+                let detectors: Result<Option<Sensors>, RedfishError> = async {
+                    let leak_detection: Option<LeakDetection> = self
+                        .s
+                        .follow_link(subsystem.leak_detection.as_ref())
+                        .await?;
+                    self.s
+                        .follow_link(leak_detection.and_then(|l| l.leak_detectors).as_ref())
+                        .await
+                }
+                .await;
+
+                if let Ok(Some(sensors)) = detectors {
+                    for sensor in sensors.members {
+                        url = sensor
+                            .odata_id
+                            .replace(&format!("/{REDFISH_ENDPOINT}/"), "");
+                        let (_status_code, l): (StatusCode, LeakDetector) =
+                            self.s.client.get(&url).await?;
+                        leak_detectors.push(l);
+                    }
+                }
+            }
+            let sensors = self.s.follow_sensors_link(chassis.sensors.as_ref()).await?;
+            if let Some(sensors) = sensors.as_ref() {
+                // Special handling for GB200s that may not have all their drives installed but still have sensors
+                if let Some(backplane_num) = chassis_id.strip_prefix("StorageBackplane_") {
+                    url = format!("Chassis/{}/Drives", chassis_id);
+
+                    // Fetch drives and find their respective sensor
+                    if let Ok((_status_code, drives)) =
+                        self.s.client.get::<DriveCollection>(&url).await
+                    {
+                        for sensor in drives
+                            .members
+                            .iter()
+                            .filter_map(|drive| {
+                                // Extract drive slot ID: "/path/NVMe_SSD_200" -> "200" -> 200
+                                let drive_id = drive
+                                    .odata_id
+                                    .split('/')
+                                    .next_back()?
+                                    .split('_')
+                                    .next_back()?
+                                    .parse::<u32>()
+                                    .ok()?;
+
+                                Some((drive_id % 4, backplane_num))
+                            })
+                            .map(|(sensor_index, backplane)| {
+                                format!(
+                                    "Chassis/{}/Sensors/StorageBackplane_{}_SSD_{}_Temp_0",
+                                    chassis_id, backplane, sensor_index
+                                )
+                            })
+                        {
+                            // Fetch sensor and add to temperatures if successful
+                            if let Ok((_status_code, sensor_data)) =
+                                self.s.client.get::<Sensor>(&sensor).await
+                            {
+                                temperatures.push(Temperature::from(sensor_data));
+                            }
+                        }
+                    }
+                } else {
+                    // walk through Chassis/*/Sensors/*/*Temp*/
+                    for sensor in sensors.members.iter() {
+                        if !sensor.odata_id.contains("Temp") {
+                            continue;
+                        }
+                        url = sensor
+                            .odata_id
+                            .replace(&format!("/{REDFISH_ENDPOINT}/"), "");
+                        let (_status_code, t): (StatusCode, Sensor) =
+                            self.s.client.get(&url).await?;
+                        let sensor: Temperature = Temperature::from(t);
+                        temperatures.push(sensor);
+                    }
+                }
+            }
+
+            // gb200 has fans under chassis sensors instead of thermal like other vendors, look for them in Chassis_0
+            if let Some(sensors) = sensors.filter(|_| chassis_id == "Chassis_0") {
+                for sensor in sensors.members {
+                    if sensor.odata_id.contains("FAN") {
+                        url = sensor
+                            .odata_id
+                            .replace(&format!("/{REDFISH_ENDPOINT}/"), "");
+                        let (_status_code, fan): (StatusCode, Fan) =
+                            self.s.client.get(&url).await?;
+                        fans.push(fan);
+                    }
+                }
+            }
+        }
+        let thermals = Thermal {
+            temperatures,
+            fans,
+            leak_detectors: Some(leak_detectors),
+            ..Default::default()
+        };
+        Ok(thermals)
+    }
+
+    /// PSU redundancy sets from the legacy Power resource of any of `chassis_all` that has one
+    async fn get_power_redundancy(
+        &self,
+        chassis_all: &[(String, Chassis)],
+    ) -> Result<Vec<Redundancy>, RedfishError> {
+        #[derive(Deserialize, Debug)]
+        #[serde(rename_all = "PascalCase")]
+        struct PowerRedundancy {
+            #[serde(default)]
+            redundancy: Vec<Redundancy>,
+        }
+
+        // Most chassis have no Power resource, or one that doesn't serve GET
+        let absent = |e: &RedfishError| {
+            e.not_found()
+                || crate::standard::method_not_allowed(e)
+                || matches!(e, RedfishError::NotSupported(_))
+        };
+        let mut redundancy = Vec::new();
+        for (_chassis_id, chassis) in chassis_all {
+            match self
+                .s
+                .follow_link::<PowerRedundancy>(chassis.power.as_ref())
+                .await
+            {
+                Ok(power) => redundancy.extend(power.into_iter().flat_map(|p| p.redundancy)),
+                Err(e) if absent(&e) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(redundancy)
+    }
+
+    /// Check BIOS and BMC attributes and return differences
+    async fn diff_bios_bmc_attr(&self) -> Result<Vec<MachineSetupDiff>, RedfishError> {
+        let mut diffs = vec![];
+
+        // Check BIOS and BMC attributes
+        let sb = self.get_secure_boot().await?;
+        if sb.secure_boot_enable.unwrap_or(false) {
+            diffs.push(MachineSetupDiff {
+                key: "SecureBoot".to_string(),
+                expected: "false".to_string(),
+                actual: "true".to_string(),
+            });
+        }
+
+        let bios = self.s.bios_attributes().await?;
+        let expected_attrs = self.machine_setup_attrs().await?;
+        for (key, expected) in expected_attrs {
+            let Some(actual) = bios.get(&key) else {
+                diffs.push(MachineSetupDiff {
+                    key: key.to_string(),
+                    expected: expected.to_string(),
                     actual: "_missing_".to_string(),
                 });
                 continue;
@@ -1141,10 +1586,6 @@ impl Bmc {
         &self,
         boot_interface_mac: &str,
     ) -> Result<(Option<String>, Option<String>), RedfishError> {
-        let mac_address = boot_interface_mac.replace(':', "").to_uppercase();
-        let boot_option_name =
-            format!("{} (MAC:{})", BootOptionName::Http.to_string(), mac_address);
-
         let boot_options = self.s.get_system().await?.boot.boot_order;
 
         // Get actual first boot option
@@ -1158,7 +1599,7 @@ impl Bmc {
         let mut expected_first_boot_option = None;
         for member in &boot_options {
             let b = self.s.get_boot_option(member.as_str()).await?;
-            if b.display_name.starts_with(&boot_option_name) {
+            if BootOptionName::Http.matches_mac(&b, boot_interface_mac) {
                 expected_first_boot_option = Some(b.display_name);
                 break;
             }
@@ -1181,7 +1622,7 @@ impl Bmc {
             "BootSourceOverrideTarget".to_string(),
             format!("{}", override_target),
         );
-        let url = format!("Systems/{}/Settings ", self.s.system_id());
+        let url = format!("Systems/{}/Settings ", self.s.resolve_system_id().await?);
         self.s
             .client
             .patch(&url, HashMap::from([("Boot", data)]))
@@ -1220,8 +1661,9 @@ impl Bmc {
             let is_match = match match_field {
                 BootOptionMatchField::DisplayName => b.display_name.starts_with(name_str),
                 BootOptionMatchField::UefiDevicePath => {
-                    matches!(b.uefi_device_path, Some(x) if x.starts_with(name_str))
+                    with_name.matches_device_path(&b.device_path())
                 }
+                BootOptionMatchField::Mac => with_name.matches_mac(&b, name_str),
             };
             if is_match {
                 ordered.insert(0, b.id);
@@ -1238,8 +1680,51 @@ impl Bmc {
         Ok(ordered)
     }
 
+    // Like get_boot_options_ids_with_first for BootOptionName::Http, but for when there's no MAC
+    // address to pick the option by. Errors unless there is exactly one HTTP boot option.
+    async fn get_boot_options_ids_with_only_http_first(&self) -> Result<Vec<String>, RedfishError> {
+        let with_name = BootOptionName::Http;
+        let mut http = Vec::new(); // the matching boot options
+        let mut ordered = Vec::new(); // the final boot options
+        let boot_options = self.s.get_system().await?.boot.boot_order;
+        for member in &boot_options {
+            let b: BootOption = self.s.get_boot_option(member.as_str()).await?;
+            if b.display_name.starts_with(with_name.to_string())
+                || with_name.matches_device_path(&b.device_path())
+            {
+                http.push(b);
+            } else {
+                ordered.push(b.id);
+            }
+        }
+
+        match http.as_slice() {
+            [only] => {
+                ordered.insert(0, only.id.clone());
+                Ok(ordered)
+            }
+            [] => Err(RedfishError::GenericError {
+                error: format!(
+                    "Could not find boot option matching {}; boot options: {boot_options:#?}",
+                    with_name.to_string()
+                ),
+            }),
+            _ => Err(RedfishError::GenericError {
+                error: format!(
+                    "{} boot options match {}, pass the DPU's MAC address to pick one: {:#?}",
+                    http.len(),
+                    with_name.to_string(),
+                    http.iter().map(|b| &b.display_name).collect::<Vec<_>>()
+                ),
+            }),
+        }
+    }
+
     async fn get_system_event_log(&self) -> Result<Vec<LogEntry>, RedfishError> {
-        let url = format!("Systems/{}/LogServices/SEL/Entries", self.s.system_id());
+        let url = format!(
+            "Systems/{}/LogServices/SEL/Entries",
+            self.s.resolve_system_id().await?
+        );
         let (_status_code, log_entry_collection): (_, LogEntryCollection) =
             self.s.client.get(&url).await?;
         let log_entries = log_entry_collection.members;
@@ -1283,7 +1768,7 @@ impl Bmc {
                 "Enabled" => Ok(EnabledDisabled::Enabled),
                 "Disabled" => Ok(EnabledDisabled::Disabled),
                 _ => Err(RedfishError::InvalidValue {
-                    url: format!("Systems/{}/Bios", self.s.system_id()),
+                    url: format!("Systems/{}/Bios", self.s.resolve_system_id().await?),
                     field: "EmbeddedUefiShell".to_string(),
                     err: crate::model::InvalidValueError(format!(
                         "Expected 'Enabled' or 'Disabled', got '{}'",
@@ -1295,12 +1780,23 @@ impl Bmc {
             Err(RedfishError::InvalidKeyType {
                 key: "Attributes".to_string(),
                 expected_type: "Object".to_string(),
-                url: format!("Systems/{}/Bios", self.s.system_id()),
+                url: format!("Systems/{}/Bios", self.s.resolve_system_id().await?),
             })
         }
     }
 }
 
+const HGX_BMC_MANAGER_ID: &str = "HGX_BMC_0";
+
+// The NVIDIA OEM action that power cycles an ERoT, and the only reset type it takes, see the
+// NvidiaChassis schema
+const EROT_RESET_ACTION: &str = "#NvidiaChassis.AuxPowerReset";
+const EROT_RESET_TYPE: &str = "AuxPowerCycle";
+
+// SimpleUpdate target for the HGX BMC's firmware. HGX_Chassis_0 isn't enough to tell, UEFI
+// updates target it too.
+const HGX_BMC_FIRMWARE: &str = "/redfish/v1/UpdateService/FirmwareInventory/HGX_FW_BMC_0";
+
 // UpdateParameters is what is sent for a multipart firmware upload's metadata.
 #[derive(Serialize)]
 #[serde(rename_all = "PascalCase")]
@@ -1335,7 +1831,10 @@ impl UpdateParameters {
 
 #[cfg(test)]
 mod tests {
+    use serde_json::json;
+
     use super::*;
+    use crate::fake_bmc::{FakeBmc, Response};
 
     #[test]
     fn test_update_parameters_targets_all_variants() {
@@ -1400,4 +1899,216 @@ mod tests {
             );
         }
     }
+
+    async fn erot_bmc(actions: &[&str]) -> (FakeBmc, Bmc) {
+        let chassis = |id: &str, with_reset: bool| {
+            let url = format!("/redfish/v1/Chassis/{id}");
+            let mut oem = json!({
+                "#NvidiaChassis.BootProtectedDevice": {
+                    "target": format!("{url}/Actions/Oem/NvidiaChassis.BootProtectedDevice")
+                }
+            });
+            if with_reset {
+                oem[EROT_RESET_ACTION] = json!({
+                    "target": format!("{url}/Actions/Oem/NvidiaChassis.AuxPowerReset"),
+                    "ResetType@Redfish.AllowableValues": ["AuxPowerCycle"]
+                });
+            }
+            (
+                url.clone(),
+                json!({"@odata.id": url, "Id": id, "Actions": {"Oem": oem}}),
+            )
+        };
+        let ids = [
+            "ERoT_BMC_0",
+            "HGX_ERoT_BMC_0",
+            "HGX_ERoT_CPU_0",
+            "HGX_ERoT_CPU_1",
+        ];
+        let mut resources = vec![(
+            "/redfish/v1/Chassis".to_string(),
+            json!({"Members": ids
+                .iter()
+                .map(|id| json!({"@odata.id": format!("/redfish/v1/Chassis/{id}")}))
+                .collect::<Vec<_>>()}),
+        )];
+        resources.extend(ids.iter().map(|id| chassis(id, actions.contains(id))));
+        let fake = FakeBmc::start(&resources).await;
+        let bmc = Bmc::new(fake.standard()).unwrap();
+        (fake, bmc)
+    }
+
+    #[tokio::test]
+    async fn test_reset_erot() {
+        let all = [
+            "ERoT_BMC_0",
+            "HGX_ERoT_BMC_0",
+            "HGX_ERoT_CPU_0",
+            "HGX_ERoT_CPU_1",
+        ];
+        let (fake, bmc) = erot_bmc(&all).await;
+        bmc.reset_erot(ComponentType::EROTBIOS).await.unwrap();
+        let writes = fake.writes();
+        let paths: Vec<&str> = writes.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "/redfish/v1/Chassis/HGX_ERoT_CPU_0/Actions/Oem/NvidiaChassis.AuxPowerReset",
+                "/redfish/v1/Chassis/HGX_ERoT_CPU_1/Actions/Oem/NvidiaChassis.AuxPowerReset",
+            ]
+        );
+        assert!(writes
+            .iter()
+            .all(|r| r.body == json!({"ResetType": "AuxPowerCycle"})));
+
+        // One ERoT without the action: nothing is reset, and no fallback to Chassis.Reset
+        let (fake, bmc) = erot_bmc(&["HGX_ERoT_BMC_0"]).await;
+        assert!(matches!(
+            bmc.reset_erot(ComponentType::EROTBMC).await,
+            Err(RedfishError::NotSupported(_))
+        ));
+        assert!(fake.writes().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_service_for() {
+        const HGX_BMC: &str = "/redfish/v1/Managers/HGX_BMC_0";
+        let mut manager: serde_json::Value = serde_json::from_str(include_str!(
+            "../tests/mockups/nvidia_gb200/redfish/v1/Managers/HGX_BMC_0/index.json"
+        ))
+        .unwrap();
+
+        // The HGX BMC's image is in the host BMC's inventory, so that takes the update
+        let fake = FakeBmc::start(&[(HGX_BMC, manager.clone())]).await;
+        let bmc = Bmc::new(fake.standard()).unwrap();
+        let host = bmc.s.update_service();
+        for component in [ComponentType::HGXBMC, ComponentType::BMC] {
+            assert_eq!(bmc.update_service_for(&component).await.unwrap(), host);
+        }
+
+        manager["Links"]["ActiveSoftwareImage"]["@odata.id"] =
+            json!("/redfish/v1/Managers/HGX_BMC_0/UpdateService/FirmwareInventory/HGX_FW_BMC_0");
+        let fake = FakeBmc::start(&[(HGX_BMC, manager)]).await;
+        let bmc = Bmc::new(fake.standard()).unwrap();
+        assert_eq!(
+            bmc.update_service_for(&ComponentType::HGXBMC)
+                .await
+                .unwrap(),
+            "Managers/HGX_BMC_0/UpdateService"
+        );
+        assert_eq!(
+            bmc.update_service_for(&ComponentType::BMC).await.unwrap(),
+            host
+        );
+
+        // No HGX BMC at all
+        let fake = FakeBmc::start::<&str>(&[]).await;
+        let bmc = Bmc::new(fake.standard()).unwrap();
+        assert_eq!(
+            bmc.update_service_for(&ComponentType::HGXBMC)
+                .await
+                .unwrap(),
+            host
+        );
+    }
+
+    #[tokio::test]
+    async fn test_power_redundancy_skips_unsupported() {
+        let chassis = |id: &str| {
+            let url = format!("/redfish/v1/Chassis/{id}");
+            let mut chassis = json!({"@odata.id": url, "Id": id});
+            if id != "HGX_Chassis_0" {
+                chassis["Power"] = json!({"@odata.id": format!("{url}/Power")});
+            }
+            (url, chassis)
+        };
+        let fake = FakeBmc::start(&[
+            (
+                "/redfish/v1/Chassis".to_string(),
+                json!({"Members": [
+                    {"@odata.id": "/redfish/v1/Chassis/BMC_0"},
+                    {"@odata.id": "/redfish/v1/Chassis/HGX_Chassis_0"},
+                    {"@odata.id": "/redfish/v1/Chassis/PDB_0"}
+                ]}),
+            ),
+            chassis("BMC_0"),
+            chassis("HGX_Chassis_0"),
+            chassis("PDB_0"),
+            (
+                "/redfish/v1/Chassis/PDB_0/Power".to_string(),
+                json!({"Redundancy": [{
+                    "MaxNumSupported": 6,
+                    "MemberId": "0",
+                    "MinNumNeeded": 5,
+                    "Mode": "N+m",
+                    "Name": "PSU Redundancy",
+                    "RedundancySet": [],
+                    "Status": {"Health": "Warning", "State": "Enabled"}
+                }]}),
+            ),
+        ])
+        .await
+        .with_handler(|req, _| {
+            (req.path == "/redfish/v1/Chassis/BMC_0/Power").then(|| Response::status(405))
+        });
+        let bmc = Bmc::new(fake.standard()).unwrap();
+        let chassis_all = bmc.walk_chassis().await.unwrap();
+        let gets = fake.requests.lock().unwrap().len();
+        // BMC_0's Power refuses GET, HGX_Chassis_0 has none
+        let redundancy = bmc.get_power_redundancy(&chassis_all).await.unwrap();
+        assert_eq!(redundancy.len(), 1);
+        assert!(!redundancy[0].is_intact());
+        // Only the two Power resources, the chassis aren't walked again
+        assert_eq!(fake.requests.lock().unwrap().len(), gets + 2);
+    }
+
+    #[tokio::test]
+    async fn test_only_http_first_ignores_ipv6() {
+        let mut system: serde_json::Value = serde_json::from_str(include_str!(
+            "../tests/mockups/nvidia_gb200/redfish/v1/Systems/System_0/index.json"
+        ))
+        .unwrap();
+        system["Boot"]["BootOrder"] = json!(["Boot0002", "Boot0006", "Boot0005"]);
+        let nic = "PciRoot(0x5)/Pci(0x0,0x0)/MAC(EEA3AB6B5303,0x1)";
+        let option = |id: &str, name: &str, path: String| {
+            let url = format!("/redfish/v1/Systems/1/BootOptions/{id}");
+            let option = json!({
+                "@odata.id": url,
+                "@odata.type": "#BootOption.v1_0_4.BootOption",
+                "Id": id,
+                "Name": id,
+                "BootOptionEnabled": true,
+                "BootOptionReference": id,
+                "DisplayName": name,
+                "UefiDevicePath": path
+            });
+            (url, option)
+        };
+        let fake = FakeBmc::start(&[
+            ("/redfish/v1/Systems/1".to_string(), system),
+            option(
+                "Boot0002",
+                "UEFI SAMSUNG MZ1L21T9HCLS-00A07 S666NN0W314049 1",
+                "PciRoot(0x15)/Pci(0x0,0x0)/NVMe(0x1,00-00-00-00-00-00-00-00)".to_string(),
+            ),
+            option(
+                "Boot0005",
+                "UEFI HTTPv4 (MAC:EEA3AB6B5303)",
+                format!("{nic}/IPv4(0.0.0.0)/Uri()"),
+            ),
+            option(
+                "Boot0006",
+                "UEFI HTTPv6 (MAC:EEA3AB6B5303)",
+                format!("{nic}/IPv6(0000:0000:0000:0000:0000:0000:0000:0000)/Uri()"),
+            ),
+        ])
+        .await;
+        let bmc = Bmc::new(fake.standard()).unwrap();
+        assert_eq!(
+            bmc.get_boot_options_ids_with_only_http_first()
+                .await
+                .unwrap(),
+            ["Boot0005", "Boot0002", "Boot0006"]
+        );
+    }
 }
@@ -55,14 +55,16 @@ use crate::{
         storage::Drives,
         task::Task,
         thermal::Thermal,
+        uefi_device_path::normalize_mac,
         update_service::{ComponentType, TransferProtocolType, UpdateService},
         BootOption, ComputerSystem, EnableDisable, Manager, ManagerResetType,
     },
     standard::RedfishStandard,
     BiosProfileType, Boot, BootOptions, Collection,
     EnabledDisabled::{self, Disabled, Enabled},
-    JobState, MachineSetupDiff, MachineSetupStatus, ODataId, PCIeDevice, PowerState, Redfish,
-    RedfishError, Resource, RoleId, Status, StatusInternal, SystemPowerControl,
+    JobState, MachineSetupDiff, MachineSetupStatus, NewBootOption, ODataId, PCIeDevice, PowerState,
+    Redfish, RedfishError, Resource, RoleId, SecretString, Status, StatusInternal,
+    SystemPowerControl,
 };
 
 const UEFI_PASSWORD_NAME: &str = "AdminPassword";
@@ -79,10 +81,14 @@ impl Bmc {
 
 #[async_trait::async_trait]
 impl Redfish for Bmc {
+    fn standard(&self) -> &RedfishStandard {
+        &self.s
+    }
+
     async fn create_user(
         &self,
         username: &str,
-        password: &str,
+        password: &SecretString,
         role_id: RoleId,
     ) -> Result<(), RedfishError> {
         self.s.create_user(username, password, role_id).await
@@ -96,7 +102,7 @@ impl Redfish for Bmc {
         self.s.change_username(old_name, new_name).await
     }
 
-    async fn change_password(&self, user: &str, new: &str) -> Result<(), RedfishError> {
+    async fn change_password(&self, user: &str, new: &SecretString) -> Result<(), RedfishError> {
         self.s.change_password(user, new).await
     }
 
@@ -107,7 +113,7 @@ impl Redfish for Bmc {
     async fn change_password_by_id(
         &self,
         account_id: &str,
-        new_pass: &str,
+        new_pass: &SecretString,
     ) -> Result<(), RedfishError> {
         let url = format!("AccountService/Accounts/{}", account_id);
         let mut data = HashMap::new();
@@ -135,6 +141,29 @@ impl Redfish for Bmc {
         false
     }
 
+    fn supported_features(&self) -> crate::FeatureSet {
+        crate::FeatureSet {
+            power_metrics: true,
+            thermal_metrics: true,
+            gpu_sensors: true,
+            system_event_log: true,
+            reset_bios: true,
+            lockdown: true,
+            serial_console: true,
+            secure_boot: true,
+            clear_tpm: true,
+            clear_nvram: true,
+            multipart_firmware_update: true,
+            system_ethernet_interfaces: true,
+            chassis_network_adapters: true,
+            dpu_first_boot_order: true,
+            component_integrity: true,
+            infinite_boot: true,
+            ipmi_over_lan: true,
+            ..Default::default()
+        }
+    }
+
     async fn bmc_reset(&self) -> Result<(), RedfishError> {
         self.s
             .reset_manager(
@@ -184,6 +213,16 @@ impl Redfish for Bmc {
         self.get_system_event_log().await
     }
 
+    async fn get_system_event_log_since(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        limit: Option<usize>,
+    ) -> Result<Vec<LogEntry>, RedfishError> {
+        let manager_id = self.s.manager_id();
+        let url = format!("Managers/{manager_id}/LogServices/SEL/Entries");
+        self.s.fetch_system_event_log(url, since, limit).await
+    }
+
     async fn get_bmc_event_log(
         &self,
         from: Option<chrono::DateTime<chrono::Utc>>,
@@ -206,20 +245,29 @@ impl Redfish for Bmc {
         self.s.set_bios(values).await
     }
 
-    async fn reset_bios(&self) -> Result<(), RedfishError> {
+    async fn reset_bios(&self) -> Result<crate::ApplyResult, RedfishError> {
         self.clear_nvram().await
     }
 
     async fn machine_setup(
         &self,
-        _boot_interface_mac: Option<&str>,
+        boot_interface_mac: Option<&str>,
         _bios_profiles: &HashMap<
             RedfishVendor,
             HashMap<String, HashMap<BiosProfileType, HashMap<String, serde_json::Value>>>,
         >,
         _selected_profile: BiosProfileType,
-    ) -> Result<(), RedfishError> {
-        self.set_bios_attributes().await
+    ) -> Result<crate::MachineSetupReport, RedfishError> {
+        let status = crate::MachineSetupStatus::or_apply_all(
+            self.machine_setup_status(boot_interface_mac).await,
+        );
+        if status.as_ref().is_some_and(|s| !s.needs_setup()) {
+            return Ok(crate::MachineSetupReport::skipped(&["set_bios_attributes"]));
+        }
+
+        let mut report = crate::MachineSetupReport::default();
+        report.record("set_bios_attributes", self.set_bios_attributes().await)?;
+        Ok(report)
     }
 
     async fn machine_setup_status(
@@ -315,7 +363,11 @@ impl Redfish for Bmc {
         Ok(Status { message, status })
     }
 
-    async fn setup_serial_console(&self) -> Result<(), RedfishError> {
+    async fn setup_serial_console(&self) -> Result<bool, RedfishError> {
+        // If we can't tell, set it up anyway
+        if matches!(self.serial_console_status().await, Ok(status) if status.is_fully_enabled()) {
+            return Ok(false);
+        }
         let serial_console = BiosAttributes {
             acpi_spcr_baud_rate: DEFAULT_ACPI_SPCR_BAUD_RATE.to_string().into(),
             baud_rate0: DEFAULT_BAUD_RATE0.to_string().into(),
@@ -341,7 +393,8 @@ impl Redfish for Bmc {
         let set_serial_attrs = SetBiosAttributes {
             attributes: serial_console,
         };
-        return self.patch_bios_attributes(set_serial_attrs).await;
+        self.patch_bios_attributes(set_serial_attrs).await?;
+        Ok(true)
     }
 
     async fn serial_console_status(&self) -> Result<Status, RedfishError> {
@@ -391,7 +444,7 @@ impl Redfish for Bmc {
         }
     }
 
-    async fn clear_tpm(&self) -> Result<(), RedfishError> {
+    async fn clear_tpm(&self) -> Result<crate::ApplyResult, RedfishError> {
         let tpm = BiosAttributes {
             tpm_operation: Some(DEFAULT_TPM_OPERATION.to_string()),
             tpm_support: Some(DEFAULT_TPM_SUPPORT),
@@ -399,17 +452,20 @@ impl Redfish for Bmc {
         };
 
         let set_tpm_attrs = SetBiosAttributes { attributes: tpm };
-        return self.patch_bios_attributes(set_tpm_attrs).await;
+        // The BMC replies 204 with no messages
+        self.patch_bios_attributes(set_tpm_attrs).await?;
+        let url = format!("Systems/{}/Bios/SD", self.s.resolve_system_id().await?);
+        Ok(crate::ApplyResult::from_extended_info(None, &url))
     }
 
     async fn pending(&self) -> Result<HashMap<String, serde_json::Value>, RedfishError> {
-        let url = format!("Systems/{}/Bios/SD", self.s.system_id());
+        let url = format!("Systems/{}/Bios/SD", self.s.resolve_system_id().await?);
         self.s.pending_with_url(&url).await
     }
 
-    async fn clear_pending(&self) -> Result<(), RedfishError> {
+    async fn clear_pending(&self) -> Result<crate::ClearPendingResult, RedfishError> {
         // TODO: check with viking team, unsupported
-        Ok(())
+        Ok(crate::ClearPendingResult::Unknown)
     }
 
     async fn pcie_devices(&self) -> Result<Vec<PCIeDevice>, RedfishError> {
@@ -450,7 +506,7 @@ impl Redfish for Bmc {
                 }
             })?;
 
-        let (_status_code, loc, _body) = self
+        let response = self
             .s
             .client
             .req_update_firmware_multipart(
@@ -463,13 +519,7 @@ impl Redfish for Bmc {
             )
             .await?;
 
-        let loc = match loc {
-            None => "Unknown".to_string(),
-            Some(x) => x,
-        };
-
-        // It returns the full endpoint, we just want the task ID
-        Ok(loc.replace("/redfish/v1/TaskService/Tasks/", ""))
+        response.task_id("UpdateService/upload")
     }
 
     async fn get_tasks(&self) -> Result<Vec<String>, RedfishError> {
@@ -489,12 +539,15 @@ impl Redfish for Bmc {
     }
 
     async fn get_software_inventories(&self) -> Result<Vec<String>, RedfishError> {
-        self.s
+        let mut ids = self
+            .s
             .get_members_with_timout(
                 "UpdateService/FirmwareInventory",
                 Some(Duration::from_secs(180)),
             )
-            .await
+            .await?;
+        crate::standard::sort_inventory_ids(&mut ids);
+        Ok(ids)
     }
 
     async fn get_system(&self) -> Result<ComputerSystem, RedfishError> {
@@ -535,15 +588,17 @@ impl Redfish for Bmc {
     async fn enable_secure_boot(&self) -> Result<(), RedfishError> {
         let mut data = HashMap::new();
         data.insert("SecureBootEnable", true);
-        let url = format!("Systems/{}/SecureBoot", self.s.system_id());
-        return self.s.client.patch_with_if_match(&url, data).await;
+        let url = format!("Systems/{}/SecureBoot", self.s.resolve_system_id().await?);
+        self.s.client.patch_with_if_match(&url, data).await?;
+        self.s.verify_secure_boot(true).await
     }
 
     async fn disable_secure_boot(&self) -> Result<(), RedfishError> {
         let mut data = HashMap::new();
         data.insert("SecureBootEnable", false);
-        let url = format!("Systems/{}/SecureBoot", self.s.system_id());
-        return self.s.client.patch_with_if_match(&url, data).await;
+        let url = format!("Systems/{}/SecureBoot", self.s.resolve_system_id().await?);
+        self.s.client.patch_with_if_match(&url, data).await?;
+        self.s.verify_secure_boot(false).await
     }
 
     async fn get_network_device_function(
@@ -647,9 +702,9 @@ impl Redfish for Bmc {
 
     async fn change_uefi_password(
         &self,
-        current_uefi_password: &str,
-        new_uefi_password: &str,
-    ) -> Result<Option<String>, RedfishError> {
+        current_uefi_password: &SecretString,
+        new_uefi_password: &SecretString,
+    ) -> Result<crate::BiosPasswordChange, RedfishError> {
         self.s
             .change_bios_password(UEFI_PASSWORD_NAME, current_uefi_password, new_uefi_password)
             .await
@@ -704,7 +759,7 @@ impl Redfish for Bmc {
         address: &str,
     ) -> Result<Option<String>, RedfishError> {
         let mut system: ComputerSystem = self.s.get_system().await?;
-        let mac_address = address.replace(':', "").to_uppercase();
+        let mac_address = normalize_mac(address);
 
         debug!("Using DPU with mac_address {}", mac_address);
 
@@ -743,13 +798,8 @@ impl Redfish for Bmc {
             .clone()
             .into_iter()
             .filter_map(|v| {
-                let path = v
-                    .uefi_device_path
-                    .clone()
-                    .unwrap_or_default()
-                    .to_uppercase();
-                if path.contains(mac_address.as_str())
-                    && path.contains("IPV4")
+                if v.matches_mac(&mac_address)
+                    && v.device_path().is_ipv4()
                     && v.alias
                         .clone()
                         .unwrap_or("".to_string())
@@ -834,15 +884,21 @@ impl Redfish for Bmc {
 
     async fn clear_uefi_password(
         &self,
-        current_uefi_password: &str,
+        current_uefi_password: &SecretString,
     ) -> Result<Option<String>, RedfishError> {
-        self.change_uefi_password(current_uefi_password, "").await
+        self.change_uefi_password(current_uefi_password, &SecretString::default())
+            .await?
+            .job_id()
     }
 
-    async fn lockdown_bmc(&self, target: crate::EnabledDisabled) -> Result<(), RedfishError> {
+    async fn lockdown_bmc(&self, target: crate::EnabledDisabled) -> Result<bool, RedfishError> {
         self.s.lockdown_bmc(target).await
     }
 
+    async fn bmc_lockdown_status(&self) -> Result<Status, RedfishError> {
+        self.s.bmc_lockdown_status().await
+    }
+
     async fn is_ipmi_over_lan_enabled(&self) -> Result<bool, RedfishError> {
         self.s.is_ipmi_over_lan_enabled().await
     }
@@ -850,7 +906,7 @@ impl Redfish for Bmc {
     async fn enable_ipmi_over_lan(
         &self,
         target: crate::EnabledDisabled,
-    ) -> Result<(), RedfishError> {
+    ) -> Result<bool, RedfishError> {
         self.s.enable_ipmi_over_lan(target).await
     }
 
@@ -865,7 +921,7 @@ impl Redfish for Bmc {
             .await
     }
 
-    async fn enable_rshim_bmc(&self) -> Result<(), RedfishError> {
+    async fn enable_rshim_bmc(&self) -> Result<bool, RedfishError> {
         self.s.enable_rshim_bmc().await
     }
 
@@ -878,20 +934,34 @@ impl Redfish for Bmc {
          "Targets": ["/redfish/v1/UpdateService/FirmwareInventory/HostBIOS_0"]
          }'
     ***/
-    async fn clear_nvram(&self) -> Result<(), RedfishError> {
+    async fn clear_nvram(&self) -> Result<crate::ApplyResult, RedfishError> {
         let data = HashMap::from([(
             "Targets",
             vec!["/redfish/v1/UpdateService/FirmwareInventory/HostBIOS_0".to_string()],
         )]);
 
-        self.s
-            .client
-            .post(
-                "UpdateService/Actions/Oem/NvidiaUpdateService.ClearNVRAM",
-                data,
-            )
-            .await
-            .map(|_status_code| Ok(()))?
+        let url = "UpdateService/Actions/Oem/NvidiaUpdateService.ClearNVRAM";
+        let response = self.s.client.post(url, data).await?;
+        Ok(crate::ApplyResult::from_action_response(&response, url))
+    }
+
+    async fn set_uefi_http(&self, cfg: UefiHttpAttributes) -> Result<(), RedfishError> {
+        self.patch_bios_attributes(SetBiosAttributes {
+            attributes: cfg.into(),
+        })
+        .await
+    }
+
+    async fn set_virtualization(&self, cfg: VirtAttributes) -> Result<(), RedfishError> {
+        self.patch_bios_attributes(SetBiosAttributes {
+            attributes: cfg.into(),
+        })
+        .await
+    }
+
+    async fn get_bios_lockdown(&self) -> Result<BiosLockdownAttributes, RedfishError> {
+        let bios = self.get_bios().await?;
+        Ok((&bios.attributes).into())
     }
 
     async fn get_nic_mode(&self) -> Result<Option<NicMode>, RedfishError> {
@@ -1001,6 +1071,206 @@ impl Redfish for Bmc {
     ) -> Result<crate::model::component_integrity::Evidence, RedfishError> {
         self.s.get_evidence(url).await
     }
+
+    async fn reset_secure_boot_keys(
+        &self,
+        reset_type: crate::model::secure_boot::SecureBootResetType,
+    ) -> Result<(), RedfishError> {
+        self.s.reset_secure_boot_keys(reset_type).await
+    }
+
+    async fn get_boot_progress(&self) -> Result<crate::model::system::BootProgress, RedfishError> {
+        self.s.get_boot_progress().await
+    }
+
+    async fn get_post_codes(&self) -> Result<Vec<LogEntry>, RedfishError> {
+        self.s.get_post_codes().await
+    }
+
+    async fn get_environment_metrics(
+        &self,
+        chassis_id: &str,
+    ) -> Result<crate::model::environment_metrics::EnvironmentMetrics, RedfishError> {
+        self.s.get_environment_metrics(chassis_id).await
+    }
+
+    async fn get_energy_kwh(
+        &self,
+    ) -> Result<Vec<crate::model::sensor::EnergyReading>, RedfishError> {
+        self.s.get_energy_kwh().await
+    }
+
+    async fn get_sensors(
+        &self,
+        chassis_id: &str,
+        reading_type: Option<crate::model::sensor::ReadingType>,
+    ) -> Result<Vec<crate::model::sensor::Sensor>, RedfishError> {
+        self.s.get_sensors(chassis_id, reading_type).await
+    }
+
+    async fn wait_for_power_state(
+        &self,
+        target: crate::PowerState,
+        timeout: std::time::Duration,
+        poll: std::time::Duration,
+    ) -> Result<(), RedfishError> {
+        self.s.wait_for_power_state(target, timeout, poll).await
+    }
+
+    async fn pending_diff(&self) -> Result<Vec<crate::model::BiosAttrChange>, RedfishError> {
+        let pending = self.pending().await?;
+        self.s.bios_attr_changes(pending).await
+    }
+
+    async fn set_bios_attributes(
+        &self,
+        attrs: HashMap<String, serde_json::Value>,
+    ) -> Result<(), RedfishError> {
+        let targets = self.s.bios_attribute_targets(attrs).await?;
+        self.s
+            .write_bios_attribute_targets(targets, |settings| {
+                self.patch_bios_attributes(HashMap::from([("Attributes", settings)]))
+            })
+            .await
+    }
+
+    async fn get_decoded_sel(
+        &self,
+    ) -> Result<Vec<crate::model::sel::DecodedSelEntry>, RedfishError> {
+        let sel = self.get_system_event_log().await?;
+        Ok(sel
+            .iter()
+            .map(crate::model::sel::DecodedSelEntry::from)
+            .collect())
+    }
+
+    async fn resolve_origin(
+        &self,
+        entry: &crate::model::sel::LogEntry,
+    ) -> Result<crate::model::resource::Resource, RedfishError> {
+        self.s.resolve_origin(entry).await
+    }
+
+    async fn attach_to_update(
+        &self,
+        task_id: &str,
+    ) -> Result<crate::model::task::Task, RedfishError> {
+        self.s.attach_to_update(task_id).await
+    }
+
+    async fn get_port_metrics(
+        &self,
+        chassis_id: &str,
+        network_adapter: &str,
+        id: &str,
+    ) -> Result<crate::model::port::PortMetrics, RedfishError> {
+        self.s
+            .get_port_metrics(chassis_id, network_adapter, id)
+            .await
+    }
+
+    async fn get_fru_inventory(&self) -> Result<crate::model::fru::FruInventory, RedfishError> {
+        self.s.get_fru_inventory().await
+    }
+
+    async fn get_power_supplies(
+        &self,
+    ) -> Result<Vec<crate::model::power::PowerSupplyInfo>, RedfishError> {
+        self.s.get_power_supplies().await
+    }
+
+    async fn download_firmware_image(
+        &self,
+        inventory_id: &str,
+        out: &std::path::Path,
+    ) -> Result<(), RedfishError> {
+        self.s.download_firmware_image(inventory_id, out).await
+    }
+
+    async fn create_boot_option(&self, opt: NewBootOption) -> Result<String, RedfishError> {
+        self.s.create_boot_option(opt).await
+    }
+
+    async fn delete_boot_option(&self, option_id: &str) -> Result<(), RedfishError> {
+        self.s.delete_boot_option_for(self, option_id).await
+    }
+
+    async fn get_bios_prerequisites(
+        &self,
+        attrs: &HashMap<String, serde_json::Value>,
+    ) -> Result<HashMap<String, serde_json::Value>, RedfishError> {
+        self.s.get_bios_prerequisites(attrs).await
+    }
+
+    async fn get_bmc_journal(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<String>, RedfishError> {
+        self.s.get_bmc_journal(since).await
+    }
+
+    async fn get_all_metrics(&self) -> Result<crate::AllMetrics, RedfishError> {
+        crate::AllMetrics::gather(self).await
+    }
+
+    async fn get_power_restore_policy(&self) -> Result<crate::PowerRestorePolicy, RedfishError> {
+        self.s.get_power_restore_policy().await
+    }
+
+    async fn set_power_restore_policy(
+        &self,
+        policy: crate::PowerRestorePolicy,
+    ) -> Result<(), RedfishError> {
+        self.s.set_power_restore_policy(policy).await
+    }
+
+    async fn set_power_on_delay(&self, seconds: u32) -> Result<(), RedfishError> {
+        self.s.set_power_on_delay(seconds).await
+    }
+
+    async fn get_manager_health(&self) -> Result<crate::model::ManagerHealth, RedfishError> {
+        self.s.get_manager_health().await
+    }
+
+    async fn get_active_conditions(&self) -> Result<Vec<crate::model::Condition>, RedfishError> {
+        self.s.get_active_conditions().await
+    }
+
+    async fn get_fabric_topology(
+        &self,
+    ) -> Result<Vec<crate::model::fabric::FabricTopology>, RedfishError> {
+        self.s.get_fabric_topology().await
+    }
+
+    async fn get_pcie_switches(
+        &self,
+        chassis_id: &str,
+    ) -> Result<Vec<crate::model::fabric::SwitchPorts>, RedfishError> {
+        self.s.get_pcie_switches(chassis_id).await
+    }
+
+    async fn reset_erot(&self, target: ComponentType) -> Result<(), RedfishError> {
+        self.s.reset_erot(target).await
+    }
+
+    async fn get_firmware_slots(
+        &self,
+        component: &str,
+    ) -> Result<Vec<crate::model::software_inventory::FirmwareSlot>, RedfishError> {
+        self.s.get_firmware_slots(component).await
+    }
+
+    async fn activate_firmware_slot(
+        &self,
+        component: &str,
+        slot: &str,
+    ) -> Result<(), RedfishError> {
+        self.s.activate_firmware_slot(component, slot).await
+    }
+
+    async fn get_pending_jobs(&self) -> Result<Vec<crate::model::job::Job>, RedfishError> {
+        self.s.get_pending_jobs().await
+    }
 }
 
 impl Bmc {
@@ -1074,7 +1344,7 @@ impl Bmc {
         boot_interface_mac: &str,
     ) -> Result<(Option<String>, Option<String>), RedfishError> {
         let system = self.s.get_system().await?;
-        let mac_address = boot_interface_mac.replace(':', "").to_uppercase();
+        let mac_address = normalize_mac(boot_interface_mac);
 
         // Get all boot options
         let all_boot_options: Vec<BootOption> = match system.boot.boot_options {
@@ -1098,13 +1368,8 @@ impl Bmc {
             .clone()
             .into_iter()
             .filter_map(|v| {
-                let path = v
-                    .uefi_device_path
-                    .clone()
-                    .unwrap_or_default()
-                    .to_uppercase();
-                if path.contains(mac_address.as_str())
-                    && path.contains("IPV4")
+                if v.matches_mac(&mac_address)
+                    && v.device_path().is_ipv4()
                     && v.alias
                         .clone()
                         .unwrap_or("".to_string())
@@ -1337,7 +1602,7 @@ impl Bmc {
             format!("{}", override_target),
         );
         let data = HashMap::from([("Boot", boot_data)]);
-        let url = format!("Systems/{}/SD ", self.s.system_id());
+        let url = format!("Systems/{}/SD ", self.s.resolve_system_id().await?);
         let (_, body): (_, HashMap<String, serde_json::Value>) = self.s.client.get(&url).await?;
         let key = "@odata.etag";
         let etag = body
@@ -1389,8 +1654,8 @@ impl Bmc {
         boot_array: Vec<String>,
         oetag: Option<String>,
     ) -> Result<(), RedfishError> {
-        let data = HashMap::from([("Boot", HashMap::from([("BootOrder", boot_array)]))]);
-        let url = format!("Systems/{}/SD", self.s.system_id());
+        let data = HashMap::from([("Boot", HashMap::from([("BootOrder", &boot_array)]))]);
+        let url = format!("Systems/{}/SD", self.s.resolve_system_id().await?);
         let etag = match oetag {
             Some(x) => x,
             None => {
@@ -1431,21 +1696,24 @@ impl Bmc {
                 headers,
             )
             .await?;
-        Ok(())
+        self.s
+            .verify_boot_order(&url, "/Boot/BootOrder", &boot_array)
+            .await
     }
     ///
     /// Returns current BIOS attributes that are used/modified
     ///
     async fn get_bios(&self) -> Result<Bios, RedfishError> {
-        let url = &format!("Systems/{}/Bios", self.s.system_id());
+        let url = &format!("Systems/{}/Bios", self.s.resolve_system_id().await?);
         let (_status_code, bios): (_, Bios) = self.s.client.get(url).await?;
         Ok(bios)
     }
 
     async fn set_bios_attributes(&self) -> Result<(), RedfishError> {
-        let url = &format!("Systems/{}/Bios", self.s.system_id());
+        let url = &format!("Systems/{}/Bios", self.s.resolve_system_id().await?);
         let (_status_code, bios): (_, Bios) = self.s.client.get(url).await?;
         let current_values = bios.attributes;
+        let current = serde_json::to_value(&current_values);
 
         let new_values = BiosAttributes {
             acpi_spcr_baud_rate: current_values
@@ -1489,17 +1757,32 @@ impl Bmc {
                 .and(DEFAULT_NVIDIA_INFINITEBOOT.into()),
         };
 
-        self.patch_bios_attributes(SetBiosAttributes {
-            attributes: new_values,
-        })
-        .await
+        // Only the attributes that change, the rest of the settings object is left alone
+        let changes = current.and_then(|current| {
+            Ok(crate::standard::changed_only(
+                &current,
+                &serde_json::to_value(&new_values)?,
+            ))
+        });
+        let changes = changes.map_err(|source| RedfishError::JsonSerializeError {
+            url: url.to_string(),
+            object_debug: format!("{new_values:?}"),
+            source,
+        })?;
+        match changes {
+            Some(attributes) => {
+                self.patch_bios_attributes(HashMap::from([("Attributes", attributes)]))
+                    .await
+            }
+            None => Ok(()),
+        }
     }
 
     async fn patch_bios_attributes<B>(&self, data: B) -> Result<(), RedfishError>
     where
         B: Serialize + ::std::fmt::Debug,
     {
-        let url = format!("Systems/{}/Bios/SD", self.s.system_id());
+        let url = format!("Systems/{}/Bios/SD", self.s.resolve_system_id().await?);
         self.s.client.patch_with_if_match(&url, data).await
     }
 }
@@ -1553,3 +1836,54 @@ impl UpdateParameters {
         UpdateParameters { targets }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use serde_json::json;
+
+    use super::Bmc;
+    use crate::fake_bmc::{FakeBmc, Response};
+    use crate::{Redfish, RedfishError};
+
+    const SECURE_BOOT: &str = "/redfish/v1/Systems/1/SecureBoot";
+
+    async fn viking(secure_boot_enabled: bool, ignore_writes: bool) -> (FakeBmc, Bmc) {
+        let fake = FakeBmc::start(&[(
+            SECURE_BOOT,
+            json!({"@odata.id": SECURE_BOOT, "SecureBootEnable": secure_boot_enabled}),
+        )])
+        .await
+        .with_handler(move |req, _| {
+            (ignore_writes && req.method == "PATCH").then(|| Response::status(204))
+        });
+        let s = fake.standard_from(fake.client().with_verify_writes(true));
+        (fake.clone(), Bmc::new(s).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_enable_secure_boot_verified() {
+        let (fake, bmc) = viking(false, false).await;
+        bmc.enable_secure_boot().await.unwrap();
+        assert_eq!(fake.writes()[0].body, json!({"SecureBootEnable": true}));
+
+        let (_fake, bmc) = viking(false, true).await;
+        assert!(matches!(
+            bmc.enable_secure_boot().await,
+            Err(RedfishError::VerificationFailed { expected, .. }) if expected == "true"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_disable_secure_boot_verified() {
+        let (fake, bmc) = viking(true, false).await;
+        bmc.disable_secure_boot().await.unwrap();
+        assert_eq!(fake.writes()[0].body, json!({"SecureBootEnable": false}));
+
+        let (_fake, bmc) = viking(true, true).await;
+        assert!(matches!(
+            bmc.disable_secure_boot().await,
+            Err(RedfishError::VerificationFailed { expected, .. }) if expected == "false"
+        ));
+    }
+}
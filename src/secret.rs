@@ -0,0 +1,154 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2025 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: MIT
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a
+ * copy of this software and associated documentation files (the "Software"),
+ * to deal in the Software without restriction, including without limitation
+ * the rights to use, copy, modify, merge, publish, distribute, sublicense,
+ * and/or sell copies of the Software, and to permit persons to whom the
+ * Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL
+ * THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+ * FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+//! Passwords and other credentials, kept out of logs and wiped from memory once dropped.
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use zeroize::Zeroize;
+
+/// Shown instead of a secret
+pub const REDACTED: &str = "[REDACTED]";
+
+/// A password. Its buffer is zeroed when dropped and Debug never shows it. Only serializing it
+/// into a request body or `expose_secret` reveal it. There is deliberately no Display, so it
+/// can't end up in a `format!` or `to_string` by accident.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SecretString(String);
+
+impl SecretString {
+    pub fn new(secret: String) -> Self {
+        Self(secret)
+    }
+
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        Self(secret)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(secret: &str) -> Self {
+        Self(secret.to_string())
+    }
+}
+
+impl std::fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SecretString({REDACTED})")
+    }
+}
+
+impl Serialize for SecretString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self)
+    }
+}
+
+/// A JSON request body fit for the log: the value of every key that mentions a password,
+/// such as Password or NewPassword, is replaced, as are password attributes in the XML Dell
+/// takes in ImportBuffer. Anything that isn't JSON is passed through.
+pub(crate) fn redact_body(body: &str) -> String {
+    let Ok(mut value) = serde_json::from_str::<serde_json::Value>(body) else {
+        return body.to_string();
+    };
+    redact_value(&mut value);
+    value.to_string()
+}
+
+fn redact_value(value: &mut serde_json::Value) {
+    static XML_PASSWORD: OnceLock<Regex> = OnceLock::new();
+    match value {
+        serde_json::Value::Object(obj) => {
+            for (key, v) in obj.iter_mut() {
+                if key.to_lowercase().contains("password") && !v.is_null() {
+                    *v = serde_json::Value::String(REDACTED.to_string());
+                } else {
+                    redact_value(v);
+                }
+            }
+        }
+        serde_json::Value::Array(arr) => arr.iter_mut().for_each(redact_value),
+        serde_json::Value::String(s) if s.contains("Password\">") => {
+            let re = XML_PASSWORD.get_or_init(|| {
+                Regex::new(r#"(Name="[^"]*Password">)[^<]*"#).expect("valid regex")
+            });
+            *s = re.replace_all(s, format!("${{1}}{REDACTED}")).into_owned();
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_secret_string() {
+        let secret = SecretString::from("hunter2");
+        assert_eq!(secret.expose_secret(), "hunter2");
+        assert!(!format!("{secret:?}").contains("hunter2"));
+        assert_eq!(serde_json::to_string(&secret).unwrap(), "\"hunter2\"");
+    }
+
+    #[test]
+    fn test_redact_body() {
+        let body = r#"{"UserName":"admin","Password":"hunter2","RoleId":"Administrator"}"#;
+        let redacted = redact_body(body);
+        assert!(!redacted.contains("hunter2"));
+        assert!(redacted.contains("admin"));
+
+        let body = serde_json::json!({
+            "Attributes": {"OldPassword": "a", "NewPassword": "b", "PasswordName": "Setup"},
+            "ImportBuffer": "<Attribute Name=\"OldSetupPassword\">hunter2</Attribute><Attribute Name=\"NewSetupPassword\"></Attribute>",
+        })
+        .to_string();
+        let redacted = redact_body(&body);
+        assert!(!redacted.contains("hunter2"));
+        assert!(!redacted.contains("\"a\""));
+        assert!(redacted.contains("OldSetupPassword"));
+
+        assert_eq!(redact_body("not json"), "not json");
+    }
+}
@@ -20,16 +20,24 @@
  * FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
  * DEALINGS IN THE SOFTWARE.
  */
-use std::{collections::HashMap, default, path::Path, time::Duration};
+use std::{
+    collections::HashMap,
+    default,
+    path::Path,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
 
-use reqwest::{header::HeaderName, Method, StatusCode};
+use futures_util::{StreamExt, TryStreamExt};
+use reqwest::{header::HeaderName, StatusCode};
 use serde_json::json;
-use tracing::debug;
+use tracing::{debug, warn};
 
+use crate::model::boot::BootSourceOverrideTarget;
 use crate::model::certificate::Certificate;
 use crate::model::chassis::Assembly;
 use crate::model::component_integrity::ComponentIntegrities;
-use crate::model::service_root::ServiceRoot;
+use crate::model::service_root::{ServiceRoot, ServiceRootLink};
 use crate::model::software_inventory::SoftwareInventory;
 use crate::model::task::Task;
 use crate::model::thermal::Thermal;
@@ -41,9 +49,15 @@ use crate::model::{
 };
 use crate::model::{power, thermal, BootOption, InvalidValueError, Manager, Managers, ODataId};
 use crate::model::{power::Power, update_service::UpdateService};
-use crate::model::{secure_boot::SecureBoot, sensor::GPUSensors};
+use crate::model::{
+    secure_boot::{SecureBoot, SecureBootResetType},
+    sensor::GPUSensors,
+};
 use crate::model::{sel::LogEntry, ManagerResetType};
-use crate::model::{sel::LogEntryCollection, serial_interface::SerialInterface};
+use crate::model::{
+    sel::LogEntryCollection,
+    serial_interface::{SerialInterface, SerialInterfaceConfig},
+};
 use crate::model::{storage::Drives, storage::Storage};
 use crate::network::{RedfishHttpClient, REDFISH_ENDPOINT};
 use crate::{
@@ -54,32 +68,44 @@ use crate::{
     model::chassis::{Chassis, NetworkAdapter},
     MachineSetupStatus,
 };
-use crate::{BootOptions, Collection, PCIeDevice, RedfishError, Resource};
+use crate::{
+    BootOptions, ClearPendingResult, Collection, NewBootOption, PCIeDevice, RedfishError, Resource,
+    SecretString,
+};
 
 const UEFI_PASSWORD_NAME: &str = "AdministratorPassword";
 
+/// Member GETs in flight at once per collection in `get_fru_inventory`
+const FRU_CONCURRENCY: usize = 4;
+
 /// The calls that use the Redfish standard without any OEM extensions.
 #[derive(Clone)]
 pub struct RedfishStandard {
     pub client: RedfishHttpClient,
     pub vendor: Option<RedfishVendor>,
     manager_id: String,
-    system_id: String,
+    // Shared with the vendor clients cloned from this one, so a lookup done by any of them sticks
+    system_id: Arc<OnceLock<String>>,
     service_root: ServiceRoot,
 }
 
 #[async_trait::async_trait]
 impl Redfish for RedfishStandard {
+    fn standard(&self) -> &RedfishStandard {
+        self
+    }
+
     async fn create_user(
         &self,
         username: &str,
-        password: &str,
+        password: &SecretString,
         role_id: RoleId,
     ) -> Result<(), RedfishError> {
+        let role_id = role_id.to_string();
         let mut data = HashMap::new();
-        data.insert("UserName", username.to_string());
-        data.insert("Password", password.to_string());
-        data.insert("RoleId", role_id.to_string());
+        data.insert("UserName", username);
+        data.insert("Password", password.expose_secret());
+        data.insert("RoleId", &role_id);
         self.client
             .post("AccountService/Accounts", data)
             .await
@@ -107,7 +133,11 @@ impl Redfish for RedfishStandard {
             .map(|_status_code| Ok(()))?
     }
 
-    async fn change_password(&self, user: &str, new_pass: &str) -> Result<(), RedfishError> {
+    async fn change_password(
+        &self,
+        user: &str,
+        new_pass: &SecretString,
+    ) -> Result<(), RedfishError> {
         let account = self.get_account_by_name(user).await?;
         let Some(account_id) = account.id else {
             return Err(RedfishError::UserNotFound(format!(
@@ -120,11 +150,11 @@ impl Redfish for RedfishStandard {
     async fn change_password_by_id(
         &self,
         account_id: &str,
-        new_pass: &str,
+        new_pass: &SecretString,
     ) -> Result<(), RedfishError> {
         let url = format!("AccountService/Accounts/{}", account_id);
         let mut data = HashMap::new();
-        data.insert("Password", new_pass);
+        data.insert("Password", new_pass.expose_secret());
         let service_root = self.get_service_root().await?;
         if service_root.vendor() == Some(RedfishVendor::AMI) {
             self.client.patch_with_if_match(&url, &data).await
@@ -156,20 +186,118 @@ impl Redfish for RedfishStandard {
         Ok(system.power_state)
     }
 
+    async fn wait_for_power_state(
+        &self,
+        target: PowerState,
+        timeout: Duration,
+        poll: Duration,
+    ) -> Result<(), RedfishError> {
+        let backoff = crate::Backoff {
+            initial: poll,
+            ..self.client.poll_backoff()
+        };
+        let last_seen = std::sync::Mutex::new(None);
+        let what = format!("power state {target}");
+        let result = self
+            .client
+            .poll_until(&what, backoff, timeout, || async {
+                let current = self.get_power_state().await?;
+                *last_seen.lock().unwrap_or_else(|e| e.into_inner()) = Some(current);
+                Ok((current == target).then_some(()))
+            })
+            .await;
+        let last_seen = *last_seen.lock().unwrap_or_else(|e| e.into_inner());
+        match (result, last_seen) {
+            (Err(RedfishError::Timeout(msg)), Some(current)) => {
+                Err(RedfishError::Timeout(format!("{msg}, last seen {current}")))
+            }
+            (result, _) => result,
+        }
+    }
+
     async fn get_power_metrics(&self) -> Result<Power, RedfishError> {
         let power = self.get_power_metrics().await?;
         Ok(power)
     }
 
+    async fn get_power_supplies(&self) -> Result<Vec<model::power::PowerSupplyInfo>, RedfishError> {
+        let mut supplies = Vec::new();
+        for chassis_id in self.get_chassis_all().await? {
+            let chassis = self.get_chassis(&chassis_id).await?;
+            let mut found = self.get_power_subsystem_supplies(&chassis).await?;
+            if found.is_empty() {
+                found = self.get_legacy_power_supplies(&chassis).await?;
+            }
+            for mut supply in found {
+                supply.chassis_id = chassis_id.clone();
+                supplies.push(supply);
+            }
+        }
+        Ok(supplies)
+    }
+
+    async fn get_power_restore_policy(&self) -> Result<crate::PowerRestorePolicy, RedfishError> {
+        self.get_system()
+            .await?
+            .power_restore_policy
+            .ok_or_else(|| RedfishError::MissingKey {
+                key: "PowerRestorePolicy".to_string(),
+                url: format!("Systems/{}", self.system_id()),
+            })
+    }
+
+    async fn set_power_restore_policy(
+        &self,
+        policy: crate::PowerRestorePolicy,
+    ) -> Result<(), RedfishError> {
+        if policy == crate::PowerRestorePolicy::Unknown {
+            return Err(RedfishError::NotSupported(
+                "PowerRestorePolicy must be AlwaysOn, AlwaysOff or LastState".to_string(),
+            ));
+        }
+        let url = format!("Systems/{}", self.resolve_system_id().await?);
+        let body = HashMap::from([("PowerRestorePolicy", policy.to_string())]);
+        self.client.patch(&url, body).await.map(|_status_code| ())
+    }
+
+    async fn set_power_on_delay(&self, seconds: u32) -> Result<(), RedfishError> {
+        if self.get_system().await?.power_on_delay_seconds.is_none() {
+            return Err(RedfishError::NotSupported(
+                "ComputerSystem has no PowerOnDelaySeconds".to_string(),
+            ));
+        }
+        let url = format!("Systems/{}", self.resolve_system_id().await?);
+        let body = HashMap::from([("PowerOnDelaySeconds", seconds)]);
+        self.client.patch(&url, body).await.map(|_status_code| ())
+    }
+
     async fn power(&self, action: model::SystemPowerControl) -> Result<(), RedfishError> {
-        if action == model::SystemPowerControl::ACPowercycle {
+        let Some(reset_type) = action.reset_type() else {
             return Err(RedfishError::NotSupported(
                 "AC power cycle not supported on this platform".to_string(),
             ));
+        };
+        if action.is_graceful() {
+            // Don't let a BMC without ACPI shutdown quietly turn this into a hard power off
+            let allowed = self
+                .get_system()
+                .await?
+                .actions
+                .and_then(|a| a.computer_system_reset)
+                .map(|r| r.reset_type_allowable_values)
+                .unwrap_or_default();
+            if !allowed.is_empty() && !allowed.iter().any(|v| v == reset_type) {
+                return Err(RedfishError::NotSupported(format!(
+                    "{reset_type} is not an allowed ResetType on this platform"
+                )));
+            }
         }
-        let url = format!("Systems/{}/Actions/ComputerSystem.Reset", self.system_id);
+        let url = format!(
+            "Systems/{}/Actions/ComputerSystem.Reset",
+            self.resolve_system_id().await?
+        );
         let mut arg = HashMap::new();
-        arg.insert("ResetType", action.to_string());
+        arg.insert("ResetType", reset_type.to_string());
         // Lenovo: The expected HTTP response code is 204 No Content
         self.client.post(&url, arg).await.map(|_resp| Ok(()))?
     }
@@ -178,11 +306,62 @@ impl Redfish for RedfishStandard {
         false
     }
 
+    fn supported_features(&self) -> crate::FeatureSet {
+        crate::FeatureSet {
+            power_metrics: true,
+            thermal_metrics: true,
+            reset_bios: true,
+            secure_boot: true,
+            system_ethernet_interfaces: true,
+            chassis_network_adapters: true,
+            component_integrity: true,
+            ipmi_over_lan: true,
+            ..Default::default()
+        }
+    }
+
     async fn bmc_reset(&self) -> Result<(), RedfishError> {
         self.reset_manager(ManagerResetType::GracefulRestart, None)
             .await
     }
 
+    async fn get_firmware_slots(
+        &self,
+        component: &str,
+    ) -> Result<Vec<model::software_inventory::FirmwareSlot>, RedfishError> {
+        let links = self.software_image_links(component).await?;
+        let mut images = Vec::with_capacity(links.software_images.len());
+        for image in &links.software_images {
+            let (_, inventory): (_, SoftwareInventory) =
+                self.client.get(&image.relative_path()).await?;
+            images.push(inventory);
+        }
+        Ok(model::software_inventory::FirmwareSlot::from_images(
+            images,
+            links.active_software_image.as_ref(),
+        ))
+    }
+
+    async fn activate_firmware_slot(
+        &self,
+        component: &str,
+        slot: &str,
+    ) -> Result<(), RedfishError> {
+        let links = self.software_image_links(component).await?;
+        let image = links
+            .software_images
+            .into_iter()
+            .find(|image| image.odata_id_get().is_ok_and(|id| id == slot))
+            .ok_or_else(|| RedfishError::NotSupported(format!("{component} has no slot {slot}")))?;
+        let body = json!({"Links": {"ActiveSoftwareImage": image}});
+        let url = format!("Managers/{component}");
+        self.client.patch(&url, body).await.map(|_status_code| ())
+    }
+
+    async fn reset_erot(&self, _target: ComponentType) -> Result<(), RedfishError> {
+        Err(RedfishError::NotSupported("reset_erot".to_string()))
+    }
+
     async fn chassis_reset(
         &self,
         chassis_id: &str,
@@ -200,6 +379,10 @@ impl Redfish for RedfishStandard {
         Ok(thermal)
     }
 
+    async fn get_all_metrics(&self) -> Result<crate::AllMetrics, RedfishError> {
+        crate::AllMetrics::gather(self).await
+    }
+
     async fn get_gpu_sensors(&self) -> Result<Vec<GPUSensors>, RedfishError> {
         Err(RedfishError::NotSupported(
             "No GPUs on this machine".to_string(),
@@ -210,6 +393,19 @@ impl Redfish for RedfishStandard {
         Err(RedfishError::NotSupported("SEL".to_string()))
     }
 
+    async fn get_decoded_sel(&self) -> Result<Vec<model::sel::DecodedSelEntry>, RedfishError> {
+        let sel = self.get_system_event_log().await?;
+        Ok(sel.iter().map(model::sel::DecodedSelEntry::from).collect())
+    }
+
+    async fn get_system_event_log_since(
+        &self,
+        _since: Option<chrono::DateTime<chrono::Utc>>,
+        _limit: Option<usize>,
+    ) -> Result<Vec<LogEntry>, RedfishError> {
+        Err(RedfishError::NotSupported("SEL".to_string()))
+    }
+
     async fn get_bmc_event_log(
         &self,
         _from: Option<chrono::DateTime<chrono::Utc>>,
@@ -217,12 +413,92 @@ impl Redfish for RedfishStandard {
         Err(RedfishError::NotSupported("BMC Event Log".to_string()))
     }
 
+    async fn get_bmc_journal(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<String>, RedfishError> {
+        use futures_util::TryStreamExt;
+        let url = format!("Managers/{}/LogServices/Journal/Entries", self.manager_id());
+        // journal_lines drops older entries again, for BMCs that claim $filter but ignore it
+        let url = match since {
+            Some(since) if self.service_root.supports_filter_query() => {
+                created_since_filter(url, since)
+            }
+            _ => url,
+        };
+        let entries = match self.iter_collection::<LogEntry>(&url).try_collect().await {
+            Ok(entries) => entries,
+            Err(e) if e.not_found() => {
+                return Err(RedfishError::NotSupported("BMC journal".to_string()))
+            }
+            Err(e) => return Err(e),
+        };
+        Ok(model::sel::journal_lines(entries, since))
+    }
+
+    async fn get_boot_progress(&self) -> Result<model::system::BootProgress, RedfishError> {
+        let system = self.get_system().await?;
+        system
+            .boot_progress
+            .ok_or_else(|| RedfishError::MissingKey {
+                key: "BootProgress".to_string(),
+                url: format!("Systems/{}/", self.system_id()),
+            })
+    }
+
+    async fn get_post_codes(&self) -> Result<Vec<LogEntry>, RedfishError> {
+        let url = format!(
+            "Systems/{}/LogServices/PostCodes/Entries",
+            self.resolve_system_id().await?
+        );
+        match self.client.get::<LogEntryCollection>(&url).await {
+            Ok((_status_code, log_entry_collection)) => Ok(log_entry_collection.members),
+            Err(e) if e.not_found() => Err(RedfishError::NotSupported("PostCodes".to_string())),
+            Err(e) => Err(e),
+        }
+    }
+
     async fn get_drives_metrics(&self) -> Result<Vec<Drives>, RedfishError> {
         self.get_drives_metrics().await
     }
 
+    async fn get_fru_inventory(&self) -> Result<model::fru::FruInventory, RedfishError> {
+        let system_url = format!("Systems/{}", self.resolve_system_id().await?);
+        let processors_url = format!("{system_url}/Processors/");
+        let memory_url = format!("{system_url}/Memory/");
+        let chassis_url = format!("{}/", self.root_path(ServiceRootLink::Chassis));
+        let (chassis, systems, processors, memory, drives, power_supplies) = tokio::join!(
+            self.get_fru_members(&chassis_url),
+            self.get_fru_entry(&system_url),
+            self.get_fru_members(&processors_url),
+            self.get_fru_members(&memory_url),
+            self.get_drives_metrics(),
+            self.get_fru_power_supplies(),
+        );
+        let drives = drives?
+            .into_iter()
+            .map(|d| model::fru::FruEntry {
+                odata_id: d.odata.odata_id,
+                id: d.id,
+                name: d.name,
+                manufacturer: d.manufacturer,
+                model: d.model,
+                serial_number: d.serial_number,
+                part_number: d.part_number,
+            })
+            .collect();
+        Ok(model::fru::FruInventory {
+            chassis: chassis?,
+            systems: vec![systems?],
+            processors: processors?,
+            memory: memory?,
+            drives,
+            power_supplies: power_supplies?,
+        })
+    }
+
     async fn bios(&self) -> Result<HashMap<String, serde_json::Value>, RedfishError> {
-        let url = format!("Systems/{}/Bios", self.system_id());
+        let url = format!("Systems/{}/Bios", self.resolve_system_id().await?);
         let (_status_code, body) = self.client.get(&url).await?;
         Ok(body)
     }
@@ -236,19 +512,41 @@ impl Redfish for RedfishStandard {
         ))
     }
 
-    async fn reset_bios(&self) -> Result<(), RedfishError> {
-        Err(RedfishError::NotSupported(
-            "reset_bios is vendor specific and not available on this platform".to_string(),
-        ))
+    async fn reset_bios(&self) -> Result<crate::ApplyResult, RedfishError> {
+        self.factory_reset_bios().await
     }
 
     async fn pending(&self) -> Result<HashMap<String, serde_json::Value>, RedfishError> {
-        let url = format!("Systems/{}/Bios/Settings", self.system_id());
+        let url = format!("Systems/{}/Bios/Settings", self.resolve_system_id().await?);
         self.pending_with_url(&url).await
     }
 
-    async fn clear_pending(&self) -> Result<(), RedfishError> {
-        let url = format!("Systems/{}/Bios/Settings", self.system_id());
+    async fn pending_diff(&self) -> Result<Vec<model::BiosAttrChange>, RedfishError> {
+        let pending = self.pending().await?;
+        self.bios_attr_changes(pending).await
+    }
+
+    async fn get_bios_prerequisites(
+        &self,
+        attrs: &HashMap<String, serde_json::Value>,
+    ) -> Result<HashMap<String, serde_json::Value>, RedfishError> {
+        match self.bios_attribute_registry_if_any().await? {
+            Some(registry) => self.prerequisites_from(&registry, attrs).await,
+            None => Ok(HashMap::new()),
+        }
+    }
+
+    async fn set_bios_attributes(
+        &self,
+        attrs: HashMap<String, serde_json::Value>,
+    ) -> Result<(), RedfishError> {
+        let targets = self.bios_attribute_targets(attrs).await?;
+        self.write_bios_attribute_targets(targets, |settings| self.patch_bios_settings(settings))
+            .await
+    }
+
+    async fn clear_pending(&self) -> Result<ClearPendingResult, RedfishError> {
+        let url = format!("Systems/{}/Bios/Settings", self.resolve_system_id().await?);
         self.clear_pending_with_url(&url).await
     }
 
@@ -260,7 +558,7 @@ impl Redfish for RedfishStandard {
             HashMap<String, HashMap<BiosProfileType, HashMap<String, serde_json::Value>>>,
         >,
         _selected_profile: BiosProfileType,
-    ) -> Result<(), RedfishError> {
+    ) -> Result<crate::MachineSetupReport, RedfishError> {
         Err(RedfishError::NotSupported("machine_setup".to_string()))
     }
 
@@ -294,7 +592,7 @@ impl Redfish for RedfishStandard {
         Err(RedfishError::NotSupported("lockdown_status".to_string()))
     }
 
-    async fn setup_serial_console(&self) -> Result<(), RedfishError> {
+    async fn setup_serial_console(&self) -> Result<bool, RedfishError> {
         Err(RedfishError::NotSupported(
             "setup_serial_console".to_string(),
         ))
@@ -306,30 +604,98 @@ impl Redfish for RedfishStandard {
         ))
     }
 
+    async fn get_serial_interface_config(&self) -> Result<SerialInterfaceConfig, RedfishError> {
+        Ok(self.get_serial_interface().await?.config())
+    }
+
+    async fn set_serial_interface_config(
+        &self,
+        config: SerialInterfaceConfig,
+    ) -> Result<(), RedfishError> {
+        let interface_id = self.get_serial_interface_name().await?;
+        let url = format!(
+            "Managers/{}/SerialInterfaces/{}",
+            self.manager_id(),
+            interface_id
+        );
+        let (_status_code, current): (_, SerialInterface) = self.client.get(&url).await?;
+        if current.config().satisfies(&config) {
+            return Ok(());
+        }
+        self.client.patch(&url, config).await.map(|_status_code| ())
+    }
+
     async fn get_boot_options(&self) -> Result<BootOptions, RedfishError> {
         self.get_boot_options().await
     }
 
     async fn get_boot_option(&self, option_id: &str) -> Result<BootOption, RedfishError> {
-        let url = format!("Systems/{}/BootOptions/{}", self.system_id(), option_id);
+        let url = format!(
+            "Systems/{}/BootOptions/{}",
+            self.resolve_system_id().await?,
+            option_id
+        );
         let (_status_code, body) = self.client.get(&url).await?;
         Ok(body)
     }
 
+    async fn create_boot_option(&self, opt: NewBootOption) -> Result<String, RedfishError> {
+        let url = format!("Systems/{}/BootOptions", self.resolve_system_id().await?);
+        let response = match self.client.post(&url, opt).await {
+            Err(e) if method_not_allowed(&e) => {
+                return Err(RedfishError::NotSupported(
+                    "create_boot_option: BMC does not allow creating boot options".to_string(),
+                ));
+            }
+            r => r?,
+        };
+        if let Some(id) = response
+            .body()
+            .and_then(|body| body.get("Id"))
+            .and_then(|id| id.as_str())
+        {
+            return Ok(id.to_string());
+        }
+        // Empty or partial body, read the new option from its Location
+        let path = response.created_path().ok_or(RedfishError::NoHeader)?;
+        let (_, option): (_, BootOption) = self.client.get(path).await?;
+        Ok(option.id)
+    }
+
+    async fn delete_boot_option(&self, option_id: &str) -> Result<(), RedfishError> {
+        self.delete_boot_option_for(self, option_id).await
+    }
+
     async fn boot_once(&self, _target: Boot) -> Result<(), RedfishError> {
         Err(RedfishError::NotSupported("boot_once".to_string()))
     }
 
+    async fn set_pxe_boot_once(&self) -> Result<(), RedfishError> {
+        self.ensure_boot_target_allowed(BootSourceOverrideTarget::Pxe)
+            .await?;
+        let boot = HashMap::from([
+            ("BootSourceOverrideEnabled", "Once".to_string()),
+            (
+                "BootSourceOverrideTarget",
+                BootSourceOverrideTarget::Pxe.to_string(),
+            ),
+        ]);
+        let url = format!("Systems/{}", self.resolve_system_id().await?);
+        self.client
+            .patch_with_etag_retry(&url, HashMap::from([("Boot", boot)]))
+            .await
+    }
+
     async fn boot_first(&self, _target: Boot) -> Result<(), RedfishError> {
         Err(RedfishError::NotSupported("boot_first".to_string()))
     }
 
-    async fn clear_tpm(&self) -> Result<(), RedfishError> {
+    async fn clear_tpm(&self) -> Result<crate::ApplyResult, RedfishError> {
         Err(RedfishError::NotSupported("clear_tpm".to_string()))
     }
 
     async fn pcie_devices(&self) -> Result<Vec<PCIeDevice>, RedfishError> {
-        self.pcie_devices_for_chassis(vec![self.system_id().into()])
+        self.pcie_devices_for_chassis(vec![self.resolve_system_id().await?.into()])
             .await
     }
 
@@ -339,9 +705,32 @@ impl Redfish for RedfishStandard {
         Ok(body)
     }
 
+    async fn download_firmware_image(
+        &self,
+        inventory_id: &str,
+        out: &Path,
+    ) -> Result<(), RedfishError> {
+        let inventory = match self.get_firmware(inventory_id).await {
+            Err(e) if e.not_found() => {
+                let url = format!("UpdateService/SoftwareInventory/{inventory_id}");
+                let (_status_code, inventory): (_, SoftwareInventory) =
+                    self.client.get(&url).await?;
+                inventory
+            }
+            other => other?,
+        };
+        let Some(uri) = inventory.image_download_uri() else {
+            return Err(RedfishError::NotSupported(format!(
+                "download_firmware_image: {inventory_id} exposes no image URI"
+            )));
+        };
+        self.client.download_to_file(uri, out).await?;
+        Ok(())
+    }
+
     async fn update_firmware(&self, firmware: tokio::fs::File) -> Result<Task, RedfishError> {
-        let (_status_code, body) = self.client.post_file("UpdateService", firmware).await?;
-        Ok(body)
+        let response = self.client.post_file("UpdateService", firmware).await?;
+        self.action_task("UpdateService", response).await
     }
 
     async fn update_firmware_multipart(
@@ -360,6 +749,16 @@ impl Redfish for RedfishStandard {
         self.get_members("TaskService/Tasks/").await
     }
 
+    async fn get_pending_jobs(&self) -> Result<Vec<Job>, RedfishError> {
+        self.pending_jobs_at("JobService/Jobs").await
+    }
+
+    async fn get_task_service(&self) -> Result<model::task::TaskService, RedfishError> {
+        let url = self.root_path(ServiceRootLink::TaskService);
+        let (_status_code, body) = self.client.get(&url).await?;
+        Ok(body)
+    }
+
     /// http://redfish.dmtf.org/schemas/v1/TaskCollection.json
     async fn get_task(&self, id: &str) -> Result<Task, RedfishError> {
         let url = format!("TaskService/Tasks/{}", id);
@@ -386,10 +785,23 @@ impl Redfish for RedfishStandard {
         Ok(body)
     }
 
+    async fn attach_to_update(&self, task_id: &str) -> Result<Task, RedfishError> {
+        let url = format!("TaskService/Tasks/{}", task_id);
+        let (_status_code, task) = self.client.get::<Task>(&url).await?;
+        if !task.is_update_task() {
+            return Err(RedfishError::GenericError {
+                error: format!("Task {task_id} is not a firmware update task"),
+            });
+        }
+        // Follow a redirect to the JobService the same way get_task does
+        self.get_task(task_id).await
+    }
+
     /// Vec of chassis id
     /// http://redfish.dmtf.org/schemas/v1/ChassisCollection.json
     async fn get_chassis_all(&self) -> Result<Vec<String>, RedfishError> {
-        self.get_members("Chassis/").await
+        let url = format!("{}/", self.root_path(ServiceRootLink::Chassis));
+        self.get_members(&url).await
     }
 
     async fn get_chassis(&self, id: &str) -> Result<Chassis, RedfishError> {
@@ -398,6 +810,73 @@ impl Redfish for RedfishStandard {
         Ok(body)
     }
 
+    async fn get_environment_metrics(
+        &self,
+        chassis_id: &str,
+    ) -> Result<model::environment_metrics::EnvironmentMetrics, RedfishError> {
+        let url = format!("Chassis/{}/EnvironmentMetrics", chassis_id);
+        let (_status_code, body) = self.client.get(&url).await?;
+        Ok(body)
+    }
+
+    async fn get_energy_kwh(&self) -> Result<Vec<model::sensor::EnergyReading>, RedfishError> {
+        let mut readings = Vec::new();
+        for chassis_id in self.get_chassis_all().await? {
+            let metrics = match self.get_environment_metrics(&chassis_id).await {
+                Ok(metrics) => metrics,
+                Err(e) if e.not_found() => continue,
+                Err(e) => return Err(e),
+            };
+            let Some(energy) = metrics.energy_kwh else {
+                continue;
+            };
+            // The excerpt has no SensorResetTime, that is only on the sensor itself
+            let sensor = match &energy.data_source_uri {
+                Some(uri) => {
+                    let url = uri.replace(&format!("/{REDFISH_ENDPOINT}/"), "");
+                    let (_status_code, sensor): (StatusCode, model::sensor::Sensor) =
+                        self.client.get(&url).await?;
+                    Some(sensor)
+                }
+                None => None,
+            };
+            let kwh = sensor.as_ref().and_then(|s| s.reading).or(energy.reading);
+            let Some(kwh) = kwh else {
+                continue;
+            };
+            readings.push(model::sensor::EnergyReading {
+                chassis_id,
+                data_source_uri: energy.data_source_uri,
+                kwh,
+                sensor_reset_time: sensor.and_then(|s| s.sensor_reset_time),
+            });
+        }
+        Ok(readings)
+    }
+
+    async fn get_sensors(
+        &self,
+        chassis_id: &str,
+        reading_type: Option<model::sensor::ReadingType>,
+    ) -> Result<Vec<model::sensor::Sensor>, RedfishError> {
+        let chassis = self.get_chassis(chassis_id).await?;
+        let Some(sensors) = self.follow_sensors_link(chassis.sensors.as_ref()).await? else {
+            return Ok(vec![]);
+        };
+        let mut out = Vec::with_capacity(sensors.members.len());
+        for member in sensors.members {
+            let url = member
+                .odata_id
+                .replace(&format!("/{REDFISH_ENDPOINT}/"), "");
+            let (_status_code, sensor): (StatusCode, model::sensor::Sensor) =
+                self.client.get(&url).await?;
+            if reading_type.is_none() || sensor.reading_type == reading_type {
+                out.push(sensor);
+            }
+        }
+        Ok(out)
+    }
+
     async fn get_chassis_assembly(&self, chassis_id: &str) -> Result<Assembly, RedfishError> {
         let url = format!("Chassis/{}/Assembly", chassis_id);
         let (_status_code, body) = self.client.get(&url).await?;
@@ -459,7 +938,10 @@ impl Redfish for RedfishStandard {
     }
 
     async fn get_system_ethernet_interfaces(&self) -> Result<Vec<String>, RedfishError> {
-        let url = format!("Systems/{}/EthernetInterfaces", self.system_id);
+        let url = format!(
+            "Systems/{}/EthernetInterfaces",
+            self.resolve_system_id().await?
+        );
         self.get_members(&url).await
     }
 
@@ -467,24 +949,30 @@ impl Redfish for RedfishStandard {
         &self,
         id: &str,
     ) -> Result<crate::EthernetInterface, RedfishError> {
-        let url = format!("Systems/{}/EthernetInterfaces/{}", self.system_id(), id);
+        let url = format!(
+            "Systems/{}/EthernetInterfaces/{}",
+            self.resolve_system_id().await?,
+            id
+        );
         let (_status_code, body) = self.client.get(&url).await?;
         Ok(body)
     }
 
     /// http://redfish.dmtf.org/schemas/v1/SoftwareInventoryCollection.json#/definitions/SoftwareInventoryCollection
     async fn get_software_inventories(&self) -> Result<Vec<String>, RedfishError> {
-        self.get_members("UpdateService/FirmwareInventory").await
+        let mut ids = self.get_members("UpdateService/FirmwareInventory").await?;
+        sort_inventory_ids(&mut ids);
+        Ok(ids)
     }
 
     async fn get_system(&self) -> Result<model::ComputerSystem, RedfishError> {
-        let url = format!("Systems/{}/", self.system_id);
+        let url = format!("Systems/{}/", self.resolve_system_id().await?);
         let host: model::ComputerSystem = self.client.get(&url).await?.1;
         Ok(host)
     }
 
     async fn get_secure_boot(&self) -> Result<SecureBoot, RedfishError> {
-        let url = format!("Systems/{}/SecureBoot", self.system_id());
+        let url = format!("Systems/{}/SecureBoot", self.resolve_system_id().await?);
         let (_status_code, body) = self.client.get(&url).await?;
         Ok(body)
     }
@@ -492,9 +980,9 @@ impl Redfish for RedfishStandard {
     async fn enable_secure_boot(&self) -> Result<(), RedfishError> {
         let mut data = HashMap::new();
         data.insert("SecureBootEnable", true);
-        let url = format!("Systems/{}/SecureBoot", self.system_id());
+        let url = format!("Systems/{}/SecureBoot", self.resolve_system_id().await?);
         let _status_code = self.client.patch(&url, data).await?;
-        Ok(())
+        self.verify_secure_boot(true).await
     }
 
     async fn get_secure_boot_certificate(
@@ -504,7 +992,7 @@ impl Redfish for RedfishStandard {
     ) -> Result<Certificate, RedfishError> {
         let url = format!(
             "Systems/{}/SecureBoot/SecureBootDatabases/{}/Certificates/{}",
-            self.system_id(),
+            self.resolve_system_id().await?,
             database_id,
             certificate_id
         );
@@ -518,7 +1006,7 @@ impl Redfish for RedfishStandard {
     ) -> Result<Vec<String>, RedfishError> {
         let url = format!(
             "Systems/{}/SecureBoot/SecureBootDatabases/{}/Certificates",
-            self.system_id(),
+            self.resolve_system_id().await?,
             database_id
         );
         self.get_members(&url).await
@@ -534,25 +1022,32 @@ impl Redfish for RedfishStandard {
         data.insert("CertificateType", "PEM");
         let url = format!(
             "Systems/{}/SecureBoot/SecureBootDatabases/{}/Certificates",
-            self.system_id(),
+            self.resolve_system_id().await?,
             database_id
         );
-        let (_status_code, resp_opt, _resp_headers) = self
-            .client
-            .req::<Task, _>(Method::POST, &url, Some(data), None, None, Vec::new())
-            .await?;
-        match resp_opt {
-            Some(response_body) => Ok(response_body),
-            None => Err(RedfishError::NoContent),
-        }
+        let response = self.client.post(&url, data).await?;
+        self.action_task(&url, response).await
     }
 
     async fn disable_secure_boot(&self) -> Result<(), RedfishError> {
         let mut data = HashMap::new();
         data.insert("SecureBootEnable", false);
-        let url = format!("Systems/{}/SecureBoot", self.system_id());
+        let url = format!("Systems/{}/SecureBoot", self.resolve_system_id().await?);
         let _status_code = self.client.patch(&url, data).await?;
-        Ok(())
+        self.verify_secure_boot(false).await
+    }
+
+    async fn reset_secure_boot_keys(
+        &self,
+        reset_type: SecureBootResetType,
+    ) -> Result<(), RedfishError> {
+        let url = format!(
+            "Systems/{}/SecureBoot/Actions/SecureBoot.ResetKeys",
+            self.resolve_system_id().await?
+        );
+        let mut arg = HashMap::new();
+        arg.insert("ResetKeysType", reset_type.to_string());
+        self.client.post(&url, arg).await.map(|_resp| Ok(()))?
     }
 
     async fn get_network_device_functions(
@@ -592,17 +1087,27 @@ impl Redfish for RedfishStandard {
         Err(RedfishError::NotSupported("get_port".to_string()))
     }
 
+    async fn get_port_metrics(
+        &self,
+        _chassis_id: &str,
+        _network_adapter: &str,
+        _id: &str,
+    ) -> Result<model::port::PortMetrics, RedfishError> {
+        Err(RedfishError::NotSupported("get_port_metrics".to_string()))
+    }
+
     async fn change_uefi_password(
         &self,
-        current_uefi_password: &str,
-        new_uefi_password: &str,
-    ) -> Result<Option<String>, RedfishError> {
+        current_uefi_password: &SecretString,
+        new_uefi_password: &SecretString,
+    ) -> Result<crate::BiosPasswordChange, RedfishError> {
         self.change_bios_password(UEFI_PASSWORD_NAME, current_uefi_password, new_uefi_password)
             .await
     }
 
-    async fn change_boot_order(&self, _boot_array: Vec<String>) -> Result<(), RedfishError> {
-        Err(RedfishError::NotSupported("change_boot_order".to_string()))
+    async fn change_boot_order(&self, boot_array: Vec<String>) -> Result<(), RedfishError> {
+        let target = model::boot::BootOrderTarget::detect(&self.get_system().await?);
+        self.change_boot_order_at(&target, boot_array).await
     }
 
     async fn get_service_root(&self) -> Result<ServiceRoot, RedfishError> {
@@ -611,11 +1116,9 @@ impl Redfish for RedfishStandard {
     }
 
     async fn get_systems(&self) -> Result<Vec<String>, RedfishError> {
-        let (_, systems): (_, Systems) = self.client.get("Systems/").await?;
-        if systems.members.is_empty() {
-            return Ok(vec!["1".to_string()]); // default to DMTF standard suggested
-        }
-        let v: Result<Vec<String>, RedfishError> = systems
+        let url = format!("{}/", self.root_path(ServiceRootLink::Systems));
+        let (_, systems): (_, Systems) = self.client.get(&url).await?;
+        systems
             .members
             .into_iter()
             .map(|d| {
@@ -628,21 +1131,109 @@ impl Redfish for RedfishStandard {
                         error: format!("Invalid odata_id format: {}", d.odata_id),
                     })
             })
-            .collect();
-
-        v
+            .collect()
     }
 
     async fn get_manager(&self) -> Result<Manager, RedfishError> {
-        let (_, manager): (_, Manager) = self
+        self.get_manager_by_id(self.manager_id()).await
+    }
+
+    async fn get_manager_by_id(&self, id: &str) -> Result<Manager, RedfishError> {
+        let url = format!("{}/{}", self.root_path(ServiceRootLink::Managers), id);
+        let (_, manager): (_, Manager) = self.client.get(&url).await?;
+        Ok(manager)
+    }
+
+    async fn get_manager_health(&self) -> Result<model::ManagerHealth, RedfishError> {
+        let (_, manager): (_, model::ManagerHealthResource) = self
             .client
             .get(&format!("Managers/{}", self.manager_id()))
             .await?;
-        Ok(manager)
+        let diagnostic_data = manager.manager_diagnostic_data.clone();
+        let health = model::ManagerHealth::from(manager);
+        let Some(link) = diagnostic_data else {
+            return Ok(health);
+        };
+        let url = link.odata_id.replace(&format!("/{REDFISH_ENDPOINT}/"), "");
+        let (_, data): (_, model::ManagerDiagnosticData) = self.client.get(&url).await?;
+        Ok(health.with_diagnostic_data(&data))
+    }
+
+    async fn get_fabric_topology(
+        &self,
+    ) -> Result<Vec<model::fabric::FabricTopology>, RedfishError> {
+        use futures_util::TryStreamExt;
+        let fabrics: Vec<model::fabric::Fabric> =
+            match self.iter_collection("Fabrics").try_collect().await {
+                Ok(fabrics) => fabrics,
+                Err(e) if e.not_found() => return Ok(Vec::new()),
+                Err(e) => return Err(e),
+            };
+        let mut topology = Vec::with_capacity(fabrics.len());
+        for fabric in fabrics {
+            let mut switches = Vec::new();
+            if let Some(link) = fabric.switches.as_ref() {
+                let found: Vec<model::fabric::Switch> = self
+                    .iter_collection(&link.relative_path())
+                    .try_collect()
+                    .await?;
+                for switch in found {
+                    let ports = match switch.ports.as_ref() {
+                        Some(link) => {
+                            self.iter_collection(&link.relative_path())
+                                .try_collect()
+                                .await?
+                        }
+                        None => Vec::new(),
+                    };
+                    switches.push(model::fabric::SwitchPorts { switch, ports });
+                }
+            }
+            topology.push(model::fabric::FabricTopology { fabric, switches });
+        }
+        Ok(topology)
+    }
+
+    async fn get_pcie_switches(
+        &self,
+        chassis_id: &str,
+    ) -> Result<Vec<model::fabric::SwitchPorts>, RedfishError> {
+        let chassis = format!("/{REDFISH_ENDPOINT}/Chassis/{chassis_id}");
+        Ok(self
+            .get_fabric_topology()
+            .await?
+            .into_iter()
+            .flat_map(|t| t.switches)
+            .filter(|s| s.switch.switch_type.as_deref() == Some("PCIe"))
+            .filter(|s| {
+                s.switch
+                    .links
+                    .as_ref()
+                    .and_then(|l| l.chassis.as_ref())
+                    .is_some_and(|c| c.odata_id.trim_end_matches('/') == chassis)
+            })
+            .collect())
+    }
+
+    async fn get_active_conditions(&self) -> Result<Vec<model::Condition>, RedfishError> {
+        let mut urls = vec![
+            format!("Systems/{}", self.resolve_system_id().await?),
+            format!("Managers/{}", self.manager_id()),
+        ];
+        for chassis_id in self.get_chassis_all().await? {
+            urls.push(format!("Chassis/{chassis_id}"));
+        }
+        let mut resources = Vec::with_capacity(urls.len());
+        for url in urls {
+            let (_, resource): (_, model::ResourceWithConditions) = self.client.get(&url).await?;
+            resources.push(resource);
+        }
+        Ok(model::Condition::collect(resources))
     }
 
     async fn get_managers(&self) -> Result<Vec<String>, RedfishError> {
-        let (_, bmcs): (_, Managers) = self.client.get("Managers/").await?;
+        let url = format!("{}/", self.root_path(ServiceRootLink::Managers));
+        let (_, bmcs): (_, Managers) = self.client.get(&url).await?;
         if bmcs.members.is_empty() {
             return Ok(vec!["1".to_string()]);
         }
@@ -664,13 +1255,15 @@ impl Redfish for RedfishStandard {
     }
 
     async fn bmc_reset_to_defaults(&self) -> Result<(), RedfishError> {
-        let url = format!(
-            "Managers/{}/Actions/Manager.ResetToDefaults",
-            self.manager_id
-        );
-        let mut arg = HashMap::new();
-        arg.insert("ResetType", "ResetAll".to_string());
-        self.client.post(&url, arg).await.map(|_resp| Ok(()))?
+        self.reset_manager_to_defaults("ResetType", None).await
+    }
+
+    async fn bmc_reset_to_defaults_with(
+        &self,
+        scope: model::manager::ResetToDefaultsType,
+    ) -> Result<(), RedfishError> {
+        self.reset_manager_to_defaults("ResetType", Some(scope))
+            .await
     }
 
     async fn get_job_state(&self, _job_id: &str) -> Result<JobState, RedfishError> {
@@ -685,6 +1278,16 @@ impl Redfish for RedfishStandard {
         Ok(resource)
     }
 
+    async fn resolve_origin(&self, entry: &LogEntry) -> Result<Resource, RedfishError> {
+        let origin = entry
+            .origin_of_condition()
+            .ok_or_else(|| RedfishError::MissingKey {
+                key: "Links.OriginOfCondition".to_string(),
+                url: entry.odata.odata_id.clone(),
+            })?;
+        self.get_resource(origin.clone()).await
+    }
+
     // This function appends ?$expand=.($levels=1) to the URL, as defined by Redfish spec, to expand first level URIs.
     async fn get_collection(&self, id: ODataId) -> Result<Collection, RedfishError> {
         let url = format!(
@@ -710,14 +1313,15 @@ impl Redfish for RedfishStandard {
 
     async fn clear_uefi_password(
         &self,
-        current_uefi_password: &str,
+        current_uefi_password: &SecretString,
     ) -> Result<Option<String>, RedfishError> {
-        self.change_uefi_password(current_uefi_password, "").await
+        self.change_uefi_password(current_uefi_password, &SecretString::default())
+            .await?
+            .job_id()
     }
 
     async fn get_update_service(&self) -> Result<UpdateService, RedfishError> {
-        let (_, update_service) = self.client.get(self.update_service().as_str()).await?;
-        Ok(update_service)
+        self.get_update_service_at(&self.update_service()).await
     }
 
     async fn get_base_mac_address(&self) -> Result<Option<String>, RedfishError> {
@@ -726,8 +1330,14 @@ impl Redfish for RedfishStandard {
         ))
     }
 
-    async fn lockdown_bmc(&self, _target: EnabledDisabled) -> Result<(), RedfishError> {
-        Ok(())
+    async fn lockdown_bmc(&self, _target: EnabledDisabled) -> Result<bool, RedfishError> {
+        Ok(false)
+    }
+
+    async fn bmc_lockdown_status(&self) -> Result<Status, RedfishError> {
+        Err(RedfishError::NotSupported(
+            "bmc_lockdown_status".to_string(),
+        ))
     }
 
     async fn is_ipmi_over_lan_enabled(&self) -> Result<bool, RedfishError> {
@@ -749,7 +1359,12 @@ impl Redfish for RedfishStandard {
         }
     }
 
-    async fn enable_ipmi_over_lan(&self, target: EnabledDisabled) -> Result<(), RedfishError> {
+    async fn enable_ipmi_over_lan(&self, target: EnabledDisabled) -> Result<bool, RedfishError> {
+        // If we can't tell, set it anyway
+        if matches!(self.is_ipmi_over_lan_enabled().await, Ok(enabled) if enabled == target.is_enabled())
+        {
+            return Ok(false);
+        }
         let url = format!("Managers/{}/NetworkProtocol", self.manager_id(),);
         let mut ipmi_data = HashMap::new();
         ipmi_data.insert("ProtocolEnabled", target.is_enabled());
@@ -757,7 +1372,7 @@ impl Redfish for RedfishStandard {
         let mut data = HashMap::new();
         data.insert("IPMI", ipmi_data);
 
-        self.client.patch(&url, data).await.map(|_status_code| ())
+        self.client.patch(&url, data).await.map(|_status_code| true)
     }
 
     async fn update_firmware_simple_update(
@@ -766,37 +1381,43 @@ impl Redfish for RedfishStandard {
         targets: Vec<String>,
         transfer_protocol: TransferProtocolType,
     ) -> Result<Task, RedfishError> {
-        let data: HashMap<String, serde_json::Value> = HashMap::from([
-            ("ImageURI".to_string(), json!(image_uri)),
-            ("TransferProtocol".to_string(), json!(transfer_protocol)),
-            ("Targets".to_string(), json!(targets)),
-        ]);
-
-        let (_status_code, resp_opt, _) = self
-            .client
-            .req::<Task, _>(
-                Method::POST,
-                "UpdateService/Actions/UpdateService.SimpleUpdate",
-                Some(data),
-                None,
-                None,
-                Vec::new(),
-            )
-            .await?;
-        match resp_opt {
-            Some(response_body) => Ok(response_body),
-            None => Err(RedfishError::NoContent),
-        }
+        self.simple_update_at(
+            &self.update_service(),
+            image_uri,
+            targets,
+            transfer_protocol,
+        )
+        .await
     }
 
-    async fn enable_rshim_bmc(&self) -> Result<(), RedfishError> {
+    async fn enable_rshim_bmc(&self) -> Result<bool, RedfishError> {
         Err(RedfishError::NotSupported("enable_rshim_bmc".to_string()))
     }
 
-    async fn clear_nvram(&self) -> Result<(), RedfishError> {
+    async fn clear_nvram(&self) -> Result<crate::ApplyResult, RedfishError> {
         Err(RedfishError::NotSupported("clear_nvram".to_string()))
     }
 
+    async fn set_uefi_http(
+        &self,
+        _cfg: crate::model::oem::nvidia_viking::UefiHttpAttributes,
+    ) -> Result<(), RedfishError> {
+        Err(RedfishError::NotSupported("set_uefi_http".to_string()))
+    }
+
+    async fn set_virtualization(
+        &self,
+        _cfg: crate::model::oem::nvidia_viking::VirtAttributes,
+    ) -> Result<(), RedfishError> {
+        Err(RedfishError::NotSupported("set_virtualization".to_string()))
+    }
+
+    async fn get_bios_lockdown(
+        &self,
+    ) -> Result<crate::model::oem::nvidia_viking::BiosLockdownAttributes, RedfishError> {
+        Err(RedfishError::NotSupported("get_bios_lockdown".to_string()))
+    }
+
     async fn get_nic_mode(&self) -> Result<Option<NicMode>, RedfishError> {
         Ok(None)
     }
@@ -893,14 +1514,8 @@ impl Redfish for RedfishStandard {
         let url = url.replace("/redfish/v1/", "");
         let mut arg = HashMap::new();
         arg.insert("Nonce", nonce.to_string());
-        let (_status_code, resp_opt, _) = self
-            .client
-            .req::<Task, _>(Method::POST, &url, Some(arg), None, None, Vec::new())
-            .await?;
-        match resp_opt {
-            Some(response_body) => Ok(response_body),
-            None => Err(RedfishError::NoContent),
-        }
+        let response = self.client.post(&url, arg).await?;
+        self.action_task(&url, response).await
     }
 
     async fn get_evidence(
@@ -913,11 +1528,432 @@ impl Redfish for RedfishStandard {
     }
 }
 
+// 405 or 501: the BMC doesn't allow this method on the resource
+pub(crate) fn method_not_allowed(err: &RedfishError) -> bool {
+    matches!(err, RedfishError::HTTPErrorCode { status_code, .. }
+        if *status_code == StatusCode::METHOD_NOT_ALLOWED
+            || *status_code == StatusCode::NOT_IMPLEMENTED)
+}
+
 impl RedfishStandard {
+    /// Fails with BootTargetNotAvailable if the system lists its allowable boot override
+    /// targets and `target` isn't one of them. Systems that don't list them pass.
+    pub async fn ensure_boot_target_allowed(
+        &self,
+        target: BootSourceOverrideTarget,
+    ) -> Result<(), RedfishError> {
+        self.get_system().await?.boot.ensure_target_allowed(target)
+    }
+
     //
     // PUBLIC
     //
 
+    /// GETs the resource a link points to, or None when the BMC doesn't expose the link.
+    /// Sub-resource paths vary by vendor and firmware, so walks follow links instead of
+    /// building paths.
+    pub async fn follow_link<T>(&self, link: Option<&ODataId>) -> Result<Option<T>, RedfishError>
+    where
+        T: serde::de::DeserializeOwned + ::std::fmt::Debug,
+    {
+        let Some(link) = link else {
+            return Ok(None);
+        };
+        let (_status_code, body) = self.client.get(&link.relative_path()).await?;
+        Ok(Some(body))
+    }
+
+    /// Like `follow_link` for a Sensors collection, but follows `Members@odata.nextLink`
+    /// so a paginated collection comes back with the members of every page.
+    pub async fn follow_sensors_link(
+        &self,
+        link: Option<&ODataId>,
+    ) -> Result<Option<model::sensor::Sensors>, RedfishError> {
+        use futures_util::TryStreamExt;
+        let Some(mut sensors) = self.follow_link::<model::sensor::Sensors>(link).await? else {
+            return Ok(None);
+        };
+        if let Some(next) = sensors.members_next_link.take() {
+            let rest: Vec<ODataId> = self
+                .iter_collection_links(&ODataId::from(next).relative_path())
+                .try_collect()
+                .await?;
+            sensors.members.extend(rest);
+        }
+        Ok(Some(sensors))
+    }
+
+    async fn get_power_subsystem_supplies(
+        &self,
+        chassis: &Chassis,
+    ) -> Result<Vec<model::power::PowerSupplyInfo>, RedfishError> {
+        let subsystem: Option<model::power::PowerSubsystem> =
+            match self.follow_link(chassis.power_subsystem.as_ref()).await {
+                Ok(subsystem) => subsystem,
+                Err(e) if e.not_found() => None,
+                Err(e) => return Err(e),
+            };
+        let Some(supplies_link) = subsystem.and_then(|s| s.power_supplies) else {
+            return Ok(vec![]);
+        };
+        let url = supplies_link.relative_path();
+        let members = match self.get_members(&url).await {
+            Ok(members) => members,
+            Err(e) if e.not_found() => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+        let mut supplies = Vec::with_capacity(members.len());
+        for member in members {
+            let (_status_code, mut supply): (_, model::power::PowerSupplyInfo) =
+                self.client.get(&format!("{url}/{member}")).await?;
+            // The input voltage reading moved to the Metrics sub-resource
+            match self
+                .follow_link::<model::power::PowerSupplyMetrics>(supply.metrics.as_ref())
+                .await
+            {
+                Ok(Some(m)) => supply.line_input_voltage = m.input_voltage.and_then(|v| v.reading),
+                Ok(None) => {}
+                Err(e) if e.not_found() => {}
+                Err(e) => return Err(e),
+            }
+            supplies.push(supply);
+        }
+        Ok(supplies)
+    }
+
+    async fn get_legacy_power_supplies(
+        &self,
+        chassis: &Chassis,
+    ) -> Result<Vec<model::power::PowerSupplyInfo>, RedfishError> {
+        #[derive(serde::Deserialize, Debug)]
+        #[serde(rename_all = "PascalCase")]
+        struct PowerSupplies {
+            #[serde(default)]
+            power_supplies: Vec<model::power::PowerSupplyInfo>,
+        }
+
+        match self
+            .follow_link::<PowerSupplies>(chassis.power.as_ref())
+            .await
+        {
+            Ok(power) => Ok(power.map(|p| p.power_supplies).unwrap_or_default()),
+            Err(e) if e.not_found() => Ok(vec![]),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The UpdateService at `url`. Usually that's the one the service root links to, but on
+    /// GB200 the HGX BMC can have its own.
+    pub async fn get_update_service_at(&self, url: &str) -> Result<UpdateService, RedfishError> {
+        let (_, update_service) = self.client.get(url).await?;
+        Ok(update_service)
+    }
+
+    /// SimpleUpdate on the UpdateService at `update_service_url`
+    pub async fn simple_update_at(
+        &self,
+        update_service_url: &str,
+        image_uri: &str,
+        targets: Vec<String>,
+        transfer_protocol: TransferProtocolType,
+    ) -> Result<Task, RedfishError> {
+        let update_service = match self.get_update_service_at(update_service_url).await {
+            Ok(update_service) => Some(update_service),
+            Err(e) => {
+                debug!("Not validating SimpleUpdate transfer protocol: {e}");
+                None
+            }
+        };
+        let action_url = update_service
+            .as_ref()
+            .and_then(|u| u.actions.as_ref())
+            .and_then(|a| a.simple_update.as_ref())
+            .and_then(|s| s.target.as_ref())
+            .map(|target| target.replace(&format!("/{REDFISH_ENDPOINT}/"), ""))
+            .unwrap_or_else(|| {
+                format!(
+                    "{}/Actions/UpdateService.SimpleUpdate",
+                    update_service_url.replace(&format!("/{REDFISH_ENDPOINT}/"), "")
+                )
+            });
+
+        let allowed = match update_service {
+            Some(update_service) => {
+                self.simple_update_transfer_protocols(update_service)
+                    .await?
+            }
+            None => vec![],
+        };
+        if !allowed.is_empty() && !allowed.contains(&transfer_protocol) {
+            let allowed: Vec<String> = allowed.iter().map(|p| format!("{p:?}")).collect();
+            return Err(RedfishError::NotSupported(format!(
+                "SimpleUpdate transfer protocol {transfer_protocol:?}, supported: {}",
+                allowed.join(", ")
+            )));
+        }
+
+        let data: HashMap<String, serde_json::Value> = HashMap::from([
+            ("ImageURI".to_string(), json!(image_uri)),
+            ("TransferProtocol".to_string(), json!(transfer_protocol)),
+            ("Targets".to_string(), json!(targets)),
+        ]);
+
+        let response = self.client.post(&action_url, data).await?;
+        self.action_task(&action_url, response).await
+    }
+
+    /// Transfer protocols SimpleUpdate accepts, from the inline allowable values or
+    /// the action's ActionInfo. Empty means the BMC doesn't say, so don't validate.
+    async fn simple_update_transfer_protocols(
+        &self,
+        update_service: UpdateService,
+    ) -> Result<Vec<TransferProtocolType>, RedfishError> {
+        let allowed = update_service.allowable_transfer_protocols();
+        if !allowed.is_empty() {
+            return Ok(allowed.to_vec());
+        }
+        let Some(action_info) = update_service
+            .actions
+            .and_then(|a| a.simple_update)
+            .and_then(|s| s.action_info)
+        else {
+            return Ok(vec![]);
+        };
+        let url = action_info.replace(&format!("/{REDFISH_ENDPOINT}/"), "");
+        match self
+            .client
+            .get::<model::update_service::ActionInfo>(&url)
+            .await
+        {
+            Ok((_status_code, info)) => Ok(info.transfer_protocols()),
+            Err(e) if e.not_found() => Ok(vec![]),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// DELETE the boot option, then drop it from BootOrder if the BMC left it there, since
+    /// the next change_boot_order built from the stale order would be rejected. The order is
+    /// written with `vendor`'s change_boot_order, which knows where that BMC takes it.
+    pub async fn delete_boot_option_for<R: Redfish + ?Sized>(
+        &self,
+        vendor: &R,
+        option_id: &str,
+    ) -> Result<(), RedfishError> {
+        let option = self.get_boot_option(option_id).await?;
+        let url = format!(
+            "Systems/{}/BootOptions/{}",
+            self.resolve_system_id().await?,
+            option_id
+        );
+        match self.client.delete(&url).await {
+            Err(e) if method_not_allowed(&e) => {
+                return Err(RedfishError::NotSupported(
+                    "delete_boot_option: BMC does not allow deleting boot options".to_string(),
+                ));
+            }
+            r => r?,
+        };
+
+        let boot_order = self.get_system().await?.boot.boot_order;
+        let pruned: Vec<String> = boot_order
+            .iter()
+            .filter(|r| **r != option.boot_option_reference && **r != option.id)
+            .cloned()
+            .collect();
+        if pruned.len() != boot_order.len() {
+            vendor.change_boot_order(pruned).await?;
+        }
+        Ok(())
+    }
+
+    /// PATCH Boot.BootOrder to `target`, completing the array first if it needs the full order.
+    pub async fn change_boot_order_at(
+        &self,
+        target: &model::boot::BootOrderTarget,
+        boot_array: Vec<String>,
+    ) -> Result<(), RedfishError> {
+        let current = if target.full_order {
+            self.get_system().await?.boot.boot_order
+        } else {
+            Vec::new()
+        };
+        let boot_order = target.boot_order(boot_array, &current);
+        self.patch_preserving(&target.url, json!({"Boot": {"BootOrder": &boot_order}}))
+            .await?;
+        self.verify_boot_order(&target.url, "/Boot/BootOrder", &boot_order)
+            .await
+    }
+
+    /// Read-modify-write of the resource at `url` that sends only the keys of `changes` whose
+    /// value differs from the current one. Read-only properties and ones this crate doesn't
+    /// model, such as Oem, are left out of the PATCH so the BMC keeps them as they are. The
+    /// PATCH is guarded by the ETag of the read. Nothing is sent if the values are already set.
+    pub async fn patch_preserving(
+        &self,
+        url: &str,
+        changes: serde_json::Value,
+    ) -> Result<(), RedfishError> {
+        self.client
+            .patch_read_modify_write(url, |current| changed_only(current, &changes))
+            .await
+    }
+
+    /// With verify_writes on, re-read `url` and fail with VerificationFailed unless the
+    /// property at JSON pointer `pointer` passes `accept`, either in the resource or in
+    /// the settings object holding changes for the next reboot. `expected` is only
+    /// used in the error.
+    pub async fn verify_write<F>(
+        &self,
+        url: &str,
+        pointer: &str,
+        expected: &serde_json::Value,
+        accept: F,
+    ) -> Result<(), RedfishError>
+    where
+        F: Fn(&serde_json::Value) -> bool,
+    {
+        if !self.client.verify_writes() {
+            return Ok(());
+        }
+        let (_status_code, live): (_, serde_json::Value) = self.client.get(url).await?;
+        let mut actual = live.pointer(pointer).cloned();
+        if actual.as_ref().is_some_and(&accept) {
+            return Ok(());
+        }
+        let pending_url = live
+            .pointer("/@Redfish.Settings/SettingsObject/@odata.id")
+            .and_then(|id| id.as_str())
+            .map(|id| ODataId::from(id.to_string()).relative_path())
+            .filter(|pending_url| pending_url != url);
+        if let Some(pending_url) = pending_url {
+            let (_status_code, pending): (_, serde_json::Value) =
+                self.client.get(&pending_url).await?;
+            if let Some(value) = pending.pointer(pointer) {
+                if accept(value) {
+                    return Ok(());
+                }
+                actual = Some(value.clone());
+            }
+        }
+        Err(RedfishError::VerificationFailed {
+            url: url.to_string(),
+            property: pointer.trim_start_matches('/').replace('/', "."),
+            expected: expected.to_string(),
+            actual: actual.map_or_else(|| "missing".to_string(), |v| v.to_string()),
+        })
+    }
+
+    /// verify_write for SecureBootEnable after setting it to `enabled`
+    pub async fn verify_secure_boot(&self, enabled: bool) -> Result<(), RedfishError> {
+        let url = format!("Systems/{}/SecureBoot", self.resolve_system_id().await?);
+        let expected = json!(enabled);
+        self.verify_write(&url, "/SecureBootEnable", &expected, |v| v == &expected)
+            .await
+    }
+
+    /// verify_write for a boot order: the BMC may list more entries after the ones we sent.
+    pub async fn verify_boot_order(
+        &self,
+        url: &str,
+        pointer: &str,
+        boot_order: &[String],
+    ) -> Result<(), RedfishError> {
+        let expected = json!(boot_order);
+        self.verify_write(url, pointer, &expected, |v| {
+            v.as_array().is_some_and(|actual| {
+                actual.len() >= boot_order.len()
+                    && actual.iter().zip(boot_order).all(|(a, b)| a == b)
+            })
+        })
+        .await
+    }
+
+    // Manager Links naming the firmware banks, NotSupported if it has fewer than two
+    async fn software_image_links(
+        &self,
+        component: &str,
+    ) -> Result<model::software_inventory::SoftwareImageLinks, RedfishError> {
+        let url = format!("Managers/{component}");
+        let (_, manager): (_, model::software_inventory::ManagerSoftwareImages) =
+            self.client.get(&url).await?;
+        if manager.links.software_images.len() < 2 {
+            return Err(RedfishError::NotSupported(format!(
+                "{component} does not list dual firmware banks"
+            )));
+        }
+        Ok(manager.links)
+    }
+
+    /// The unfinished jobs in the job collection at `url`, none if there isn't one
+    pub async fn pending_jobs_at(&self, url: &str) -> Result<Vec<Job>, RedfishError> {
+        use futures_util::TryStreamExt;
+        match self
+            .iter_collection::<Job>(url)
+            .try_collect::<Vec<_>>()
+            .await
+        {
+            Ok(jobs) => Ok(jobs.into_iter().filter(Job::is_pending).collect()),
+            Err(e) if e.not_found() => Ok(Vec::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Stream the members of the collection at `url`, following nextLink, without
+    /// fetching them all up front. See `CollectionIter`.
+    pub fn iter_collection<T>(&self, url: &str) -> crate::CollectionIter<T>
+    where
+        T: serde::de::DeserializeOwned + std::fmt::Debug + Send + 'static,
+    {
+        crate::CollectionIter::new(self.client.clone(), url)
+    }
+
+    /// Like `iter_collection`, but only the member links, nothing is fetched past the pages
+    pub fn iter_collection_links(&self, url: &str) -> crate::CollectionIter<ODataId> {
+        crate::CollectionIter::links(self.client.clone(), url)
+    }
+
+    /// The task an action at `url` started. Taken from the response when the BMC sent it
+    /// inline, else read by polling the task monitor, which answers with the Task while it
+    /// runs. A monitor that answers with anything else is followed to the task it names, if any.
+    pub(crate) async fn action_task(
+        &self,
+        url: &str,
+        response: crate::ActionResponse,
+    ) -> Result<Task, RedfishError> {
+        let monitor = match response {
+            crate::ActionResponse::Accepted(monitor) => monitor,
+            crate::ActionResponse::Immediate(body) => {
+                return crate::network::from_json_value(url, &body)
+            }
+            crate::ActionResponse::Created { .. } => {
+                return Err(RedfishError::NoTask {
+                    url: url.to_string(),
+                })
+            }
+            crate::ActionResponse::NoContent => return Err(RedfishError::NoContent),
+        };
+        if let Some(task) = &monitor.task {
+            return crate::network::from_json_value(url, task);
+        }
+        let polled = match self.client.get::<serde_json::Value>(monitor.path()).await {
+            Ok((_status_code, body)) => Some(body),
+            Err(RedfishError::NoContent) => None,
+            Err(e) => return Err(e),
+        };
+        let polled_task = polled.filter(|body| {
+            body.get("@odata.type")
+                .and_then(|t| t.as_str())
+                .is_some_and(|t| t.starts_with("#Task."))
+        });
+        if let Some(task) = polled_task {
+            return crate::network::from_json_value(monitor.path(), &task);
+        }
+        let id = monitor.task_id().ok_or_else(|| RedfishError::NoTask {
+            url: url.to_string(),
+        })?;
+        self.get_task(&id).await
+    }
+
     pub async fn get_members(&self, url: &str) -> Result<Vec<String>, RedfishError> {
         let (_, body): (_, HashMap<String, serde_json::Value>) = self.client.get(url).await?;
         self.parse_members(url, body)
@@ -967,12 +2003,13 @@ impl RedfishStandard {
             // nvidia dgx systems may have both ami and nvidia as vendor strings depending on hw
             // ami also ships its bmc fw for other system vendors.
             RedfishVendor::AMI => {
-                if self.system_id == "DGX" && self.manager_id == "BMC" {
+                if self.system_id() == "DGX" && self.manager_id == "BMC" {
                     Ok(Box::new(crate::nvidia_viking::Bmc::new(self.clone())?))
                 } else {
                     Err(RedfishError::NotSupported(format!(
                         "vendor: AMI, system: {}, bmc: {}",
-                        self.system_id, self.manager_id
+                        self.system_id(),
+                        self.manager_id
                     )))
                 }
             }
@@ -996,7 +2033,7 @@ impl RedfishStandard {
 
     /// Needed for all `Systems/{system_id}/...` calls
     pub fn set_system_id(&mut self, system_id: &str) -> Result<(), RedfishError> {
-        self.system_id = system_id.to_string();
+        self.system_id = Arc::new(OnceLock::from(system_id.to_string()));
         Ok(())
     }
 
@@ -1006,6 +2043,12 @@ impl RedfishStandard {
         Ok(())
     }
 
+    /// Path of a top-level collection or service as the service root links it, or the
+    /// canonical one if it doesn't. Canonical until `set_service_root` is called.
+    pub fn root_path(&self, link: ServiceRootLink) -> String {
+        self.service_root.link_path(link)
+    }
+
     /// Saves the service_root for later use
     pub fn set_service_root(&mut self, service_root: ServiceRoot) -> Result<(), RedfishError> {
         self.service_root = service_root;
@@ -1017,14 +2060,32 @@ impl RedfishStandard {
         Self {
             client,
             manager_id: "".to_string(),
-            system_id: "".to_string(),
+            system_id: Arc::new(OnceLock::new()),
             vendor: None,
             service_root: default::Default::default(),
         }
     }
 
+    /// The system id, empty if it hasn't been looked up yet. See `resolve_system_id`.
     pub fn system_id(&self) -> &str {
-        &self.system_id
+        self.system_id.get().map(String::as_str).unwrap_or_default()
+    }
+
+    /// The system id, looked up from the first member of Systems on first use when the client
+    /// was created without one, e.g. by `create_standard_client`. A failed lookup is not
+    /// remembered: the next call tries again. An empty Systems collection is
+    /// `RedfishError::NoSystemsFound`.
+    pub async fn resolve_system_id(&self) -> Result<&str, RedfishError> {
+        if let Some(id) = self.system_id.get() {
+            return Ok(id);
+        }
+        let id = self
+            .get_systems()
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(RedfishError::NoSystemsFound)?;
+        Ok(self.system_id.get_or_init(|| id))
     }
 
     pub fn manager_id(&self) -> &str {
@@ -1048,7 +2109,7 @@ impl RedfishStandard {
     }
 
     pub async fn get_boot_options(&self) -> Result<model::BootOptions, RedfishError> {
-        let url = format!("Systems/{}/BootOptions", self.system_id());
+        let url = format!("Systems/{}/BootOptions", self.resolve_system_id().await?);
         let (_status_code, body) = self.client.get(&url).await?;
         Ok(body)
     }
@@ -1071,14 +2132,7 @@ impl RedfishStandard {
         from: Option<chrono::DateTime<chrono::Utc>>,
     ) -> Result<Vec<LogEntry>, RedfishError> {
         let url_with_filter = match from {
-            Some(from) => {
-                let filter_value = format!(
-                    "Created ge '{}'",
-                    from.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
-                );
-                let encoded_filter = urlencoding::encode(&filter_value).into_owned();
-                format!("{}?$filter={}", url, encoded_filter)
-            }
+            Some(from) => created_since_filter(url, from),
             None => url,
         };
 
@@ -1087,6 +2141,30 @@ impl RedfishStandard {
         Ok(log_entry_collection.members)
     }
 
+    /// Fetches log entries newest-first, created at or after `since` and at most `limit` of them.
+    /// `since` is sent as `$filter` when the service root advertises support for it. `$top` is
+    /// not used because collections are usually oldest-first, so the limit is applied here.
+    pub async fn fetch_system_event_log(
+        &self,
+        url: String,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        limit: Option<usize>,
+    ) -> Result<Vec<LogEntry>, RedfishError> {
+        let url = match since {
+            Some(since) if self.service_root.supports_filter_query() => {
+                created_since_filter(url, since)
+            }
+            _ => url,
+        };
+        let (_status_code, log_entry_collection): (_, LogEntryCollection) =
+            self.client.get(&url).await?;
+        Ok(model::sel::newest_first(
+            log_entry_collection.members,
+            since,
+            limit,
+        ))
+    }
+
     // The URL differs for Lenovo, but the rest is the same
     pub async fn pending_with_url(
         &self,
@@ -1099,10 +2177,16 @@ impl RedfishStandard {
 
     // There's no standard Redfish way to clear pending BIOS settings, so we find the
     // pending changes and set them back to their existing values
-    pub async fn clear_pending_with_url(&self, pending_url: &str) -> Result<(), RedfishError> {
+    pub async fn clear_pending_with_url(
+        &self,
+        pending_url: &str,
+    ) -> Result<ClearPendingResult, RedfishError> {
         let pending_attrs = self.pending_attributes(pending_url).await?;
         let current_attrs = self.bios_attributes().await?;
         let diff = attr_diff(&pending_attrs, &current_attrs);
+        if diff.is_empty() {
+            return Ok(ClearPendingResult::NothingPending);
+        }
 
         let mut reset_attrs = HashMap::new();
         for k in diff.keys() {
@@ -1113,7 +2197,7 @@ impl RedfishStandard {
         self.client
             .patch(pending_url, body)
             .await
-            .map(|_status_code| ())
+            .map(|_status_code| ClearPendingResult::Cleared(diff.len()))
     }
 
     /// Get the first serial interface
@@ -1182,12 +2266,248 @@ impl RedfishStandard {
             })
     }
 
-    pub async fn factory_reset_bios(&self) -> Result<(), RedfishError> {
-        let url = format!("Systems/{}/Bios/Actions/Bios.ResetBios", self.system_id());
-        self.client
-            .req::<(), ()>(Method::POST, &url, None, None, None, Vec::new())
+    /// POST Manager.ResetToDefaults with `scope` in the `parameter` field, after checking it
+    /// against the allowable values. The DMTF name of the parameter is ResetType, some
+    /// BMCs call it something else. Without a scope the safest one the BMC allows is used,
+    /// see `ManagerResetToDefaultsResource::safest`.
+    pub async fn reset_manager_to_defaults(
+        &self,
+        parameter: &str,
+        scope: Option<model::manager::ResetToDefaultsType>,
+    ) -> Result<(), RedfishError> {
+        let url = format!("Managers/{}", self.manager_id());
+        let (_status_code, manager): (_, model::manager::ManagerResetToDefaultsResource) =
+            self.client.get(&url).await?;
+        let scope = match scope {
+            Some(scope) if manager.allows(parameter, scope) => scope,
+            Some(scope) => {
+                return Err(RedfishError::NotSupported(format!(
+                    "ResetToDefaults {scope}, supported: {}",
+                    manager.allowable_values(parameter).join(", ")
+                )));
+            }
+            None => manager.safest(parameter).ok_or_else(|| {
+                RedfishError::NotSupported(
+                    "ResetToDefaults keeping the network settings, only ResetAll is offered"
+                        .to_string(),
+                )
+            })?,
+        };
+        let url = format!("{url}/Actions/Manager.ResetToDefaults");
+        let arg = HashMap::from([(parameter, scope.to_string())]);
+        self.client.post(&url, arg).await.map(|_resp| ())
+    }
+
+    /// Fetch the BIOS AttributeRegistry named by Systems/{id}/Bios
+    pub async fn get_bios_attribute_registry(
+        &self,
+    ) -> Result<model::AttributeRegistry, RedfishError> {
+        let bios = self.bios().await?;
+        let Some(name) = bios.get("AttributeRegistry").and_then(|v| v.as_str()) else {
+            return Err(RedfishError::NotSupported("AttributeRegistry".to_string()));
+        };
+        let registry_file: model::MessageRegistryFile =
+            match self.client.get(&format!("Registries/{name}")).await {
+                Ok((_status_code, registry_file)) => registry_file,
+                Err(e) if e.not_found() => {
+                    // Some BMCs drop the version from the id, e.g. Registries/BiosAttributeRegistry
+                    let id = self
+                        .get_members("Registries")
+                        .await?
+                        .into_iter()
+                        .find(|id| name.starts_with(id.as_str()))
+                        .ok_or_else(|| RedfishError::NotSupported(format!("Registry {name}")))?;
+                    self.client.get(&format!("Registries/{id}")).await?.1
+                }
+                Err(e) => return Err(e),
+            };
+        let uri = registry_file
+            .location
+            .iter()
+            .filter(|l| l.language.as_deref().is_none_or(|lang| lang == "en"))
+            .find_map(|l| l.uri.clone())
+            .ok_or_else(|| RedfishError::MissingKey {
+                key: "Location.Uri".to_string(),
+                url: format!("Registries/{}", registry_file.id),
+            })?;
+        let url = uri.replace(&format!("/{REDFISH_ENDPOINT}/"), "");
+        let (_status_code, registry) = self.client.get(&url).await?;
+        Ok(registry)
+    }
+
+    /// Add the prerequisites the BIOS AttributeRegistry's Dependencies require for attrs, then
+    /// check them all against the registry. Skipped if the BMC doesn't publish a registry.
+    pub async fn prepare_bios_attributes(
+        &self,
+        attrs: &mut HashMap<String, serde_json::Value>,
+    ) -> Result<(), RedfishError> {
+        let Some(registry) = self.bios_attribute_registry_if_any().await? else {
+            return Ok(());
+        };
+        self.prepare_bios_attributes_with(&registry, attrs).await
+    }
+
+    /// prepare_bios_attributes, then split attrs into the ones that take effect when written
+    /// to the live Bios resource and the ones that belong in Settings, see `split_bios_attributes`
+    pub async fn bios_attribute_targets(
+        &self,
+        mut attrs: HashMap<String, serde_json::Value>,
+    ) -> Result<model::BiosAttributeTargets, RedfishError> {
+        let registry = self.bios_attribute_registry_if_any().await?;
+        if let Some(registry) = &registry {
+            self.prepare_bios_attributes_with(registry, &mut attrs)
+                .await?;
+        }
+        self.split_bios_attributes(registry.as_ref(), attrs).await
+    }
+
+    /// Split attrs by the registry's ResetRequired, without adding prerequisites or validating
+    /// them, for attributes the registry may not list such as passwords. Everything goes to
+    /// Settings if there is no registry, or if Systems/{id}/Bios has `@Redfish.Settings`: the
+    /// live resource is then read-only and only the settings object takes writes.
+    pub async fn split_bios_attributes(
+        &self,
+        registry: Option<&model::AttributeRegistry>,
+        attrs: HashMap<String, serde_json::Value>,
+    ) -> Result<model::BiosAttributeTargets, RedfishError> {
+        let Some(registry) = registry else {
+            return Ok(model::BiosAttributeTargets::settings_only(attrs));
+        };
+        if self.bios().await?.contains_key("@Redfish.Settings") {
+            return Ok(model::BiosAttributeTargets::settings_only(attrs));
+        }
+        Ok(registry.targets(attrs))
+    }
+
+    /// Write targets.live to Systems/{id}/Bios, then targets.settings with write_settings, which
+    /// is skipped if there are none. If write_settings fails after live attributes were applied
+    /// the error is RedfishError::BiosAttributesPartiallyApplied, naming them.
+    pub async fn write_bios_attribute_targets<F, Fut>(
+        &self,
+        targets: model::BiosAttributeTargets,
+        write_settings: F,
+    ) -> Result<(), RedfishError>
+    where
+        F: FnOnce(HashMap<String, serde_json::Value>) -> Fut,
+        Fut: std::future::Future<Output = Result<(), RedfishError>>,
+    {
+        let mut applied: Vec<String> = targets.live.keys().cloned().collect();
+        applied.sort();
+        self.set_live_bios_attributes(targets.live).await?;
+        if targets.settings.is_empty() {
+            return Ok(());
+        }
+        write_settings(targets.settings).await.map_err(|source| {
+            if applied.is_empty() {
+                source
+            } else {
+                RedfishError::BiosAttributesPartiallyApplied {
+                    applied,
+                    source: Box::new(source),
+                }
+            }
+        })
+    }
+
+    /// Write attributes that apply without a reset to Systems/{id}/Bios. Nothing to do if empty.
+    pub async fn set_live_bios_attributes(
+        &self,
+        attrs: HashMap<String, serde_json::Value>,
+    ) -> Result<(), RedfishError> {
+        if attrs.is_empty() {
+            return Ok(());
+        }
+        let url = format!("Systems/{}/Bios", self.resolve_system_id().await?);
+        self.patch_preserving(&url, json!({ "Attributes": attrs }))
             .await
-            .map(|_resp| Ok(()))?
+    }
+
+    /// Write attributes to the standard settings object, Systems/{id}/Bios/Settings
+    pub async fn patch_bios_settings(
+        &self,
+        attrs: HashMap<String, serde_json::Value>,
+    ) -> Result<(), RedfishError> {
+        let url = format!("Systems/{}/Bios/Settings", self.resolve_system_id().await?);
+        self.patch_preserving(&url, json!({ "Attributes": attrs }))
+            .await
+    }
+
+    async fn prepare_bios_attributes_with(
+        &self,
+        registry: &model::AttributeRegistry,
+        attrs: &mut HashMap<String, serde_json::Value>,
+    ) -> Result<(), RedfishError> {
+        for (name, value) in self.prerequisites_from(registry, attrs).await? {
+            warn!("Also setting BIOS attribute {name} to {value}, a prerequisite of the requested attributes");
+            attrs.insert(name, value);
+        }
+        let errors = registry.validate(attrs);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(RedfishError::InvalidBiosAttributes(errors))
+        }
+    }
+
+    /// The BIOS AttributeRegistry, or None if the BMC doesn't publish one
+    pub async fn bios_attribute_registry_if_any(
+        &self,
+    ) -> Result<Option<model::AttributeRegistry>, RedfishError> {
+        match self.get_bios_attribute_registry().await {
+            Ok(registry) => Ok(Some(registry)),
+            Err(e) if e.not_found() || matches!(e, RedfishError::NotSupported(_)) => {
+                debug!("No BIOS attribute registry: {e}");
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn prerequisites_from(
+        &self,
+        registry: &model::AttributeRegistry,
+        attrs: &HashMap<String, serde_json::Value>,
+    ) -> Result<HashMap<String, serde_json::Value>, RedfishError> {
+        if registry.registry_entries.dependencies.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let current: HashMap<String, serde_json::Value> = self
+            .bios_attributes()
+            .await?
+            .as_object()
+            .map(|m| m.clone().into_iter().collect())
+            .unwrap_or_default();
+        Ok(registry.prerequisites(attrs, &current))
+    }
+
+    /// Pair each pending attribute, as returned by `pending()`, with its current value
+    pub async fn bios_attr_changes(
+        &self,
+        pending: HashMap<String, serde_json::Value>,
+    ) -> Result<Vec<model::BiosAttrChange>, RedfishError> {
+        let current = self.bios_attributes().await?;
+        let mut changes: Vec<model::BiosAttrChange> = pending
+            .into_iter()
+            .map(|(name, pending)| model::BiosAttrChange {
+                current: current.get(&name).cloned(),
+                name,
+                pending,
+            })
+            .filter(|c| c.current.as_ref() != Some(&c.pending))
+            .collect();
+        changes.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(changes)
+    }
+
+    pub async fn factory_reset_bios(&self) -> Result<crate::ApplyResult, RedfishError> {
+        let url = format!(
+            "Systems/{}/Bios/Actions/Bios.ResetBios",
+            self.resolve_system_id().await?
+        );
+        let response = self.client.post(&url, json!({})).await?;
+        Ok(crate::ApplyResult::from_reset_bios_response(
+            &response, &url,
+        ))
     }
 
     pub async fn get_account_by_id(
@@ -1222,7 +2542,7 @@ impl RedfishStandard {
         &self,
         account_id: u8,
         username: &str,
-        password: &str,
+        password: &SecretString,
         role_id: RoleId,
         enabled: bool,
     ) -> Result<(), RedfishError> {
@@ -1230,7 +2550,7 @@ impl RedfishStandard {
         let account = ManagerAccount {
             id: None, // it's in the URL, must not be set here
             username: username.to_string(),
-            password: Some(password.to_string()),
+            password: Some(password.clone()),
             enabled: Some(enabled),
             role_id: role_id.to_string(),
             ..Default::default()
@@ -1245,26 +2565,78 @@ impl RedfishStandard {
     // PRIVATE
     //
 
+    async fn get_fru_entry(&self, url: &str) -> Result<model::fru::FruEntry, RedfishError> {
+        let (_status_code, entry) = self.client.get(url).await?;
+        Ok(entry)
+    }
+
+    /// FRU identity of every member of a collection. A missing collection is empty.
+    async fn get_fru_members(
+        &self,
+        collection_url: &str,
+    ) -> Result<Vec<model::fru::FruEntry>, RedfishError> {
+        let members = match self.get_members(collection_url).await {
+            Ok(members) => members,
+            Err(e) if e.not_found() => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+        let collection_url = collection_url.trim_end_matches('/');
+        futures_util::stream::iter(members)
+            .map(|member| async move {
+                self.get_fru_entry(&format!("{collection_url}/{member}"))
+                    .await
+            })
+            .buffered(FRU_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+
+    async fn get_fru_power_supplies(&self) -> Result<Vec<model::fru::FruEntry>, RedfishError> {
+        let supplies = self.get_power_supplies().await?;
+        Ok(supplies
+            .into_iter()
+            .map(|p| model::fru::FruEntry {
+                odata_id: p.odata_id,
+                id: p.id,
+                name: p.name,
+                manufacturer: p.manufacturer,
+                model: p.model,
+                serial_number: p.serial_number,
+                part_number: p.part_number,
+            })
+            .collect())
+    }
+
     /// Query the power status from the server
     #[allow(dead_code)]
     pub async fn get_power_status(&self) -> Result<power::Power, RedfishError> {
-        let url = format!("Chassis/{}/Power/", self.system_id());
+        let url = format!("Chassis/{}/Power/", self.resolve_system_id().await?);
         let (_status_code, body) = self.client.get(&url).await?;
         Ok(body)
     }
 
     /// Query the power supplies and voltages stats from the server
     pub async fn get_power_metrics(&self) -> Result<power::Power, RedfishError> {
-        let url = format!("Chassis/{}/Power/", self.system_id());
-        let (_status_code, body) = self.client.get(&url).await?;
-        Ok(body)
+        let chassis_id = self.resolve_system_id().await?;
+        let chassis = self.get_chassis(chassis_id).await?;
+        self.follow_link(chassis.power.as_ref())
+            .await?
+            .ok_or_else(|| RedfishError::MissingKey {
+                key: "Power".to_string(),
+                url: format!("Chassis/{chassis_id}"),
+            })
     }
 
     /// Query the thermal status from the server
     pub async fn get_thermal_metrics(&self) -> Result<thermal::Thermal, RedfishError> {
-        let url = format!("Chassis/{}/Thermal/", self.system_id());
-        let (_status_code, body) = self.client.get(&url).await?;
-        Ok(body)
+        let chassis_id = self.resolve_system_id().await?;
+        let chassis = self.get_chassis(chassis_id).await?;
+        self.follow_link(chassis.thermal.as_ref())
+            .await?
+            .ok_or_else(|| RedfishError::MissingKey {
+                key: "Thermal".to_string(),
+                url: format!("Chassis/{chassis_id}"),
+            })
     }
 
     /// Query the drives status from the server
@@ -1273,7 +2645,10 @@ impl RedfishStandard {
 
         let storages: Vec<Storage> = self
             .get_collection(ODataId {
-                odata_id: format!("/redfish/v1/Systems/{}/Storage/", self.system_id()),
+                odata_id: format!(
+                    "/redfish/v1/Systems/{}/Storage/",
+                    self.resolve_system_id().await?
+                ),
             })
             .await
             .and_then(|c| c.try_get::<Storage>())
@@ -1300,10 +2675,10 @@ impl RedfishStandard {
     pub async fn change_bios_password(
         &self,
         password_name: &str,
-        current_bios_password: &str,
-        new_bios_password: &str,
-    ) -> Result<Option<String>, RedfishError> {
-        let mut url = format!("Systems/{}/Bios/", self.system_id);
+        current_bios_password: &SecretString,
+        new_bios_password: &SecretString,
+    ) -> Result<crate::BiosPasswordChange, RedfishError> {
+        let mut url = format!("Systems/{}/Bios/", self.resolve_system_id().await?);
 
         match self.vendor {
             Some(RedfishVendor::Hpe) => {
@@ -1315,10 +2690,15 @@ impl RedfishStandard {
         }
 
         let mut arg = HashMap::new();
-        arg.insert("PasswordName", password_name.to_string());
-        arg.insert("OldPassword", current_bios_password.to_string());
-        arg.insert("NewPassword", new_bios_password.to_string());
-        self.client.post(&url, arg).await.map(|_resp| Ok(None))?
+        arg.insert("PasswordName", password_name);
+        arg.insert("OldPassword", current_bios_password.expose_secret());
+        arg.insert("NewPassword", new_bios_password.expose_secret());
+        let result = self
+            .client
+            .post(&url, arg)
+            .await
+            .map(|response| response.task_id(&url).ok());
+        crate::BiosPasswordChange::from_result(result)
     }
 
     /// Query the network service settings for the server
@@ -1378,6 +2758,28 @@ impl RedfishStandard {
     }
 }
 
+/// The PATCH body for `patch_preserving`: the parts of `changes` that differ from `current`.
+/// Objects are compared key by key, so only changed leaves are kept, arrays and scalars are
+/// compared whole. None when `changes` is already in place.
+pub(crate) fn changed_only(
+    current: &serde_json::Value,
+    changes: &serde_json::Value,
+) -> Option<serde_json::Value> {
+    match (current, changes) {
+        (serde_json::Value::Object(cur), serde_json::Value::Object(chg)) => {
+            let out: serde_json::Map<String, serde_json::Value> = chg
+                .iter()
+                .filter_map(|(k, v)| match cur.get(k) {
+                    Some(c) => Some((k.clone(), changed_only(c, v)?)),
+                    None => Some((k.clone(), v.clone())),
+                })
+                .collect();
+            (!out.is_empty()).then_some(serde_json::Value::Object(out))
+        }
+        (current, changes) => (current != changes).then(|| changes.clone()),
+    }
+}
+
 // Key/value pairs that different between these two sets of attributes
 // The left needs to be a full map, but the right side only needs to support `get`.
 fn attr_diff(
@@ -1389,3 +2791,669 @@ fn attr_diff(
         .map(|(k, v)| (k.clone(), v.clone()))
         .collect()
 }
+
+// Appends a `$filter` selecting log entries created at or after `from`
+fn created_since_filter(url: String, from: chrono::DateTime<chrono::Utc>) -> String {
+    let filter_value = format!(
+        "Created ge '{}'",
+        from.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    );
+    let encoded_filter = urlencoding::encode(&filter_value).into_owned();
+    format!("{}?$filter={}", url, encoded_filter)
+}
+
+/// BMCs list inventory members in no particular order, and it changes between firmware revs.
+/// Sorts them so that runs of digits compare numerically, e.g. "PSU_2" before "PSU_10".
+pub(crate) fn sort_inventory_ids(ids: &mut [String]) {
+    ids.sort_by(|a, b| natural_cmp(a, b));
+}
+
+fn natural_cmp(a: &str, b: &str) -> std::cmp::Ordering {
+    fn chunks(s: &str) -> Vec<(bool, &str)> {
+        let mut out = Vec::new();
+        let mut start = 0;
+        for (i, c) in s.char_indices().skip(1) {
+            let prev = s[..i].chars().next_back().unwrap_or_default();
+            if c.is_ascii_digit() != prev.is_ascii_digit() {
+                out.push((prev.is_ascii_digit(), &s[start..i]));
+                start = i;
+            }
+        }
+        if start < s.len() {
+            out.push((
+                s[start..].starts_with(|c: char| c.is_ascii_digit()),
+                &s[start..],
+            ));
+        }
+        out
+    }
+    let key = |s| {
+        chunks(s)
+            .into_iter()
+            .map(|(numeric, chunk)| match numeric {
+                // Compare by length first so numbers of any size order numerically
+                true => {
+                    let digits = chunk.trim_start_matches('0');
+                    (digits.len(), digits, "")
+                }
+                false => (0, "", chunk),
+            })
+            .collect::<Vec<_>>()
+    };
+    key(a).cmp(&key(b)).then_with(|| a.cmp(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use super::{changed_only, natural_cmp};
+    use crate::fake_bmc::{bios_resources, FakeBmc, Response};
+    use crate::{NewBootOption, Redfish, RedfishError};
+
+    const SETTINGS: &str = "/redfish/v1/Systems/1/Bios/Settings";
+
+    fn attrs() -> HashMap<String, serde_json::Value> {
+        HashMap::from([
+            ("LiveAttr".to_string(), json!(true)),
+            ("StagedAttr".to_string(), json!(true)),
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_set_bios_attributes_split() {
+        let fake = FakeBmc::start(&bios_resources(SETTINGS, false)).await;
+        fake.standard().set_bios_attributes(attrs()).await.unwrap();
+        let writes = fake.writes();
+        assert_eq!(writes.len(), 2);
+        assert_eq!(writes[0].path, "/redfish/v1/Systems/1/Bios");
+        assert_eq!(writes[0].body, json!({"Attributes": {"LiveAttr": true}}));
+        assert_eq!(writes[1].path, SETTINGS);
+        assert_eq!(writes[1].body, json!({"Attributes": {"StagedAttr": true}}));
+    }
+
+    #[tokio::test]
+    async fn test_set_bios_attributes_read_only_live_resource() {
+        let fake = FakeBmc::start(&bios_resources(SETTINGS, true)).await;
+        fake.standard().set_bios_attributes(attrs()).await.unwrap();
+        let writes = fake.writes();
+        assert_eq!(writes.len(), 1);
+        assert_eq!(writes[0].path, SETTINGS);
+        assert_eq!(
+            writes[0].body,
+            json!({"Attributes": {"LiveAttr": true, "StagedAttr": true}})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_vendor_set_bios_attributes_to_settings_object() {
+        type Vendor = fn(super::RedfishStandard) -> Box<dyn Redfish>;
+        let vendors: [(&str, &str, Vendor); 4] = [
+            ("dell", SETTINGS, |s| {
+                Box::new(crate::dell::Bmc::new(s).unwrap())
+            }),
+            ("hpe", "/redfish/v1/Systems/1/Bios/settings", |s| {
+                Box::new(crate::hpe::Bmc::new(s).unwrap())
+            }),
+            ("lenovo", "/redfish/v1/Systems/1/Bios/Pending", |s| {
+                Box::new(crate::lenovo::Bmc::new(s).unwrap())
+            }),
+            ("viking", "/redfish/v1/Systems/1/Bios/SD", |s| {
+                Box::new(crate::nvidia_viking::Bmc::new(s).unwrap())
+            }),
+        ];
+        for (vendor, settings, bmc) in vendors {
+            let fake = FakeBmc::start(&bios_resources(settings, true)).await;
+            bmc(fake.standard())
+                .set_bios_attributes(attrs())
+                .await
+                .unwrap();
+            let writes = fake.writes();
+            assert_eq!(writes.len(), 1, "{vendor}");
+            assert_eq!(writes[0].path, settings, "{vendor}");
+            assert_eq!(
+                writes[0].body["Attributes"],
+                json!({"LiveAttr": true, "StagedAttr": true}),
+                "{vendor}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_bios_attributes_partially_applied() {
+        let fake = FakeBmc::start(&bios_resources(SETTINGS, false))
+            .await
+            .with_handler(|req, _| (req.path == SETTINGS).then(|| Response::status(500)));
+        let err = fake
+            .standard()
+            .set_bios_attributes(attrs())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            RedfishError::BiosAttributesPartiallyApplied { applied, .. } if applied == ["LiveAttr"]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_action_task_polls_monitor() {
+        let task = |id: &str| {
+            json!({
+                "@odata.id": format!("/redfish/v1/TaskService/Tasks/{id}"),
+                "@odata.type": "#Task.v1_4_3.Task",
+                "Id": id,
+                "TaskState": "Running"
+            })
+        };
+        let fake = FakeBmc::start(&[
+            ("/redfish/v1/TaskService/TaskMonitors/Z5nQ0", task("7")),
+            ("/redfish/v1/TaskService/Tasks/8", task("8")),
+        ])
+        .await
+        .with_handler(|req, _| {
+            req.path
+                .ends_with("/Monitor")
+                .then(|| Response::status(202))
+        });
+        let accepted = |uri: &str| {
+            crate::ActionResponse::Accepted(crate::TaskMonitor {
+                uri: uri.to_string(),
+                task: None,
+            })
+        };
+        let s = fake.standard();
+
+        // The monitor's URI says nothing about the task, its answer does
+        let opaque = accepted("https://127.0.0.1/redfish/v1/TaskService/TaskMonitors/Z5nQ0");
+        assert_eq!(s.action_task("url", opaque).await.unwrap().id, "7");
+        // An empty 202 from the monitor, followed to the task it belongs to
+        let empty = accepted("/redfish/v1/TaskService/Tasks/8/Monitor");
+        assert_eq!(s.action_task("url", empty).await.unwrap().id, "8");
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_task_polls_get_task() {
+        let task = "/redfish/v1/TaskService/Tasks/7";
+        let polls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = polls.clone();
+        let fake = FakeBmc::start::<&str>(&[])
+            .await
+            .with_handler(move |req, _| {
+                (req.path == task).then(|| {
+                    let n = counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    let state = if n < 2 { "Running" } else { "Completed" };
+                    Response::json(json!({
+                        "@odata.id": task,
+                        "@odata.type": "#Task.v1_4_3.Task",
+                        "Id": "7",
+                        "TaskState": state
+                    }))
+                })
+            });
+        let done = fake
+            .standard()
+            .wait_for_task(
+                "7",
+                std::time::Duration::from_secs(10),
+                Some(std::time::Duration::from_millis(1)),
+            )
+            .await
+            .unwrap();
+        assert!(done.is_success());
+        assert_eq!(polls.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_update_firmware_follows_location() {
+        let fake = FakeBmc::start(&[(
+            "/redfish/v1/TaskService/Tasks/3",
+            json!({
+                "@odata.id": "/redfish/v1/TaskService/Tasks/3",
+                "@odata.type": "#Task.v1_4_3.Task",
+                "Id": "3",
+                "TaskState": "New"
+            }),
+        )])
+        .await
+        .with_handler(|req, _| {
+            (req.method == "POST").then(|| {
+                Response::status(202).with_header("Location", "/redfish/v1/TaskService/Tasks/3")
+            })
+        });
+        let path = std::env::temp_dir().join(format!("fw-{}.bin", std::process::id()));
+        std::fs::write(&path, b"image").unwrap();
+        let file = tokio::fs::File::open(&path).await.unwrap();
+        let task = fake.standard().update_firmware(file).await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(task.id, "3");
+        assert_eq!(fake.writes()[0].path, "/redfish/v1/UpdateService");
+    }
+
+    #[tokio::test]
+    async fn test_system_id_resolved_lazily() {
+        let systems = |ids: &[&str]| {
+            json!({
+                "@odata.id": "/redfish/v1/Systems",
+                "@odata.type": "#ComputerSystemCollection.ComputerSystemCollection",
+                "Name": "Computer System Collection",
+                "Members": ids
+                    .iter()
+                    .map(|id| json!({"@odata.id": format!("/redfish/v1/Systems/{id}")}))
+                    .collect::<Vec<_>>()
+            })
+        };
+        let system: serde_json::Value = serde_json::from_str(include_str!(
+            "../tests/mockups/nvidia_gb200/redfish/v1/Systems/System_0/index.json"
+        ))
+        .unwrap();
+        let fake = FakeBmc::start(&[
+            ("/redfish/v1/Systems", systems(&[])),
+            ("/redfish/v1/Systems/System_0", system),
+        ])
+        .await;
+        let s = super::RedfishStandard::new(fake.client());
+        assert_eq!(s.system_id(), "");
+        assert!(fake.requests.lock().unwrap().is_empty());
+
+        // Still empty, e.g. while the host powers on: an error, and nothing is remembered
+        assert!(s.get_systems().await.unwrap().is_empty());
+        assert!(matches!(
+            s.get_system().await,
+            Err(RedfishError::NoSystemsFound)
+        ));
+        assert_eq!(s.system_id(), "");
+
+        fake.resources
+            .lock()
+            .unwrap()
+            .insert("/redfish/v1/Systems".to_string(), systems(&["System_0"]));
+        // A vendor client cloned before the lookup shares its result
+        let vendor = s.clone();
+        assert_eq!(s.get_system().await.unwrap().id, "System_0");
+        assert_eq!(vendor.system_id(), "System_0");
+    }
+
+    #[tokio::test]
+    async fn test_power_thermal_follow_links() {
+        let fake = FakeBmc::start(&[
+            (
+                "/redfish/v1/Chassis/1",
+                json!({
+                    "@odata.id": "/redfish/v1/Chassis/1",
+                    "Id": "1",
+                    "Power": {"@odata.id": "/redfish/v1/Chassis/1/Oem/Power"},
+                    "Thermal": {"@odata.id": "/redfish/v1/Chassis/1/Oem/Thermal"}
+                }),
+            ),
+            (
+                "/redfish/v1/Chassis/1/Oem/Power",
+                json!({"Id": "Power", "Name": "Power", "PowerControl": []}),
+            ),
+            (
+                "/redfish/v1/Chassis/1/Oem/Thermal",
+                json!({
+                    "@odata.id": "/redfish/v1/Chassis/1/Oem/Thermal",
+                    "@odata.type": "#Thermal.v1_7_0.Thermal",
+                    "Id": "Thermal",
+                    "Name": "Thermal",
+                    "Fans": [],
+                    "Temperatures": []
+                }),
+            ),
+        ])
+        .await;
+        let s = fake.standard();
+        assert_eq!(s.get_power_metrics().await.unwrap().id, "Power");
+        assert_eq!(s.get_thermal_metrics().await.unwrap().id, "Thermal");
+    }
+
+    #[tokio::test]
+    async fn test_get_sensors_follows_pages() {
+        let sensors = "/redfish/v1/Chassis/1/Sensors";
+        let link = |id: &str| json!({"@odata.id": format!("{sensors}/{id}")});
+        let mut resources = vec![
+            (
+                "/redfish/v1/Chassis/1".to_string(),
+                json!({
+                    "@odata.id": "/redfish/v1/Chassis/1",
+                    "Id": "1",
+                    "Sensors": {"@odata.id": sensors}
+                }),
+            ),
+            (
+                sensors.to_string(),
+                json!({
+                    "@odata.id": sensors,
+                    "@odata.type": "#SensorCollection.SensorCollection",
+                    "Name": "Sensors",
+                    "Members": [link("Temp_0"), link("Temp_1")],
+                    "Members@odata.nextLink": format!("{sensors}?$skip=2"),
+                }),
+            ),
+        ];
+        for id in ["Temp_0", "Temp_1", "Temp_2"] {
+            resources.push((
+                format!("{sensors}/{id}"),
+                json!({
+                    "@odata.id": format!("{sensors}/{id}"),
+                    "@odata.type": "#Sensor.v1_2_0.Sensor",
+                    "Id": id,
+                    "ReadingType": "Temperature",
+                    "Reading": 40.0
+                }),
+            ));
+        }
+        let second = json!({"Members": [link("Temp_2")]});
+        let fake = FakeBmc::start(&resources)
+            .await
+            .with_handler(move |req, _| {
+                (req.query.as_deref() == Some("$skip=2")).then(|| Response::json(second.clone()))
+            });
+        let got = fake.standard().get_sensors("1", None).await.unwrap();
+        let ids: Vec<_> = got.iter().filter_map(|s| s.id.clone()).collect();
+        assert_eq!(ids, ["Temp_0", "Temp_1", "Temp_2"]);
+    }
+
+    #[tokio::test]
+    async fn test_change_boot_order_detects_target() {
+        let mut system: serde_json::Value = serde_json::from_str(include_str!(
+            "../tests/mockups/nvidia_gb200/redfish/v1/Systems/System_0/index.json"
+        ))
+        .unwrap();
+        system["@odata.id"] = json!("/redfish/v1/Systems/1");
+        system["@Redfish.Settings"]["SettingsObject"]["@odata.id"] =
+            json!("/redfish/v1/Systems/1/Settings");
+        system["Boot"]["BootOrder"] = json!(["Boot0003", "Boot0000", "Boot0001"]);
+        let order = || vec!["Boot0001".to_string()];
+        let full = json!({"Boot": {"BootOrder": ["Boot0001", "Boot0003", "Boot0000"]}});
+
+        // Advertises a settings object: the full order goes there
+        let fake = FakeBmc::start(&[
+            ("/redfish/v1/Systems/1", system.clone()),
+            ("/redfish/v1/Systems/1/Settings", json!({})),
+        ])
+        .await;
+        fake.standard().change_boot_order(order()).await.unwrap();
+        let writes = fake.writes();
+        assert_eq!(writes.len(), 1);
+        assert_eq!(writes[0].path, "/redfish/v1/Systems/1/Settings");
+        assert_eq!(writes[0].body, full);
+
+        // No settings object, e.g. Dell: the live system
+        system.as_object_mut().unwrap().remove("@Redfish.Settings");
+        let fake = FakeBmc::start(&[("/redfish/v1/Systems/1", system)]).await;
+        fake.standard().change_boot_order(order()).await.unwrap();
+        let writes = fake.writes();
+        assert_eq!(writes.len(), 1);
+        assert_eq!(writes[0].path, "/redfish/v1/Systems/1");
+        assert_eq!(writes[0].body, full);
+    }
+
+    #[tokio::test]
+    async fn test_bmc_journal_filters_and_pages() {
+        let entries = "/redfish/v1/Managers/BMC/LogServices/Journal/Entries";
+        let entry = |id: &str, created: &str| {
+            json!({
+                "@odata.id": format!("{entries}/{id}"),
+                "@odata.type": "#LogEntry.v1_9_0.LogEntry",
+                "Id": id,
+                "Name": "BMC Journal Entry",
+                "Created": created,
+                "Message": format!("entry {id}"),
+                "EntryType": "Oem",
+                "Severity": "OK",
+            })
+        };
+        let page = |members: Vec<serde_json::Value>, next: Option<String>| {
+            let mut page = json!({
+                "@odata.id": entries,
+                "@odata.type": "#LogEntryCollection.LogEntryCollection",
+                "Name": "Open BMC Journal Entries",
+                "Description": "Collection of BMC Journal Entries",
+                "Members": members,
+            });
+            if let Some(next) = next {
+                page["Members@odata.nextLink"] = json!(next);
+            }
+            page
+        };
+        let first = page(
+            vec![
+                entry("A", "2024-02-29T09:00:00+00:00"),
+                entry("B", "2024-02-29T10:00:01+00:00"),
+            ],
+            Some(format!("{entries}?$skip=2")),
+        );
+        let second = page(vec![entry("C", "2024-02-29T10:00:05+00:00")], None);
+        // Ignores $filter, as some BMCs do despite advertising it
+        let fake = FakeBmc::start(&[(entries, first)])
+            .await
+            .with_handler(move |req, _| {
+                (req.query.as_deref() == Some("$skip=2")).then(|| Response::json(second.clone()))
+            });
+        let mut s = fake.standard();
+        s.set_service_root(crate::model::service_root::ServiceRoot {
+            protocol_features_supported: Some(
+                crate::model::service_root::ProtocolFeaturesSupported {
+                    filter_query: Some(true),
+                    ..Default::default()
+                },
+            ),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let since = "2024-02-29T10:00:00Z".parse().unwrap();
+        let lines = s.get_bmc_journal(Some(since)).await.unwrap();
+        assert_eq!(
+            lines,
+            [
+                "2024-02-29T10:00:01+00:00 entry B",
+                "2024-02-29T10:00:05+00:00 entry C"
+            ]
+        );
+        let requests = fake.requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[0].query.as_deref(),
+            Some("$filter=Created%20ge%20%272024-02-29T10%3A00%3A00Z%27")
+        );
+    }
+
+    #[test]
+    fn test_changed_only() {
+        let current = serde_json::json!({
+            "@odata.id": "/redfish/v1/Systems/1/Settings",
+            "Boot": {
+                "BootOrder": ["Boot0001", "Boot0002"],
+                "BootOrder@Redfish.AllowableValues": ["Boot0001", "Boot0002"],
+                "BootOptions": {"@odata.id": "/redfish/v1/Systems/1/BootOptions"},
+                "BootSourceOverrideEnabled": "Disabled",
+                "Oem": {"Vendor": {"BootModeLock": true}}
+            },
+            "AssetTag": "rack7"
+        });
+        let body = changed_only(
+            &current,
+            &serde_json::json!({"Boot": {"BootOrder": ["Boot0002", "Boot0001"]}}),
+        )
+        .unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({"Boot": {"BootOrder": ["Boot0002", "Boot0001"]}})
+        );
+        // Already set
+        assert_eq!(
+            changed_only(
+                &current,
+                &serde_json::json!({
+                    "Boot": {"BootOrder": ["Boot0001", "Boot0002"]},
+                    "AssetTag": "rack7"
+                })
+            ),
+            None
+        );
+        assert_eq!(
+            changed_only(
+                &current,
+                &serde_json::json!({"AssetTag": "rack8", "Boot": {"BootSourceOverrideEnabled": "Disabled"}})
+            ),
+            Some(serde_json::json!({"AssetTag": "rack8"}))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_patch_preserving_sends_changed_keys_with_etag() {
+        let url = "/redfish/v1/Systems/1/Settings";
+        let fake = FakeBmc::start(&[(
+            url,
+            json!({
+                "@odata.id": url,
+                "@odata.etag": "\"7\"",
+                "Boot": {
+                    "BootOrder": ["Boot0001", "Boot0002"],
+                    "BootOrder@Redfish.AllowableValues": ["Boot0001", "Boot0002"],
+                    "BootOptions": {"@odata.id": "/redfish/v1/Systems/1/BootOptions"},
+                    "BootSourceOverrideEnabled": "Disabled",
+                    "Oem": {"Vendor": {"BootModeLock": true}}
+                }
+            }),
+        )])
+        .await;
+        let s = fake.standard();
+        let changes = json!({"Boot": {"BootOrder": ["Boot0002", "Boot0001"]}});
+        s.patch_preserving("Systems/1/Settings", changes.clone())
+            .await
+            .unwrap();
+        let writes = fake.writes();
+        assert_eq!(writes.len(), 1);
+        assert_eq!(writes[0].body, changes);
+        assert_eq!(writes[0].headers.get("if-match").unwrap(), "\"7\"");
+        let stored = fake.resource(url).unwrap();
+        assert_eq!(
+            stored["Boot"]["Oem"],
+            json!({"Vendor": {"BootModeLock": true}})
+        );
+
+        // Nothing to change, nothing sent
+        s.patch_preserving("Systems/1/Settings", changes)
+            .await
+            .unwrap();
+        assert_eq!(fake.writes().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_create_boot_option_reads_location() {
+        let option = "/redfish/v1/Systems/1/BootOptions/Boot0007";
+        let fake = FakeBmc::start(&[(
+            option,
+            json!({
+                "@odata.id": option,
+                "@odata.type": "#BootOption.v1_0_4.BootOption",
+                "BootOptionReference": "Boot0007",
+                "DisplayName": "Installer",
+                "Id": "Boot0007",
+                "Name": "Boot Option"
+            }),
+        )])
+        .await
+        .with_handler(|req, _| {
+            (req.method == "POST").then(|| {
+                Response::status(201).with_header(
+                    "Location",
+                    "https://10.0.0.1/redfish/v1/Systems/1/BootOptions/Boot0007",
+                )
+            })
+        });
+        let id = fake
+            .standard()
+            .create_boot_option(NewBootOption {
+                boot_option_reference: "Boot0007".to_string(),
+                display_name: "Installer".to_string(),
+                uefi_device_path: "Uri(http://10.0.0.1/ipxe.efi)".to_string(),
+                boot_option_enabled: true,
+            })
+            .await
+            .unwrap();
+        assert_eq!(id, "Boot0007");
+        assert!(fake
+            .requests
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|r| r.method == "GET" && r.path == option));
+    }
+
+    #[tokio::test]
+    async fn test_get_fru_inventory() {
+        let cpu = |id: &str| {
+            (
+                format!("/redfish/v1/Systems/1/Processors/{id}"),
+                json!({"Id": id, "Model": "Grace", "PartNumber": format!("PN-{id}")}),
+            )
+        };
+        let fake = FakeBmc::start(&[
+            (
+                "/redfish/v1/Systems/1".to_string(),
+                json!({"Id": "1", "SerialNumber": "SYS123"}),
+            ),
+            (
+                "/redfish/v1/Systems/1/Processors".to_string(),
+                json!({"Members": [
+                    {"@odata.id": "/redfish/v1/Systems/1/Processors/CPU_0"},
+                    {"@odata.id": "/redfish/v1/Systems/1/Processors/CPU_1"}
+                ]}),
+            ),
+            cpu("CPU_0"),
+            cpu("CPU_1"),
+            ("/redfish/v1/Chassis".to_string(), json!({"Members": []})),
+        ])
+        .await;
+        let fru = fake.standard().get_fru_inventory().await.unwrap();
+        assert_eq!(fru.systems[0].serial_number.as_deref(), Some("SYS123"));
+        let parts: Vec<_> = fru
+            .processors
+            .iter()
+            .map(|p| p.part_number.as_deref().unwrap())
+            .collect();
+        assert_eq!(parts, ["PN-CPU_0", "PN-CPU_1"]);
+        assert!(fru.memory.is_empty());
+        assert!(fru.chassis.is_empty());
+    }
+
+    #[test]
+    fn test_inventory_ordering_is_stable() {
+        let expected = [
+            "BMC_Firmware",
+            "CPLD_0",
+            "HGX_FW_GPU_SXM_1",
+            "HGX_FW_GPU_SXM_2",
+            "HGX_FW_GPU_SXM_10",
+            "PSU_1",
+            "PSU_02",
+            "PSU_2",
+            "PSU_10",
+            "UEFI",
+        ];
+        let mut reversed: Vec<&str> = expected.iter().rev().copied().collect();
+        reversed.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(reversed, expected);
+
+        let mut shuffled = vec![
+            "PSU_10",
+            "UEFI",
+            "PSU_2",
+            "HGX_FW_GPU_SXM_2",
+            "BMC_Firmware",
+            "PSU_02",
+            "HGX_FW_GPU_SXM_10",
+            "CPLD_0",
+            "PSU_1",
+            "HGX_FW_GPU_SXM_1",
+        ];
+        shuffled.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(shuffled, expected);
+    }
+}
@@ -29,7 +29,7 @@ use tokio::fs::File;
 use crate::{
     model::{
         account_service::ManagerAccount,
-        boot,
+        boot::{self, BootOrderTarget},
         certificate::Certificate,
         chassis::{Assembly, Chassis, NetworkAdapter},
         component_integrity::ComponentIntegrities,
@@ -52,8 +52,8 @@ use crate::{
     },
     standard::RedfishStandard,
     BiosProfileType, Boot, BootOptions, Collection, EnabledDisabled, JobState, MachineSetupDiff,
-    MachineSetupStatus, ODataId, PCIeDevice, PowerState, Redfish, RedfishError, Resource, RoleId,
-    Status, StatusInternal, SystemPowerControl,
+    MachineSetupStatus, NewBootOption, ODataId, PCIeDevice, PowerState, Redfish, RedfishError,
+    Resource, RoleId, SecretString, Status, StatusInternal, SystemPowerControl,
 };
 
 const MELLANOX_UEFI_HTTP_IPV4: &str = "UEFI HTTP IPv4 Mellanox Network Adapter";
@@ -73,10 +73,14 @@ impl Bmc {
 
 #[async_trait::async_trait]
 impl Redfish for Bmc {
+    fn standard(&self) -> &RedfishStandard {
+        &self.s
+    }
+
     async fn create_user(
         &self,
         username: &str,
-        password: &str,
+        password: &SecretString,
         role_id: RoleId,
     ) -> Result<(), RedfishError> {
         self.s.create_user(username, password, role_id).await
@@ -93,7 +97,7 @@ impl Redfish for Bmc {
     async fn change_password(
         &self,
         username: &str,
-        new_password: &str,
+        new_password: &SecretString,
     ) -> Result<(), RedfishError> {
         self.s.change_password(username, new_password).await
     }
@@ -101,7 +105,7 @@ impl Redfish for Bmc {
     async fn change_password_by_id(
         &self,
         account_id: &str,
-        new_pass: &str,
+        new_pass: &SecretString,
     ) -> Result<(), RedfishError> {
         self.s.change_password_by_id(account_id, new_pass).await
     }
@@ -124,7 +128,7 @@ impl Redfish for Bmc {
                 HashMap::from([("ResetType".to_string(), "ACCycle".to_string())]);
             let url = format!(
                 "Systems/{}/Actions/Oem/OemSystemExtensions.Reset",
-                self.s.system_id()
+                self.s.resolve_system_id().await?
             );
             return self.s.client.post(&url, args).await.map(|_status_code| ());
         }
@@ -135,6 +139,22 @@ impl Redfish for Bmc {
         true
     }
 
+    fn supported_features(&self) -> crate::FeatureSet {
+        crate::FeatureSet {
+            power_metrics: true,
+            thermal_metrics: true,
+            reset_bios: true,
+            lockdown: true,
+            serial_console: true,
+            secure_boot: true,
+            system_ethernet_interfaces: true,
+            chassis_network_adapters: true,
+            component_integrity: true,
+            ipmi_over_lan: true,
+            ..Default::default()
+        }
+    }
+
     async fn bmc_reset(&self) -> Result<(), RedfishError> {
         self.s.bmc_reset().await
     }
@@ -159,6 +179,14 @@ impl Redfish for Bmc {
         self.s.get_system_event_log().await
     }
 
+    async fn get_system_event_log_since(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        limit: Option<usize>,
+    ) -> Result<Vec<LogEntry>, RedfishError> {
+        self.s.get_system_event_log_since(since, limit).await
+    }
+
     async fn get_bmc_event_log(
         &self,
         from: Option<chrono::DateTime<chrono::Utc>>,
@@ -181,7 +209,7 @@ impl Redfish for Bmc {
         self.s.set_bios(values).await
     }
 
-    async fn reset_bios(&self) -> Result<(), RedfishError> {
+    async fn reset_bios(&self) -> Result<crate::ApplyResult, RedfishError> {
         self.s.factory_reset_bios().await
     }
 
@@ -190,21 +218,36 @@ impl Redfish for Bmc {
     /// but it won't show until after reboot so that step will fail on first time through.
     async fn machine_setup(
         &self,
-        _boot_interface_mac: Option<&str>,
+        boot_interface_mac: Option<&str>,
         _bios_profiles: &HashMap<
             RedfishVendor,
             HashMap<String, HashMap<BiosProfileType, HashMap<String, serde_json::Value>>>,
         >,
         _selected_profile: BiosProfileType,
-    ) -> Result<(), RedfishError> {
-        self.setup_serial_console().await?;
+    ) -> Result<crate::MachineSetupReport, RedfishError> {
+        let status = crate::MachineSetupStatus::or_apply_all(
+            self.machine_setup_status(boot_interface_mac).await,
+        );
+        if status.as_ref().is_some_and(|s| !s.needs_setup()) {
+            return Ok(crate::MachineSetupReport::skipped(&[
+                "setup_serial_console",
+                "set_bios_attributes",
+            ]));
+        }
+
+        let mut report = crate::MachineSetupReport::default();
+        report.record_change("setup_serial_console", self.setup_serial_console().await)?;
 
         let bios_attrs = self.machine_setup_attrs().await?;
         let mut attrs = HashMap::new();
         attrs.extend(bios_attrs);
         let body = HashMap::from([("Attributes", attrs)]);
-        let url = format!("Systems/{}/Bios", self.s.system_id());
-        self.s.client.patch(&url, body).await.map(|_status_code| ())
+        let url = format!("Systems/{}/Bios", self.s.resolve_system_id().await?);
+        report.record(
+            "set_bios_attributes",
+            self.s.client.patch(&url, body).await.map(|_status_code| ()),
+        )?;
+        Ok(report)
     }
 
     async fn machine_setup_status(
@@ -324,18 +367,14 @@ impl Redfish for Bmc {
 
     /// On Supermicro this does nothing. Serial Console is on by default and can't be disabled
     /// or enabled via redfish. The properties under Systems/1, key SerialConsole are read only.
-    async fn setup_serial_console(&self) -> Result<(), RedfishError> {
-        Ok(())
+    async fn setup_serial_console(&self) -> Result<bool, RedfishError> {
+        Ok(false)
     }
 
     async fn serial_console_status(&self) -> Result<Status, RedfishError> {
         let s_interface = self.s.get_serial_interface().await?;
         let system = self.s.get_system().await?;
-        let Some(sr) = &system.serial_console else {
-            return Err(RedfishError::NotSupported(
-                "No SerialConsole in System object. Maybe it's in Manager and you have old firmware?".to_string(),
-            ));
-        };
+        let sr = system.require_serial_console()?;
         let is_enabled = sr.ssh.service_enabled
             && sr.max_concurrent_sessions != 0
             && s_interface.is_supermicro_default();
@@ -350,6 +389,20 @@ impl Redfish for Bmc {
         })
     }
 
+    /// Supermicro's serial interface is read only, see SerialInterface::is_supermicro_default.
+    /// This only succeeds if it's already set as asked.
+    async fn set_serial_interface_config(
+        &self,
+        config: crate::model::serial_interface::SerialInterfaceConfig,
+    ) -> Result<(), RedfishError> {
+        if self.get_serial_interface_config().await?.satisfies(&config) {
+            return Ok(());
+        }
+        Err(RedfishError::NotSupported(
+            "set_serial_interface_config: Supermicro serial interface is read only".to_string(),
+        ))
+    }
+
     async fn get_boot_options(&self) -> Result<BootOptions, RedfishError> {
         self.s.get_boot_options().await
     }
@@ -377,7 +430,7 @@ impl Redfish for Bmc {
 
     /// Supermicro BMC does not appear to have this.
     /// TODO: Verify that this really clear the TPM.
-    async fn clear_tpm(&self) -> Result<(), RedfishError> {
+    async fn clear_tpm(&self) -> Result<crate::ApplyResult, RedfishError> {
         let bios_attrs = self.s.bios_attributes().await?;
         let Some(attrs_map) = bios_attrs.as_object() else {
             return Err(RedfishError::InvalidKeyType {
@@ -395,12 +448,14 @@ impl Redfish for Bmc {
         };
 
         let body = HashMap::from([("Attributes", HashMap::from([(name, "TPM Clear")]))]);
-        let url = format!("Systems/{}/Bios", self.s.system_id());
-        self.s.client.patch(&url, body).await.map(|_status_code| ())
+        let url = format!("Systems/{}/Bios", self.s.resolve_system_id().await?);
+        // Written to the live resource, the BMC says whether the operation waits for the next POST
+        let (_status_code, info) = self.s.client.patch_with_response(&url, body).await?;
+        Ok(crate::ApplyResult::from_extended_info(info, &url))
     }
 
     async fn pending(&self) -> Result<HashMap<String, serde_json::Value>, RedfishError> {
-        let url = format!("Systems/{}/Bios/SD", self.s.system_id());
+        let url = format!("Systems/{}/Bios/SD", self.s.resolve_system_id().await?);
         // Supermicro doesn't include the Attributes key if there are no pending changes
         self.s
             .pending_attributes(&url)
@@ -417,8 +472,8 @@ impl Redfish for Bmc {
 
     // TODO: This resets the pending Bios changes to their default values,
     // but DOES NOT CLEAR THEM. We don't know how to do that, or if Supermicro supports it at all.
-    async fn clear_pending(&self) -> Result<(), RedfishError> {
-        let url = format!("Systems/{}/Bios/SD", self.s.system_id());
+    async fn clear_pending(&self) -> Result<crate::ClearPendingResult, RedfishError> {
+        let url = format!("Systems/{}/Bios/SD", self.s.resolve_system_id().await?);
         self.s.clear_pending_with_url(&url).await
     }
 
@@ -469,7 +524,7 @@ impl Redfish for Bmc {
                     source: e,
                 }
             })?;
-        let (_status_code, _loc, body) = self
+        let response = self
             .s
             .client
             .req_update_firmware_multipart(
@@ -482,14 +537,7 @@ impl Redfish for Bmc {
             )
             .await?;
 
-        let task: Task =
-            serde_json::from_str(&body).map_err(|e| RedfishError::JsonDeserializeError {
-                url: update_service.multipart_http_push_uri,
-                body,
-                source: e,
-            })?;
-
-        Ok(task.id)
+        response.task_id(&update_service.multipart_http_push_uri)
     }
 
     async fn get_update_service(&self) -> Result<UpdateService, RedfishError> {
@@ -656,18 +704,21 @@ impl Redfish for Bmc {
 
     async fn change_uefi_password(
         &self,
-        current_uefi_password: &str,
-        new_uefi_password: &str,
-    ) -> Result<Option<String>, RedfishError> {
+        current_uefi_password: &SecretString,
+        new_uefi_password: &SecretString,
+    ) -> Result<crate::BiosPasswordChange, RedfishError> {
         self.s
             .change_uefi_password(current_uefi_password, new_uefi_password)
             .await
     }
 
     async fn change_boot_order(&self, boot_array: Vec<String>) -> Result<(), RedfishError> {
-        let body = HashMap::from([("Boot", HashMap::from([("BootOrder", boot_array)]))]);
-        let url = format!("Systems/{}", self.s.system_id());
-        self.s.client.patch(&url, body).await.map(|_status_code| ())
+        // The live system takes the change directly, a partial order is accepted
+        let target = BootOrderTarget {
+            url: format!("Systems/{}", self.s.resolve_system_id().await?),
+            full_order: false,
+        };
+        self.s.change_boot_order_at(&target, boot_array).await
     }
 
     async fn get_service_root(&self) -> Result<ServiceRoot, RedfishError> {
@@ -773,7 +824,7 @@ impl Redfish for Bmc {
 
         let url = format!(
             "Systems/{}/Oem/Supermicro/FixedBootOrder",
-            self.s.system_id()
+            self.s.resolve_system_id().await?
         );
         let body = HashMap::from([
             ("FixedBootOrder", order),
@@ -792,17 +843,36 @@ impl Redfish for Bmc {
 
     async fn clear_uefi_password(
         &self,
-        current_uefi_password: &str,
+        current_uefi_password: &SecretString,
     ) -> Result<Option<String>, RedfishError> {
-        self.change_uefi_password(current_uefi_password, "").await
+        self.change_uefi_password(current_uefi_password, &SecretString::default())
+            .await?
+            .job_id()
     }
 
     async fn get_base_mac_address(&self) -> Result<Option<String>, RedfishError> {
         self.s.get_base_mac_address().await
     }
 
-    async fn lockdown_bmc(&self, target: crate::EnabledDisabled) -> Result<(), RedfishError> {
-        self.set_syslockdown(target).await
+    async fn lockdown_bmc(&self, target: crate::EnabledDisabled) -> Result<bool, RedfishError> {
+        // If we can't tell, set it anyway
+        if matches!(self.bmc_lockdown_status().await, Ok(status) if status.is_fully(target)) {
+            return Ok(false);
+        }
+        self.set_syslockdown(target).await?;
+        Ok(true)
+    }
+
+    async fn bmc_lockdown_status(&self) -> Result<Status, RedfishError> {
+        let is_syslockdown = self.get_syslockdown().await?;
+        Ok(Status {
+            message: format!("SysLockdownEnabled={is_syslockdown}"),
+            status: if is_syslockdown {
+                StatusInternal::Enabled
+            } else {
+                StatusInternal::Disabled
+            },
+        })
     }
 
     async fn is_ipmi_over_lan_enabled(&self) -> Result<bool, RedfishError> {
@@ -812,7 +882,7 @@ impl Redfish for Bmc {
     async fn enable_ipmi_over_lan(
         &self,
         target: crate::EnabledDisabled,
-    ) -> Result<(), RedfishError> {
+    ) -> Result<bool, RedfishError> {
         self.s.enable_ipmi_over_lan(target).await
     }
 
@@ -827,11 +897,11 @@ impl Redfish for Bmc {
             .await
     }
 
-    async fn enable_rshim_bmc(&self) -> Result<(), RedfishError> {
+    async fn enable_rshim_bmc(&self) -> Result<bool, RedfishError> {
         self.s.enable_rshim_bmc().await
     }
 
-    async fn clear_nvram(&self) -> Result<(), RedfishError> {
+    async fn clear_nvram(&self) -> Result<crate::ApplyResult, RedfishError> {
         self.s.clear_nvram().await
     }
 
@@ -931,6 +1001,226 @@ impl Redfish for Bmc {
     ) -> Result<crate::model::component_integrity::Evidence, RedfishError> {
         self.s.get_evidence(url).await
     }
+
+    async fn reset_secure_boot_keys(
+        &self,
+        reset_type: crate::model::secure_boot::SecureBootResetType,
+    ) -> Result<(), RedfishError> {
+        self.s.reset_secure_boot_keys(reset_type).await
+    }
+
+    async fn get_boot_progress(&self) -> Result<crate::model::system::BootProgress, RedfishError> {
+        self.s.get_boot_progress().await
+    }
+
+    async fn get_post_codes(&self) -> Result<Vec<LogEntry>, RedfishError> {
+        self.s.get_post_codes().await
+    }
+
+    async fn get_environment_metrics(
+        &self,
+        chassis_id: &str,
+    ) -> Result<crate::model::environment_metrics::EnvironmentMetrics, RedfishError> {
+        self.s.get_environment_metrics(chassis_id).await
+    }
+
+    async fn get_energy_kwh(
+        &self,
+    ) -> Result<Vec<crate::model::sensor::EnergyReading>, RedfishError> {
+        self.s.get_energy_kwh().await
+    }
+
+    async fn get_sensors(
+        &self,
+        chassis_id: &str,
+        reading_type: Option<crate::model::sensor::ReadingType>,
+    ) -> Result<Vec<crate::model::sensor::Sensor>, RedfishError> {
+        self.s.get_sensors(chassis_id, reading_type).await
+    }
+
+    async fn wait_for_power_state(
+        &self,
+        target: crate::PowerState,
+        timeout: std::time::Duration,
+        poll: std::time::Duration,
+    ) -> Result<(), RedfishError> {
+        self.s.wait_for_power_state(target, timeout, poll).await
+    }
+
+    async fn pending_diff(&self) -> Result<Vec<crate::model::BiosAttrChange>, RedfishError> {
+        let pending = self.pending().await?;
+        self.s.bios_attr_changes(pending).await
+    }
+
+    async fn set_bios_attributes(
+        &self,
+        mut attrs: HashMap<String, serde_json::Value>,
+    ) -> Result<(), RedfishError> {
+        self.s.prepare_bios_attributes(&mut attrs).await?;
+        // Supermicro has no Settings resource, a PATCH of Bios is held until the next reset
+        // whenever the attribute needs one
+        let url = format!("Systems/{}/Bios", self.s.resolve_system_id().await?);
+        let body = HashMap::from([("Attributes", attrs)]);
+        self.s.client.patch(&url, body).await.map(|_status_code| ())
+    }
+
+    async fn set_uefi_http(
+        &self,
+        cfg: crate::model::oem::nvidia_viking::UefiHttpAttributes,
+    ) -> Result<(), RedfishError> {
+        self.s.set_uefi_http(cfg).await
+    }
+
+    async fn set_virtualization(
+        &self,
+        cfg: crate::model::oem::nvidia_viking::VirtAttributes,
+    ) -> Result<(), RedfishError> {
+        self.s.set_virtualization(cfg).await
+    }
+
+    async fn get_bios_lockdown(
+        &self,
+    ) -> Result<crate::model::oem::nvidia_viking::BiosLockdownAttributes, RedfishError> {
+        self.s.get_bios_lockdown().await
+    }
+
+    async fn get_decoded_sel(
+        &self,
+    ) -> Result<Vec<crate::model::sel::DecodedSelEntry>, RedfishError> {
+        let sel = self.get_system_event_log().await?;
+        Ok(sel
+            .iter()
+            .map(crate::model::sel::DecodedSelEntry::from)
+            .collect())
+    }
+
+    async fn resolve_origin(
+        &self,
+        entry: &crate::model::sel::LogEntry,
+    ) -> Result<crate::model::resource::Resource, RedfishError> {
+        self.s.resolve_origin(entry).await
+    }
+
+    async fn attach_to_update(
+        &self,
+        task_id: &str,
+    ) -> Result<crate::model::task::Task, RedfishError> {
+        self.s.attach_to_update(task_id).await
+    }
+
+    async fn get_port_metrics(
+        &self,
+        chassis_id: &str,
+        network_adapter: &str,
+        id: &str,
+    ) -> Result<crate::model::port::PortMetrics, RedfishError> {
+        self.s
+            .get_port_metrics(chassis_id, network_adapter, id)
+            .await
+    }
+
+    async fn get_fru_inventory(&self) -> Result<crate::model::fru::FruInventory, RedfishError> {
+        self.s.get_fru_inventory().await
+    }
+
+    async fn get_power_supplies(
+        &self,
+    ) -> Result<Vec<crate::model::power::PowerSupplyInfo>, RedfishError> {
+        self.s.get_power_supplies().await
+    }
+
+    async fn download_firmware_image(
+        &self,
+        inventory_id: &str,
+        out: &std::path::Path,
+    ) -> Result<(), RedfishError> {
+        self.s.download_firmware_image(inventory_id, out).await
+    }
+
+    async fn create_boot_option(&self, opt: NewBootOption) -> Result<String, RedfishError> {
+        self.s.create_boot_option(opt).await
+    }
+
+    async fn delete_boot_option(&self, option_id: &str) -> Result<(), RedfishError> {
+        self.s.delete_boot_option_for(self, option_id).await
+    }
+
+    async fn get_bios_prerequisites(
+        &self,
+        attrs: &HashMap<String, serde_json::Value>,
+    ) -> Result<HashMap<String, serde_json::Value>, RedfishError> {
+        self.s.get_bios_prerequisites(attrs).await
+    }
+
+    async fn get_bmc_journal(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<String>, RedfishError> {
+        self.s.get_bmc_journal(since).await
+    }
+
+    async fn get_all_metrics(&self) -> Result<crate::AllMetrics, RedfishError> {
+        crate::AllMetrics::gather(self).await
+    }
+
+    async fn get_power_restore_policy(&self) -> Result<crate::PowerRestorePolicy, RedfishError> {
+        self.s.get_power_restore_policy().await
+    }
+
+    async fn set_power_restore_policy(
+        &self,
+        policy: crate::PowerRestorePolicy,
+    ) -> Result<(), RedfishError> {
+        self.s.set_power_restore_policy(policy).await
+    }
+
+    async fn set_power_on_delay(&self, seconds: u32) -> Result<(), RedfishError> {
+        self.s.set_power_on_delay(seconds).await
+    }
+
+    async fn get_manager_health(&self) -> Result<crate::model::ManagerHealth, RedfishError> {
+        self.s.get_manager_health().await
+    }
+
+    async fn get_active_conditions(&self) -> Result<Vec<crate::model::Condition>, RedfishError> {
+        self.s.get_active_conditions().await
+    }
+
+    async fn get_fabric_topology(
+        &self,
+    ) -> Result<Vec<crate::model::fabric::FabricTopology>, RedfishError> {
+        self.s.get_fabric_topology().await
+    }
+
+    async fn get_pcie_switches(
+        &self,
+        chassis_id: &str,
+    ) -> Result<Vec<crate::model::fabric::SwitchPorts>, RedfishError> {
+        self.s.get_pcie_switches(chassis_id).await
+    }
+
+    async fn reset_erot(&self, target: ComponentType) -> Result<(), RedfishError> {
+        self.s.reset_erot(target).await
+    }
+
+    async fn get_firmware_slots(
+        &self,
+        component: &str,
+    ) -> Result<Vec<crate::model::software_inventory::FirmwareSlot>, RedfishError> {
+        self.s.get_firmware_slots(component).await
+    }
+
+    async fn activate_firmware_slot(
+        &self,
+        component: &str,
+        slot: &str,
+    ) -> Result<(), RedfishError> {
+        self.s.activate_firmware_slot(component, slot).await
+    }
+
+    async fn get_pending_jobs(&self) -> Result<Vec<crate::model::job::Job>, RedfishError> {
+        self.s.get_pending_jobs().await
+    }
 }
 
 impl Bmc {
@@ -1250,7 +1540,7 @@ impl Bmc {
     }
 
     async fn set_boot_override(&self, target: Boot, once: bool) -> Result<(), RedfishError> {
-        let url = format!("Systems/{}", self.s.system_id());
+        let url = format!("Systems/{}", self.s.resolve_system_id().await?);
         let boot = boot::Boot {
             boot_source_override_target: Some(match target {
                 // In UEFI mode Pxe gets converted to UefiBootNext, but it won't accept
@@ -1276,7 +1566,7 @@ impl Bmc {
     async fn get_boot_order(&self) -> Result<FixedBootOrder, RedfishError> {
         let url = format!(
             "Systems/{}/Oem/Supermicro/FixedBootOrder",
-            self.s.system_id()
+            self.s.resolve_system_id().await?
         );
         let (_, fbo) = self.s.client.get(&url).await?;
         Ok(fbo)
@@ -1326,7 +1616,7 @@ impl Bmc {
 
         let url = format!(
             "Systems/{}/Oem/Supermicro/FixedBootOrder",
-            self.s.system_id()
+            self.s.resolve_system_id().await?
         );
         let body = HashMap::from([
             ("FixedBootOrder", order),
@@ -1364,7 +1654,7 @@ impl Bmc {
 
             if (boot_option.display_name.contains(MELLANOX_UEFI_HTTP_IPV4)
                 || boot_option.display_name.contains(NVIDIA_UEFI_HTTP_IPV4))
-                && boot_option.display_name.contains(boot_interface)
+                && boot_option.matches_mac(boot_interface)
             {
                 // Here are the patterns we have seen so far:
                 // UEFI HTTP IPv4 Mellanox Network Adapter - A0:88:C2:EA:84:D0(MAC:A088C2EA84D0)
@@ -1479,3 +1769,60 @@ impl UpdateParameters {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::Bmc;
+    use crate::fake_bmc::FakeBmc;
+    use crate::Redfish;
+
+    #[tokio::test]
+    async fn test_change_boot_order_partial_to_system() {
+        let fake = FakeBmc::start(&[(
+            "/redfish/v1/Systems/1",
+            json!({"Boot": {"BootOrder": ["Boot0003", "Boot0001"]}}),
+        )])
+        .await;
+        let bmc = Bmc::new(fake.standard()).unwrap();
+        bmc.change_boot_order(vec!["Boot0001".to_string()])
+            .await
+            .unwrap();
+        let writes = fake.writes();
+        assert_eq!(writes.len(), 1);
+        assert_eq!(writes[0].path, "/redfish/v1/Systems/1");
+        assert_eq!(writes[0].body, json!({"Boot": {"BootOrder": ["Boot0001"]}}));
+    }
+
+    fn system(allowed: &[&str]) -> serde_json::Value {
+        json!({
+            "@odata.id": "/redfish/v1/Systems/1",
+            "@odata.type": "#ComputerSystem.v1_5_0.ComputerSystem",
+            "Id": "1",
+            "Name": "System",
+            "PowerState": "On",
+            "Boot": {"BootSourceOverrideTarget@Redfish.AllowableValues": allowed},
+        })
+    }
+
+    #[tokio::test]
+    async fn test_set_pxe_boot_once() {
+        let fake = FakeBmc::start(&[("/redfish/v1/Systems/1", system(&["Pxe", "Hdd"]))]).await;
+        let bmc = Bmc::new(fake.standard()).unwrap();
+        bmc.set_pxe_boot_once().await.unwrap();
+        let writes = fake.writes();
+        assert_eq!(writes.len(), 1);
+        assert_eq!(writes[0].path, "/redfish/v1/Systems/1");
+        assert_eq!(writes[0].body["Boot"]["BootSourceOverrideTarget"], "Pxe");
+        assert_eq!(writes[0].body["Boot"]["BootSourceOverrideEnabled"], "Once");
+
+        let fake = FakeBmc::start(&[("/redfish/v1/Systems/1", system(&["Hdd"]))]).await;
+        let bmc = Bmc::new(fake.standard()).unwrap();
+        assert!(matches!(
+            bmc.set_pxe_boot_once().await,
+            Err(crate::RedfishError::BootTargetNotAvailable { .. })
+        ));
+        assert!(fake.writes().is_empty());
+    }
+}
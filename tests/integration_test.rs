@@ -179,6 +179,8 @@ async fn nvidia_dpu_integration_test(redfish: &dyn Redfish) -> Result<(), anyhow
     let system = redfish.get_system().await?;
     assert_ne!(system.serial_number, None);
 
+    assert!(redfish.is_rshim_enabled().await?);
+
     let manager_eth_interfaces = redfish.get_manager_ethernet_interfaces().await?;
     assert!(!manager_eth_interfaces.is_empty());
     assert!(redfish